@@ -0,0 +1,34 @@
+/// Example: Export the personal assistant definition as an Ollama Modelfile.
+///
+/// Uses the persona from `[assistant.persona]` in config.toml. Does not need Ollama.
+/// The written file can be reviewed, shared, and passed back via
+/// `CreateParam::with_modelfile(Modelfile::read_from(path)?)`.
+///
+/// Run with:
+///   cargo run --example export_modelfile -- [path]
+use std::path::PathBuf;
+
+use ollama_ai_agents_playground::{
+    agents::assistant::{CreateAssistantAgent, CreateParam, Modelfile, PersonaProfile},
+    config::Config,
+};
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let config = Config::get();
+    let path = PathBuf::from(std::env::args().nth(1).unwrap_or("Modelfile".to_string()));
+
+    let param = CreateParam::new(
+        config.user.settings.name.to_owned(),
+        config.user.settings.assistant.to_owned(),
+    )
+    .with_persona(PersonaProfile::from_config(&config.assistant.persona)?);
+
+    let modelfile = CreateAssistantAgent::new().build_modelfile(&param);
+    modelfile.write_to(&path)?;
+    println!("Modelfile written to {}", path.display());
+
+    let restored = Modelfile::read_from(&path)?;
+    println!("Round trip matches: {}", restored == modelfile);
+
+    Ok(())
+}
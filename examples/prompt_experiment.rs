@@ -0,0 +1,60 @@
+/// Example: Compare the active classifier prompt against a candidate prompt file.
+///
+/// Requires a running Ollama instance at http://localhost:11434
+/// and a model matching the assistant name in config.toml.
+/// Responses are saved to `replay.json` so the experiment can be re-run offline
+/// with `ReplayBackend::load`.
+///
+/// Run with:
+///   cargo run --example prompt_experiment -- dataset.json candidate.toml
+use std::path::Path;
+
+use ollama_ai_agents_playground::{
+    agents::{
+        assistant::build_assistant_name,
+        classifier::CLASSIFIER_PROMPTS,
+        experiments::{
+            ExperimentRunner, LabelledDataset, OllamaBackend, PromptVariant, RecordingBackend,
+        },
+        prompts::{PromptFile, PromptLibrary},
+    },
+    config::Config,
+};
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args: Vec<String> = std::env::args().collect();
+    let (Some(dataset_path), Some(candidate_path)) = (args.get(1), args.get(2)) else {
+        eprintln!("Usage: prompt_experiment <dataset.json> <candidate.toml>");
+        return Ok(());
+    };
+
+    let dataset = LabelledDataset::load(Path::new(dataset_path))?;
+    let candidate = PromptFile::from_toml_str(&std::fs::read_to_string(candidate_path)?)?;
+    candidate.validate(candidate_path)?;
+
+    let baseline = PromptLibrary::get().prompt_set(CLASSIFIER_PROMPTS);
+    let variants = vec![
+        PromptVariant::new("baseline", baseline.clone()),
+        PromptVariant::with_override("candidate", &baseline, candidate),
+    ];
+
+    let model = build_assistant_name(&Config::get().user.settings.assistant);
+    let runner = ExperimentRunner::new(RecordingBackend::new(OllamaBackend::new(&model)), dataset);
+    let report = runner.run(&variants).await?;
+    runner
+        .into_backend()
+        .into_replay()
+        .save(Path::new("replay.json"))?;
+
+    println!("{report}");
+    match report.winner() {
+        Some(winner) => println!(
+            "Winner: {} — promote with PromptVariant::promote",
+            winner.name
+        ),
+        None => println!("No variant beat the baseline with 95% confidence."),
+    }
+
+    Ok(())
+}
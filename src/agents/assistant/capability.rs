@@ -0,0 +1,109 @@
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+use crate::agents::AgentError;
+
+/// A skill area rendered in the CORE CAPABILITIES section of the system prompt.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Capability {
+    CalendarManagement,
+    TaskManagement,
+    Communication,
+    TravelPlanning,
+    QuickResearch,
+    LanguageAssistance,
+    UnitConversion,
+    MathCalculation,
+    PersonalFinance,
+    CodeReview,
+    TechnicalWriting,
+    FinancialAnalysis,
+    MedicalResearch,
+    CreativeWriting,
+    ThreatAnalysis,
+}
+
+impl Capability {
+    pub const ALL: [Capability; 15] = [
+        Self::CalendarManagement,
+        Self::TaskManagement,
+        Self::Communication,
+        Self::TravelPlanning,
+        Self::QuickResearch,
+        Self::LanguageAssistance,
+        Self::UnitConversion,
+        Self::MathCalculation,
+        Self::PersonalFinance,
+        Self::CodeReview,
+        Self::TechnicalWriting,
+        Self::FinancialAnalysis,
+        Self::MedicalResearch,
+        Self::CreativeWriting,
+        Self::ThreatAnalysis,
+    ];
+
+    pub fn to_str(&self) -> &str {
+        match self {
+            Self::CalendarManagement => "calendar_management",
+            Self::TaskManagement => "task_management",
+            Self::Communication => "communication",
+            Self::TravelPlanning => "travel_planning",
+            Self::QuickResearch => "quick_research",
+            Self::LanguageAssistance => "language_assistance",
+            Self::UnitConversion => "unit_conversion",
+            Self::MathCalculation => "math_calculation",
+            Self::PersonalFinance => "personal_finance",
+            Self::CodeReview => "code_review",
+            Self::TechnicalWriting => "technical_writing",
+            Self::FinancialAnalysis => "financial_analysis",
+            Self::MedicalResearch => "medical_research",
+            Self::CreativeWriting => "creative_writing",
+            Self::ThreatAnalysis => "threat_analysis",
+        }
+    }
+
+    pub fn description(&self) -> &str {
+        match self {
+            Self::CalendarManagement => "Calendar Management & Scheduling Optimization",
+            Self::TaskManagement => "Task Prioritization & Project Organization",
+            Self::Communication => "Communication Management & Email Drafting",
+            Self::TravelPlanning => "Travel Planning & Logistics Coordination",
+            Self::QuickResearch => "Quick Research & General Queries",
+            Self::LanguageAssistance => "Language Assistance & Word Support",
+            Self::UnitConversion => "Unit Conversion & Measurement Tools",
+            Self::MathCalculation => "Mathematical Calculations & Problem Solving",
+            Self::PersonalFinance => "Personal Finance Organization & Budget Tracking",
+            Self::CodeReview => "Code Review: Security, Architecture, Performance & Code Quality",
+            Self::TechnicalWriting => {
+                "Technical Documentation: APIs, Architecture Guides & Tutorials"
+            }
+            Self::FinancialAnalysis => "Financial Statement Analysis, Valuation & Risk Assessment",
+            Self::MedicalResearch => {
+                "Evidence-Based Medicine, Clinical Trials & Systematic Reviews"
+            }
+            Self::CreativeWriting => "Creative Writing Mentoring: Fiction, Poetry & Nonfiction",
+            Self::ThreatAnalysis => {
+                "Threat Intelligence, Incident Response & Vulnerability Assessment"
+            }
+        }
+    }
+}
+
+impl FromStr for Capability {
+    type Err = AgentError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let input = input.trim().to_lowercase();
+        Self::ALL
+            .into_iter()
+            .find(|capability| capability.to_str() == input)
+            .ok_or_else(|| AgentError::ParseError(format!("Unknown capability: {input}")))
+    }
+}
+
+impl fmt::Display for Capability {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_str())
+    }
+}
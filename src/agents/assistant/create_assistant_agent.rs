@@ -1,10 +1,17 @@
 use crate::{
-    agents::{Agent, AgentError, AgentPrompt, assistant::CreateResult},
+    agents::{
+        Agent, AgentError, AgentPrompt,
+        assistant::CreateResult,
+        prompts::{PromptLibrary, PromptSet},
+    },
+    config::Config,
     infra::assistant_ollama_client::AssistantOllamaClient,
 };
 
 use super::assistant_name::build_assistant_name;
 use super::create_param::CreateParam;
+use super::modelfile::Modelfile;
+use super::persona_preset::PersonaPreset;
 
 #[derive(Default)]
 pub struct CreateAssistantAgent {}
@@ -13,6 +20,12 @@ impl CreateAssistantAgent {
     pub fn new() -> Self {
         Self {}
     }
+
+    /// The Modelfile `process` creates the assistant from, for review or export.
+    pub fn build_modelfile(&self, input: &CreateParam) -> Modelfile {
+        let prompts = PromptLibrary::get().prompt_set(PERSONAL_ASSISTANT_PROMPTS);
+        build_modelfile(&prompts, input)
+    }
 }
 
 impl Agent<CreateParam, CreateResult> for CreateAssistantAgent {
@@ -21,16 +34,19 @@ impl Agent<CreateParam, CreateResult> for CreateAssistantAgent {
         input: CreateParam,
     ) -> impl std::future::Future<Output = Result<CreateResult, AgentError>> + Send {
         async move {
+            let prompts = PromptLibrary::get().prompt_set(PERSONAL_ASSISTANT_PROMPTS);
+            let modelfile = build_modelfile(&prompts, &input);
             let result = AssistantOllamaClient::new()
-                .create_assistant(
-                    build_system_prompt(&input),
-                    build_assistant_name(input.name()),
-                )
+                .create_from_modelfile(&build_assistant_name(input.name()), &modelfile)
                 .await;
 
             match result {
                 Ok(create_response) => {
-                    Ok(CreateResult::new(create_response.is_success()))
+                    let create_result = CreateResult::new(create_response.is_success());
+                    match input.modelfile() {
+                        Some(_) => Ok(create_result),
+                        None => Ok(create_result.with_prompt_version(prompts.version())),
+                    }
                 }
                 Err(e) => Err(AgentError::ParseError(format!(
                     "Model creation failed: {}",
@@ -41,40 +57,73 @@ impl Agent<CreateParam, CreateResult> for CreateAssistantAgent {
     }
 }
 
-fn build_system_prompt(input: &CreateParam) -> String {
-    AgentPrompt::builder()
-        .add_instruction(PERSONAL_IDENTITY)
+/// An imported Modelfile is used as is; otherwise one is generated from the persona.
+fn build_modelfile(prompts: &PromptSet, input: &CreateParam) -> Modelfile {
+    if let Some(modelfile) = input.modelfile() {
+        return modelfile.clone();
+    }
+
+    let api = &Config::get().ollama.api;
+    // Temperature is sent with every chat request, so it is not baked in
+    let mut modelfile =
+        Modelfile::new(&api.model).with_system(&build_system_prompt(prompts, input));
+    for (name, value) in input.parameters() {
+        modelfile = modelfile.with_parameter(name, value);
+    }
+    if let Some(num_ctx) = api.options.num_ctx {
+        modelfile = modelfile.with_parameter(NUM_CTX, &num_ctx.to_string());
+    }
+    if let Some(template) = input.template() {
+        modelfile = modelfile.with_template(template);
+    }
+    input
+        .examples()
+        .iter()
+        .fold(modelfile, |modelfile, example| {
+            modelfile.with_message(example.into())
+        })
+}
+
+fn build_system_prompt(prompts: &PromptSet, input: &CreateParam) -> String {
+    let mut builder = AgentPrompt::builder()
+        .add_instruction(prompts.section("personal_identity", PERSONAL_IDENTITY))
         .add_instruction(LINE)
-        .add_instruction(YOUR_NAME.replace("{}", input.name()).as_str())
+        .add_instruction(
+            prompts
+                .section("your_name", YOUR_NAME)
+                .replace("{}", input.name())
+                .as_str(),
+        )
         .add_instruction(LINE)
-        .add_instruction(ASSISTANT_TO.replace("{}", input.assistant_to()).as_str())
+        .add_instruction(
+            prompts
+                .section("assistant_to", ASSISTANT_TO)
+                .replace("{}", input.assistant_to())
+                .as_str(),
+        )
         .add_instruction(LINE)
-        .add_instruction(PERSONAL_ASSISTANT)
-        .build()
-        .content()
-        .to_string()
+        .add_instruction(input.persona().build_prompt(prompts).as_str());
+
+    if input.persona().preset == PersonaPreset::PersonalAssistant {
+        builder = builder
+            .add_instruction(LINE)
+            .add_instruction(prompts.section("methodology", ASSISTANT_METHODOLOGY));
+    }
+
+    builder.build().content().to_string()
 }
 
+/// Prompt set name in the prompt library; the constants below are the fallback.
+const PERSONAL_ASSISTANT_PROMPTS: &str = "personal_assistant";
+
 const PERSONAL_IDENTITY: &str = "        PERSONAL IDENTITY:";
 const YOUR_NAME: &str = "Your name is {} ";
 const ASSISTANT_TO: &str = "You are assistant to {}";
 const LINE: &str = "\n";
+const NUM_CTX: &str = "num_ctx";
 
-const PERSONAL_ASSISTANT: &str = r#"
-        PROFESSIONAL IDENTITY:
-        You are an Intelligent Personal Assistant with extensive experience in executive support, project management, and personal organization.
-
-        CORE CAPABILITIES:
-        - Calendar Management & Scheduling Optimization
-        - Task Prioritization & Project Organization
-        - Communication Management & Email Drafting
-        - Travel Planning & Logistics Coordination
-        - Quick Research & General Queries
-        - Language Assistance & Word Support
-        - Unit Conversion & Measurement Tools
-        - Mathematical Calculations & Problem Solving
-        - Personal Finance Organization & Budget Tracking
-
+/// Working method appended for the personal assistant preset only.
+const ASSISTANT_METHODOLOGY: &str = r#"
         ASSISTANT METHODOLOGY:
 
         1. USER INTENT CLASSIFICATION
@@ -176,19 +225,4 @@ const PERSONAL_ASSISTANT: &str = r#"
            - Habit formation and maintenance
            - Time blocking and focus strategies
            - Energy management and well-being
-
-        COMMUNICATION STYLE:
-        - Professional yet personable tone
-        - Clear, actionable recommendations
-        - Proactive problem-solving approach
-        - Respectful of personal preferences
-        - Efficient and concise communication
-        - Empathetic understanding of stress and workload
-
-        MULTILINGUAL SUPPORT:
-        - Primary: English (US/UK), Portuguese (BR)
-        - Secondary: Spanish (ES), French (FR)
-        - Cultural sensitivity in international communications
-        - Time zone awareness for global interactions
-        - Local custom and etiquette considerations
     "#;
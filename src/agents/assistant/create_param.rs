@@ -1,14 +1,58 @@
-use crate::agents::agent_param::AgentParam;
+use crate::agents::{agent_param::AgentParam, context::ConversationMessage};
+
+use super::{modelfile::Modelfile, persona_profile::PersonaProfile};
 
 #[derive(Debug, Clone)]
 pub struct CreateParam {
     assistant_to: String,
     name: String,
+    persona: PersonaProfile,
+    template: Option<String>,
+    examples: Vec<ConversationMessage>,
+    parameters: Vec<(String, String)>,
+    modelfile: Option<Modelfile>,
 }
 
 impl CreateParam {
     pub fn new(assistant_to: String, name: String) -> Self {
-        Self { assistant_to, name }
+        Self {
+            assistant_to,
+            name,
+            persona: PersonaProfile::default(),
+            template: None,
+            examples: Vec::new(),
+            parameters: Vec::new(),
+            modelfile: None,
+        }
+    }
+
+    pub fn with_persona(mut self, persona: PersonaProfile) -> Self {
+        self.persona = persona;
+        self
+    }
+
+    /// Prompt template written as the Modelfile `TEMPLATE`.
+    pub fn with_template(mut self, template: &str) -> Self {
+        self.template = Some(template.to_string());
+        self
+    }
+
+    /// Few-shot turns written as Modelfile `MESSAGE` instructions.
+    pub fn with_examples(mut self, examples: Vec<ConversationMessage>) -> Self {
+        self.examples = examples;
+        self
+    }
+
+    /// A Modelfile `PARAMETER`, e.g. `temperature` or `stop`; may repeat.
+    pub fn with_parameter(mut self, name: &str, value: &str) -> Self {
+        self.parameters.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Creates the assistant from an existing Modelfile instead of the persona.
+    pub fn with_modelfile(mut self, modelfile: Modelfile) -> Self {
+        self.modelfile = Some(modelfile);
+        self
     }
 
     pub fn assistant_to(&self) -> &str {
@@ -18,6 +62,26 @@ impl CreateParam {
     pub fn name(&self) -> &str {
         &self.name
     }
+
+    pub fn persona(&self) -> &PersonaProfile {
+        &self.persona
+    }
+
+    pub fn template(&self) -> Option<&str> {
+        self.template.as_deref()
+    }
+
+    pub fn examples(&self) -> &[ConversationMessage] {
+        &self.examples
+    }
+
+    pub fn parameters(&self) -> &[(String, String)] {
+        &self.parameters
+    }
+
+    pub fn modelfile(&self) -> Option<&Modelfile> {
+        self.modelfile.as_ref()
+    }
 }
 
 impl AgentParam for CreateParam {}
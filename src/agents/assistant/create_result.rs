@@ -6,11 +6,22 @@ use crate::agents::AgentResult;
 pub struct CreateResult {
     #[serde(default)]
     pub success: bool,
+    /// Version of the prompt set baked into the assistant's system prompt.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt_version: Option<String>,
 }
 
 impl CreateResult {
     pub fn new(success: bool) -> Self {
-        Self { success }
+        Self {
+            success,
+            prompt_version: None,
+        }
+    }
+
+    pub fn with_prompt_version(mut self, version: &str) -> Self {
+        self.prompt_version = Some(version.to_string());
+        self
     }
 }
 
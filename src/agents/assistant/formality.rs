@@ -0,0 +1,58 @@
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+use crate::agents::AgentError;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Formality {
+    Casual,
+    Neutral,
+    Formal,
+}
+
+impl Formality {
+    pub fn to_str(&self) -> &str {
+        match self {
+            Self::Casual => CASUAL,
+            Self::Neutral => NEUTRAL,
+            Self::Formal => FORMAL,
+        }
+    }
+
+    /// Instruction line rendered in the COMMUNICATION STYLE section.
+    pub fn instruction(&self) -> &str {
+        match self {
+            Self::Casual => {
+                "Casual register: first names, contractions and short sentences are fine"
+            }
+            Self::Neutral => "Neutral register: courteous and clear without being stiff",
+            Self::Formal => "Formal register: full titles, complete sentences, no slang",
+        }
+    }
+}
+
+impl FromStr for Formality {
+    type Err = AgentError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input.trim().to_lowercase().as_str() {
+            CASUAL => Ok(Self::Casual),
+            NEUTRAL => Ok(Self::Neutral),
+            FORMAL => Ok(Self::Formal),
+            other => Err(AgentError::ParseError(format!(
+                "Unknown formality: {other}"
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for Formality {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_str())
+    }
+}
+
+const CASUAL: &str = "casual";
+const NEUTRAL: &str = "neutral";
+const FORMAL: &str = "formal";
@@ -1,15 +1,33 @@
 pub mod assistant_name;
+pub mod capability;
 pub mod check_assistant_agent;
 pub mod check_param;
 pub mod check_result;
 pub mod create_assistant_agent;
 pub mod create_param;
 pub mod create_result;
+pub mod formality;
+pub mod modelfile;
+pub mod modelfile_error;
+pub mod modelfile_message;
+pub mod persona_preset;
+pub mod persona_profile;
+pub mod persona_tone;
+pub mod working_hours;
 
 pub use assistant_name::build_assistant_name;
+pub use capability::Capability;
 pub use check_assistant_agent::CheckAssistantAgent;
 pub use check_param::CheckParam;
 pub use check_result::CheckResult;
 pub use create_assistant_agent::CreateAssistantAgent;
 pub use create_param::CreateParam;
 pub use create_result::CreateResult;
+pub use formality::Formality;
+pub use modelfile::Modelfile;
+pub use modelfile_error::ModelfileError;
+pub use modelfile_message::{ModelfileMessage, ModelfileRole};
+pub use persona_preset::PersonaPreset;
+pub use persona_profile::PersonaProfile;
+pub use persona_tone::PersonaTone;
+pub use working_hours::WorkingHours;
@@ -0,0 +1,385 @@
+use serde::{Deserialize, Serialize};
+use std::{fmt, fs, path::Path, str::Lines};
+
+use super::{
+    modelfile_error::ModelfileError,
+    modelfile_message::{ModelfileMessage, ModelfileRole},
+};
+
+/// An Ollama Modelfile: the reviewable, shareable definition of an assistant.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Modelfile {
+    pub from: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system: Option<String>,
+    /// `PARAMETER` lines in file order; names such as `stop` may repeat.
+    #[serde(default)]
+    pub parameters: Vec<(String, String)>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>,
+    #[serde(default)]
+    pub messages: Vec<ModelfileMessage>,
+}
+
+impl Modelfile {
+    pub fn new(from: &str) -> Self {
+        Self {
+            from: from.to_string(),
+            template: None,
+            system: None,
+            parameters: Vec::new(),
+            license: None,
+            messages: Vec::new(),
+        }
+    }
+
+    pub fn with_template(mut self, template: &str) -> Self {
+        self.template = Some(template.to_string());
+        self
+    }
+
+    pub fn with_system(mut self, system: &str) -> Self {
+        self.system = Some(system.to_string());
+        self
+    }
+
+    pub fn with_parameter(mut self, name: &str, value: &str) -> Self {
+        self.parameters.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    pub fn with_license(mut self, license: &str) -> Self {
+        self.license = Some(license.to_string());
+        self
+    }
+
+    pub fn with_message(mut self, message: ModelfileMessage) -> Self {
+        self.messages.push(message);
+        self
+    }
+
+    /// First value of the `name` parameter, if set.
+    pub fn parameter(&self, name: &str) -> Option<&str> {
+        self.parameters
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn parse(content: &str) -> Result<Self, ModelfileError> {
+        let mut from: Option<String> = None;
+        let mut modelfile = Modelfile::new("");
+        let mut lines = content.lines();
+
+        while let Some(line) = lines.next() {
+            let line = line.trim_start();
+            if line.trim_end().is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (instruction, rest) = split_word(line);
+            match instruction.to_uppercase().as_str() {
+                FROM => from = Some(read_value(rest, &mut lines)?),
+                TEMPLATE => modelfile.template = Some(read_value(rest, &mut lines)?),
+                SYSTEM => modelfile.system = Some(read_value(rest, &mut lines)?),
+                LICENSE => modelfile.license = Some(read_value(rest, &mut lines)?),
+                PARAMETER => {
+                    let (name, value) = split_word(rest);
+                    if name.is_empty() {
+                        return Err(parse_error("PARAMETER without a name"));
+                    }
+                    let value = read_value(value, &mut lines)?;
+                    modelfile.parameters.push((name.to_string(), value));
+                }
+                MESSAGE => {
+                    let (role, value) = split_word(rest);
+                    let role = role.parse::<ModelfileRole>()?;
+                    let content = read_value(value, &mut lines)?;
+                    modelfile
+                        .messages
+                        .push(ModelfileMessage::new(role, &content));
+                }
+                other => {
+                    return Err(parse_error(&format!("Unsupported instruction: {other}")));
+                }
+            }
+        }
+
+        match from {
+            Some(from) if !from.is_empty() => Ok(Modelfile { from, ..modelfile }),
+            _ => Err(parse_error("Missing FROM instruction")),
+        }
+    }
+
+    pub fn read_from(path: &Path) -> Result<Self, ModelfileError> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| ModelfileError::IoError(format!("{}: {e}", path.display())))?;
+        Self::parse(&content)
+    }
+
+    pub fn write_to(&self, path: &Path) -> Result<(), ModelfileError> {
+        self.validate()?;
+        if let Some(parent) = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            fs::create_dir_all(parent)
+                .map_err(|e| ModelfileError::IoError(format!("{}: {e}", parent.display())))?;
+        }
+        fs::write(path, self.render())
+            .map_err(|e| ModelfileError::IoError(format!("{}: {e}", path.display())))
+    }
+
+    /// Fails when a value contains `"""`, which a Modelfile cannot quote.
+    pub fn validate(&self) -> Result<(), ModelfileError> {
+        let values = [
+            (FROM, Some(&self.from)),
+            (TEMPLATE, self.template.as_ref()),
+            (SYSTEM, self.system.as_ref()),
+            (LICENSE, self.license.as_ref()),
+        ]
+        .into_iter()
+        .filter_map(|(instruction, value)| Some((instruction, value?)))
+        .chain(self.parameters.iter().map(|(_, value)| (PARAMETER, value)))
+        .chain(
+            self.messages
+                .iter()
+                .map(|message| (MESSAGE, &message.content)),
+        );
+        for (instruction, value) in values {
+            if value.contains(TRIPLE_QUOTE) {
+                return Err(parse_error(&format!(
+                    "{instruction} value cannot contain \"\"\""
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Renders the Modelfile text; for a definition that passes
+    /// [`validate`](Self::validate), `parse(render())` yields the same one.
+    pub fn render(&self) -> String {
+        let mut content = format!("{FROM} {}\n", render_value(&self.from));
+        if let Some(template) = &self.template {
+            content.push_str(&format!("{TEMPLATE} {}\n", render_value(template)));
+        }
+        if let Some(system) = &self.system {
+            content.push_str(&format!("{SYSTEM} {}\n", render_value(system)));
+        }
+        for (name, value) in &self.parameters {
+            content.push_str(&format!("{PARAMETER} {name} {}\n", render_value(value)));
+        }
+        if let Some(license) = &self.license {
+            content.push_str(&format!("{LICENSE} {}\n", render_value(license)));
+        }
+        for message in &self.messages {
+            content.push_str(&format!(
+                "{MESSAGE} {} {}\n",
+                message.role.to_str(),
+                render_value(&message.content)
+            ));
+        }
+        content
+    }
+}
+
+impl fmt::Display for Modelfile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.render())
+    }
+}
+
+fn split_word(line: &str) -> (&str, &str) {
+    match line.split_once(char::is_whitespace) {
+        Some((word, rest)) => (word, rest.trim_start()),
+        None => (line.trim_end(), ""),
+    }
+}
+
+/// Reads a bare, `"quoted"` or `"""multi-line"""` value starting at `rest`.
+/// A block ends with the first line that ends in `"""`, so the value itself
+/// may end with a quote.
+fn read_value(rest: &str, lines: &mut Lines) -> Result<String, ModelfileError> {
+    if let Some(body) = rest.strip_prefix(TRIPLE_QUOTE) {
+        if let Some(value) = block_end(body) {
+            return Ok(value.to_string());
+        }
+        let mut value = body.to_string();
+        for line in lines.by_ref() {
+            value.push('\n');
+            if let Some(end) = block_end(line) {
+                value.push_str(end);
+                return Ok(value);
+            }
+            value.push_str(line);
+        }
+        return Err(parse_error("Unterminated \"\"\" block"));
+    }
+
+    let rest = rest.trim_end();
+    match rest
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+    {
+        Some(value) => Ok(value.to_string()),
+        None => Ok(rest.to_string()),
+    }
+}
+
+/// `line` without its closing `"""`, if it ends the block.
+fn block_end(line: &str) -> Option<&str> {
+    line.trim_end().strip_suffix(TRIPLE_QUOTE)
+}
+
+fn render_value(value: &str) -> String {
+    let needs_quotes =
+        value.is_empty() || value.contains('\n') || value.contains('"') || value.trim() != value;
+    if needs_quotes {
+        format!("{TRIPLE_QUOTE}{value}{TRIPLE_QUOTE}")
+    } else {
+        value.to_string()
+    }
+}
+
+fn parse_error(msg: &str) -> ModelfileError {
+    ModelfileError::ParseError(msg.to_string())
+}
+
+const FROM: &str = "FROM";
+const TEMPLATE: &str = "TEMPLATE";
+const SYSTEM: &str = "SYSTEM";
+const PARAMETER: &str = "PARAMETER";
+const LICENSE: &str = "LICENSE";
+const MESSAGE: &str = "MESSAGE";
+const TRIPLE_QUOTE: &str = "\"\"\"";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Modelfile {
+        Modelfile::new("qwen3:0.6b")
+            .with_template("{{ .System }}\n{{ .Prompt }}")
+            .with_system(
+                "        PERSONAL IDENTITY:\nYour name is Tereza \nYou are assistant to Ana",
+            )
+            .with_parameter("temperature", "0")
+            .with_parameter("stop", "<|im_end|>")
+            .with_parameter("stop", "<|endoftext|>")
+            .with_message(ModelfileMessage::new(
+                ModelfileRole::User,
+                "Send an email to Eva",
+            ))
+            .with_message(ModelfileMessage::new(
+                ModelfileRole::Assistant,
+                "{\"intent\": \"send_email\"}",
+            ))
+    }
+
+    #[test]
+    fn test_render_then_parse_round_trips() {
+        let original = sample();
+        let parsed = Modelfile::parse(&original.render()).unwrap();
+        assert_eq!(parsed, original);
+    }
+
+    #[test]
+    fn test_values_ending_in_quotes_round_trip() {
+        let original = Modelfile::new("qwen3")
+            .with_system("Say \"hi\"")
+            .with_message(ModelfileMessage::new(
+                ModelfileRole::User,
+                "Reply \"ok\"\nor \"no\"",
+            ));
+        let rendered = original.render();
+        assert!(rendered.contains("SYSTEM \"\"\"Say \"hi\"\"\"\"\n"));
+        assert_eq!(Modelfile::parse(&rendered).unwrap(), original);
+    }
+
+    #[test]
+    fn test_values_with_triple_quotes_are_rejected() {
+        let modelfile = Modelfile::new("qwen3").with_parameter("stop", "\"\"\"");
+        assert!(matches!(
+            modelfile.validate(),
+            Err(ModelfileError::ParseError(_))
+        ));
+        let path = std::env::temp_dir().join(format!("modelfile_quotes_{}", std::process::id()));
+        assert!(modelfile.write_to(&path).is_err());
+        assert!(!path.exists());
+        assert!(sample().validate().is_ok());
+    }
+
+    #[test]
+    fn test_render_uses_triple_quotes_only_when_needed() {
+        let rendered = sample().render();
+        assert!(rendered.starts_with("FROM qwen3:0.6b\n"));
+        assert!(rendered.contains("PARAMETER temperature 0\n"));
+        assert!(rendered.contains("SYSTEM \"\"\"        PERSONAL IDENTITY:\n"));
+        assert!(rendered.contains("MESSAGE user Send an email to Eva\n"));
+        assert!(rendered.contains("MESSAGE assistant \"\"\"{\"intent\": \"send_email\"}\"\"\"\n"));
+    }
+
+    #[test]
+    fn test_parse_accepts_comments_quotes_and_lowercase_instructions() {
+        let content = r#"
+# Assistant for Ana
+from llama3.2
+system "You are concise."
+parameter num_ctx 4096
+PARAMETER stop "<|eot_id|>"
+"#;
+        let modelfile = Modelfile::parse(content).unwrap();
+        assert_eq!(modelfile.from, "llama3.2");
+        assert_eq!(modelfile.system.as_deref(), Some("You are concise."));
+        assert_eq!(modelfile.parameter("num_ctx"), Some("4096"));
+        assert_eq!(modelfile.parameter("stop"), Some("<|eot_id|>"));
+        assert!(modelfile.messages.is_empty());
+    }
+
+    #[test]
+    fn test_parse_accepts_instructions_before_from() {
+        let modelfile = Modelfile::parse("SYSTEM hello\nFROM llama3.2\n").unwrap();
+        assert_eq!(modelfile.from, "llama3.2");
+        assert_eq!(modelfile.system.as_deref(), Some("hello"));
+    }
+
+    #[test]
+    fn test_parse_rejects_missing_from() {
+        let result = Modelfile::parse("SYSTEM hello\n");
+        assert!(matches!(result, Err(ModelfileError::ParseError(_))));
+    }
+
+    #[test]
+    fn test_parse_rejects_unterminated_block() {
+        let result = Modelfile::parse("FROM llama3.2\nSYSTEM \"\"\"never closed\n");
+        assert!(matches!(result, Err(ModelfileError::ParseError(_))));
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_instruction_and_role() {
+        assert!(Modelfile::parse("FROM llama3.2\nADAPTER ./lora.gguf\n").is_err());
+        assert!(Modelfile::parse("FROM llama3.2\nMESSAGE tool hi\n").is_err());
+    }
+
+    #[test]
+    fn test_write_and_read_round_trip() {
+        let path = std::env::temp_dir()
+            .join(format!("modelfile_test_{}", std::process::id()))
+            .join("Modelfile");
+
+        let original = sample();
+        original.write_to(&path).unwrap();
+        let restored = Modelfile::read_from(&path).unwrap();
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+
+        assert_eq!(restored, original);
+    }
+
+    #[test]
+    fn test_read_from_missing_file_is_io_error() {
+        let result = Modelfile::read_from(Path::new("does/not/exist/Modelfile"));
+        assert!(matches!(result, Err(ModelfileError::IoError(_))));
+    }
+}
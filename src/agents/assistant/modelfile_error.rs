@@ -0,0 +1,19 @@
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug)]
+pub enum ModelfileError {
+    IoError(String),
+    ParseError(String),
+}
+
+impl fmt::Display for ModelfileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ModelfileError::IoError(msg) => write!(f, "Modelfile IO error: {msg}"),
+            ModelfileError::ParseError(msg) => write!(f, "Modelfile parse error: {msg}"),
+        }
+    }
+}
+
+impl Error for ModelfileError {}
@@ -0,0 +1,65 @@
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use crate::agents::context::{ConversationMessage, ConversationRole};
+
+use super::modelfile_error::ModelfileError;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ModelfileRole {
+    System,
+    User,
+    Assistant,
+}
+
+impl ModelfileRole {
+    pub fn to_str(&self) -> &str {
+        match self {
+            Self::System => "system",
+            Self::User => "user",
+            Self::Assistant => "assistant",
+        }
+    }
+}
+
+impl FromStr for ModelfileRole {
+    type Err = ModelfileError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input.to_lowercase().as_str() {
+            "system" => Ok(Self::System),
+            "user" => Ok(Self::User),
+            "assistant" => Ok(Self::Assistant),
+            other => Err(ModelfileError::ParseError(format!(
+                "Unknown MESSAGE role: {other}"
+            ))),
+        }
+    }
+}
+
+/// A `MESSAGE` instruction: a few-shot turn baked into the model.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ModelfileMessage {
+    pub role: ModelfileRole,
+    pub content: String,
+}
+
+impl ModelfileMessage {
+    pub fn new(role: ModelfileRole, content: &str) -> Self {
+        Self {
+            role,
+            content: content.to_string(),
+        }
+    }
+}
+
+impl From<&ConversationMessage> for ModelfileMessage {
+    fn from(message: &ConversationMessage) -> Self {
+        let role = match message.role {
+            ConversationRole::User => ModelfileRole::User,
+            ConversationRole::Assistant => ModelfileRole::Assistant,
+        };
+        Self::new(role, &message.content)
+    }
+}
@@ -0,0 +1,183 @@
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+use crate::agents::AgentError;
+
+use super::{
+    capability::Capability, formality::Formality, persona_profile::PersonaProfile,
+    persona_tone::PersonaTone, working_hours::WorkingHours,
+};
+
+/// Ready-made personas. Apart from the default personal assistant they are
+/// modelled on `spec/COMPLEX_SYSTEM_PROMPTS_EXAMPLES.md`.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum PersonaPreset {
+    #[default]
+    PersonalAssistant,
+    CodeReviewer,
+    TechnicalWriter,
+    FinancialAnalyst,
+    MedicalResearcher,
+    CreativeWritingMentor,
+    CybersecurityAnalyst,
+}
+
+impl PersonaPreset {
+    pub const ALL: [PersonaPreset; 7] = [
+        Self::PersonalAssistant,
+        Self::CodeReviewer,
+        Self::TechnicalWriter,
+        Self::FinancialAnalyst,
+        Self::MedicalResearcher,
+        Self::CreativeWritingMentor,
+        Self::CybersecurityAnalyst,
+    ];
+
+    pub fn to_str(&self) -> &str {
+        match self {
+            Self::PersonalAssistant => "personal_assistant",
+            Self::CodeReviewer => "code_reviewer",
+            Self::TechnicalWriter => "technical_writer",
+            Self::FinancialAnalyst => "financial_analyst",
+            Self::MedicalResearcher => "medical_researcher",
+            Self::CreativeWritingMentor => "creative_writing_mentor",
+            Self::CybersecurityAnalyst => "cybersecurity_analyst",
+        }
+    }
+
+    /// The preset's profile, ready to be customised with `PersonaProfile::with_*`.
+    pub fn profile(&self) -> PersonaProfile {
+        match self {
+            Self::PersonalAssistant => PersonaProfile::new(
+                *self,
+                "personal assistance",
+                "You are an Intelligent Personal Assistant with extensive experience in executive support, project management, and personal organization.",
+                PersonaTone::Professional,
+                Formality::Neutral,
+            )
+            .with_languages("en", &["pt-BR", "es", "fr"])
+            .with_capabilities(&[
+                Capability::CalendarManagement,
+                Capability::TaskManagement,
+                Capability::Communication,
+                Capability::TravelPlanning,
+                Capability::QuickResearch,
+                Capability::LanguageAssistance,
+                Capability::UnitConversion,
+                Capability::MathCalculation,
+                Capability::PersonalFinance,
+            ])
+            .with_guidelines(&[
+                "Clear, actionable recommendations",
+                "Proactive problem-solving approach",
+                "Respectful of personal preferences",
+                "Efficient and concise communication",
+                "Cultural sensitivity and time zone awareness in international communications",
+            ])
+            .with_working_hours(WorkingHours::new("09:00", "18:00")),
+            Self::CodeReviewer => PersonaProfile::new(
+                *self,
+                "software engineering",
+                "You are a Senior Software Engineer with 15+ years of experience, specialized in code review and mentoring.",
+                PersonaTone::Constructive,
+                Formality::Neutral,
+            )
+            .with_capabilities(&[Capability::CodeReview, Capability::TechnicalWriting])
+            .with_guidelines(&[
+                "Provide specific examples and alternatives",
+                "Include code snippets for suggested improvements",
+                "Prioritize high-impact issues and always explain the reasoning",
+                "Consider team skill level in recommendations",
+            ]),
+            Self::TechnicalWriter => PersonaProfile::new(
+                *self,
+                "technical documentation",
+                "You are an expert Technical Documentation Specialist with expertise in multiple domains.",
+                PersonaTone::Professional,
+                Formality::Neutral,
+            )
+            .with_languages("en", &["pt-BR", "es", "fr"])
+            .with_capabilities(&[Capability::TechnicalWriting, Capability::LanguageAssistance])
+            .with_guidelines(&[
+                "Adapt depth to the audience: developers, operators or end users",
+                "Prefer short sentences, consistent terminology and runnable examples",
+                "Structure content with clear headings and step-by-step procedures",
+            ]),
+            Self::FinancialAnalyst => PersonaProfile::new(
+                *self,
+                "finance",
+                "You are a Senior Financial Analyst with CFA designation and 12+ years of experience in investment banking, corporate finance, and quantitative analysis.",
+                PersonaTone::Analytical,
+                Formality::Formal,
+            )
+            .with_capabilities(&[
+                Capability::FinancialAnalysis,
+                Capability::PersonalFinance,
+                Capability::MathCalculation,
+            ])
+            .with_guidelines(&[
+                "Support every conclusion with figures and state assumptions explicitly",
+                "Quantify risks and present scenarios rather than single predictions",
+                "Never present analysis as personalised investment advice",
+            ]),
+            Self::MedicalResearcher => PersonaProfile::new(
+                *self,
+                "biomedical research",
+                "You are a Medical Research Assistant with PhD in Biomedical Sciences, specializing in evidence-based medicine and systematic reviews.",
+                PersonaTone::Analytical,
+                Formality::Formal,
+            )
+            .with_capabilities(&[Capability::MedicalResearch, Capability::QuickResearch])
+            .with_guidelines(&[
+                "Grade the strength of evidence and cite study designs",
+                "Distinguish established findings from preliminary results",
+                "Never give individual diagnoses or treatment decisions",
+            ]),
+            Self::CreativeWritingMentor => PersonaProfile::new(
+                *self,
+                "creative writing",
+                "You are a Master of Fine Arts (MFA) Creative Writing Professor with 20+ years of experience teaching and mentoring writers across all genres.",
+                PersonaTone::Encouraging,
+                Formality::Casual,
+            )
+            .with_capabilities(&[Capability::CreativeWriting, Capability::LanguageAssistance])
+            .with_guidelines(&[
+                "Balance encouragement with constructive criticism",
+                "Respect the writer's voice and intentions",
+                "Illustrate suggestions with short rewritten passages",
+            ]),
+            Self::CybersecurityAnalyst => PersonaProfile::new(
+                *self,
+                "cybersecurity",
+                "You are a Senior Cybersecurity Threat Analyst with CISSP, CEH, and GCIH certifications, specializing in threat intelligence and incident response.",
+                PersonaTone::Analytical,
+                Formality::Formal,
+            )
+            .with_capabilities(&[Capability::ThreatAnalysis, Capability::CodeReview])
+            .with_guidelines(&[
+                "Rate findings by severity and likelihood",
+                "Map threats to MITRE ATT&CK techniques where applicable",
+                "Recommend defensive mitigations; never provide weaponised exploits",
+            ]),
+        }
+    }
+}
+
+impl FromStr for PersonaPreset {
+    type Err = AgentError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let input = input.trim().to_lowercase();
+        Self::ALL
+            .into_iter()
+            .find(|preset| preset.to_str() == input)
+            .ok_or_else(|| AgentError::ParseError(format!("Unknown persona preset: {input}")))
+    }
+}
+
+impl fmt::Display for PersonaPreset {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_str())
+    }
+}
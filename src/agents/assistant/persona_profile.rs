@@ -0,0 +1,502 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    agents::{AgentError, AgentPrompt, prompts::PromptSet},
+    config::PersonaConfig,
+};
+
+use super::{
+    capability::Capability, formality::Formality, persona_preset::PersonaPreset,
+    persona_tone::PersonaTone, working_hours::WorkingHours,
+};
+
+/// Everything that shapes the assistant's system prompt besides its name and owner.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct PersonaProfile {
+    pub preset: PersonaPreset,
+    pub domain: String,
+    pub role: String,
+    pub tone: PersonaTone,
+    pub formality: Formality,
+    pub primary_language: String,
+    pub secondary_languages: Vec<String>,
+    pub capabilities: Vec<Capability>,
+    pub guidelines: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub working_hours: Option<WorkingHours>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+}
+
+impl PersonaProfile {
+    pub fn new(
+        preset: PersonaPreset,
+        domain: &str,
+        role: &str,
+        tone: PersonaTone,
+        formality: Formality,
+    ) -> Self {
+        Self {
+            preset,
+            domain: domain.to_string(),
+            role: role.to_string(),
+            tone,
+            formality,
+            primary_language: DEFAULT_PRIMARY_LANGUAGE.to_string(),
+            secondary_languages: Vec::new(),
+            capabilities: Vec::new(),
+            guidelines: Vec::new(),
+            working_hours: None,
+            signature: None,
+        }
+    }
+
+    /// Starts from `preset` and applies every override present in `config`.
+    pub fn from_config(config: &PersonaConfig) -> Result<Self, AgentError> {
+        let preset = match &config.preset {
+            Some(preset) => preset.parse::<PersonaPreset>()?,
+            None => PersonaPreset::default(),
+        };
+        let mut profile = preset.profile();
+
+        if let Some(domain) = &config.domain {
+            profile = profile.with_domain(domain);
+        }
+        if let Some(tone) = &config.tone {
+            profile = profile.with_tone(tone.parse()?);
+        }
+        if let Some(formality) = &config.formality {
+            profile = profile.with_formality(formality.parse()?);
+        }
+        if let Some(primary_language) = &config.primary_language {
+            profile.primary_language = primary_language.clone();
+        }
+        if let Some(secondary_languages) = &config.secondary_languages {
+            profile.secondary_languages = secondary_languages.clone();
+        }
+        if let Some(capabilities) = &config.capabilities {
+            profile.capabilities = capabilities
+                .iter()
+                .map(|capability| capability.parse())
+                .collect::<Result<Vec<Capability>, AgentError>>()?;
+        }
+        if let Some(range) = &config.working_hours {
+            let mut working_hours = WorkingHours::parse_range(range)
+                .ok_or_else(|| AgentError::ParseError(format!("Invalid working hours: {range}")))?;
+            if let Some(days) = &config.working_days {
+                working_hours = working_hours.with_days(days);
+            }
+            if let Some(timezone) = &config.timezone {
+                working_hours = working_hours.with_timezone(timezone);
+            }
+            profile = profile.with_working_hours(working_hours);
+        }
+        if let Some(signature) = &config.signature {
+            profile = profile.with_signature(signature);
+        }
+
+        Ok(profile)
+    }
+
+    pub fn with_domain(mut self, domain: &str) -> Self {
+        self.domain = domain.to_string();
+        self
+    }
+
+    pub fn with_tone(mut self, tone: PersonaTone) -> Self {
+        self.tone = tone;
+        self
+    }
+
+    pub fn with_formality(mut self, formality: Formality) -> Self {
+        self.formality = formality;
+        self
+    }
+
+    pub fn with_languages(mut self, primary: &str, secondary: &[&str]) -> Self {
+        self.primary_language = primary.to_string();
+        self.secondary_languages = secondary.iter().map(|code| code.to_string()).collect();
+        self
+    }
+
+    pub fn with_capabilities(mut self, capabilities: &[Capability]) -> Self {
+        self.capabilities = capabilities.to_vec();
+        self
+    }
+
+    pub fn with_guidelines(mut self, guidelines: &[&str]) -> Self {
+        self.guidelines = guidelines.iter().map(|line| line.to_string()).collect();
+        self
+    }
+
+    pub fn with_working_hours(mut self, working_hours: WorkingHours) -> Self {
+        self.working_hours = Some(working_hours);
+        self
+    }
+
+    pub fn with_signature(mut self, signature: &str) -> Self {
+        self.signature = Some(signature.to_string());
+        self
+    }
+
+    pub fn has_capability(&self, capability: Capability) -> bool {
+        self.capabilities.contains(&capability)
+    }
+
+    /// Renders the persona sections of the system prompt with the wording of
+    /// `prompts` (the `personal_assistant` set), falling back to the English
+    /// text compiled into the binary. The role, domain and guidelines are read
+    /// from the preset's sections (`<preset>_role`, ...) unless they were
+    /// customised.
+    pub fn build_prompt(&self, prompts: &PromptSet) -> String {
+        let preset = self.preset.profile();
+        let text = |key: &str, fallback: &str| prompts.section(key, fallback).to_string();
+        let role = if self.role == preset.role {
+            text(&preset_key(self.preset, ROLE), &self.role)
+        } else {
+            self.role.clone()
+        };
+        let domain = if self.domain == preset.domain {
+            text(&preset_key(self.preset, DOMAIN), &self.domain)
+        } else {
+            self.domain.clone()
+        };
+        let guidelines: Vec<String> = if self.guidelines == preset.guidelines {
+            text(
+                &preset_key(self.preset, GUIDELINES),
+                &self.guidelines.join("\n"),
+            )
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(str::to_string)
+            .collect()
+        } else {
+            self.guidelines.clone()
+        };
+
+        let mut builder = AgentPrompt::builder()
+            .add_instruction(&section(
+                prompts.section("identity_title", PROFESSIONAL_IDENTITY),
+                &[role],
+            ))
+            .add_instruction(&section(
+                prompts.section("domain_title", DOMAIN_TITLE),
+                &[domain],
+            ));
+
+        if !self.capabilities.is_empty() {
+            let capabilities = self
+                .capabilities
+                .iter()
+                .map(|capability| {
+                    bullet(prompts.section(
+                        &format!("capability_{}", capability.to_str()),
+                        capability.description(),
+                    ))
+                })
+                .collect::<Vec<String>>();
+            builder = builder.add_instruction(&section(
+                prompts.section("capabilities_title", CORE_CAPABILITIES),
+                &capabilities,
+            ));
+        }
+
+        let style = [
+            prompts.section(
+                &format!("tone_{}", self.tone.to_str()),
+                self.tone.instruction(),
+            ),
+            prompts.section(
+                &format!("formality_{}", self.formality.to_str()),
+                self.formality.instruction(),
+            ),
+        ]
+        .into_iter()
+        .chain(guidelines.iter().map(String::as_str))
+        .map(bullet)
+        .collect::<Vec<String>>();
+        builder = builder.add_instruction(&section(
+            prompts.section("style_title", COMMUNICATION_STYLE),
+            &style,
+        ));
+
+        let language = |code: &str| {
+            text(
+                &format!("language_{}", code.to_lowercase().replace('-', "_")),
+                &language_name(code),
+            )
+        };
+        let mut languages = vec![bullet(
+            &prompts
+                .section("primary_language", PRIMARY_LANGUAGE)
+                .replace("{}", &language(&self.primary_language)),
+        )];
+        if !self.secondary_languages.is_empty() {
+            let secondary = self
+                .secondary_languages
+                .iter()
+                .map(|code| language(code))
+                .collect::<Vec<String>>()
+                .join(", ");
+            languages.push(bullet(
+                &prompts
+                    .section("secondary_languages", SECONDARY_LANGUAGES)
+                    .replace("{}", &secondary),
+            ));
+        }
+        languages.push(bullet(prompts.section("language_rule", LANGUAGE_RULE)));
+        builder = builder.add_instruction(&section(
+            prompts.section("languages_title", LANGUAGES),
+            &languages,
+        ));
+
+        if let Some(working_hours) = &self.working_hours {
+            builder = builder.add_instruction(&section(
+                prompts.section("working_hours_title", WORKING_HOURS),
+                &[
+                    bullet(&working_hours.describe()),
+                    bullet(prompts.section("working_hours_rule", WORKING_HOURS_RULE)),
+                ],
+            ));
+        }
+
+        if let Some(signature) = &self.signature {
+            builder = builder.add_instruction(&section(
+                prompts.section("signature_title", SIGNATURE),
+                &[
+                    prompts.section("signature_rule", SIGNATURE_RULE),
+                    signature.as_str(),
+                ],
+            ));
+        }
+
+        builder.build().content().to_string()
+    }
+}
+
+impl Default for PersonaProfile {
+    fn default() -> Self {
+        PersonaPreset::default().profile()
+    }
+}
+
+fn section<S: AsRef<str>>(title: &str, lines: &[S]) -> String {
+    let mut content = format!("\n{INDENT}{title}\n");
+    for line in lines {
+        content.push_str(INDENT);
+        content.push_str(line.as_ref());
+        content.push('\n');
+    }
+    content
+}
+
+fn bullet(text: &str) -> String {
+    format!("- {text}")
+}
+
+/// Section of the preset's own wording, e.g. `code_reviewer_role`.
+fn preset_key(preset: PersonaPreset, part: &str) -> String {
+    format!("{}_{part}", preset.to_str())
+}
+
+/// Human-readable name for the language codes used in the config, falling back to the code.
+fn language_name(code: &str) -> String {
+    match code {
+        "en" => "English",
+        "en-US" => "English (US)",
+        "en-GB" => "English (UK)",
+        "pt" => "Portuguese",
+        "pt-BR" => "Portuguese (BR)",
+        "pt-PT" => "Portuguese (PT)",
+        "es" => "Spanish",
+        "fr" => "French",
+        "de" => "German",
+        "it" => "Italian",
+        other => other,
+    }
+    .to_string()
+}
+
+const DEFAULT_PRIMARY_LANGUAGE: &str = "en";
+const INDENT: &str = "        ";
+
+const ROLE: &str = "role";
+const DOMAIN: &str = "domain";
+const GUIDELINES: &str = "guidelines";
+
+const PROFESSIONAL_IDENTITY: &str = "PROFESSIONAL IDENTITY:";
+const DOMAIN_TITLE: &str = "DOMAIN:";
+const CORE_CAPABILITIES: &str = "CORE CAPABILITIES:";
+const COMMUNICATION_STYLE: &str = "COMMUNICATION STYLE:";
+const LANGUAGES: &str = "LANGUAGES:";
+const WORKING_HOURS: &str = "WORKING HOURS:";
+const SIGNATURE: &str = "SIGNATURE:";
+
+const PRIMARY_LANGUAGE: &str = "Primary: {}";
+const SECONDARY_LANGUAGES: &str = "Secondary: {}";
+const LANGUAGE_RULE: &str =
+    "Reply in the user's language when it is listed above; otherwise use the primary language";
+const WORKING_HOURS_RULE: &str =
+    "Do not schedule meetings or promise follow-ups outside these hours";
+const SIGNATURE_RULE: &str = "Sign messages drafted on the user's behalf with:";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agents::prompts::PromptFile;
+
+    fn builtin() -> PromptSet {
+        PromptSet::builtin("personal_assistant")
+    }
+
+    #[test]
+    fn test_default_profile_is_personal_assistant() {
+        let profile = PersonaProfile::default();
+        assert_eq!(profile.preset, PersonaPreset::PersonalAssistant);
+        assert_eq!(profile.tone, PersonaTone::Professional);
+        assert_eq!(profile.capabilities.len(), 9);
+        assert_eq!(profile.primary_language, "en");
+    }
+
+    #[test]
+    fn test_build_prompt_renders_sections() {
+        let prompt = PersonaProfile::default().build_prompt(&builtin());
+        assert!(prompt.contains("PROFESSIONAL IDENTITY:"));
+        assert!(prompt.contains("Intelligent Personal Assistant"));
+        assert!(prompt.contains("CORE CAPABILITIES:"));
+        assert!(prompt.contains("- Calendar Management & Scheduling Optimization"));
+        assert!(prompt.contains("- Professional yet personable tone"));
+        assert!(prompt.contains("- Primary: English"));
+        assert!(prompt.contains("- Secondary: Portuguese (BR), Spanish, French"));
+        assert!(prompt.contains("WORKING HOURS:"));
+        assert!(prompt.contains("- Mon-Fri 09:00-18:00"));
+        assert!(!prompt.contains("SIGNATURE:"));
+    }
+
+    #[test]
+    fn test_build_prompt_reads_the_prompt_set() {
+        let file = PromptFile {
+            version: "test".to_string(),
+            sections: [
+                ("identity_title", "IDENTIDADE PROFISSIONAL:"),
+                ("personal_assistant_role", "Você é uma assistente pessoal."),
+                (
+                    "personal_assistant_guidelines",
+                    "Seja breve\n\nSeja clara\n",
+                ),
+                ("capability_calendar_management", "Gestão de agenda"),
+                ("primary_language", "Principal: {}"),
+                ("language_en", "Inglês"),
+            ]
+            .into_iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect(),
+        };
+        let prompts = PromptSet::new("personal_assistant", "pt-BR", vec![file]);
+
+        let prompt = PersonaProfile::default().build_prompt(&prompts);
+        assert!(
+            prompt.contains("IDENTIDADE PROFISSIONAL:\n        Você é uma assistente pessoal.")
+        );
+        assert!(prompt.contains("- Gestão de agenda"));
+        assert!(prompt.contains("- Seja breve\n        - Seja clara\n"));
+        assert!(prompt.contains("- Principal: Inglês"));
+        assert!(prompt.contains("- Task Prioritization & Project Organization"));
+
+        let custom = PersonaProfile::default()
+            .with_guidelines(&["Use bullet points"])
+            .build_prompt(&prompts);
+        assert!(custom.contains("- Use bullet points"));
+        assert!(!custom.contains("Seja breve"));
+    }
+
+    #[test]
+    fn test_build_prompt_renders_signature() {
+        let prompt = PersonaProfile::default()
+            .with_signature("Best regards,\nAna")
+            .build_prompt(&builtin());
+        assert!(prompt.contains("SIGNATURE:"));
+        assert!(prompt.contains("Best regards,\nAna"));
+    }
+
+    #[test]
+    fn test_build_prompt_skips_empty_sections() {
+        let prompt = PersonaProfile::new(
+            PersonaPreset::TechnicalWriter,
+            "docs",
+            "You write docs.",
+            PersonaTone::Friendly,
+            Formality::Casual,
+        )
+        .build_prompt(&builtin());
+        assert!(!prompt.contains("CORE CAPABILITIES:"));
+        assert!(!prompt.contains("WORKING HOURS:"));
+        assert!(!prompt.contains("Secondary:"));
+        assert!(prompt.contains("- Warm, friendly and approachable tone"));
+    }
+
+    #[test]
+    fn test_from_config_defaults_to_personal_assistant() {
+        let profile = PersonaProfile::from_config(&PersonaConfig::default()).unwrap();
+        assert_eq!(profile, PersonaProfile::default());
+    }
+
+    #[test]
+    fn test_from_config_applies_overrides() {
+        let config = PersonaConfig {
+            preset: Some("code_reviewer".to_string()),
+            tone: Some("friendly".to_string()),
+            formality: Some("casual".to_string()),
+            primary_language: Some("pt-BR".to_string()),
+            secondary_languages: Some(vec!["en".to_string()]),
+            capabilities: Some(vec!["code_review".to_string()]),
+            working_hours: Some("08:00-16:00".to_string()),
+            working_days: Some("Mon-Thu".to_string()),
+            timezone: Some("America/Sao_Paulo".to_string()),
+            signature: Some("Ana".to_string()),
+            domain: Some("backend services".to_string()),
+        };
+
+        let profile = PersonaProfile::from_config(&config).unwrap();
+        assert_eq!(profile.preset, PersonaPreset::CodeReviewer);
+        assert_eq!(profile.tone, PersonaTone::Friendly);
+        assert_eq!(profile.formality, Formality::Casual);
+        assert_eq!(profile.primary_language, "pt-BR");
+        assert_eq!(profile.secondary_languages, vec!["en".to_string()]);
+        assert_eq!(profile.capabilities, vec![Capability::CodeReview]);
+        assert_eq!(
+            profile.working_hours.as_ref().unwrap().describe(),
+            "Mon-Thu 08:00-16:00 (America/Sao_Paulo)"
+        );
+        assert_eq!(profile.signature.as_deref(), Some("Ana"));
+        assert_eq!(profile.domain, "backend services");
+    }
+
+    #[test]
+    fn test_from_config_rejects_unknown_values() {
+        let unknown_preset = PersonaConfig {
+            preset: Some("astronaut".to_string()),
+            ..PersonaConfig::default()
+        };
+        assert!(PersonaProfile::from_config(&unknown_preset).is_err());
+
+        let unknown_capability = PersonaConfig {
+            capabilities: Some(vec!["juggling".to_string()]),
+            ..PersonaConfig::default()
+        };
+        assert!(PersonaProfile::from_config(&unknown_capability).is_err());
+
+        let bad_hours = PersonaConfig {
+            working_hours: Some("9am-6pm".to_string()),
+            ..PersonaConfig::default()
+        };
+        assert!(PersonaProfile::from_config(&bad_hours).is_err());
+    }
+
+    #[test]
+    fn test_language_name_falls_back_to_code() {
+        assert_eq!(language_name("pt-BR"), "Portuguese (BR)");
+        assert_eq!(language_name("ja"), "ja");
+    }
+}
@@ -0,0 +1,73 @@
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+use crate::agents::AgentError;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PersonaTone {
+    Professional,
+    Friendly,
+    Constructive,
+    Analytical,
+    Empathetic,
+    Encouraging,
+}
+
+impl PersonaTone {
+    pub fn to_str(&self) -> &str {
+        match self {
+            Self::Professional => PROFESSIONAL,
+            Self::Friendly => FRIENDLY,
+            Self::Constructive => CONSTRUCTIVE,
+            Self::Analytical => ANALYTICAL,
+            Self::Empathetic => EMPATHETIC,
+            Self::Encouraging => ENCOURAGING,
+        }
+    }
+
+    /// Instruction line rendered in the COMMUNICATION STYLE section.
+    pub fn instruction(&self) -> &str {
+        match self {
+            Self::Professional => "Professional yet personable tone",
+            Self::Friendly => "Warm, friendly and approachable tone",
+            Self::Constructive => {
+                "Constructive and educational tone, balancing criticism with positive reinforcement"
+            }
+            Self::Analytical => "Objective, data-driven and precise tone",
+            Self::Empathetic => "Empathetic tone, acknowledging stress and workload",
+            Self::Encouraging => "Encouraging and supportive tone that builds confidence",
+        }
+    }
+}
+
+impl FromStr for PersonaTone {
+    type Err = AgentError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input.trim().to_lowercase().as_str() {
+            PROFESSIONAL => Ok(Self::Professional),
+            FRIENDLY => Ok(Self::Friendly),
+            CONSTRUCTIVE => Ok(Self::Constructive),
+            ANALYTICAL => Ok(Self::Analytical),
+            EMPATHETIC => Ok(Self::Empathetic),
+            ENCOURAGING => Ok(Self::Encouraging),
+            other => Err(AgentError::ParseError(format!(
+                "Unknown persona tone: {other}"
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for PersonaTone {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_str())
+    }
+}
+
+const PROFESSIONAL: &str = "professional";
+const FRIENDLY: &str = "friendly";
+const CONSTRUCTIVE: &str = "constructive";
+const ANALYTICAL: &str = "analytical";
+const EMPATHETIC: &str = "empathetic";
+const ENCOURAGING: &str = "encouraging";
@@ -0,0 +1,65 @@
+use serde::{Deserialize, Serialize};
+
+/// When the assistant should assume the user is available, e.g. `09:00`–`18:00` on `Mon-Fri`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct WorkingHours {
+    pub start: String,
+    pub end: String,
+    #[serde(default = "default_days")]
+    pub days: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+}
+
+impl WorkingHours {
+    pub fn new(start: &str, end: &str) -> Self {
+        Self {
+            start: start.to_string(),
+            end: end.to_string(),
+            days: default_days(),
+            timezone: None,
+        }
+    }
+
+    pub fn with_days(mut self, days: &str) -> Self {
+        self.days = days.to_string();
+        self
+    }
+
+    pub fn with_timezone(mut self, timezone: &str) -> Self {
+        self.timezone = Some(timezone.to_string());
+        self
+    }
+
+    /// Parses `"09:00-18:00"`.
+    pub fn parse_range(range: &str) -> Option<Self> {
+        let (start, end) = range.split_once('-')?;
+        let (start, end) = (start.trim(), end.trim());
+        if is_time(start) && is_time(end) {
+            Some(Self::new(start, end))
+        } else {
+            None
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        match &self.timezone {
+            Some(timezone) => format!("{} {}-{} ({timezone})", self.days, self.start, self.end),
+            None => format!("{} {}-{}", self.days, self.start, self.end),
+        }
+    }
+}
+
+fn default_days() -> String {
+    "Mon-Fri".to_string()
+}
+
+fn is_time(value: &str) -> bool {
+    match value.split_once(':') {
+        Some((hours, minutes)) => {
+            matches!(hours.parse::<u8>(), Ok(h) if h < 24 && hours.len() == 2)
+                && matches!(minutes.parse::<u8>(), Ok(m) if m < 60 && minutes.len() == 2)
+        }
+        None => false,
+    }
+}
@@ -1,16 +1,37 @@
 use serde::{Deserialize, Serialize};
 
-use crate::agents::{AgentResult, Intent, classifier::Params};
+use crate::agents::{AgentResult, Intent, classifier::Params, context::ContextReport};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ClassificationResult {
     pub intent: Intent,
     pub params: Params,
+    /// Version of the prompt set that produced this result.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt_version: Option<String>,
+    /// Context-window usage and the prompt sections trimmed to fit it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context: Option<ContextReport>,
 }
 
 impl ClassificationResult {
     pub fn new(intent: Intent, params: Params) -> Self {
-        Self { intent, params }
+        Self {
+            intent,
+            params,
+            prompt_version: None,
+            context: None,
+        }
+    }
+
+    pub fn with_prompt_version(mut self, version: &str) -> Self {
+        self.prompt_version = Some(version.to_string());
+        self
+    }
+
+    pub fn with_context_report(mut self, report: ContextReport) -> Self {
+        self.context = Some(report);
+        self
     }
 
     pub fn from_json_str(json_str: &str) -> Result<Self, serde_json::Error> {
@@ -122,4 +143,33 @@ mod tests {
             cloned.params.to_json_string().unwrap()
         );
     }
+
+    #[test]
+    fn test_prompt_version_is_optional_and_roundtrips() {
+        let params = Params::with_values("eva@company.com".to_string(), "Hi".to_string());
+        let result = ClassificationResult::new(Intent::SendEmail, params.clone());
+        assert_eq!(result.prompt_version, None);
+        assert!(!result.to_json_string().unwrap().contains("prompt_version"));
+
+        let versioned = ClassificationResult::new(Intent::SendEmail, params)
+            .with_prompt_version("1.0.0");
+        let json_string = versioned.to_json_string().unwrap();
+        let deserialized = ClassificationResult::from_json_str(&json_string).unwrap();
+        assert_eq!(deserialized.prompt_version.as_deref(), Some("1.0.0"));
+    }
+
+    #[test]
+    fn test_context_report_roundtrips() {
+        let params = Params::with_values("eva@company.com".to_string(), "Hi".to_string());
+        let result = ClassificationResult::new(Intent::SendEmail, params)
+            .with_context_report(ContextReport::new(2048, 900, vec!["history_0".to_string()]));
+
+        let json_string = result.to_json_string().unwrap();
+        let deserialized = ClassificationResult::from_json_str(&json_string).unwrap();
+        let report = deserialized.context.unwrap();
+
+        assert_eq!(report.num_ctx, 2048);
+        assert!(report.was_trimmed());
+        assert_eq!(report.dropped, vec!["history_0".to_string()]);
+    }
 }
@@ -6,6 +6,10 @@ use crate::{
         agent_prompt::AgentPrompt,
         assistant::build_assistant_name,
         classifier::FromMarkdownJson,
+        context::{
+            BudgetedPrompt, ContextBudget, ConversationRole, PromptSection, SectionPriority,
+        },
+        prompts::{PromptLibrary, PromptSet},
     },
     infra::assistant_ollama_client::AssistantOllamaClient,
 };
@@ -28,19 +32,19 @@ impl Agent<IntentParam, ClassificationResult> for IntentClassifierAgent {
     ) -> impl std::future::Future<Output = Result<ClassificationResult, AgentError>> + Send {
         async move {
             // Build classification prompt
-            let systen_prompt = build_system_prompt();
+            let prompts = PromptLibrary::get().prompt_set(CLASSIFIER_PROMPTS);
+            let model = build_assistant_name(input.assistant());
+            let client = AssistantOllamaClient::new();
 
-            let user_prompt = build_user_prompt(input.input());
+            // Fit instructions, examples, history and input into the context window
+            let budgeted = ContextBudget::for_model(&client, &model)
+                .await
+                .fit(build_prompt_sections(&prompts, &input))
+                .map_err(|e| AgentError::ProcessingError(format!("Classification failed: {e}")))?;
 
             // Send to Ollama API
-            let result = AssistantOllamaClient::new()
-                .send_classifier_message(
-                    vec![
-                        ChatMessage::system(format!(r#"{}"#, systen_prompt.replace('"', "\\\""))),
-                        ChatMessage::user(format!(r#"{}"#, user_prompt.replace('"', "\\\""))),
-                    ],
-                    build_assistant_name(input.assistant()).as_str(),
-                )
+            let result = client
+                .send_classifier_message(build_messages(&prompts, &input, &budgeted), &model)
                 .await;
 
             match result {
@@ -50,7 +54,9 @@ impl Agent<IntentParam, ClassificationResult> for IntentClassifierAgent {
                         &ollama_response.message.unwrap().content().unwrap(),
                     )
                     {
-                        Ok(classification_result) => Ok(classification_result),
+                        Ok(classification_result) => Ok(classification_result
+                            .with_prompt_version(prompts.version())
+                            .with_context_report(budgeted.report())),
                         Err(mapper_error) => Err(AgentError::ParseError(format!(
                             "Classification failed: {}",
                             mapper_error
@@ -65,45 +71,133 @@ impl Agent<IntentParam, ClassificationResult> for IntentClassifierAgent {
     }
 }
 
-fn build_user_prompt(input: &str) -> String {
+/// Splits the prompt into budgetable sections: instructions and input are required,
+/// examples are trimmed after the conversation history.
+fn build_prompt_sections(prompts: &PromptSet, input: &IntentParam) -> Vec<PromptSection> {
+    let mut sections: Vec<PromptSection> = SYSTEM_SECTIONS
+        .iter()
+        .map(|(key, fallback, priority)| {
+            PromptSection::new(key, prompts.section(key, fallback), *priority)
+                .in_message(SYSTEM_MESSAGE)
+        })
+        .collect();
+    for (index, message) in input.history().iter().enumerate() {
+        sections.push(PromptSection::new(
+            &history_section_name(index),
+            &message.content,
+            SectionPriority::Low,
+        ));
+    }
+    sections.push(PromptSection::required(
+        USER_INPUT_SECTION,
+        &build_user_prompt(prompts, input.input()),
+    ));
+    sections
+}
+
+fn build_messages(
+    prompts: &PromptSet,
+    input: &IntentParam,
+    budgeted: &BudgetedPrompt,
+) -> Vec<ChatMessage> {
+    let systen_prompt = join_system_sections(prompts, |key| budgeted.is_kept(key));
+    let user_prompt = build_user_prompt(prompts, input.input());
+
+    let mut messages = vec![ChatMessage::system(escape_quotes(&systen_prompt))];
+    for (index, message) in input.history().iter().enumerate() {
+        if budgeted.is_kept(&history_section_name(index)) {
+            messages.push(match message.role {
+                ConversationRole::User => ChatMessage::user(message.content.clone()),
+                ConversationRole::Assistant => ChatMessage::assistant(message.content.clone()),
+            });
+        }
+    }
+    messages.push(ChatMessage::user(escape_quotes(&user_prompt)));
+    messages
+}
+
+/// System and user prompts for `input` with every section kept, as sent to the model.
+pub fn build_classifier_prompts(prompts: &PromptSet, input: &str) -> (String, String) {
+    (
+        escape_quotes(&join_system_sections(prompts, |_| true)),
+        escape_quotes(&build_user_prompt(prompts, input)),
+    )
+}
+
+fn escape_quotes(prompt: &str) -> String {
+    prompt.replace('"', "\\\"")
+}
+
+fn history_section_name(index: usize) -> String {
+    format!("{HISTORY_SECTION}_{index}")
+}
+
+fn build_user_prompt(prompts: &PromptSet, input: &str) -> String {
     AgentPrompt::builder()
-        .add_instruction(INPUT.replace("{}", input).as_str())
+        .add_instruction(prompts.section("input", INPUT).replace("{}", input).as_str())
         .add_instruction(SPACE)
-        .add_instruction(OUTPUT)
+        .add_instruction(prompts.section("output", OUTPUT))
         .build()
         .content()
         .to_string()
 }
 
-fn build_system_prompt() -> String {
+fn join_system_sections(prompts: &PromptSet, keep: impl Fn(&str) -> bool) -> String {
+    let instructions: Vec<&str> = SYSTEM_SECTIONS
+        .iter()
+        .filter(|(key, _, _)| keep(key))
+        .map(|(key, fallback, _)| prompts.section(key, fallback))
+        .collect();
+
     AgentPrompt::builder()
-        .add_instruction(YOUT_ARE)
-        .add_instruction(SPACE)
-        .add_instruction(OUTPUT_FORMART)
-        .add_instruction(SPACE)
-        .add_instruction(EXAMPLE_1)
-        .add_instruction(SPACE)
-        .add_instruction(EXAMPLE_2)
-        .add_instruction(SPACE)
-        .add_instruction(TASK)
+        .add_instruction(instructions.join(SPACE).as_str())
         .build()
         .content()
         .to_string()
 }
 
+/// Prompt set name in the prompt library; the constants below are the fallback.
+pub const CLASSIFIER_PROMPTS: &str = "classifier";
+const HISTORY_SECTION: &str = "history";
+/// The system sections are joined into this one chat message.
+const SYSTEM_MESSAGE: &str = "system";
+const USER_INPUT_SECTION: &str = "input";
+
+/// System prompt sections in order: (prompt library key, fallback, budget priority).
+const SYSTEM_SECTIONS: [(&str, &str, SectionPriority); 7] = [
+    ("you_are", YOUT_ARE, SectionPriority::Required),
+    ("output_format", OUTPUT_FORMART, SectionPriority::Required),
+    ("example_1", EXAMPLE_1, SectionPriority::Medium),
+    ("example_2", EXAMPLE_2, SectionPriority::Medium),
+    ("example_3", EXAMPLE_3, SectionPriority::Medium),
+    ("example_4", EXAMPLE_4, SectionPriority::Medium),
+    ("task", TASK, SectionPriority::Required),
+];
+
 const SPACE: &str = "        ";
 const YOUT_ARE: &str = "You are a helpful assistant that classifies user intent. Your response MUST be a JSON object. You will never use markdown notation in outputs. Here are some examples:";
 const OUTPUT_FORMART: &str =
     "Output-Format: {\"intent\":\"\",\"params\":{\"recipient\":\"\",\"message\":\"\"}}";
 const EXAMPLE_1: &str = "Example 1:        Input: \"Send an email to Carlos about the delay\"        Output: {\"intent\":\"send_email\", \"params\":{\"recipient\":\"Carlos\",\"message\":\"About the delay\"}}";
 const EXAMPLE_2: &str = "Example 2:        Input: \"Send message to Sofia: I'll arrive in 10 min\"        Output: {\"intent\":\"send_message\", \"params\":{\"recipient\":\"Sofia\",\"message\":\"I'll arrive in 10 min\"}}";
-const TASK: &str = "Task: Return JSON with: action (send_email, schedule_meeting, no_action)";
+const EXAMPLE_3: &str = "Example 3:        Input: \"Tag Dolphin as doctor\"        Output: {\"intent\":\"contact_management\", \"params\":{\"recipient\":\"Dolphin\",\"message\":\"Tag Dolphin as doctor\"}}";
+const EXAMPLE_4: &str = "Example 4:        Input: \"Email everyone in Engineering at Tech Solutions that the release moved to Friday\"        Output: {\"intent\":\"send_email\", \"params\":{\"recipient\":\"everyone in Engineering at Tech Solutions\",\"message\":\"The release moved to Friday\",\"filter\":{\"company\":\"Tech Solutions\",\"department\":\"Engineering\"}}}";
+const TASK: &str = "Task: Return JSON with: action (send_email, schedule_meeting, contact_management, no_action). When the recipient is a group of contacts, add params.filter with any of: company, department, jobTitle, tags, favorite, city, country, notes";
 const INPUT: &str = "Input: \"{}\"";
 const OUTPUT: &str = "Output: ";
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::agents::context::{ConversationMessage, context_budget::DEFAULT_NUM_CTX};
+
+    fn builtin() -> PromptSet {
+        PromptSet::builtin(CLASSIFIER_PROMPTS)
+    }
+
+    fn build_system_prompt(prompts: &PromptSet) -> String {
+        join_system_sections(prompts, |_| true)
+    }
 
     #[test]
     fn test_intent_classifier_agent_new() {
@@ -120,7 +214,7 @@ mod tests {
     #[test]
     fn test_build_user_prompt() {
         let input = "Send an email to Eva";
-        let result = build_user_prompt(input);
+        let result = build_user_prompt(&builtin(), input);
 
         assert!(result.contains("Send an email to Eva"));
         assert!(result.contains("Input:"));
@@ -130,7 +224,7 @@ mod tests {
     #[test]
     fn test_build_user_prompt_with_special_characters() {
         let input = "Send email with \"quotes\" and 'apostrophes'";
-        let result = build_user_prompt(input);
+        let result = build_user_prompt(&builtin(), input);
 
         assert!(result.contains("quotes"));
         assert!(result.contains("apostrophes"));
@@ -139,7 +233,7 @@ mod tests {
     #[test]
     fn test_build_user_prompt_with_unicode() {
         let input = "Envie um email para João sobre café";
-        let result = build_user_prompt(input);
+        let result = build_user_prompt(&builtin(), input);
 
         assert!(result.contains("João"));
         assert!(result.contains("café"));
@@ -147,7 +241,7 @@ mod tests {
 
     #[test]
     fn test_build_system_prompt() {
-        let result = build_system_prompt();
+        let result = build_system_prompt(&builtin());
 
         assert!(result.contains("You are a helpful assistant"));
         assert!(result.contains("JSON object"));
@@ -160,7 +254,7 @@ mod tests {
 
     #[test]
     fn test_build_system_prompt_contains_format() {
-        let result = build_system_prompt();
+        let result = build_system_prompt(&builtin());
 
         assert!(result.contains("Output-Format"));
         assert!(result.contains("intent"));
@@ -171,7 +265,7 @@ mod tests {
 
     #[test]
     fn test_build_system_prompt_contains_examples() {
-        let result = build_system_prompt();
+        let result = build_system_prompt(&builtin());
 
         assert!(result.contains("Carlos"));
         assert!(result.contains("Sofia"));
@@ -187,8 +281,11 @@ mod tests {
         assert!(OUTPUT_FORMART.contains("params"));
         assert!(EXAMPLE_1.contains("Carlos"));
         assert!(EXAMPLE_2.contains("Sofia"));
+        assert!(EXAMPLE_3.contains("contact_management"));
+        assert!(EXAMPLE_4.contains("\"filter\""));
         assert!(TASK.contains("send_email"));
         assert!(TASK.contains("schedule_meeting"));
+        assert!(TASK.contains("contact_management"));
         assert!(TASK.contains("no_action"));
         assert_eq!(INPUT, "Input: \"{}\"");
         assert_eq!(OUTPUT, "Output: ");
@@ -197,7 +294,7 @@ mod tests {
     #[test]
     fn test_build_user_prompt_formatting() {
         let input = "Test message";
-        let result = build_user_prompt(input);
+        let result = build_user_prompt(&builtin(), input);
 
         assert!(result.contains("\"Test message\""));
 
@@ -209,7 +306,7 @@ mod tests {
 
     #[test]
     fn test_build_system_prompt_no_markdown() {
-        let result = build_system_prompt();
+        let result = build_system_prompt(&builtin());
 
         assert!(!result.contains("```"));
         assert!(!result.contains("**"));
@@ -236,8 +333,8 @@ mod tests {
     #[test]
     fn test_prompt_builder_integration() {
         let input = "Send email to test@example.com";
-        let user_prompt = build_user_prompt(input);
-        let system_prompt = build_system_prompt();
+        let user_prompt = build_user_prompt(&builtin(), input);
+        let system_prompt = build_system_prompt(&builtin());
 
         assert!(!user_prompt.is_empty());
         assert!(!system_prompt.is_empty());
@@ -247,4 +344,95 @@ mod tests {
 
         assert!(user_prompt.contains("test@example.com"));
     }
+
+    #[test]
+    fn test_build_system_prompt_prefers_prompt_library_sections() {
+        let file = crate::agents::prompts::PromptFile::from_toml_str(
+            "version = \"2.0.0\"\n[sections]\ntask = \"Task: Return JSON with: intent\"\n",
+        )
+        .unwrap();
+        let prompts = PromptSet::new(CLASSIFIER_PROMPTS, "en", vec![file]);
+
+        let result = build_system_prompt(&prompts);
+
+        assert!(result.contains("Task: Return JSON with: intent"));
+        assert!(!result.contains(TASK));
+        assert!(result.contains(YOUT_ARE));
+        assert_eq!(prompts.version(), "2.0.0");
+    }
+
+    #[test]
+    fn test_build_prompt_sections_priorities() {
+        let input = IntentParam::new("Send it to Eva".to_string(), "Tereza".to_string())
+            .with_history(vec![ConversationMessage::user("Write to Carlos")]);
+        let sections = build_prompt_sections(&builtin(), &input);
+        let priority = |name: &str| {
+            sections
+                .iter()
+                .find(|section| section.name() == name)
+                .map(|section| section.priority())
+        };
+
+        assert_eq!(sections.len(), SYSTEM_SECTIONS.len() + 2);
+        assert_eq!(priority("you_are"), Some(SectionPriority::Required));
+        assert_eq!(priority("example_1"), Some(SectionPriority::Medium));
+        assert_eq!(priority("history_0"), Some(SectionPriority::Low));
+        assert_eq!(
+            priority(USER_INPUT_SECTION),
+            Some(SectionPriority::Required)
+        );
+    }
+
+    #[test]
+    fn test_build_messages_skips_trimmed_sections() {
+        let history = vec![
+            ConversationMessage::user(&"long earlier request ".repeat(200)),
+            ConversationMessage::assistant("ok"),
+        ];
+        let input = IntentParam::new("Send it to Eva".to_string(), "Tereza".to_string())
+            .with_history(history);
+        let prompts = builtin();
+
+        let roomy = ContextBudget::new(DEFAULT_NUM_CTX)
+            .fit(build_prompt_sections(&prompts, &input))
+            .unwrap();
+        assert_eq!(build_messages(&prompts, &input, &roomy).len(), 4);
+
+        let tight = ContextBudget::new(300)
+            .with_response_reserve(0)
+            .fit(build_prompt_sections(&prompts, &input))
+            .unwrap();
+        let report = tight.report();
+        assert!(report.dropped.contains(&"history_0".to_string()));
+        assert!(tight.is_kept(USER_INPUT_SECTION));
+        assert_eq!(
+            build_messages(&prompts, &input, &tight).len(),
+            4 - report
+                .dropped
+                .iter()
+                .filter(|d| d.starts_with("history"))
+                .count()
+        );
+    }
+
+    #[test]
+    fn test_join_system_sections_omits_trimmed_examples() {
+        let prompts = builtin();
+        let result = join_system_sections(&prompts, |key| !key.starts_with("example"));
+
+        assert!(result.contains(YOUT_ARE));
+        assert!(result.contains(TASK));
+        assert!(!result.contains("Carlos"));
+        assert!(!result.contains("Sofia"));
+    }
+
+    #[test]
+    fn test_build_classifier_prompts_escapes_quotes() {
+        let (system, user) = build_classifier_prompts(&builtin(), "Email Eva");
+
+        assert!(system.starts_with("You are a helpful assistant"));
+        assert!(system.contains("Example 2:"));
+        assert!(system.contains("\\\"intent\\\""));
+        assert_eq!(user, "Input: \\\"Email Eva\\\"        Output: ");
+    }
 }
@@ -1,14 +1,26 @@
-use crate::agents::agent_param::AgentParam;
+use crate::agents::{agent_param::AgentParam, context::ConversationMessage};
 
 #[derive(Debug)]
 pub struct IntentParam {
     input: String,
     assistant: String,
+    history: Vec<ConversationMessage>,
 }
 
 impl IntentParam {
     pub fn new(input: String, assistant: String) -> Self {
-        Self { input, assistant }
+        Self {
+            input,
+            assistant,
+            history: Vec::new(),
+        }
+    }
+
+    /// Previous turns, oldest first. They are the first thing trimmed when the
+    /// prompt does not fit the model's context window.
+    pub fn with_history(mut self, history: Vec<ConversationMessage>) -> Self {
+        self.history = history;
+        self
     }
 
     pub fn input(&self) -> &str {
@@ -18,6 +30,10 @@ impl IntentParam {
     pub fn assistant(&self) -> &str {
         &self.assistant
     }
+
+    pub fn history(&self) -> &[ConversationMessage] {
+        &self.history
+    }
 }
 
 impl AgentParam for IntentParam {}
@@ -63,4 +79,21 @@ mod tests {
 
         assert!(debug_str.contains("IntentParam"));
     }
+
+    #[test]
+    fn test_intent_param_with_history() {
+        let param = IntentParam::new("And to Carlos?".to_string(), "assistant".to_string())
+            .with_history(vec![
+                ConversationMessage::user("Send an email to Eva"),
+                ConversationMessage::assistant("Done"),
+            ]);
+
+        assert_eq!(param.history().len(), 2);
+        assert_eq!(param.history()[0].content, "Send an email to Eva");
+        assert!(
+            IntentParam::new("x".to_string(), "y".to_string())
+                .history()
+                .is_empty()
+        );
+    }
 }
@@ -12,7 +12,9 @@ pub mod to_classification_result;
 
 pub use classification_result::ClassificationResult;
 pub use from_markdown_json::FromMarkdownJson;
-pub use intent_classifier_agent::IntentClassifierAgent;
+pub use intent_classifier_agent::{
+    CLASSIFIER_PROMPTS, IntentClassifierAgent, build_classifier_prompts,
+};
 pub use intent_param::IntentParam;
 pub use mapper::Mapper;
 pub use mapper_error::MapperError;
@@ -1,23 +1,38 @@
 use serde::{Deserialize, Serialize};
 
+use crate::infra::contacts::ContactQuery;
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Params {
     recipient: Option<String>,
     message: Option<String>,
+    /// Contact filters when the recipient is a group ("everyone in Engineering at Tech Solutions").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    filter: Option<ContactQuery>,
 }
 
 impl Params {
     pub fn new(recipient: Option<String>, message: Option<String>) -> Self {
-        Self { recipient, message }
+        Self {
+            recipient,
+            message,
+            filter: None,
+        }
     }
 
     pub fn with_values(recipient: String, message: String) -> Self {
         Self {
             recipient: Some(recipient),
             message: Some(message),
+            filter: None,
         }
     }
 
+    pub fn with_filter(mut self, filter: ContactQuery) -> Self {
+        self.filter = Some(filter);
+        self
+    }
+
     pub fn from_json_str(json_str: &str) -> Result<Self, serde_json::Error> {
         serde_json::from_str(json_str)
     }
@@ -33,6 +48,11 @@ impl Params {
     pub fn message(&self) -> Option<&str> {
         self.message.as_deref()
     }
+
+    /// The group filter, unless the classifier left every field blank.
+    pub fn filter(&self) -> Option<&ContactQuery> {
+        self.filter.as_ref().filter(|filter| !filter.is_empty())
+    }
 }
 
 #[cfg(test)]
@@ -240,4 +260,24 @@ mod tests {
         let deserialized = Params::from_json_str(&json_string).unwrap();
         assert_eq!(deserialized.message(), Some(long_message.as_str()));
     }
+
+    #[test]
+    fn test_params_with_group_filter() {
+        let json_str = r#"{"recipient":"everyone in Engineering at Tech Solutions","message":"Release moved","filter":{"company":"Tech Solutions","department":"Engineering","city":""}}"#;
+        let params = Params::from_json_str(json_str).unwrap();
+        let filter = params.filter().unwrap();
+        assert_eq!(filter.company.as_deref(), Some("Tech Solutions"));
+        assert_eq!(filter.department.as_deref(), Some("Engineering"));
+
+        let blank =
+            Params::from_json_str(r#"{"recipient":"Eva","message":"Hi","filter":{"company":""}}"#)
+                .unwrap();
+        assert!(blank.filter().is_none());
+        assert!(
+            !Params::new(None, None)
+                .to_json_string()
+                .unwrap()
+                .contains("filter")
+        );
+    }
 }
@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+use crate::infra::contacts::ContactLabel;
+
+use super::{
+    contact_channel::ContactChannel, message_context::MessageContext,
+    selection_reason::SelectionReason,
+};
+
+/// The email address or phone number chosen for a contact, with the reason
+/// shown to the user so they can correct it.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AddressSelection {
+    pub contact_id: String,
+    pub channel: ContactChannel,
+    pub address: String,
+    pub label: ContactLabel,
+    pub reason: SelectionReason,
+    /// The context the choice was made for, when one was known.
+    pub context: Option<MessageContext>,
+    /// The contact's other addresses on the same channel, as `address (label)`.
+    pub alternatives: Vec<String>,
+    pub explanation: String,
+}
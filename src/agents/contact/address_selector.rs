@@ -0,0 +1,524 @@
+use crate::infra::{
+    calendar::CalendarEvent,
+    contacts::{Contact, ContactLabel, ContactPhone},
+};
+
+use super::{
+    address_selection::AddressSelection, contact_channel::ContactChannel,
+    context_signal::ContextSignal, message_context::MessageContext,
+    name_similarity::normalize_name, selection_reason::SelectionReason,
+};
+
+/// Picks which of a contact's email addresses or phone numbers a message
+/// should use. In order:
+///
+/// 1. the address the user chose for the contact and context
+///    (`X-PREFERRED-EMAIL-WORK`, or `X-PREFERRED-EMAIL` for any context)
+/// 2. an address whose label fits the context: `work` for work messages,
+///    `personal`/`home` (and `mobile` for phones) for personal ones
+/// 3. the primary address
+/// 4. the first one listed
+///
+/// The context comes from [`infer_from_text`](Self::infer_from_text) or
+/// [`infer_from_event`](Self::infer_from_event).
+#[derive(Debug, Clone, Default)]
+pub struct AddressSelector {}
+
+impl AddressSelector {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    /// Work or personal, from keywords in English and Portuguese. `None` when
+    /// neither side has more hits.
+    pub fn infer_from_text(&self, text: &str) -> Option<ContextSignal> {
+        let normalized = normalize_name(text);
+        let words: Vec<&str> = normalized.split_whitespace().collect();
+        let hits = |keywords: &[&str]| -> Vec<String> {
+            words
+                .iter()
+                .filter(|word| keywords.contains(word))
+                .map(|word| word.to_string())
+                .collect()
+        };
+        let work = hits(WORK_KEYWORDS);
+        let personal = hits(PERSONAL_KEYWORDS);
+
+        let (context, evidence) = match work.len().cmp(&personal.len()) {
+            std::cmp::Ordering::Greater => (MessageContext::Work, work),
+            std::cmp::Ordering::Less => (MessageContext::Personal, personal),
+            std::cmp::Ordering::Equal => return None,
+        };
+        Some(ContextSignal::new(
+            context,
+            &format!("it mentions \"{}\"", evidence[0]),
+        ))
+    }
+
+    /// From the category of the event the message is about.
+    pub fn infer_from_event(&self, event: &CalendarEvent) -> Option<ContextSignal> {
+        let category = event.category.to_lowercase();
+        let context = if WORK_CATEGORIES.contains(&category.as_str()) {
+            MessageContext::Work
+        } else if PERSONAL_CATEGORIES.contains(&category.as_str()) {
+            MessageContext::Personal
+        } else {
+            return None;
+        };
+        Some(ContextSignal::new(
+            context,
+            &format!("the calendar event is in the \"{category}\" category"),
+        ))
+    }
+
+    /// The channel the user chose for the contact (`X-PREFERRED-CHANNEL`),
+    /// otherwise email when the contact has one, otherwise phone.
+    pub fn preferred_channel(&self, contact: &Contact) -> Option<ContactChannel> {
+        let chosen = contact
+            .extensions
+            .get(PREFERRED_CHANNEL)
+            .and_then(|value| match value.trim().to_lowercase().as_str() {
+                "email" => Some(ContactChannel::Email),
+                "phone" => Some(ContactChannel::Phone),
+                _ => None,
+            })
+            .filter(|channel| !entries(contact, *channel).is_empty());
+        chosen.or_else(|| {
+            [ContactChannel::Email, ContactChannel::Phone]
+                .into_iter()
+                .find(|channel| !entries(contact, *channel).is_empty())
+        })
+    }
+
+    pub fn select(
+        &self,
+        contact: &Contact,
+        channel: ContactChannel,
+        signal: Option<&ContextSignal>,
+    ) -> Option<AddressSelection> {
+        let entries = entries(contact, channel);
+        let context = signal.map(|signal| signal.context);
+        let name = contact.name();
+        let noun = noun(channel);
+
+        let preferred = [
+            preference_key(channel, context),
+            preference_key(channel, None),
+        ]
+        .into_iter()
+        .filter_map(|key| contact.extensions.get(&key))
+        .find_map(|chosen| {
+            entries
+                .iter()
+                .position(|entry| same_address(channel, &entry.address, chosen))
+        });
+        let fitting = context.and_then(|context| {
+            let labels = context_labels(channel, context);
+            entries
+                .iter()
+                .enumerate()
+                .filter(|(_, entry)| labels.contains(&entry.label))
+                .min_by_key(|(index, entry)| (!entry.primary, *index))
+                .map(|(index, _)| index)
+        });
+        let primary = entries.iter().position(|entry| entry.primary);
+
+        let (index, reason) = if let Some(index) = preferred {
+            (index, SelectionReason::Preference)
+        } else if let Some(index) = fitting {
+            (index, SelectionReason::ContextLabel)
+        } else if entries.len() == 1 {
+            (0, SelectionReason::OnlyAddress)
+        } else if let Some(index) = primary {
+            (index, SelectionReason::Primary)
+        } else if !entries.is_empty() {
+            (0, SelectionReason::FirstListed)
+        } else {
+            return None;
+        };
+        let chosen = &entries[index];
+
+        let mut explanation = match reason {
+            SelectionReason::Preference => match context {
+                Some(context) => format!(
+                    "Using {} because you chose it for {context} messages to {name}.",
+                    chosen.address
+                ),
+                None => format!("Using {} because you chose it for {name}.", chosen.address),
+            },
+            SelectionReason::ContextLabel => format!(
+                "Using {} ({}) because the message looks {}: {}.",
+                chosen.address,
+                chosen.label,
+                describe_context(context.unwrap_or(MessageContext::Work)),
+                signal
+                    .map(|signal| signal.evidence.as_str())
+                    .unwrap_or_default()
+            ),
+            SelectionReason::OnlyAddress => {
+                format!("Using {}, the only {noun} of {name}.", chosen.address)
+            }
+            SelectionReason::Primary => format!(
+                "Using {} ({}), the primary {noun} of {name}.",
+                chosen.address, chosen.label
+            ),
+            SelectionReason::FirstListed => format!(
+                "Using {} ({}), the first {noun} listed for {name}.",
+                chosen.address, chosen.label
+            ),
+        };
+        if let Some(context) = context
+            && fitting.is_none()
+            && reason != SelectionReason::Preference
+        {
+            explanation.push_str(&format!(" {name} has no {context} {noun}."));
+        }
+        let alternatives: Vec<String> = entries
+            .iter()
+            .enumerate()
+            .filter(|(other, _)| *other != index)
+            .map(|(_, entry)| format!("{} ({})", entry.address, entry.label))
+            .collect();
+        if !alternatives.is_empty() {
+            explanation.push_str(&format!(" Other options: {}.", alternatives.join(", ")));
+        }
+
+        Some(AddressSelection {
+            contact_id: contact.id.clone(),
+            channel,
+            address: chosen.address.clone(),
+            label: chosen.label,
+            reason,
+            context,
+            alternatives,
+            explanation,
+        })
+    }
+}
+
+/// Records `address` as the user's choice for `contact` on `channel`, for one
+/// context or (with `None`) for every message. Returns `false`, leaving the
+/// contact unchanged, when the address is not one of the contact's.
+pub fn prefer_address(
+    contact: &mut Contact,
+    channel: ContactChannel,
+    context: Option<MessageContext>,
+    address: &str,
+) -> bool {
+    let Some(entry) = entries(contact, channel)
+        .into_iter()
+        .find(|entry| same_address(channel, &entry.address, address))
+    else {
+        return false;
+    };
+    contact
+        .extensions
+        .insert(preference_key(channel, context), entry.address);
+    true
+}
+
+/// Extension key holding the chosen address, e.g. `X-PREFERRED-EMAIL-WORK`.
+pub fn preference_key(channel: ContactChannel, context: Option<MessageContext>) -> String {
+    let channel = channel.to_str().to_uppercase();
+    match context {
+        Some(context) => format!("X-PREFERRED-{channel}-{}", context.to_str().to_uppercase()),
+        None => format!("X-PREFERRED-{channel}"),
+    }
+}
+
+pub const PREFERRED_CHANNEL: &str = "X-PREFERRED-CHANNEL";
+
+struct Entry {
+    address: String,
+    label: ContactLabel,
+    primary: bool,
+}
+
+fn entries(contact: &Contact, channel: ContactChannel) -> Vec<Entry> {
+    match channel {
+        ContactChannel::Email => contact
+            .emails
+            .iter()
+            .map(|email| Entry {
+                address: email.address.clone(),
+                label: email.label,
+                primary: email.primary,
+            })
+            .collect(),
+        ContactChannel::Phone => contact
+            .phone_numbers
+            .iter()
+            .map(|phone| Entry {
+                address: phone.number.clone(),
+                label: phone.label,
+                primary: phone.primary,
+            })
+            .collect(),
+    }
+}
+
+fn same_address(channel: ContactChannel, a: &str, b: &str) -> bool {
+    match channel {
+        ContactChannel::Email => a.trim().eq_ignore_ascii_case(b.trim()),
+        ContactChannel::Phone => ContactPhone::new(ContactLabel::Other, a)
+            .same_number(&ContactPhone::new(ContactLabel::Other, b)),
+    }
+}
+
+fn context_labels(channel: ContactChannel, context: MessageContext) -> &'static [ContactLabel] {
+    match (channel, context) {
+        (_, MessageContext::Work) => &[ContactLabel::Work],
+        (ContactChannel::Email, MessageContext::Personal) => {
+            &[ContactLabel::Personal, ContactLabel::Home]
+        }
+        (ContactChannel::Phone, MessageContext::Personal) => &[
+            ContactLabel::Mobile,
+            ContactLabel::Personal,
+            ContactLabel::Home,
+        ],
+    }
+}
+
+fn noun(channel: ContactChannel) -> &'static str {
+    match channel {
+        ContactChannel::Email => "email",
+        ContactChannel::Phone => "phone number",
+    }
+}
+
+fn describe_context(context: MessageContext) -> &'static str {
+    match context {
+        MessageContext::Work => "work-related",
+        MessageContext::Personal => "personal",
+    }
+}
+
+/// Matched against `normalize_name` words, so accents are already stripped.
+const WORK_KEYWORDS: &[&str] = &[
+    "meeting",
+    "meetings",
+    "project",
+    "projects",
+    "deadline",
+    "client",
+    "clients",
+    "customer",
+    "invoice",
+    "report",
+    "proposal",
+    "contract",
+    "budget",
+    "presentation",
+    "release",
+    "sprint",
+    "office",
+    "review",
+    "roadmap",
+    "quarter",
+    "quarterly",
+    "reuniao",
+    "projeto",
+    "prazo",
+    "cliente",
+    "fatura",
+    "relatorio",
+    "proposta",
+    "contrato",
+    "orcamento",
+    "apresentacao",
+    "escritorio",
+    "entrega",
+];
+const PERSONAL_KEYWORDS: &[&str] = &[
+    "birthday",
+    "dinner",
+    "family",
+    "weekend",
+    "party",
+    "vacation",
+    "holiday",
+    "holidays",
+    "wedding",
+    "kids",
+    "mom",
+    "dad",
+    "barbecue",
+    "congratulations",
+    "aniversario",
+    "jantar",
+    "familia",
+    "festa",
+    "ferias",
+    "casamento",
+    "parabens",
+    "churrasco",
+    "filhos",
+];
+const WORK_CATEGORIES: &[&str] = &["work", "education"];
+const PERSONAL_CATEGORIES: &[&str] = &["personal", "family", "birthday"];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infra::contacts::{ContactEmail, ContactPhone};
+
+    fn tiger() -> Contact {
+        Contact::new("c001", "Tiger", "Brilliant")
+            .with_email(
+                ContactEmail::new(ContactLabel::Personal, "tiger.brilliant@gmail.com")
+                    .with_primary(true),
+            )
+            .with_email(ContactEmail::new(
+                ContactLabel::Work,
+                "t.brilliant@techsolutions.com",
+            ))
+            .with_phone(
+                ContactPhone::new(ContactLabel::Mobile, "+1-555-123-4567").with_primary(true),
+            )
+            .with_phone(ContactPhone::new(ContactLabel::Work, "+1-555-987-6543"))
+    }
+
+    fn select(contact: &Contact, text: &str) -> AddressSelection {
+        let selector = AddressSelector::new();
+        let signal = selector.infer_from_text(text);
+        selector
+            .select(contact, ContactChannel::Email, signal.as_ref())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_infer_from_text() {
+        let selector = AddressSelector::new();
+        let signal = selector
+            .infer_from_text("Send Tiger the project report before the deadline")
+            .unwrap();
+        assert_eq!(signal.context, MessageContext::Work);
+        assert_eq!(signal.evidence, "it mentions \"project\"");
+        assert_eq!(
+            selector
+                .infer_from_text("Convide a Eva para o jantar de aniversário")
+                .map(|s| s.context),
+            Some(MessageContext::Personal)
+        );
+        assert_eq!(selector.infer_from_text("Say hi to Tiger"), None);
+    }
+
+    #[test]
+    fn test_context_picks_the_matching_label() {
+        let work = select(&tiger(), "Send Tiger the project report");
+        assert_eq!(work.address, "t.brilliant@techsolutions.com");
+        assert_eq!(work.reason, SelectionReason::ContextLabel);
+        assert_eq!(
+            work.explanation,
+            "Using t.brilliant@techsolutions.com (work) because the message looks work-related: \
+             it mentions \"project\". Other options: tiger.brilliant@gmail.com (personal)."
+        );
+
+        let personal = select(&tiger(), "Invite Tiger to the birthday party");
+        assert_eq!(personal.address, "tiger.brilliant@gmail.com");
+
+        let neutral = select(&tiger(), "Say hi to Tiger");
+        assert_eq!(neutral.reason, SelectionReason::Primary);
+        assert_eq!(neutral.address, "tiger.brilliant@gmail.com");
+    }
+
+    #[test]
+    fn test_falls_back_to_primary_without_a_fitting_label() {
+        let contact = Contact::new("c003", "Dolphin", "Wise").with_email(
+            ContactEmail::new(ContactLabel::Work, "d.wise@citymedical.com").with_primary(true),
+        );
+        let selection = select(&contact, "Happy birthday!");
+        assert_eq!(selection.reason, SelectionReason::OnlyAddress);
+        assert_eq!(
+            selection.explanation,
+            "Using d.wise@citymedical.com, the only email of Dolphin Wise. \
+             Dolphin Wise has no personal email."
+        );
+    }
+
+    #[test]
+    fn test_preference_overrides_context() {
+        let mut contact = tiger();
+        assert!(prefer_address(
+            &mut contact,
+            ContactChannel::Email,
+            Some(MessageContext::Work),
+            "TIGER.BRILLIANT@gmail.com"
+        ));
+        assert_eq!(
+            contact
+                .extensions
+                .get("X-PREFERRED-EMAIL-WORK")
+                .map(String::as_str),
+            Some("tiger.brilliant@gmail.com")
+        );
+        let selection = select(&contact, "Send Tiger the project report");
+        assert_eq!(selection.reason, SelectionReason::Preference);
+        assert_eq!(selection.address, "tiger.brilliant@gmail.com");
+
+        assert!(!prefer_address(
+            &mut contact,
+            ContactChannel::Email,
+            None,
+            "someone@else.com"
+        ));
+    }
+
+    #[test]
+    fn test_phones_and_channels() {
+        let selector = AddressSelector::new();
+        let signal = ContextSignal::new(MessageContext::Personal, "test");
+        let phone = selector
+            .select(&tiger(), ContactChannel::Phone, Some(&signal))
+            .unwrap();
+        assert_eq!(phone.address, "+1-555-123-4567");
+
+        let mut contact = tiger();
+        assert!(prefer_address(
+            &mut contact,
+            ContactChannel::Phone,
+            None,
+            "555 987 6543"
+        ));
+        let phone = selector
+            .select(&contact, ContactChannel::Phone, Some(&signal))
+            .unwrap();
+        assert_eq!(phone.address, "+1-555-987-6543");
+
+        assert_eq!(
+            selector.preferred_channel(&contact),
+            Some(ContactChannel::Email)
+        );
+        contact
+            .extensions
+            .insert(PREFERRED_CHANNEL.to_string(), "phone".to_string());
+        assert_eq!(
+            selector.preferred_channel(&contact),
+            Some(ContactChannel::Phone)
+        );
+        assert_eq!(
+            selector.preferred_channel(&Contact::new("c099", "No", "Address")),
+            None
+        );
+    }
+
+    #[test]
+    fn test_infer_from_event_category() {
+        let event: CalendarEvent = serde_json::from_value(serde_json::json!({
+            "id": "evt_002",
+            "title": "Project Meeting",
+            "startDateTime": "2025-08-22T10:00:00-04:00",
+            "endDateTime": "2025-08-22T11:30:00-04:00",
+            "category": "work",
+            "priority": "medium",
+            "status": "confirmed"
+        }))
+        .unwrap();
+        let signal = AddressSelector::new().infer_from_event(&event).unwrap();
+        assert_eq!(signal.context, MessageContext::Work);
+        assert_eq!(
+            signal.evidence,
+            "the calendar event is in the \"work\" category"
+        );
+    }
+}
@@ -0,0 +1,61 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{agents::AgentResult, infra::contacts::Contact};
+
+use super::{contact_action_status::ContactActionStatus, contact_command::ContactCommand};
+
+/// Outcome of planning a contact management request. Writes are only
+/// described here; they happen when the result is passed to
+/// [`ContactAgent::confirm`](super::ContactAgent::confirm).
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ContactActionResult {
+    /// `None` when the request was not understood.
+    pub command: Option<ContactCommand>,
+    pub status: ContactActionStatus,
+    /// The contact as it will be stored after confirmation, or the contact a
+    /// query was about.
+    pub contact: Option<Contact>,
+    /// Contacts a list request matched.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub matches: Vec<Contact>,
+    /// Confirmation question, answer or follow-up question for the user.
+    pub message: String,
+    /// Identifies a pending write; [`ContactAgent::confirm`](super::ContactAgent::confirm)
+    /// accepts each token once.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+}
+
+impl ContactActionResult {
+    pub fn new(
+        command: Option<ContactCommand>,
+        status: ContactActionStatus,
+        contact: Option<Contact>,
+        message: &str,
+    ) -> Self {
+        Self {
+            command,
+            status,
+            contact,
+            matches: Vec::new(),
+            message: message.to_string(),
+            token: None,
+        }
+    }
+
+    pub fn with_matches(mut self, matches: Vec<Contact>) -> Self {
+        self.matches = matches;
+        self
+    }
+
+    pub fn with_token(mut self, token: &str) -> Self {
+        self.token = Some(token.to_string());
+        self
+    }
+
+    pub fn needs_confirmation(&self) -> bool {
+        self.status == ContactActionStatus::PendingConfirmation
+    }
+}
+
+impl AgentResult for ContactActionResult {}
@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Where a contact management request stands after the agent planned it.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ContactActionStatus {
+    /// A write is ready and waits for [`ContactAgent::confirm`](super::ContactAgent::confirm).
+    PendingConfirmation,
+    /// Nothing to write: a query was answered or the change is already there.
+    Answered,
+    /// The request or its contact reference needs a follow-up question.
+    NeedsClarification,
+}
+
+impl ContactActionStatus {
+    pub fn to_str(&self) -> &str {
+        match self {
+            Self::PendingConfirmation => "pending_confirmation",
+            Self::Answered => "answered",
+            Self::NeedsClarification => "needs_clarification",
+        }
+    }
+}
+
+impl fmt::Display for ContactActionStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_str())
+    }
+}
@@ -1,37 +1,539 @@
-use crate::agents::{Agent, AgentError, AgentParam, contact::contact_result::ContactResult};
+use std::{
+    collections::HashSet,
+    sync::{
+        Mutex, MutexGuard,
+        atomic::{AtomicUsize, Ordering},
+    },
+};
 
-pub struct ContactAgent {}
+use crate::{
+    agents::{Agent, AgentError, AgentParam, contact::contact_result::ContactResult},
+    infra::contacts::{Contact, ContactQuery, ContactStore},
+};
 
-impl ContactAgent {
-    pub fn new() -> Self {
-        Self {}
+use super::{
+    address_selection::AddressSelection,
+    address_selector::{AddressSelector, prefer_address},
+    contact_action_result::ContactActionResult,
+    contact_action_status::ContactActionStatus,
+    contact_channel::ContactChannel,
+    contact_command::ContactCommand,
+    contact_command_parser::parse_contact_command,
+    contact_deduplicator::ContactDeduplicator,
+    contact_field::ContactField,
+    contact_operation::ContactOperation,
+    contact_query_parser::parse_contact_query,
+    context_signal::ContextSignal,
+    match_field::MatchField,
+    recipient_candidate::RecipientCandidate,
+    recipient_matcher::RecipientMatcher,
+    resolution_status::ResolutionStatus,
+};
+
+/// Resolves recipient references ("Eva", "Dr. Wise", "the engineering team")
+/// to contacts in the store and handles contact management requests ("what's
+/// Tiger's email?", "tag Dolphin as doctor", "show my favourite contacts in New
+/// York"). Requests that write are planned first and only applied by
+/// [`confirm`](Self::confirm), once each.
+pub struct ContactAgent<S: ContactStore> {
+    store: S,
+    matcher: RecipientMatcher,
+    /// Tokens of planned writes not confirmed yet.
+    pending: Mutex<HashSet<String>>,
+    planned: AtomicUsize,
+}
+
+impl<S: ContactStore> ContactAgent<S> {
+    pub fn new(store: S) -> Self {
+        Self {
+            store,
+            matcher: RecipientMatcher::default(),
+            pending: Mutex::new(HashSet::new()),
+            planned: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn with_matcher(mut self, matcher: RecipientMatcher) -> Self {
+        self.matcher = matcher;
+        self
+    }
+
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// Applies a write planned by this agent and returns the stored contact.
+    /// Each plan is confirmed at most once. Updates apply the requested changes
+    /// to the contact as it is now, so edits made since planning are kept; a
+    /// create is refused when the contact is already stored.
+    pub fn confirm(&mut self, action: &ContactActionResult) -> Result<Contact, AgentError> {
+        let (Some(command), Some(contact)) = (&action.command, &action.contact) else {
+            return Err(AgentError::ProcessingError(
+                "Nothing to confirm".to_string(),
+            ));
+        };
+        if !action.needs_confirmation() {
+            return Err(AgentError::ProcessingError(format!(
+                "Action is {}, not pending confirmation",
+                action.status
+            )));
+        }
+        let token = action.token.as_deref().unwrap_or_default();
+        if !self.pending()?.contains(token) {
+            return Err(AgentError::ProcessingError(format!(
+                "Action '{token}' was already confirmed or was not planned by this agent"
+            )));
+        }
+
+        let stored = match command.operation {
+            ContactOperation::Create => {
+                if let Some(existing) = self
+                    .contacts()?
+                    .iter()
+                    .find(|existing| is_same_contact(existing, contact))
+                {
+                    return Err(AgentError::ProcessingError(format!(
+                        "{} is already in your contacts ({})",
+                        contact.name(),
+                        existing.id
+                    )));
+                }
+                self.store.create(contact.clone())
+            }
+            _ => {
+                let mut current = self.contact(&contact.id)?;
+                let mut changed = false;
+                for change in &command.changes {
+                    changed |= change.apply(&mut current);
+                }
+                if changed {
+                    self.store.update(current)
+                } else {
+                    Ok(current)
+                }
+            }
+        }
+        .map_err(|e| AgentError::ProcessingError(e.to_string()))?;
+        self.pending()?.remove(token);
+        Ok(stored)
+    }
+
+    /// The address to use for `contact_id` on `channel`, given what the message
+    /// is about. `None` when the contact has no address on that channel.
+    pub fn select_address(
+        &self,
+        contact_id: &str,
+        channel: ContactChannel,
+        signal: Option<&ContextSignal>,
+    ) -> Result<Option<AddressSelection>, AgentError> {
+        let contact = self.contact(contact_id)?;
+        Ok(AddressSelector::new().select(&contact, channel, signal))
+    }
+
+    /// Stores the user's correction of `selection`: `address` is used from now
+    /// on for the same contact, channel and context.
+    pub fn prefer_address(
+        &mut self,
+        selection: &AddressSelection,
+        address: &str,
+    ) -> Result<Contact, AgentError> {
+        let mut contact = self.contact(&selection.contact_id)?;
+        if !prefer_address(&mut contact, selection.channel, selection.context, address) {
+            return Err(AgentError::ProcessingError(format!(
+                "{address} is not one of {}'s addresses",
+                contact.name()
+            )));
+        }
+        self.store
+            .update(contact)
+            .map_err(|e| AgentError::ProcessingError(e.to_string()))
+    }
+
+    /// Registers a planned write so [`confirm`](Self::confirm) accepts it once.
+    fn pending_confirmation(
+        &self,
+        action: ContactActionResult,
+    ) -> Result<ContactActionResult, AgentError> {
+        if !action.needs_confirmation() {
+            return Ok(action);
+        }
+        let token = format!("a{:03}", self.planned.fetch_add(1, Ordering::Relaxed) + 1);
+        self.pending()?.insert(token.clone());
+        Ok(action.with_token(&token))
+    }
+
+    fn pending(&self) -> Result<MutexGuard<'_, HashSet<String>>, AgentError> {
+        self.pending
+            .lock()
+            .map_err(|e| AgentError::ProcessingError(e.to_string()))
+    }
+
+    fn contact(&self, id: &str) -> Result<Contact, AgentError> {
+        self.store
+            .get(id)
+            .map_err(|e| AgentError::ProcessingError(e.to_string()))?
+            .ok_or_else(|| AgentError::ProcessingError(format!("Contact {id} not found")))
+    }
+
+    fn contacts(&self) -> Result<Vec<Contact>, AgentError> {
+        self.store
+            .list()
+            .map_err(|e| AgentError::ProcessingError(e.to_string()))
+    }
+
+    /// Contacts matching `query`. A lone "in X" is read as a city by
+    /// [`parse_contact_query`]; when no city matches, X is tried as a department.
+    pub fn find(&self, query: &ContactQuery) -> Result<Vec<Contact>, AgentError> {
+        let query_store = |query: &ContactQuery| {
+            self.store
+                .query(query)
+                .map_err(|e| AgentError::ProcessingError(e.to_string()))
+        };
+        let matches = query_store(query)?;
+        if !matches.is_empty() || query.department.is_some() {
+            return Ok(matches);
+        }
+        let Some(city) = &query.city else {
+            return Ok(matches);
+        };
+        let mut as_department = query.clone();
+        as_department.department = Some(city.clone());
+        as_department.city = None;
+        query_store(&as_department)
+    }
+
+    fn resolve(&self, param: &ContactParam) -> Result<ContactResult, AgentError> {
+        let query = param
+            .query()
+            .filter(|query| !query.is_empty())
+            .cloned()
+            .or_else(|| parse_contact_query(param.input()));
+        let Some(query) = query else {
+            return Ok(self.matcher.resolve(param.input(), &self.contacts()?));
+        };
+
+        let candidates: Vec<RecipientCandidate> = self
+            .find(&query)?
+            .iter()
+            .map(|contact| RecipientCandidate {
+                contact_id: contact.id.clone(),
+                name: contact.name(),
+                email: contact.primary_email().map(|email| email.address.clone()),
+                score: 1.0,
+                matched_on: MatchField::Filter,
+            })
+            .collect();
+        let status = if candidates.is_empty() {
+            ResolutionStatus::NotFound
+        } else {
+            ResolutionStatus::Group
+        };
+        Ok(ContactResult::new(param.input(), status, candidates))
+    }
+
+    fn plan(&self, input: &str) -> Result<ContactActionResult, AgentError> {
+        let Some(command) = parse_contact_command(input) else {
+            return Ok(ContactActionResult::new(
+                None,
+                ContactActionStatus::NeedsClarification,
+                None,
+                UNRECOGNISED,
+            ));
+        };
+        if command.operation == ContactOperation::List {
+            return self.plan_list(command);
+        }
+        let contacts = self.contacts()?;
+
+        let Some(reference) = command.reference.clone() else {
+            return self.pending_confirmation(plan_create(command, &contacts));
+        };
+        let resolution = self.matcher.resolve(&reference, &contacts);
+        let contact = resolution
+            .resolved()
+            .and_then(|candidate| contacts.iter().find(|c| c.id == candidate.contact_id));
+        let Some(contact) = contact else {
+            let question = match resolution.status {
+                ResolutionStatus::NotFound => {
+                    format!("I could not find {reference} in your contacts.")
+                }
+                _ => resolution.follow_up_question().unwrap_or_default(),
+            };
+            return Ok(ContactActionResult::new(
+                Some(command),
+                ContactActionStatus::NeedsClarification,
+                None,
+                &question,
+            ));
+        };
+
+        if command.operation == ContactOperation::Query {
+            let answer = answer_query(contact, &command.fields);
+            return Ok(ContactActionResult::new(
+                Some(command),
+                ContactActionStatus::Answered,
+                Some(contact.clone()),
+                &answer,
+            ));
+        }
+
+        let mut updated = contact.clone();
+        let mut changed = false;
+        for change in &command.changes {
+            changed |= change.apply(&mut updated);
+        }
+        let (status, message) = if changed {
+            (
+                ContactActionStatus::PendingConfirmation,
+                format!(
+                    "Update {}: {}? Reply yes to confirm.",
+                    contact.name(),
+                    command.describe_changes()
+                ),
+            )
+        } else {
+            (
+                ContactActionStatus::Answered,
+                format!("{} already has that information.", contact.name()),
+            )
+        };
+        self.pending_confirmation(ContactActionResult::new(
+            Some(command),
+            status,
+            Some(updated),
+            &message,
+        ))
+    }
+
+    fn plan_list(&self, command: ContactCommand) -> Result<ContactActionResult, AgentError> {
+        let query = command.query.clone().unwrap_or_default();
+        let matches = self.find(&query)?;
+        let names: Vec<String> = matches.iter().map(Contact::name).collect();
+        let message = match names.len() {
+            0 => format!("No contacts match {}.", query.describe()),
+            1 => format!("1 contact matches {}: {}.", query.describe(), names[0]),
+            count => format!(
+                "{count} contacts match {}: {}.",
+                query.describe(),
+                names.join(", ")
+            ),
+        };
+        Ok(
+            ContactActionResult::new(Some(command), ContactActionStatus::Answered, None, &message)
+                .with_matches(matches),
+        )
+    }
+}
+
+fn plan_create(command: ContactCommand, contacts: &[Contact]) -> ContactActionResult {
+    let mut contact = Contact::new("", "", "");
+    for change in &command.changes {
+        change.apply(&mut contact);
+    }
+    if contact.display_name.is_empty() {
+        contact.display_name = contact
+            .primary_email()
+            .map(|email| email.address.clone())
+            .unwrap_or_default();
+    }
+
+    let mut message = format!(
+        "Create contact {} ({})?",
+        contact.name(),
+        command.describe_changes()
+    );
+    let similar: Vec<String> = ContactDeduplicator::default()
+        .duplicates_of(&contact, contacts)
+        .iter()
+        .filter_map(|candidate| contacts.iter().find(|c| c.id == candidate.primary_id))
+        .map(Contact::name)
+        .collect();
+    if !similar.is_empty() {
+        message.push_str(&format!(" Similar contacts: {}.", similar.join(", ")));
+    }
+    message.push_str(" Reply yes to confirm.");
+    ContactActionResult::new(
+        Some(command),
+        ContactActionStatus::PendingConfirmation,
+        Some(contact),
+        &message,
+    )
+}
+
+/// Whether `stored` is the contact `planned` would create: same name and
+/// already holding each of its emails and phones.
+fn is_same_contact(stored: &Contact, planned: &Contact) -> bool {
+    stored.name().eq_ignore_ascii_case(&planned.name())
+        && planned.emails.iter().all(|email| {
+            stored
+                .emails
+                .iter()
+                .any(|known| known.address.eq_ignore_ascii_case(&email.address))
+        })
+        && planned.phone_numbers.iter().all(|phone| {
+            stored
+                .phone_numbers
+                .iter()
+                .any(|known| known.number == phone.number)
+        })
+}
+
+/// One line per requested field; company, job title, email and phone when
+/// the query named none.
+fn answer_query(contact: &Contact, fields: &[ContactField]) -> String {
+    let name = contact.name();
+    if fields.is_empty() {
+        let lines: Vec<String> = SUMMARY_FIELDS
+            .iter()
+            .filter_map(|&field| {
+                field_value(contact, field)
+                    .map(|value| format!("{name}'s {}: {value}", field.label()))
+            })
+            .collect();
+        if lines.is_empty() {
+            return format!("{name} has no details on file.");
+        }
+        return lines.join("\n");
     }
+    let lines: Vec<String> = fields
+        .iter()
+        .map(|&field| match field_value(contact, field) {
+            Some(value) => format!("{name}'s {}: {value}", field.label()),
+            None => format!("{name} has no {} on file.", field.label()),
+        })
+        .collect();
+    lines.join("\n")
 }
+
+fn field_value(contact: &Contact, field: ContactField) -> Option<String> {
+    let labelled = |values: Vec<(String, &str, bool)>| {
+        let values: Vec<String> = values
+            .into_iter()
+            .map(|(value, label, primary)| {
+                if primary {
+                    format!("{value} ({label}, primary)")
+                } else {
+                    format!("{value} ({label})")
+                }
+            })
+            .collect();
+        Some(values.join(", ")).filter(|values| !values.is_empty())
+    };
+    match field {
+        ContactField::Name => Some(contact.name()),
+        ContactField::Company => contact.company.clone(),
+        ContactField::JobTitle => contact.job_title.clone(),
+        ContactField::Nickname => contact.nickname.clone(),
+        ContactField::Birthday => contact.birthday.clone(),
+        ContactField::Notes => contact.notes.clone(),
+        ContactField::Email => labelled(
+            contact
+                .emails
+                .iter()
+                .map(|e| (e.address.clone(), e.label.to_str(), e.primary))
+                .collect(),
+        ),
+        ContactField::Phone => labelled(
+            contact
+                .phone_numbers
+                .iter()
+                .map(|p| (p.number.clone(), p.label.to_str(), p.primary))
+                .collect(),
+        ),
+        ContactField::Address => labelled(
+            contact
+                .addresses
+                .iter()
+                .map(|a| {
+                    let parts: Vec<&str> =
+                        [&a.street, &a.city, &a.state, &a.postal_code, &a.country]
+                            .into_iter()
+                            .flatten()
+                            .map(String::as_str)
+                            .collect();
+                    (parts.join(", "), a.label.to_str(), a.primary)
+                })
+                .collect(),
+        ),
+        ContactField::Tag => Some(contact.tags.join(", ")).filter(|tags| !tags.is_empty()),
+    }
+}
+
 pub struct ContactParam {
     input: String,
+    query: Option<ContactQuery>,
 }
 
 impl ContactParam {
     pub fn new(input: &String) -> Self {
         Self {
             input: input.clone(),
+            query: None,
         }
     }
+
+    /// Filters the classifier extracted for a group recipient (`params.filter`);
+    /// they take precedence over parsing the reference.
+    pub fn with_query(mut self, query: ContactQuery) -> Self {
+        self.query = Some(query);
+        self
+    }
+
+    /// The recipient reference to resolve, as extracted by the classifier.
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+
+    pub fn query(&self) -> Option<&ContactQuery> {
+        self.query.as_ref()
+    }
 }
 
 impl AgentParam for ContactParam {}
 
-impl Agent<ContactParam, ContactResult> for ContactAgent {
+/// A contact management request in the user's words.
+pub struct ContactCommandParam {
+    input: String,
+}
+
+impl ContactCommandParam {
+    pub fn new(input: &str) -> Self {
+        Self {
+            input: input.to_string(),
+        }
+    }
+
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+}
+
+impl AgentParam for ContactCommandParam {}
+
+impl<S: ContactStore> Agent<ContactParam, ContactResult> for ContactAgent<S> {
     fn process(
         &self,
-        _input: ContactParam,
+        input: ContactParam,
     ) -> impl std::future::Future<Output = Result<ContactResult, AgentError>> + Send {
-        async move {
-            // TODO: Implement contact data discovery logic
+        let result = self.resolve(&input);
+        async move { result }
+    }
+}
 
-            Err(AgentError::ProcessingError(
-                "Contact finding not implemented yet".to_string(),
-            ))
-        }
+impl<S: ContactStore> Agent<ContactCommandParam, ContactActionResult> for ContactAgent<S> {
+    fn process(
+        &self,
+        input: ContactCommandParam,
+    ) -> impl std::future::Future<Output = Result<ContactActionResult, AgentError>> + Send {
+        let result = self.plan(input.input());
+        async move { result }
     }
 }
+
+const SUMMARY_FIELDS: [ContactField; 4] = [
+    ContactField::Company,
+    ContactField::JobTitle,
+    ContactField::Email,
+    ContactField::Phone,
+];
+const UNRECOGNISED: &str = "I could not tell what to do with your contacts. Try \"what's Tiger's email?\", \"tag Dolphin as doctor\" or \"add Carlos's work phone +55 11 91234-5678\".";
@@ -0,0 +1,103 @@
+use serde::{Deserialize, Serialize};
+
+use crate::infra::contacts::{Contact, ContactEmail, ContactLabel, ContactPhone};
+
+use super::contact_field::ContactField;
+
+/// One edit requested for a contact: set a single value, or add a phone,
+/// email or tag.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ContactChange {
+    pub field: ContactField,
+    pub value: String,
+    /// Label for added phones and emails; `mobile` and `personal` when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<ContactLabel>,
+}
+
+impl ContactChange {
+    pub fn new(field: ContactField, value: &str) -> Self {
+        Self {
+            field,
+            value: value.to_string(),
+            label: None,
+        }
+    }
+
+    pub fn with_label(mut self, label: ContactLabel) -> Self {
+        self.label = Some(label);
+        self
+    }
+
+    /// Applies the change and reports whether `contact` changed. Phones,
+    /// emails and tags already on the contact are not added twice; the first
+    /// phone or email becomes the primary one.
+    pub fn apply(&self, contact: &mut Contact) -> bool {
+        let value = self.value.trim();
+        let set = |slot: &mut Option<String>| {
+            let changed = slot.as_deref() != Some(value);
+            *slot = Some(value.to_string());
+            changed
+        };
+        match self.field {
+            ContactField::Name => {
+                let (first_name, last_name) = value.split_once(' ').unwrap_or((value, ""));
+                let changed = contact.name() != value;
+                contact.first_name = first_name.to_string();
+                contact.last_name = last_name.trim().to_string();
+                contact.display_name = value.to_string();
+                changed
+            }
+            ContactField::Company => set(&mut contact.company),
+            ContactField::JobTitle => set(&mut contact.job_title),
+            ContactField::Nickname => set(&mut contact.nickname),
+            ContactField::Birthday => set(&mut contact.birthday),
+            ContactField::Notes => set(&mut contact.notes),
+            ContactField::Phone => {
+                let phone = ContactPhone::new(self.label.unwrap_or(ContactLabel::Mobile), value)
+                    .with_primary(contact.phone_numbers.is_empty());
+                if contact.phone_numbers.iter().any(|p| p.same_number(&phone)) {
+                    return false;
+                }
+                contact.phone_numbers.push(phone);
+                true
+            }
+            ContactField::Email => {
+                if contact
+                    .emails
+                    .iter()
+                    .any(|e| e.address.eq_ignore_ascii_case(value))
+                {
+                    return false;
+                }
+                let email = ContactEmail::new(self.label.unwrap_or(ContactLabel::Personal), value)
+                    .with_primary(contact.emails.is_empty());
+                contact.emails.push(email);
+                true
+            }
+            ContactField::Tag => {
+                if contact.has_tag(value) {
+                    return false;
+                }
+                contact.tags.push(value.to_string());
+                true
+            }
+            ContactField::Address => false,
+        }
+    }
+
+    /// "add work phone +55 11 91234-5678", "set company to Acme".
+    pub fn describe(&self) -> String {
+        let label = self
+            .label
+            .map(|label| format!("{} ", label.to_str()))
+            .unwrap_or_default();
+        match self.field {
+            ContactField::Phone | ContactField::Email => {
+                format!("add {label}{} {}", self.field.label(), self.value)
+            }
+            ContactField::Tag => format!("add tag {}", self.value),
+            field => format!("set {} to {}", field.label(), self.value),
+        }
+    }
+}
@@ -0,0 +1,64 @@
+use serde::{Deserialize, Serialize};
+
+use crate::infra::contacts::ContactQuery;
+
+use super::{
+    contact_change::ContactChange, contact_field::ContactField, contact_operation::ContactOperation,
+};
+
+/// A contact management request in structured form, as produced by
+/// [`parse_contact_command`](super::parse_contact_command).
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ContactCommand {
+    pub operation: ContactOperation,
+    /// The existing contact the request is about ("Tiger", "Dr. Wise");
+    /// `None` when creating.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reference: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub changes: Vec<ContactChange>,
+    /// Fields a query asks for; empty means a summary of the contact.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<ContactField>,
+    /// Filters of a list request ("everyone in Engineering at Tech Solutions").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub query: Option<ContactQuery>,
+}
+
+impl ContactCommand {
+    pub fn new(operation: ContactOperation) -> Self {
+        Self {
+            operation,
+            reference: None,
+            changes: Vec::new(),
+            fields: Vec::new(),
+            query: None,
+        }
+    }
+
+    pub fn with_reference(mut self, reference: &str) -> Self {
+        self.reference = Some(reference.to_string());
+        self
+    }
+
+    pub fn with_change(mut self, change: ContactChange) -> Self {
+        self.changes.push(change);
+        self
+    }
+
+    pub fn with_field(mut self, field: ContactField) -> Self {
+        self.fields.push(field);
+        self
+    }
+
+    pub fn with_query(mut self, query: ContactQuery) -> Self {
+        self.query = Some(query);
+        self
+    }
+
+    /// The requested changes joined for a confirmation question.
+    pub fn describe_changes(&self) -> String {
+        let changes: Vec<String> = self.changes.iter().map(ContactChange::describe).collect();
+        changes.join(", ")
+    }
+}
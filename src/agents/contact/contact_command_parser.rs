@@ -0,0 +1,515 @@
+use crate::infra::contacts::ContactLabel;
+
+use super::{
+    contact_change::ContactChange, contact_command::ContactCommand, contact_field::ContactField,
+    contact_operation::ContactOperation, contact_query_parser::parse_contact_query,
+};
+
+/// Turns a contact management request in English or Portuguese into a
+/// [`ContactCommand`]:
+///
+/// - "what's Tiger's email?", "qual o telefone do Dr. Wise?" → query
+/// - "tag Dolphin as doctor", "marque Fox como cliente" → tag
+/// - "add Carlos's new work phone +55 11 91234-5678", "set Owl's company to
+///   Wisdom University" → update
+/// - "add John Smith from XYZ Company to my professional contacts" → create
+/// - "show my favourite contacts in New York", "who works at Tech Solutions?"
+///   → list, with the filters from [`parse_contact_query`]
+///
+/// Returns `None` when the request does not follow any of these shapes.
+pub fn parse_contact_command(input: &str) -> Option<ContactCommand> {
+    let text = input.trim().trim_end_matches(['.', '!']).trim();
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let verb = normalize_word(words.first()?);
+
+    let asks = QUERY_WORDS.contains(&verb.as_str())
+        || LIST_VERBS.contains(&verb.as_str())
+        || text.ends_with('?');
+    if asks && let Some(query) = parse_contact_query(text) {
+        return Some(ContactCommand::new(ContactOperation::List).with_query(query));
+    }
+
+    if TAG_VERBS.contains(&verb.as_str()) {
+        parse_tag(&words[1..])
+    } else if WRITE_VERBS.contains(&verb.as_str()) {
+        parse_write(text, &words)
+    } else if QUERY_WORDS.contains(&verb.as_str()) || text.ends_with('?') {
+        parse_query(&words)
+    } else {
+        None
+    }
+}
+
+fn parse_tag(words: &[&str]) -> Option<ContactCommand> {
+    let separator = words
+        .iter()
+        .position(|word| TAG_SEPARATORS.contains(&normalize_word(word).as_str()))?;
+    let reference = join_words(&words[..separator]);
+    let tags = join_words(&words[separator + 1..]);
+    if reference.is_empty() || tags.is_empty() {
+        return None;
+    }
+
+    let command = split_list(&tags).into_iter().fold(
+        ContactCommand::new(ContactOperation::Tag),
+        |command, tag| command.with_change(ContactChange::new(ContactField::Tag, &tag)),
+    );
+    Some(command.with_reference(&reference))
+}
+
+fn parse_query(words: &[&str]) -> Option<ContactCommand> {
+    let reference = possessive_reference(words).or_else(|| {
+        let connector = words
+            .iter()
+            .rposition(|word| QUERY_CONNECTORS.contains(&normalize_word(word).as_str()))?;
+        Some(join_words(&words[connector + 1..])).filter(|reference| !reference.is_empty())
+    })?;
+
+    let command = detect_fields(words).into_iter().fold(
+        ContactCommand::new(ContactOperation::Query),
+        |command, field| command.with_field(field),
+    );
+    Some(command.with_reference(&reference))
+}
+
+fn parse_write(text: &str, words: &[&str]) -> Option<ContactCommand> {
+    let fields = detect_fields(&words[1..]);
+    let mentions_contacts = words
+        .iter()
+        .any(|word| CONTACTS_WORDS.contains(&normalize_word(word).as_str()));
+    let reference = possessive_reference(words).or_else(|| connector_reference(words));
+
+    match reference {
+        Some(reference) if !fields.is_empty() && !mentions_contacts => {
+            parse_update(text, words, &fields, &reference)
+        }
+        _ => parse_create(text, words),
+    }
+}
+
+fn parse_update(
+    text: &str,
+    words: &[&str],
+    fields: &[ContactField],
+    reference: &str,
+) -> Option<ContactCommand> {
+    let label = detect_label(words);
+    let mut command = ContactCommand::new(ContactOperation::Update).with_reference(reference);
+    for &field in fields {
+        let changes = match field {
+            ContactField::Email => find_emails(text),
+            ContactField::Phone => find_phones(text),
+            ContactField::Address => Vec::new(),
+            _ => value_after(words, field).into_iter().collect(),
+        };
+        for value in changes {
+            let change = ContactChange::new(field, &value);
+            let label = match field {
+                ContactField::Email => label.and_then(email_label),
+                ContactField::Phone => label,
+                _ => None,
+            };
+            command = command.with_change(match label {
+                Some(label) => change.with_label(label),
+                None => change,
+            });
+        }
+    }
+    Some(command).filter(|command| !command.changes.is_empty())
+}
+
+fn parse_create(text: &str, words: &[&str]) -> Option<ContactCommand> {
+    let start = words[1..]
+        .iter()
+        .position(|word| !CREATE_FILLERS.contains(&normalize_word(word).as_str()))?
+        + 1;
+    let name = join_words(&take_until_boundary(&words[start..]));
+    let part_after = |markers: &[&str]| {
+        words
+            .iter()
+            .position(|word| markers.contains(&normalize_word(word).as_str()))
+            .map(|index| join_words(&take_until_boundary(&words[index + 1..])))
+            .filter(|value| !value.is_empty())
+    };
+
+    let mut command = ContactCommand::new(ContactOperation::Create);
+    if !name.is_empty() {
+        command = command.with_change(ContactChange::new(ContactField::Name, &name));
+    }
+    if let Some(company) = part_after(COMPANY_MARKERS) {
+        command = command.with_change(ContactChange::new(ContactField::Company, &company));
+    }
+    if let Some(role) = part_after(ROLE_MARKERS) {
+        command = command.with_change(ContactChange::new(ContactField::JobTitle, &role));
+    }
+
+    let label = detect_label(words);
+    let emails = find_emails(text);
+    for email in &emails {
+        let change = ContactChange::new(ContactField::Email, email);
+        command = command.with_change(match label.and_then(email_label) {
+            Some(label) => change.with_label(label),
+            None => change,
+        });
+    }
+    for phone in find_phones(text) {
+        let change = ContactChange::new(ContactField::Phone, &phone);
+        command = command.with_change(match label {
+            Some(label) => change.with_label(label),
+            None => change,
+        });
+    }
+
+    // "to my professional contacts" → tag "professional".
+    let my = words.iter().position(|word| normalize_word(word) == "my");
+    let contacts = words
+        .iter()
+        .rposition(|word| CONTACTS_WORDS.contains(&normalize_word(word).as_str()));
+    if let (Some(my), Some(contacts)) = (my, contacts)
+        && my + 1 < contacts
+    {
+        let relationship = join_words(&words[my + 1..contacts]);
+        command = command.with_change(ContactChange::new(ContactField::Tag, &relationship));
+    }
+
+    Some(command).filter(|_| !name.is_empty() || !emails.is_empty())
+}
+
+/// "Tiger's", "Dr. Wise's": the possessive word plus the capitalised words
+/// right before it.
+fn possessive_reference(words: &[&str]) -> Option<String> {
+    let index = words.iter().position(|word| {
+        let word = normalize_word(word);
+        possessive_base(&word).is_some() && !CONTRACTIONS.contains(&word.as_str())
+    })?;
+    let base = trim_punctuation(words[index]);
+    let base = possessive_base(base)?;
+
+    let mut start = index;
+    while start > 0 && is_name_word(words[start - 1]) {
+        start -= 1;
+    }
+    let mut reference: Vec<&str> = words[start..index].to_vec();
+    reference.push(base);
+    Some(join_words(&reference)).filter(|reference| !reference.is_empty())
+}
+
+fn possessive_base(word: &str) -> Option<&str> {
+    word.strip_suffix("'s")
+        .or_else(|| word.strip_suffix("’s"))
+        .or_else(|| word.strip_suffix("s'").map(|_| &word[..word.len() - 1]))
+}
+
+/// "the email of Tiger to …", "o e-mail do Tiger para …": the words after the
+/// connector, up to the value.
+fn connector_reference(words: &[&str]) -> Option<String> {
+    let connector = words
+        .iter()
+        .position(|word| WRITE_CONNECTORS.contains(&normalize_word(word).as_str()))?;
+    let reference: Vec<&str> = words[connector + 1..]
+        .iter()
+        .take_while(|word| {
+            !VALUE_MARKERS.contains(&normalize_word(word).as_str()) && !is_value_token(word)
+        })
+        .copied()
+        .collect();
+    Some(join_words(&reference)).filter(|reference| !reference.is_empty())
+}
+
+/// Text after "to"/"para"/"as" that follows the field keyword.
+fn value_after(words: &[&str], field: ContactField) -> Option<String> {
+    let field_index = words
+        .iter()
+        .position(|word| field_for_word(&normalize_word(word)) == Some(field))?;
+    let marker = words[field_index + 1..]
+        .iter()
+        .position(|word| VALUE_MARKERS.contains(&normalize_word(word).as_str()))?
+        + field_index
+        + 1;
+    Some(join_words(&words[marker + 1..])).filter(|value| !value.is_empty())
+}
+
+fn take_until_boundary<'a>(words: &[&'a str]) -> Vec<&'a str> {
+    let mut taken = Vec::new();
+    for word in words {
+        let normalized = normalize_word(word);
+        if CREATE_BOUNDARIES.contains(&normalized.as_str())
+            || CONTACTS_WORDS.contains(&normalized.as_str())
+            || is_value_token(word)
+        {
+            break;
+        }
+        taken.push(*word);
+        if word.ends_with(',') {
+            break;
+        }
+    }
+    taken
+}
+
+fn detect_fields(words: &[&str]) -> Vec<ContactField> {
+    let mut fields = Vec::new();
+    for word in words {
+        if let Some(field) = field_for_word(&normalize_word(word))
+            && !fields.contains(&field)
+        {
+            fields.push(field);
+        }
+    }
+    fields
+}
+
+fn field_for_word(word: &str) -> Option<ContactField> {
+    let word = word.strip_suffix("'s").unwrap_or(word);
+    match word {
+        "email" | "emails" | "e-mail" | "e-mails" | "mail" => Some(ContactField::Email),
+        "phone" | "phones" | "number" | "numbers" | "telefone" | "telefones" | "celular"
+        | "número" | "numero" => Some(ContactField::Phone),
+        "company" | "employer" | "empresa" => Some(ContactField::Company),
+        "title" | "role" | "position" | "cargo" => Some(ContactField::JobTitle),
+        "nickname" | "apelido" => Some(ContactField::Nickname),
+        "birthday" | "aniversário" | "aniversario" => Some(ContactField::Birthday),
+        "address" | "endereço" | "endereco" => Some(ContactField::Address),
+        "notes" | "note" | "nota" | "notas" => Some(ContactField::Notes),
+        "tags" | "etiquetas" => Some(ContactField::Tag),
+        _ => None,
+    }
+}
+
+fn detect_label(words: &[&str]) -> Option<ContactLabel> {
+    words
+        .iter()
+        .find_map(|word| match normalize_word(word).as_str() {
+            "work" | "business" | "office" | "trabalho" | "comercial" => Some(ContactLabel::Work),
+            "mobile" | "cell" | "cellphone" | "celular" => Some(ContactLabel::Mobile),
+            "home" | "casa" | "residencial" => Some(ContactLabel::Home),
+            "personal" | "pessoal" => Some(ContactLabel::Personal),
+            _ => None,
+        })
+}
+
+/// Emails are `work` or `personal`; "home email" means personal and "mobile" does not apply.
+fn email_label(label: ContactLabel) -> Option<ContactLabel> {
+    match label {
+        ContactLabel::Home | ContactLabel::Personal => Some(ContactLabel::Personal),
+        ContactLabel::Work => Some(ContactLabel::Work),
+        ContactLabel::Mobile | ContactLabel::Other => None,
+    }
+}
+
+fn find_emails(text: &str) -> Vec<String> {
+    text.split_whitespace()
+        .map(trim_punctuation)
+        .filter(|word| {
+            word.split_once('@')
+                .is_some_and(|(local, domain)| !local.is_empty() && domain.contains('.'))
+        })
+        .map(str::to_string)
+        .collect()
+}
+
+/// Runs of digits, spaces, `+`, `-`, `.` and parentheses with at least
+/// seven digits; email addresses are skipped.
+fn find_phones(text: &str) -> Vec<String> {
+    let text: String = text
+        .split_whitespace()
+        .filter(|word| !word.contains('@'))
+        .collect::<Vec<_>>()
+        .join(" ");
+    let mut phones = Vec::new();
+    let mut current = String::new();
+    for c in text.chars().chain(std::iter::once('\n')) {
+        let starts = c.is_ascii_digit() || c == '+' || c == '(';
+        if starts || (!current.is_empty() && PHONE_CHARS.contains(c)) {
+            current.push(c);
+            continue;
+        }
+        let phone = current.trim_end_matches(|c: char| !c.is_ascii_digit());
+        if phone.chars().filter(char::is_ascii_digit).count() >= MIN_PHONE_DIGITS {
+            phones.push(phone.trim().to_string());
+        }
+        current.clear();
+    }
+    phones
+}
+
+fn is_value_token(word: &str) -> bool {
+    word.contains('@') || word.starts_with('+') || word.starts_with(|c: char| c.is_ascii_digit())
+}
+
+fn is_name_word(word: &str) -> bool {
+    word.starts_with(char::is_uppercase)
+        && !WRITE_VERBS.contains(&normalize_word(word).as_str())
+        && !QUERY_WORDS.contains(&normalize_word(word).as_str())
+        && !TAG_VERBS.contains(&normalize_word(word).as_str())
+}
+
+/// Splits "doctor, friend and neighbour" into its items.
+fn split_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .flat_map(|part| part.split(" and "))
+        .flat_map(|part| part.split(" e "))
+        .map(|part| trim_punctuation(part.trim()).to_string())
+        .filter(|part| !part.is_empty())
+        .collect()
+}
+
+fn join_words(words: &[&str]) -> String {
+    trim_punctuation(&words.join(" ")).to_string()
+}
+
+fn normalize_word(word: &str) -> String {
+    trim_punctuation(word).replace('’', "'").to_lowercase()
+}
+
+fn trim_punctuation(word: &str) -> &str {
+    word.trim_matches(|c: char| matches!(c, ',' | '.' | ';' | ':' | '!' | '?' | '"' | '(' | ')'))
+}
+
+const TAG_VERBS: &[&str] = &["tag", "label", "marque", "marcar", "etiquete"];
+const TAG_SEPARATORS: &[&str] = &["as", "with", "como", "com"];
+const WRITE_VERBS: &[&str] = &[
+    "add",
+    "set",
+    "update",
+    "change",
+    "save",
+    "create",
+    "adicione",
+    "adicionar",
+    "atualize",
+    "atualizar",
+    "altere",
+    "mude",
+    "salve",
+    "crie",
+];
+const QUERY_WORDS: &[&str] = &[
+    "what", "what's", "whats", "show", "find", "get", "give", "tell", "who", "who's", "qual",
+    "quais", "mostre", "quem",
+];
+const LIST_VERBS: &[&str] = &["list", "liste", "listar"];
+const QUERY_CONNECTORS: &[&str] = &["of", "for", "about", "is", "do", "da", "de", "sobre", "é"];
+const WRITE_CONNECTORS: &[&str] = &["of", "for", "do", "da"];
+const VALUE_MARKERS: &[&str] = &["to", "para", "as", "como", "is", "é"];
+const CONTACTS_WORDS: &[&str] = &["contact", "contacts", "contato", "contatos"];
+const CREATE_FILLERS: &[&str] = &[
+    "a", "an", "the", "new", "contact", "um", "o", "novo", "contato",
+];
+const CREATE_BOUNDARIES: &[&str] = &[
+    "from", "at", "as", "to", "with", "and", "na", "no", "com", "para", "como",
+];
+const COMPANY_MARKERS: &[&str] = &["from", "at", "na", "no"];
+const ROLE_MARKERS: &[&str] = &["as", "como"];
+const CONTRACTIONS: &[&str] = &[
+    "what's", "that's", "it's", "who's", "where's", "here's", "there's", "let's", "he's", "she's",
+];
+const PHONE_CHARS: &str = "0123456789+-(). ";
+const MIN_PHONE_DIGITS: usize = 7;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(input: &str) -> ContactCommand {
+        parse_contact_command(input).unwrap_or_else(|| panic!("not parsed: {input}"))
+    }
+
+    #[test]
+    fn test_parse_lists() {
+        let command = parse("Show my favourite contacts in New York");
+        assert_eq!(command.operation, ContactOperation::List);
+        assert_eq!(command.reference, None);
+        let query = command.query.unwrap();
+        assert_eq!(query.favorite, Some(true));
+        assert_eq!(query.city.as_deref(), Some("New York"));
+
+        let command = parse("quem trabalha na Tech Solutions?");
+        assert_eq!(command.operation, ContactOperation::List);
+
+        assert_eq!(
+            parse("who is Penguin Swift?").operation,
+            ContactOperation::Query
+        );
+    }
+
+    #[test]
+    fn test_parse_queries() {
+        let command = parse("What's Tiger's email?");
+        assert_eq!(command.operation, ContactOperation::Query);
+        assert_eq!(command.reference.as_deref(), Some("Tiger"));
+        assert_eq!(command.fields, vec![ContactField::Email]);
+
+        let command = parse("qual o telefone do Dr. Wise?");
+        assert_eq!(command.reference.as_deref(), Some("Dr. Wise"));
+        assert_eq!(command.fields, vec![ContactField::Phone]);
+
+        let command = parse("who is Penguin Swift?");
+        assert_eq!(command.reference.as_deref(), Some("Penguin Swift"));
+        assert!(command.fields.is_empty());
+    }
+
+    #[test]
+    fn test_parse_tags() {
+        let command = parse("tag Dolphin as doctor");
+        assert_eq!(command.operation, ContactOperation::Tag);
+        assert_eq!(command.reference.as_deref(), Some("Dolphin"));
+        assert_eq!(command.describe_changes(), "add tag doctor");
+
+        let command = parse("marque Fox Clever como cliente e vip");
+        assert_eq!(command.reference.as_deref(), Some("Fox Clever"));
+        assert_eq!(command.changes.len(), 2);
+        assert_eq!(command.changes[1].value, "vip");
+    }
+
+    #[test]
+    fn test_parse_updates() {
+        let command = parse("add Carlos's new work phone +55 11 91234-5678");
+        assert_eq!(command.operation, ContactOperation::Update);
+        assert_eq!(command.reference.as_deref(), Some("Carlos"));
+        assert_eq!(
+            command.changes,
+            vec![
+                ContactChange::new(ContactField::Phone, "+55 11 91234-5678")
+                    .with_label(ContactLabel::Work)
+            ]
+        );
+
+        let command = parse("set Owl Wise's company to Wisdom University.");
+        assert_eq!(command.reference.as_deref(), Some("Owl Wise"));
+        assert_eq!(
+            command.describe_changes(),
+            "set company to Wisdom University"
+        );
+
+        let command = parse("atualize o e-mail do Tiger para tiger@jungle.net");
+        assert_eq!(command.reference.as_deref(), Some("Tiger"));
+        assert_eq!(command.describe_changes(), "add email tiger@jungle.net");
+    }
+
+    #[test]
+    fn test_parse_create() {
+        let command = parse("Add John Smith from XYZ Company to my professional contacts");
+        assert_eq!(command.operation, ContactOperation::Create);
+        assert_eq!(command.reference, None);
+        assert_eq!(
+            command.describe_changes(),
+            "set name to John Smith, set company to XYZ Company, add tag professional"
+        );
+
+        let command = parse("add a new contact Ana Lima, ana@lima.com, mobile +55 21 99876-5432");
+        assert_eq!(
+            command.describe_changes(),
+            "set name to Ana Lima, add email ana@lima.com, add mobile phone +55 21 99876-5432"
+        );
+    }
+
+    #[test]
+    fn test_unrecognised_requests() {
+        assert_eq!(parse_contact_command(""), None);
+        assert_eq!(parse_contact_command("Send an email to Carlos"), None);
+        assert_eq!(parse_contact_command("tag Dolphin"), None);
+        assert_eq!(parse_contact_command("add"), None);
+    }
+}
@@ -0,0 +1,429 @@
+use std::cmp::Ordering;
+
+use crate::infra::contacts::{Contact, ContactAddress, ContactStore, ContactStoreError};
+
+use super::{
+    contact_merge::ContactMerge,
+    duplicate_candidate::DuplicateCandidate,
+    duplicate_signal::DuplicateSignal,
+    name_similarity::{name_similarity, normalize_name},
+};
+
+/// Finds contacts that describe the same person and merges them.
+///
+/// Each signal carries a weight and the weights combine as independent
+/// evidence (`1 - Π(1 - w)`): a shared email alone is enough, while a shared
+/// phone or a similar name alone is not, since offices share phone lines and
+/// people share names.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContactDeduplicator {
+    threshold: f32,
+}
+
+impl Default for ContactDeduplicator {
+    fn default() -> Self {
+        Self {
+            threshold: THRESHOLD,
+        }
+    }
+}
+
+impl ContactDeduplicator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Minimum score for a pair to be proposed as a merge.
+    pub fn with_threshold(mut self, threshold: f32) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    /// Scores `duplicate` against `primary`, whatever the threshold.
+    pub fn score(&self, primary: &Contact, duplicate: &Contact) -> DuplicateCandidate {
+        let mut signals = Vec::new();
+        let mut weights = Vec::new();
+
+        let shares_email = primary.emails.iter().any(|email| {
+            duplicate
+                .emails
+                .iter()
+                .any(|other| other.address.eq_ignore_ascii_case(&email.address))
+        });
+        if shares_email {
+            signals.push(DuplicateSignal::SharedEmail);
+            weights.push(EMAIL_WEIGHT);
+        }
+
+        let shares_phone = primary.phone_numbers.iter().any(|phone| {
+            duplicate
+                .phone_numbers
+                .iter()
+                .any(|other| phone.same_number(other))
+        });
+        if shares_phone {
+            signals.push(DuplicateSignal::SharedPhone);
+            weights.push(PHONE_WEIGHT);
+        }
+
+        let similarity = name_variants(primary)
+            .iter()
+            .flat_map(|a| {
+                name_variants(duplicate)
+                    .into_iter()
+                    .map(move |b| name_similarity(a, &b))
+            })
+            .fold(0.0f32, f32::max);
+        if similarity >= NAME_SIMILARITY {
+            signals.push(DuplicateSignal::SimilarName);
+            weights.push(NAME_WEIGHT * similarity);
+        }
+
+        let company = |contact: &Contact| contact.company.as_deref().map(normalize_name);
+        if company(primary).is_some_and(|name| !name.is_empty())
+            && company(primary) == company(duplicate)
+        {
+            signals.push(DuplicateSignal::SameCompany);
+            weights.push(COMPANY_WEIGHT);
+        }
+
+        let score = 1.0 - weights.iter().map(|weight| 1.0 - weight).product::<f32>();
+        DuplicateCandidate {
+            primary_id: primary.id.clone(),
+            duplicate_id: duplicate.id.clone(),
+            score,
+            signals,
+        }
+    }
+
+    /// Pairs at or above the threshold, best first. In each pair the contact
+    /// listed first is the primary.
+    pub fn find_duplicates(&self, contacts: &[Contact]) -> Vec<DuplicateCandidate> {
+        let mut candidates: Vec<DuplicateCandidate> = contacts
+            .iter()
+            .enumerate()
+            .flat_map(|(i, primary)| {
+                contacts[i + 1..]
+                    .iter()
+                    .map(move |duplicate| self.score(primary, duplicate))
+            })
+            .filter(|candidate| candidate.score >= self.threshold)
+            .collect();
+        candidates.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(Ordering::Equal));
+        candidates
+    }
+
+    /// Contacts in `contacts` that `contact` would duplicate, best first, with
+    /// the existing contact as primary.
+    pub fn duplicates_of(
+        &self,
+        contact: &Contact,
+        contacts: &[Contact],
+    ) -> Vec<DuplicateCandidate> {
+        let mut candidates: Vec<DuplicateCandidate> = contacts
+            .iter()
+            .map(|existing| self.score(existing, contact))
+            .filter(|candidate| candidate.score >= self.threshold)
+            .collect();
+        candidates.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(Ordering::Equal));
+        candidates
+    }
+
+    /// Folds `duplicate_id` into `primary_id`: the primary is updated with the
+    /// merged contact and the duplicate is deleted.
+    pub fn merge(
+        &self,
+        store: &mut impl ContactStore,
+        primary_id: &str,
+        duplicate_id: &str,
+    ) -> Result<ContactMerge, ContactStoreError> {
+        let get = |id: &str| {
+            store
+                .get(id)?
+                .ok_or_else(|| ContactStoreError::NotFound(id.to_string()))
+        };
+        let primary = get(primary_id)?;
+        let duplicate = get(duplicate_id)?;
+        if primary.id == duplicate.id {
+            return Err(ContactStoreError::DuplicateId(primary.id));
+        }
+
+        let merged = store.update(merge_contacts(&primary, &duplicate))?;
+        store.delete(&duplicate.id)?;
+        Ok(ContactMerge {
+            primary,
+            duplicate,
+            merged,
+        })
+    }
+}
+
+/// Combines two records of the same person. Single values come from
+/// `primary` and fall back to `duplicate`; lists are unioned without repeats,
+/// and `duplicate` entries lose their `primary` flag when `primary` already
+/// has one of that kind. The ids of every record folded in are kept in the
+/// `X-MERGED-FROM` extension.
+pub fn merge_contacts(primary: &Contact, duplicate: &Contact) -> Contact {
+    let mut merged = primary.clone();
+    let fill = |value: &mut String, other: &str| {
+        if value.is_empty() {
+            *value = other.to_string();
+        }
+    };
+    fill(&mut merged.first_name, &duplicate.first_name);
+    fill(&mut merged.last_name, &duplicate.last_name);
+    fill(&mut merged.display_name, &duplicate.display_name);
+    for (value, other) in [
+        (&mut merged.nickname, &duplicate.nickname),
+        (&mut merged.company, &duplicate.company),
+        (&mut merged.job_title, &duplicate.job_title),
+        (&mut merged.department, &duplicate.department),
+        (&mut merged.birthday, &duplicate.birthday),
+        (&mut merged.anniversary, &duplicate.anniversary),
+        (&mut merged.photo, &duplicate.photo),
+    ] {
+        if value.is_none() {
+            value.clone_from(other);
+        }
+    }
+
+    merged.notes = match (&primary.notes, &duplicate.notes) {
+        (Some(a), Some(b)) if a != b => Some(format!("{a}\n\n{b}")),
+        (a, b) => a.clone().or_else(|| b.clone()),
+    };
+
+    for phone in &duplicate.phone_numbers {
+        if !merged.phone_numbers.iter().any(|p| p.same_number(phone)) {
+            let has_primary = merged.phone_numbers.iter().any(|p| p.primary);
+            let phone = phone.clone().with_primary(phone.primary && !has_primary);
+            merged.phone_numbers.push(phone);
+        }
+    }
+    for email in &duplicate.emails {
+        if !merged
+            .emails
+            .iter()
+            .any(|e| e.address.eq_ignore_ascii_case(&email.address))
+        {
+            let has_primary = merged.emails.iter().any(|e| e.primary);
+            let email = email.clone().with_primary(email.primary && !has_primary);
+            merged.emails.push(email);
+        }
+    }
+    for address in &duplicate.addresses {
+        if !merged.addresses.iter().any(|a| same_address(a, address)) {
+            let has_primary = merged.addresses.iter().any(|a| a.primary);
+            let mut address = address.clone();
+            address.primary = address.primary && !has_primary;
+            merged.addresses.push(address);
+        }
+    }
+    for profile in &duplicate.social_profiles {
+        let known = merged.social_profiles.iter().any(|p| {
+            p.platform.eq_ignore_ascii_case(&profile.platform)
+                && ((p.username.is_some() && p.username == profile.username)
+                    || (p.url.is_some() && p.url == profile.url))
+        });
+        if !known {
+            merged.social_profiles.push(profile.clone());
+        }
+    }
+    for tag in &duplicate.tags {
+        if !merged.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+            merged.tags.push(tag.clone());
+        }
+    }
+
+    merged.favorite = primary.favorite || duplicate.favorite;
+    merged.created_at = [&primary.created_at, &duplicate.created_at]
+        .into_iter()
+        .flatten()
+        .min()
+        .cloned();
+    merged.updated_at = [&primary.updated_at, &duplicate.updated_at]
+        .into_iter()
+        .flatten()
+        .max()
+        .cloned();
+    for (key, value) in &duplicate.extensions {
+        merged
+            .extensions
+            .entry(key.clone())
+            .or_insert_with(|| value.clone());
+    }
+    let sources: Vec<String> = [primary, duplicate]
+        .iter()
+        .map(|contact| {
+            contact
+                .extensions
+                .get(MERGED_FROM)
+                .cloned()
+                .unwrap_or_else(|| contact.id.clone())
+        })
+        .collect();
+    merged
+        .extensions
+        .insert(MERGED_FROM.to_string(), sources.join(","));
+    merged
+}
+
+fn name_variants(contact: &Contact) -> Vec<String> {
+    let full_name = format!("{} {}", contact.first_name, contact.last_name);
+    [contact.display_name.as_str(), full_name.as_str()]
+        .into_iter()
+        .map(normalize_name)
+        .filter(|name| !name.is_empty())
+        .collect()
+}
+
+fn same_address(a: &ContactAddress, b: &ContactAddress) -> bool {
+    let key = |address: &ContactAddress| {
+        [&address.street, &address.city, &address.postal_code]
+            .map(|part| part.as_deref().map(normalize_name).unwrap_or_default())
+    };
+    key(a) == key(b)
+}
+
+/// Extension listing the ids of the records a merged contact came from.
+pub const MERGED_FROM: &str = "X-MERGED-FROM";
+
+const THRESHOLD: f32 = 0.75;
+const EMAIL_WEIGHT: f32 = 0.9;
+const PHONE_WEIGHT: f32 = 0.6;
+const NAME_WEIGHT: f32 = 0.6;
+const COMPANY_WEIGHT: f32 = 0.4;
+const NAME_SIMILARITY: f32 = 0.85;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infra::{
+        contacts::{ContactEmail, ContactLabel, ContactPhone},
+        persistence::InMemoryStore,
+    };
+
+    fn tiger() -> Contact {
+        Contact::new("c001", "Tiger", "Brilliant")
+            .with_company("Tech Solutions Inc.")
+            .with_email(
+                ContactEmail::new(ContactLabel::Work, "t.brilliant@techsolutions.com")
+                    .with_primary(true),
+            )
+            .with_phone(
+                ContactPhone::new(ContactLabel::Mobile, "+1-555-123-4567").with_primary(true),
+            )
+            .with_tag("work")
+    }
+
+    fn tiger_from_phone() -> Contact {
+        Contact::new("c018", "Tigre", "Brilliant")
+            .with_email(
+                ContactEmail::new(ContactLabel::Personal, "T.Brilliant@techsolutions.com")
+                    .with_primary(true),
+            )
+            .with_email(ContactEmail::new(ContactLabel::Personal, "tiger@home.net"))
+            .with_phone(ContactPhone::new(ContactLabel::Mobile, "(555) 123-4567"))
+            .with_phone(ContactPhone::new(ContactLabel::Home, "+1 555 000 1111").with_primary(true))
+            .with_birthday("1985-03-15")
+            .with_tag("Work")
+            .with_tag("gym")
+    }
+
+    #[test]
+    fn test_score_combines_signals() {
+        let deduplicator = ContactDeduplicator::new();
+        let candidate = deduplicator.score(&tiger(), &tiger_from_phone());
+        assert_eq!(
+            candidate.signals,
+            vec![
+                DuplicateSignal::SharedEmail,
+                DuplicateSignal::SharedPhone,
+                DuplicateSignal::SimilarName
+            ]
+        );
+        assert!(candidate.score > 0.95);
+    }
+
+    #[test]
+    fn test_shared_phone_or_name_alone_is_not_a_duplicate() {
+        let deduplicator = ContactDeduplicator::new();
+        let colleague = Contact::new("c020", "Owl", "Wise")
+            .with_phone(ContactPhone::new(ContactLabel::Work, "+1-555-123-4567"));
+        let namesake = Contact::new("c021", "Tiger", "Brilliant");
+
+        assert!(deduplicator.score(&tiger(), &colleague).score < THRESHOLD);
+        assert!(deduplicator.score(&tiger(), &namesake).score < THRESHOLD);
+        assert!(
+            deduplicator
+                .score(&tiger(), &namesake.with_company("Tech Solutions Inc"))
+                .score
+                >= THRESHOLD
+        );
+    }
+
+    #[test]
+    fn test_merge_unions_fields_and_respects_primary_flags() {
+        let merged = merge_contacts(&tiger(), &tiger_from_phone());
+
+        assert_eq!(merged.id, "c001");
+        assert_eq!(merged.first_name, "Tiger");
+        assert_eq!(merged.birthday.as_deref(), Some("1985-03-15"));
+        assert_eq!(merged.emails.len(), 2);
+        assert_eq!(merged.phone_numbers.len(), 2);
+        assert_eq!(
+            merged.primary_phone().map(|p| p.number.as_str()),
+            Some("+1-555-123-4567")
+        );
+        assert_eq!(merged.phone_numbers.iter().filter(|p| p.primary).count(), 1);
+        assert_eq!(merged.tags, vec!["work", "gym"]);
+        assert_eq!(
+            merged.extensions.get(MERGED_FROM).map(String::as_str),
+            Some("c001,c018")
+        );
+
+        let again = merge_contacts(&merged, &Contact::new("c019", "Tiger", "B."));
+        assert_eq!(
+            again.extensions.get(MERGED_FROM).map(String::as_str),
+            Some("c001,c018,c019")
+        );
+    }
+
+    #[test]
+    fn test_merge_in_store_is_reversible() {
+        let mut store = InMemoryStore::new();
+        store.create(tiger()).unwrap();
+        store.create(tiger_from_phone()).unwrap();
+        store
+            .create(Contact::new("c002", "Elephant", "Graceful"))
+            .unwrap();
+        let deduplicator = ContactDeduplicator::new();
+
+        let candidates = deduplicator.find_duplicates(&store.list().unwrap());
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].primary_id, "c001");
+        assert_eq!(candidates[0].duplicate_id, "c018");
+
+        let merge = deduplicator.merge(&mut store, "c001", "c018").unwrap();
+        assert_eq!(store.list().unwrap().len(), 2);
+        assert_eq!(store.get("c001").unwrap().unwrap(), merge.merged);
+        assert_eq!(store.get("c018").unwrap(), None);
+
+        merge.undo(&mut store).unwrap();
+        assert_eq!(store.get("c001").unwrap().unwrap(), tiger());
+        assert_eq!(store.get("c018").unwrap().unwrap(), tiger_from_phone());
+        assert!(matches!(
+            merge.undo(&mut store),
+            Err(ContactStoreError::DuplicateId(_))
+        ));
+    }
+
+    #[test]
+    fn test_merge_missing_contact_is_not_found() {
+        let mut store = InMemoryStore::new();
+        store.create(tiger()).unwrap();
+        assert!(matches!(
+            ContactDeduplicator::new().merge(&mut store, "c001", "c404"),
+            Err(ContactStoreError::NotFound(_))
+        ));
+    }
+}
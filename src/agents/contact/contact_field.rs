@@ -0,0 +1,46 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Contact field named in a contact management request.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ContactField {
+    Name,
+    Company,
+    JobTitle,
+    Nickname,
+    Phone,
+    Email,
+    Address,
+    Birthday,
+    Notes,
+    Tag,
+}
+
+impl ContactField {
+    pub fn to_str(&self) -> &str {
+        match self {
+            Self::Name => "name",
+            Self::Company => "company",
+            Self::JobTitle => "job_title",
+            Self::Nickname => "nickname",
+            Self::Phone => "phone",
+            Self::Email => "email",
+            Self::Address => "address",
+            Self::Birthday => "birthday",
+            Self::Notes => "notes",
+            Self::Tag => "tag",
+        }
+    }
+
+    /// Wording used in questions and answers, such as "job title".
+    pub fn label(&self) -> String {
+        self.to_str().replace('_', " ")
+    }
+}
+
+impl fmt::Display for ContactField {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_str())
+    }
+}
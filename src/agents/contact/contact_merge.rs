@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+
+use crate::infra::contacts::{Contact, ContactStore, ContactStoreError};
+
+/// A merge applied to a [`ContactStore`], with both originals kept so it can
+/// be undone. Serializable, so callers can keep it in the audit log.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ContactMerge {
+    pub primary: Contact,
+    pub duplicate: Contact,
+    pub merged: Contact,
+}
+
+impl ContactMerge {
+    /// Restores the primary as it was before the merge and recreates the
+    /// duplicate under its original id. Edits made to the merged contact
+    /// since the merge are discarded.
+    pub fn undo(&self, store: &mut impl ContactStore) -> Result<(), ContactStoreError> {
+        if store.get(&self.duplicate.id)?.is_some() {
+            return Err(ContactStoreError::DuplicateId(self.duplicate.id.clone()));
+        }
+        store.update(self.primary.clone())?;
+        store.create(self.duplicate.clone())?;
+        Ok(())
+    }
+}
@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// What a contact management request asks the agent to do.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ContactOperation {
+    Create,
+    Update,
+    Query,
+    /// Lists the contacts matching a [`ContactQuery`](crate::infra::contacts::ContactQuery).
+    List,
+    Tag,
+}
+
+impl ContactOperation {
+    pub fn to_str(&self) -> &str {
+        match self {
+            Self::Create => "create",
+            Self::Update => "update",
+            Self::Query => "query",
+            Self::List => "list",
+            Self::Tag => "tag",
+        }
+    }
+
+    /// Whether the operation writes to the store and so needs confirmation.
+    pub fn is_write(&self) -> bool {
+        !matches!(self, Self::Query | Self::List)
+    }
+}
+
+impl fmt::Display for ContactOperation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_str())
+    }
+}
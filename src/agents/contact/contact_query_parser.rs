@@ -0,0 +1,351 @@
+use crate::infra::contacts::ContactQuery;
+
+/// Turns a reference to a group of contacts in English or Portuguese into a
+/// [`ContactQuery`]:
+///
+/// - "everyone in Engineering at Tech Solutions" → department and company
+/// - "my favourite contacts in New York", "meus contatos favoritos em Nova York"
+///   → favourite and city
+/// - "the engineering team", "a equipe de engenharia" → department
+/// - "who works at Tech Solutions", "quem trabalha na Tech Solutions" → company
+/// - "contacts tagged family", "my college friends" → tags
+/// - "contacts whose notes mention AI" → notes
+/// - "everyone working as Software Engineer" → job title
+///
+/// "in X" is read as a department when a company follows ("in Engineering at
+/// Tech Solutions") and as a city otherwise. Returns `None` when the text does
+/// not name a group (no marker such as "everyone", "contacts", "team" or "my")
+/// or sets no filter, so single references like "Tiger" are left to the
+/// recipient matcher.
+pub fn parse_contact_query(input: &str) -> Option<ContactQuery> {
+    let text = input.trim().trim_end_matches(['.', '!', '?']).trim();
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let mut query = ContactQuery::new();
+    let mut is_group = false;
+    let mut place: Option<String> = None;
+    let mut pending: Vec<&str> = Vec::new();
+
+    let mut index = 0;
+    while index < words.len() {
+        let word = normalize_word(words[index]);
+        let word = word.as_str();
+        index += 1;
+
+        if FAVORITE_WORDS.contains(&word) {
+            query.favorite = Some(true);
+            is_group = true;
+        } else if TEAM_WORDS.contains(&word) {
+            is_group = true;
+            let department = if pending.is_empty() {
+                // "a equipe de engenharia", "the department of finance"
+                let start = index + usize::from(words.get(index).is_some_and(|w| is_of(w)));
+                let (value, end) = value_from(&words, start);
+                index = end;
+                value
+            } else {
+                join_words(&std::mem::take(&mut pending))
+            };
+            if !department.is_empty() {
+                query.department = Some(department);
+            }
+        } else if OWNER_WORDS.contains(&word) {
+            // "my college friends" → tags; "my favourite contacts" sets no tag.
+            is_group = true;
+            while let Some(next) = words.get(index) {
+                let next_word = normalize_word(next);
+                if is_boundary(&next_word) || TEAM_WORDS.contains(&next_word.as_str()) {
+                    break;
+                }
+                if FAVORITE_WORDS.contains(&next_word.as_str()) {
+                    query.favorite = Some(true);
+                } else if !GROUP_WORDS.contains(&next_word.as_str()) {
+                    pending.push(trim_punctuation(next));
+                }
+                index += 1;
+            }
+            if words
+                .get(index)
+                .is_none_or(|next| !TEAM_WORDS.contains(&normalize_word(next).as_str()))
+            {
+                query.tags.extend(pending.drain(..).map(str::to_string));
+            }
+        } else if GROUP_WORDS.contains(&word) || WORK_WORDS.contains(&word) {
+            is_group = true;
+        } else if PLACE_MARKERS.contains(&word) {
+            let (value, end) = value_from(&words, index);
+            index = end;
+            if !value.is_empty() {
+                place = Some(value);
+            }
+        } else if COMPANY_MARKERS.contains(&word) {
+            let (value, end) = value_from(&words, index);
+            index = end;
+            if !value.is_empty() {
+                query.company = Some(value);
+            }
+        } else if OF_WORDS.contains(&word) && is_group && query.department.is_none() {
+            // "todos da Engenharia"
+            let (value, end) = value_from(&words, index);
+            index = end;
+            if !value.is_empty() {
+                query.department = Some(value);
+            }
+        } else if TAG_MARKERS.contains(&word) {
+            let start = index + skip_fillers(&words[index..]);
+            let (value, end) = value_from(&words, start);
+            index = end;
+            query.tags.extend(split_list(&value));
+        } else if NOTES_WORDS.contains(&word) {
+            let start = words[index..]
+                .iter()
+                .position(|w| NOTES_CONNECTORS.contains(&normalize_word(w).as_str()))
+                .map_or(index, |position| index + position + 1);
+            let value = join_words(&words[start.min(words.len())..]);
+            index = words.len();
+            if !value.is_empty() {
+                query.notes = Some(value);
+            }
+        } else if TITLE_MARKERS.contains(&word)
+            || (AS_WORDS.contains(&word)
+                && index >= 2
+                && WORK_WORDS.contains(&normalize_word(words[index - 2]).as_str()))
+        {
+            let (value, end) = value_from(&words, index);
+            index = end;
+            if !value.is_empty() {
+                query.job_title = Some(value);
+            }
+        } else if !FILLERS.contains(&word) {
+            pending.push(trim_punctuation(words[index - 1]));
+        }
+    }
+
+    if let Some(place) = place {
+        if query.company.is_some() && query.department.is_none() {
+            query.department = Some(place);
+        } else {
+            query.city = Some(place);
+        }
+    }
+    Some(query).filter(|query| is_group && !query.is_empty())
+}
+
+/// The words from `start` up to the next marker, and the index after them.
+fn value_from(words: &[&str], start: usize) -> (String, usize) {
+    let mut end = start;
+    while end < words.len() && (end == start || !is_boundary(&normalize_word(words[end]))) {
+        end += 1;
+    }
+    (join_words(&words[start.min(end)..end]), end)
+}
+
+fn skip_fillers(words: &[&str]) -> usize {
+    words
+        .iter()
+        .take_while(|word| TAG_FILLERS.contains(&normalize_word(word).as_str()))
+        .count()
+}
+
+fn is_boundary(word: &str) -> bool {
+    PLACE_MARKERS.contains(&word)
+        || COMPANY_MARKERS.contains(&word)
+        || TAG_MARKERS.contains(&word)
+        || NOTES_WORDS.contains(&word)
+        || BOUNDARIES.contains(&word)
+}
+
+fn is_of(word: &str) -> bool {
+    OF_WORDS.contains(&normalize_word(word).as_str())
+}
+
+fn split_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .flat_map(|part| part.split(" and "))
+        .flat_map(|part| part.split(" e "))
+        .map(|part| trim_punctuation(part.trim()).to_string())
+        .filter(|part| !part.is_empty())
+        .collect()
+}
+
+fn join_words(words: &[&str]) -> String {
+    trim_punctuation(&words.join(" ")).to_string()
+}
+
+fn normalize_word(word: &str) -> String {
+    trim_punctuation(word).replace('’', "'").to_lowercase()
+}
+
+fn trim_punctuation(word: &str) -> &str {
+    word.trim_matches(|c: char| matches!(c, ',' | '.' | ';' | ':' | '!' | '?' | '"' | '(' | ')'))
+}
+
+const GROUP_WORDS: &[&str] = &[
+    "everyone",
+    "everybody",
+    "all",
+    "people",
+    "contacts",
+    "contatos",
+    "todos",
+    "todas",
+    "pessoas",
+];
+const WORK_WORDS: &[&str] = &["works", "work", "working", "trabalha", "trabalham"];
+const TEAM_WORDS: &[&str] = &[
+    "team",
+    "department",
+    "dept",
+    "equipe",
+    "time",
+    "departamento",
+];
+const OWNER_WORDS: &[&str] = &["my", "meus", "minhas", "meu", "minha"];
+const FAVORITE_WORDS: &[&str] = &[
+    "favourite",
+    "favourites",
+    "favorite",
+    "favorites",
+    "favorito",
+    "favoritos",
+    "favorita",
+    "favoritas",
+];
+const PLACE_MARKERS: &[&str] = &["in", "em", "based"];
+const COMPANY_MARKERS: &[&str] = &["at", "from", "na", "no"];
+const OF_WORDS: &[&str] = &["of", "da", "do", "de"];
+const TAG_MARKERS: &[&str] = &[
+    "tagged", "tag", "tags", "labelled", "labeled", "marcados", "marcadas",
+];
+const TAG_FILLERS: &[&str] = &["the", "a", "as", "with", "tag", "com"];
+const NOTES_WORDS: &[&str] = &["notes", "note", "notas", "nota"];
+const NOTES_CONNECTORS: &[&str] = &[
+    "mention",
+    "mentioning",
+    "about",
+    "containing",
+    "contain",
+    "sobre",
+    "mencionam",
+    "mencionando",
+];
+const TITLE_MARKERS: &[&str] = &["title", "cargo"];
+/// "working as", "trabalham como": a job title only right after a work word.
+const AS_WORDS: &[&str] = &["as", "como"];
+const BOUNDARIES: &[&str] = &[
+    "with",
+    "whose",
+    "who",
+    "that",
+    "about",
+    "regarding",
+    "com",
+    "que",
+    "cujas",
+    "cujo",
+    "sobre",
+];
+const FILLERS: &[&str] = &[
+    "the", "a", "an", "o", "os", "as", "show", "list", "find", "get", "email", "message", "send",
+    "to", "para", "who", "quem", "are", "is", "me", "mostre", "liste", "envie",
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(input: &str) -> ContactQuery {
+        parse_contact_query(input).unwrap_or_else(|| panic!("not parsed: {input}"))
+    }
+
+    #[test]
+    fn test_department_and_company() {
+        let query = parse("everyone in Engineering at Tech Solutions");
+        assert_eq!(query.department.as_deref(), Some("Engineering"));
+        assert_eq!(query.company.as_deref(), Some("Tech Solutions"));
+        assert_eq!(query.city, None);
+
+        let query = parse("todos da Engenharia na Tech Solutions");
+        assert_eq!(query.department.as_deref(), Some("Engenharia"));
+        assert_eq!(query.company.as_deref(), Some("Tech Solutions"));
+    }
+
+    #[test]
+    fn test_favourites_in_a_city() {
+        let query = parse("my favourite contacts in New York");
+        assert_eq!(query.favorite, Some(true));
+        assert_eq!(query.city.as_deref(), Some("New York"));
+        assert!(query.tags.is_empty());
+
+        let query = parse("meus contatos favoritos em Nova York");
+        assert_eq!(query.favorite, Some(true));
+        assert_eq!(query.city.as_deref(), Some("Nova York"));
+    }
+
+    #[test]
+    fn test_teams() {
+        assert_eq!(
+            parse("the engineering team").department.as_deref(),
+            Some("engineering")
+        );
+        assert_eq!(
+            parse("a equipe de engenharia").department.as_deref(),
+            Some("engenharia")
+        );
+        assert_eq!(
+            parse("my marketing team").department.as_deref(),
+            Some("marketing")
+        );
+    }
+
+    #[test]
+    fn test_company_tags_notes_and_title() {
+        assert_eq!(
+            parse("who works at Tech Solutions?").company.as_deref(),
+            Some("Tech Solutions")
+        );
+        assert_eq!(
+            parse("quem trabalha na Tech Solutions?").company.as_deref(),
+            Some("Tech Solutions")
+        );
+        assert_eq!(
+            parse("contacts tagged family, business").tags,
+            vec!["family", "business"]
+        );
+        assert_eq!(parse("contatos com a tag família").tags, vec!["família"]);
+        assert_eq!(parse("my college friends").tags, vec!["college", "friends"]);
+        assert_eq!(
+            parse("contacts whose notes mention AI projects")
+                .notes
+                .as_deref(),
+            Some("AI projects")
+        );
+        assert_eq!(
+            parse("everyone working as Software Engineer")
+                .job_title
+                .as_deref(),
+            Some("Software Engineer")
+        );
+        assert_eq!(
+            parse("everyone at Tech Solutions about the delay")
+                .company
+                .as_deref(),
+            Some("Tech Solutions")
+        );
+        assert_eq!(
+            parse("todas as pessoas da Engenharia")
+                .department
+                .as_deref(),
+            Some("Engenharia")
+        );
+    }
+
+    #[test]
+    fn test_single_references_are_not_groups() {
+        assert_eq!(parse_contact_query("Tiger"), None);
+        assert_eq!(parse_contact_query("Dr. Wise"), None);
+        assert_eq!(parse_contact_query("who is Penguin Swift?"), None);
+        assert_eq!(parse_contact_query("my contacts"), None);
+        assert_eq!(parse_contact_query("Carlos in New York"), None);
+    }
+}
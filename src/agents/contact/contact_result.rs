@@ -2,12 +2,68 @@ use serde::{Deserialize, Serialize};
 
 use crate::agents::AgentResult;
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct ContactResult {}
+use super::{recipient_candidate::RecipientCandidate, resolution_status::ResolutionStatus};
+
+/// Outcome of resolving a recipient reference such as "Eva" against the contacts.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ContactResult {
+    pub query: String,
+    pub status: ResolutionStatus,
+    /// Ranked best first; empty when nothing matched.
+    pub candidates: Vec<RecipientCandidate>,
+}
 
 impl ContactResult {
-    pub fn new() -> Self {
-        Self {}
+    pub fn new(query: &str, status: ResolutionStatus, candidates: Vec<RecipientCandidate>) -> Self {
+        Self {
+            query: query.to_string(),
+            status,
+            candidates,
+        }
+    }
+
+    /// The matched contact when the resolution is unique.
+    pub fn resolved(&self) -> Option<&RecipientCandidate> {
+        match self.status {
+            ResolutionStatus::Unique => self.candidates.first(),
+            _ => None,
+        }
+    }
+
+    /// Everyone the message should go to: the resolved contact, or every member
+    /// of a group. Empty when a follow-up is needed.
+    pub fn recipients(&self) -> &[RecipientCandidate] {
+        match self.status {
+            ResolutionStatus::Unique => &self.candidates[..self.candidates.len().min(1)],
+            ResolutionStatus::Group => &self.candidates,
+            _ => &[],
+        }
+    }
+
+    pub fn needs_follow_up(&self) -> bool {
+        !matches!(
+            self.status,
+            ResolutionStatus::Unique | ResolutionStatus::Group
+        )
+    }
+
+    /// Question to ask the user when the reference could not be resolved on its own.
+    pub fn follow_up_question(&self) -> Option<String> {
+        match self.status {
+            ResolutionStatus::Unique | ResolutionStatus::Group => None,
+            ResolutionStatus::Ambiguous => {
+                let options: Vec<String> = self.candidates.iter().map(|c| c.label()).collect();
+                Some(format!(
+                    "Which {} did you mean: {}?",
+                    self.query,
+                    options.join(", ")
+                ))
+            }
+            ResolutionStatus::NotFound => Some(format!(
+                "I could not find {} in your contacts. What is their email address?",
+                self.query
+            )),
+        }
     }
 }
 
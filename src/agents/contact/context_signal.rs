@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+
+use super::message_context::MessageContext;
+
+/// A message context and what it was inferred from, for explanations.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ContextSignal {
+    pub context: MessageContext,
+    /// E.g. `it mentions "project"` or `the calendar event is in the "work" category`.
+    pub evidence: String,
+}
+
+impl ContextSignal {
+    pub fn new(context: MessageContext, evidence: &str) -> Self {
+        Self {
+            context,
+            evidence: evidence.to_string(),
+        }
+    }
+}
@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+
+use super::duplicate_signal::DuplicateSignal;
+
+/// A proposed merge of two contacts.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct DuplicateCandidate {
+    /// The contact that is kept; the one that appears first in the store.
+    pub primary_id: String,
+    /// The contact folded into the primary and then deleted.
+    pub duplicate_id: String,
+    /// Combined evidence in `0.0..=1.0`.
+    pub score: f32,
+    pub signals: Vec<DuplicateSignal>,
+}
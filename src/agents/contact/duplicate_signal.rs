@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Evidence that two contacts describe the same person.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DuplicateSignal {
+    SharedEmail,
+    SharedPhone,
+    SimilarName,
+    SameCompany,
+}
+
+impl DuplicateSignal {
+    pub fn to_str(&self) -> &str {
+        match self {
+            Self::SharedEmail => "shared_email",
+            Self::SharedPhone => "shared_phone",
+            Self::SimilarName => "similar_name",
+            Self::SameCompany => "same_company",
+        }
+    }
+}
+
+impl fmt::Display for DuplicateSignal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_str())
+    }
+}
@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Contact field a recipient reference was matched against.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchField {
+    DisplayName,
+    FullName,
+    FirstName,
+    LastName,
+    Nickname,
+    EmailAddress,
+    EmailLocalPart,
+    /// Selected by a [`ContactQuery`](crate::infra::contacts::ContactQuery) filter.
+    Filter,
+}
+
+impl MatchField {
+    pub fn to_str(&self) -> &str {
+        match self {
+            Self::DisplayName => "display_name",
+            Self::FullName => "full_name",
+            Self::FirstName => "first_name",
+            Self::LastName => "last_name",
+            Self::Nickname => "nickname",
+            Self::EmailAddress => "email_address",
+            Self::EmailLocalPart => "email_local_part",
+            Self::Filter => "filter",
+        }
+    }
+}
+
+impl fmt::Display for MatchField {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_str())
+    }
+}
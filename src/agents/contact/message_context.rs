@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+use crate::agents::AgentError;
+
+/// Whether a message is about work or private life, which decides between a
+/// contact's work and personal addresses.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MessageContext {
+    Work,
+    Personal,
+}
+
+impl MessageContext {
+    pub fn to_str(&self) -> &str {
+        match self {
+            Self::Work => WORK,
+            Self::Personal => PERSONAL,
+        }
+    }
+}
+
+impl FromStr for MessageContext {
+    type Err = AgentError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input.trim().to_lowercase().as_str() {
+            WORK => Ok(Self::Work),
+            PERSONAL => Ok(Self::Personal),
+            other => Err(AgentError::ParseError(format!(
+                "Unknown message context: {other}"
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for MessageContext {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_str())
+    }
+}
+
+const WORK: &str = "work";
+const PERSONAL: &str = "personal";
@@ -1,2 +1,51 @@
+pub mod address_selection;
+pub mod address_selector;
+pub mod contact_action_result;
+pub mod contact_action_status;
 pub mod contact_agent;
+pub mod contact_change;
+pub mod contact_channel;
+pub mod contact_command;
+pub mod contact_command_parser;
+pub mod contact_deduplicator;
+pub mod contact_field;
+pub mod contact_merge;
+pub mod contact_operation;
+pub mod contact_query_parser;
 pub mod contact_result;
+pub mod context_signal;
+pub mod duplicate_candidate;
+pub mod duplicate_signal;
+pub mod match_field;
+pub mod message_context;
+pub mod name_similarity;
+pub mod recipient_candidate;
+pub mod recipient_matcher;
+pub mod resolution_status;
+pub mod selection_reason;
+
+pub use address_selection::AddressSelection;
+pub use address_selector::{AddressSelector, PREFERRED_CHANNEL, prefer_address, preference_key};
+pub use contact_action_result::ContactActionResult;
+pub use contact_action_status::ContactActionStatus;
+pub use contact_agent::{ContactAgent, ContactCommandParam, ContactParam};
+pub use contact_change::ContactChange;
+pub use contact_channel::ContactChannel;
+pub use contact_command::ContactCommand;
+pub use contact_command_parser::parse_contact_command;
+pub use contact_deduplicator::{ContactDeduplicator, MERGED_FROM, merge_contacts};
+pub use contact_field::ContactField;
+pub use contact_merge::ContactMerge;
+pub use contact_operation::ContactOperation;
+pub use contact_query_parser::parse_contact_query;
+pub use contact_result::ContactResult;
+pub use context_signal::ContextSignal;
+pub use duplicate_candidate::DuplicateCandidate;
+pub use duplicate_signal::DuplicateSignal;
+pub use match_field::MatchField;
+pub use message_context::MessageContext;
+pub use name_similarity::{name_similarity, normalize_name};
+pub use recipient_candidate::RecipientCandidate;
+pub use recipient_matcher::RecipientMatcher;
+pub use resolution_status::ResolutionStatus;
+pub use selection_reason::SelectionReason;
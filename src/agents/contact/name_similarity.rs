@@ -0,0 +1,74 @@
+use unicode_normalization::{UnicodeNormalization, char::is_combining_mark};
+
+/// Lowercases, strips accents and turns punctuation into single spaces,
+/// so "Dr. João" and "dr joao" compare equal.
+pub fn normalize_name(value: &str) -> String {
+    let folded: String = value
+        .nfd()
+        .filter(|c| !is_combining_mark(*c))
+        .flat_map(char::to_lowercase)
+        .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+        .collect();
+    folded.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Similarity in `0.0..=1.0` between two already normalised names, based on
+/// the optimal string alignment distance (adjacent swaps count as one typo).
+pub fn name_similarity(a: &str, b: &str) -> f32 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let longest = a.len().max(b.len());
+    if longest == 0 {
+        return 1.0;
+    }
+    1.0 - edit_distance(&a, &b) as f32 / longest as f32
+}
+
+fn edit_distance(a: &[char], b: &[char]) -> usize {
+    let width = b.len() + 1;
+    let mut distances = vec![0usize; (a.len() + 1) * width];
+    for (i, row) in distances.chunks_mut(width).enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in distances.iter_mut().take(width).enumerate() {
+        *cell = j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut best = (distances[(i - 1) * width + j] + 1)
+                .min(distances[i * width + j - 1] + 1)
+                .min(distances[(i - 1) * width + j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                best = best.min(distances[(i - 2) * width + j - 2] + 1);
+            }
+            distances[i * width + j] = best;
+        }
+    }
+    distances[a.len() * width + b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_strips_accents_case_and_punctuation() {
+        assert_eq!(
+            normalize_name("  Dr. João   Gonçalves "),
+            "dr joao goncalves"
+        );
+        assert_eq!(normalize_name("tiger.brilliant"), "tiger brilliant");
+        assert_eq!(normalize_name("ÉVA"), "eva");
+    }
+
+    #[test]
+    fn test_similarity_tolerates_typos() {
+        assert_eq!(name_similarity("tiger", "tiger"), 1.0);
+        assert_eq!(name_similarity("tigre", "tiger"), 0.8);
+        assert_eq!(name_similarity("penguim", "penguin"), 1.0 - 1.0 / 7.0);
+        assert!(name_similarity("eva", "fox") < 0.5);
+        assert_eq!(name_similarity("", ""), 1.0);
+    }
+}
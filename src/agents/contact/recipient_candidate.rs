@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+
+use super::match_field::MatchField;
+
+/// A contact that may be the recipient the user referred to.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct RecipientCandidate {
+    pub contact_id: String,
+    pub name: String,
+    /// Primary email of the contact, when it has one.
+    pub email: Option<String>,
+    /// Similarity in `0.0..=1.0`; `1.0` is an exact match after normalisation.
+    pub score: f32,
+    pub matched_on: MatchField,
+}
+
+impl RecipientCandidate {
+    /// "Name <email>" as shown to the user in follow-up questions.
+    pub fn label(&self) -> String {
+        match &self.email {
+            Some(email) => format!("{} <{email}>", self.name),
+            None => self.name.clone(),
+        }
+    }
+}
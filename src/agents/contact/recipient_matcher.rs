@@ -0,0 +1,301 @@
+use std::cmp::Ordering;
+
+use crate::infra::contacts::Contact;
+
+use super::{
+    contact_result::ContactResult,
+    match_field::MatchField,
+    name_similarity::{name_similarity, normalize_name},
+    recipient_candidate::RecipientCandidate,
+    resolution_status::ResolutionStatus,
+};
+
+/// Ranks contacts against a free-text recipient reference.
+///
+/// Names, nicknames and email local parts are compared after accent and case
+/// folding; tokens are also matched one by one, so "Wise" and "Dr Wise" find
+/// "Dr. Dolphin Wise", and short prefixes such as "Tig" still score well.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecipientMatcher {
+    threshold: f32,
+    ambiguity_margin: f32,
+    max_candidates: usize,
+}
+
+impl Default for RecipientMatcher {
+    fn default() -> Self {
+        Self {
+            threshold: THRESHOLD,
+            ambiguity_margin: AMBIGUITY_MARGIN,
+            max_candidates: MAX_CANDIDATES,
+        }
+    }
+}
+
+impl RecipientMatcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Minimum score for a contact to be a candidate.
+    pub fn with_threshold(mut self, threshold: f32) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    /// How far ahead of the runner-up the best candidate must be to count as unique.
+    pub fn with_ambiguity_margin(mut self, ambiguity_margin: f32) -> Self {
+        self.ambiguity_margin = ambiguity_margin;
+        self
+    }
+
+    pub fn with_max_candidates(mut self, max_candidates: usize) -> Self {
+        self.max_candidates = max_candidates;
+        self
+    }
+
+    /// Candidates at or above the threshold, best first.
+    pub fn rank(&self, query: &str, contacts: &[Contact]) -> Vec<RecipientCandidate> {
+        let normalized = normalize_name(query);
+        if normalized.is_empty() {
+            return Vec::new();
+        }
+
+        let mut candidates: Vec<RecipientCandidate> = contacts
+            .iter()
+            .filter_map(|contact| score_contact(query, &normalized, contact))
+            .filter(|candidate| candidate.score >= self.threshold)
+            .collect();
+        candidates.sort_by(|a, b| {
+            b.score
+                .partial_cmp(&a.score)
+                .unwrap_or(Ordering::Equal)
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        candidates.truncate(self.max_candidates);
+        candidates
+    }
+
+    pub fn resolve(&self, query: &str, contacts: &[Contact]) -> ContactResult {
+        let candidates = self.rank(query, contacts);
+        let status = match candidates.as_slice() {
+            [] => ResolutionStatus::NotFound,
+            [_] => ResolutionStatus::Unique,
+            [best, second, ..] if best.score - second.score >= self.ambiguity_margin => {
+                ResolutionStatus::Unique
+            }
+            [best, ..] => {
+                let best_score = best.score;
+                return ContactResult::new(
+                    query.trim(),
+                    ResolutionStatus::Ambiguous,
+                    candidates
+                        .into_iter()
+                        .filter(|c| best_score - c.score < self.ambiguity_margin)
+                        .collect(),
+                );
+            }
+        };
+        ContactResult::new(query.trim(), status, candidates)
+    }
+}
+
+fn score_contact(query: &str, normalized: &str, contact: &Contact) -> Option<RecipientCandidate> {
+    let mut fields: Vec<(MatchField, String)> = vec![
+        (MatchField::DisplayName, contact.display_name.clone()),
+        (
+            MatchField::FullName,
+            format!("{} {}", contact.first_name, contact.last_name),
+        ),
+        (MatchField::FirstName, contact.first_name.clone()),
+        (MatchField::LastName, contact.last_name.clone()),
+    ];
+    if let Some(nickname) = &contact.nickname {
+        fields.push((MatchField::Nickname, nickname.clone()));
+    }
+    for email in &contact.emails {
+        if let Some((local_part, _)) = email.address.split_once('@') {
+            fields.push((MatchField::EmailLocalPart, local_part.to_string()));
+        }
+    }
+
+    let mut best: Option<(MatchField, f32)> = contact
+        .emails
+        .iter()
+        .any(|email| email.address.eq_ignore_ascii_case(query.trim()))
+        .then_some((MatchField::EmailAddress, 1.0));
+    for (field, value) in fields {
+        let score = field_score(normalized, &normalize_name(&value));
+        if best.is_none_or(|(_, best_score)| score > best_score) {
+            best = Some((field, score));
+        }
+    }
+
+    best.map(|(matched_on, score)| RecipientCandidate {
+        contact_id: contact.id.clone(),
+        name: contact.name(),
+        email: contact.primary_email().map(|email| email.address.clone()),
+        score,
+        matched_on,
+    })
+}
+
+/// Best of the whole-value similarity and the per-token match, where each
+/// query token is scored against its closest token in the value.
+fn field_score(query: &str, value: &str) -> f32 {
+    if value.is_empty() {
+        return 0.0;
+    }
+    let query_tokens: Vec<&str> = query.split(' ').collect();
+    let token_score = query_tokens
+        .iter()
+        .map(|query_token| {
+            value
+                .split(' ')
+                .map(|token| token_similarity(query_token, token))
+                .fold(0.0, f32::max)
+        })
+        .sum::<f32>()
+        / query_tokens.len() as f32;
+    name_similarity(query, value).max(token_score)
+}
+
+fn token_similarity(query: &str, token: &str) -> f32 {
+    let score = name_similarity(query, token);
+    if query.chars().count() >= MIN_PREFIX && token.starts_with(query) {
+        let coverage = query.chars().count() as f32 / token.chars().count() as f32;
+        score.max(PREFIX_BASE + (1.0 - PREFIX_BASE) * coverage)
+    } else {
+        score
+    }
+}
+
+const THRESHOLD: f32 = 0.75;
+const AMBIGUITY_MARGIN: f32 = 0.1;
+const MAX_CANDIDATES: usize = 5;
+const MIN_PREFIX: usize = 3;
+const PREFIX_BASE: f32 = 0.8;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infra::contacts::{ContactEmail, ContactLabel};
+
+    fn contacts() -> Vec<Contact> {
+        vec![
+            Contact::new("c001", "Tiger", "Brilliant")
+                .with_nickname("Tiggy")
+                .with_email(
+                    ContactEmail::new(ContactLabel::Personal, "tiger.brilliant@gmail.com")
+                        .with_primary(true),
+                ),
+            Contact::new("c003", "Dolphin", "Wise").with_display_name("Dr. Dolphin Wise"),
+            Contact::new("c014", "Owl", "Wise").with_display_name("Dr. Owl Wise"),
+            Contact::new("c020", "João", "Silva")
+                .with_email(ContactEmail::new(ContactLabel::Work, "jsilva@acme.com")),
+        ]
+    }
+
+    #[test]
+    fn test_exact_first_name_is_unique() {
+        let result = RecipientMatcher::new().resolve("Tiger", &contacts());
+        assert_eq!(result.status, ResolutionStatus::Unique);
+        let resolved = result.resolved().unwrap();
+        assert_eq!(resolved.contact_id, "c001");
+        assert_eq!(resolved.score, 1.0);
+        assert_eq!(resolved.email.as_deref(), Some("tiger.brilliant@gmail.com"));
+    }
+
+    #[test]
+    fn test_accents_and_case_are_ignored() {
+        let result = RecipientMatcher::new().resolve("joao", &contacts());
+        assert_eq!(
+            result.resolved().map(|c| c.contact_id.as_str()),
+            Some("c020")
+        );
+    }
+
+    #[test]
+    fn test_typos_and_prefixes_still_match() {
+        let matcher = RecipientMatcher::new();
+        assert_eq!(
+            matcher
+                .resolve("Tigre", &contacts())
+                .resolved()
+                .map(|c| c.name.as_str()),
+            Some("Tiger Brilliant")
+        );
+        assert_eq!(
+            matcher
+                .resolve("Dolph", &contacts())
+                .resolved()
+                .map(|c| c.contact_id.as_str()),
+            Some("c003")
+        );
+    }
+
+    #[test]
+    fn test_nickname_email_local_part_and_address() {
+        let matcher = RecipientMatcher::new();
+        let nickname = matcher.resolve("Tiggy", &contacts());
+        assert_eq!(
+            nickname.resolved().unwrap().matched_on,
+            MatchField::Nickname
+        );
+
+        let local_part = matcher.resolve("jsilva", &contacts());
+        assert_eq!(
+            local_part.resolved().unwrap().matched_on,
+            MatchField::EmailLocalPart
+        );
+
+        let address = matcher.resolve("JSilva@acme.com", &contacts());
+        assert_eq!(
+            address.resolved().unwrap().matched_on,
+            MatchField::EmailAddress
+        );
+    }
+
+    #[test]
+    fn test_shared_surname_is_ambiguous() {
+        let result = RecipientMatcher::new().resolve("Wise", &contacts());
+        assert_eq!(result.status, ResolutionStatus::Ambiguous);
+        assert_eq!(result.candidates.len(), 2);
+        assert!(result.resolved().is_none());
+        assert_eq!(
+            result.follow_up_question().unwrap(),
+            "Which Wise did you mean: Dr. Dolphin Wise, Dr. Owl Wise?"
+        );
+    }
+
+    #[test]
+    fn test_full_name_breaks_the_tie() {
+        let result = RecipientMatcher::new().resolve("Owl Wise", &contacts());
+        assert_eq!(
+            result.resolved().map(|c| c.contact_id.as_str()),
+            Some("c014")
+        );
+    }
+
+    #[test]
+    fn test_unknown_or_empty_reference_is_not_found() {
+        let matcher = RecipientMatcher::new();
+        let result = matcher.resolve("Eva", &contacts());
+        assert_eq!(result.status, ResolutionStatus::NotFound);
+        assert!(result.candidates.is_empty());
+        assert!(result.needs_follow_up());
+        assert_eq!(
+            matcher.resolve("  ", &contacts()).status,
+            ResolutionStatus::NotFound
+        );
+    }
+
+    #[test]
+    fn test_threshold_is_configurable() {
+        let strict = RecipientMatcher::new().with_threshold(1.0);
+        assert_eq!(
+            strict.resolve("Tigre", &contacts()).status,
+            ResolutionStatus::NotFound
+        );
+    }
+}
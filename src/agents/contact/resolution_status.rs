@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ResolutionStatus {
+    /// One contact clearly matches.
+    Unique,
+    /// Several contacts match about equally well; ask the user which one.
+    Ambiguous,
+    NotFound,
+    /// The reference named several contacts by their details ("the engineering
+    /// team"); every candidate is a recipient.
+    Group,
+}
+
+impl ResolutionStatus {
+    pub fn to_str(&self) -> &str {
+        match self {
+            Self::Unique => "unique",
+            Self::Ambiguous => "ambiguous",
+            Self::NotFound => "not_found",
+            Self::Group => "group",
+        }
+    }
+}
+
+impl fmt::Display for ResolutionStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_str())
+    }
+}
@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Why an [`AddressSelection`](super::AddressSelection) picked its address.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SelectionReason {
+    /// The user chose this address for the contact.
+    Preference,
+    /// Its label fits the message context (work address for work messages).
+    ContextLabel,
+    Primary,
+    OnlyAddress,
+    FirstListed,
+}
+
+impl SelectionReason {
+    pub fn to_str(&self) -> &str {
+        match self {
+            Self::Preference => "preference",
+            Self::ContextLabel => "context_label",
+            Self::Primary => "primary",
+            Self::OnlyAddress => "only_address",
+            Self::FirstListed => "first_listed",
+        }
+    }
+}
+
+impl fmt::Display for SelectionReason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_str())
+    }
+}
@@ -0,0 +1,62 @@
+use super::{
+    context_report::ContextReport,
+    prompt_section::{PromptSection, join_message},
+};
+
+/// The sections that fit the context window, in their original order, and the
+/// ones that were trimmed to make them fit.
+#[derive(Debug, Clone)]
+pub struct BudgetedPrompt {
+    kept: Vec<PromptSection>,
+    dropped: Vec<PromptSection>,
+    estimated_tokens: usize,
+    num_ctx: usize,
+}
+
+impl BudgetedPrompt {
+    pub fn new(
+        kept: Vec<PromptSection>,
+        dropped: Vec<PromptSection>,
+        estimated_tokens: usize,
+        num_ctx: usize,
+    ) -> Self {
+        Self {
+            kept,
+            dropped,
+            estimated_tokens,
+            num_ctx,
+        }
+    }
+
+    pub fn kept(&self) -> &[PromptSection] {
+        &self.kept
+    }
+
+    pub fn dropped(&self) -> &[PromptSection] {
+        &self.dropped
+    }
+
+    pub fn is_kept(&self, name: &str) -> bool {
+        self.kept.iter().any(|section| section.name() == name)
+    }
+
+    /// The kept sections of the chat message `message`, joined with `separator`.
+    pub fn message(&self, message: &str, separator: &str) -> String {
+        join_message(&self.kept, message, separator)
+    }
+
+    pub fn estimated_tokens(&self) -> usize {
+        self.estimated_tokens
+    }
+
+    pub fn report(&self) -> ContextReport {
+        ContextReport::new(
+            self.num_ctx,
+            self.estimated_tokens,
+            self.dropped
+                .iter()
+                .map(|section| section.name().to_string())
+                .collect(),
+        )
+    }
+}
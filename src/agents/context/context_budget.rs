@@ -0,0 +1,237 @@
+use crate::{config::Config, infra::assistant_ollama_client::AssistantOllamaClient};
+
+use super::{
+    budgeted_prompt::BudgetedPrompt, context_error::ContextError, prompt_section::PromptSection,
+    token_estimator::TokenEstimator,
+};
+
+/// Ollama's context length when neither the model nor the configuration sets one.
+pub const DEFAULT_NUM_CTX: usize = 2048;
+/// Tokens kept free for the model's answer.
+pub const DEFAULT_RESPONSE_RESERVE: usize = 512;
+
+/// Fits prompt sections into a model's context window.
+#[derive(Debug, Clone)]
+pub struct ContextBudget {
+    num_ctx: usize,
+    response_reserve: usize,
+    estimator: TokenEstimator,
+}
+
+impl ContextBudget {
+    pub fn new(num_ctx: usize) -> Self {
+        Self {
+            num_ctx,
+            response_reserve: DEFAULT_RESPONSE_RESERVE.min(num_ctx / 4),
+            estimator: TokenEstimator::default(),
+        }
+    }
+
+    /// The budget for `model`: the context length Ollama reports for it, else
+    /// `[ollama.api.options].num_ctx`, else [`DEFAULT_NUM_CTX`].
+    pub async fn for_model(client: &AssistantOllamaClient, model: &str) -> Self {
+        let num_ctx = client
+            .context_length(model)
+            .await
+            .ok()
+            .flatten()
+            .or(Config::get().ollama.api.options.num_ctx)
+            .unwrap_or(DEFAULT_NUM_CTX);
+        Self::new(num_ctx)
+    }
+
+    pub fn with_response_reserve(mut self, response_reserve: usize) -> Self {
+        self.response_reserve = response_reserve;
+        self
+    }
+
+    pub fn with_estimator(mut self, estimator: TokenEstimator) -> Self {
+        self.estimator = estimator;
+        self
+    }
+
+    pub fn num_ctx(&self) -> usize {
+        self.num_ctx
+    }
+
+    /// Tokens available for the prompt once the response reserve is set aside.
+    pub fn available(&self) -> usize {
+        self.num_ctx.saturating_sub(self.response_reserve)
+    }
+
+    /// Drops trimmable sections until the prompt fits: lowest priority first and,
+    /// within a priority, earliest first (so the oldest history goes first).
+    /// Fails if the required sections alone do not fit.
+    ///
+    /// The per-message overhead is charged once for each chat message that
+    /// still has a section (see [`PromptSection::in_message`]).
+    pub fn fit(&self, sections: Vec<PromptSection>) -> Result<BudgetedPrompt, ContextError> {
+        let costs: Vec<usize> = sections
+            .iter()
+            .map(|section| self.estimator.estimate(section.content()))
+            .collect();
+        let mut is_kept = vec![true; sections.len()];
+        let mut total = self.cost(&sections, &costs, &is_kept);
+        let available = self.available();
+
+        let mut drop_order: Vec<usize> = (0..sections.len())
+            .filter(|&i| sections[i].is_trimmable())
+            .collect();
+        drop_order.sort_by_key(|&i| (sections[i].priority(), i));
+
+        let mut dropped_indexes = Vec::new();
+        for index in drop_order {
+            if total <= available {
+                break;
+            }
+            is_kept[index] = false;
+            total = self.cost(&sections, &costs, &is_kept);
+            dropped_indexes.push(index);
+        }
+
+        if total > available {
+            return Err(ContextError::WindowExceeded(format!(
+                "required sections need ~{total} tokens but only {available} of {} are available",
+                self.num_ctx
+            )));
+        }
+
+        let mut kept = Vec::new();
+        let mut dropped = Vec::new();
+        for (index, section) in sections.into_iter().enumerate() {
+            if dropped_indexes.contains(&index) {
+                dropped.push(section);
+            } else {
+                kept.push(section);
+            }
+        }
+        dropped.sort_by_key(|section| section.priority());
+
+        Ok(BudgetedPrompt::new(kept, dropped, total, self.num_ctx))
+    }
+
+    /// Tokens of the kept sections plus one overhead per chat message they use.
+    fn cost(&self, sections: &[PromptSection], costs: &[usize], is_kept: &[bool]) -> usize {
+        let mut messages: Vec<&str> = Vec::new();
+        let mut tokens = 0;
+        for (index, section) in sections.iter().enumerate() {
+            if !is_kept[index] {
+                continue;
+            }
+            tokens += costs[index];
+            if !messages.contains(&section.message()) {
+                messages.push(section.message());
+            }
+        }
+        tokens + messages.len() * self.estimator.message_overhead()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agents::context::SectionPriority;
+
+    fn words(n: usize) -> String {
+        vec!["word"; n].join(" ")
+    }
+
+    fn sections() -> Vec<PromptSection> {
+        vec![
+            PromptSection::required("instructions", &words(40)),
+            PromptSection::new("example_1", &words(30), SectionPriority::Medium),
+            PromptSection::new("history_0", &words(30), SectionPriority::Low),
+            PromptSection::new("history_1", &words(30), SectionPriority::Low),
+            PromptSection::required("input", &words(20)),
+        ]
+    }
+
+    #[test]
+    fn test_fit_keeps_everything_when_it_fits() {
+        let budgeted = ContextBudget::new(2048).fit(sections()).unwrap();
+
+        assert_eq!(budgeted.kept().len(), 5);
+        assert!(budgeted.dropped().is_empty());
+        assert!(!budgeted.report().was_trimmed());
+        assert_eq!(budgeted.estimated_tokens(), 40 + 30 + 30 + 30 + 20 + 5 * 4);
+    }
+
+    #[test]
+    fn test_fit_charges_joined_sections_one_message_overhead() {
+        let joined: Vec<PromptSection> = sections()
+            .into_iter()
+            .map(|section| section.in_message("system"))
+            .collect();
+        let budgeted = ContextBudget::new(2048).fit(joined).unwrap();
+
+        assert_eq!(budgeted.estimated_tokens(), 40 + 30 + 30 + 30 + 20 + 4);
+    }
+
+    #[test]
+    fn test_fit_drops_oldest_history_first() {
+        // total = 170, drop history_0 (34) -> 136
+        let budget = ContextBudget::new(140).with_response_reserve(0);
+        let budgeted = budget.fit(sections()).unwrap();
+
+        assert_eq!(budgeted.report().dropped, vec!["history_0".to_string()]);
+        assert!(budgeted.is_kept("history_1"));
+        assert!(budgeted.is_kept("example_1"));
+    }
+
+    #[test]
+    fn test_fit_drops_history_before_examples() {
+        let budget = ContextBudget::new(105).with_response_reserve(0);
+        let budgeted = budget.fit(sections()).unwrap();
+
+        assert_eq!(
+            budgeted.report().dropped,
+            vec!["history_0".to_string(), "history_1".to_string()]
+        );
+        assert!(budgeted.is_kept("example_1"));
+    }
+
+    #[test]
+    fn test_fit_never_drops_required_sections() {
+        let budget = ContextBudget::new(70).with_response_reserve(0);
+        let budgeted = budget.fit(sections()).unwrap();
+
+        assert!(budgeted.is_kept("instructions"));
+        assert!(budgeted.is_kept("input"));
+        assert_eq!(budgeted.kept().len(), 2);
+        assert_eq!(budgeted.report().dropped.len(), 3);
+    }
+
+    #[test]
+    fn test_fit_fails_when_required_sections_exceed_window() {
+        let budget = ContextBudget::new(50).with_response_reserve(0);
+        let error = budget.fit(sections()).unwrap_err();
+        assert!(error.to_string().contains("Context window exceeded"));
+    }
+
+    #[test]
+    fn test_fit_preserves_original_order() {
+        let budget = ContextBudget::new(140).with_response_reserve(0);
+        let budgeted = budget.fit(sections()).unwrap();
+        let names: Vec<&str> = budgeted.kept().iter().map(|s| s.name()).collect();
+
+        assert_eq!(
+            names,
+            vec!["instructions", "example_1", "history_1", "input"]
+        );
+    }
+
+    #[test]
+    fn test_available_subtracts_response_reserve() {
+        assert_eq!(
+            ContextBudget::new(4096).available(),
+            4096 - DEFAULT_RESPONSE_RESERVE
+        );
+        assert_eq!(ContextBudget::new(400).available(), 300);
+        assert_eq!(
+            ContextBudget::new(1000)
+                .with_response_reserve(2000)
+                .available(),
+            0
+        );
+    }
+}
@@ -0,0 +1,17 @@
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug)]
+pub enum ContextError {
+    WindowExceeded(String),
+}
+
+impl fmt::Display for ContextError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ContextError::WindowExceeded(msg) => write!(f, "Context window exceeded: {msg}"),
+        }
+    }
+}
+
+impl Error for ContextError {}
@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+/// What was sent to the model relative to its context window.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ContextReport {
+    pub num_ctx: usize,
+    pub estimated_tokens: usize,
+    #[serde(default)]
+    pub dropped: Vec<String>,
+}
+
+impl ContextReport {
+    pub fn new(num_ctx: usize, estimated_tokens: usize, dropped: Vec<String>) -> Self {
+        Self {
+            num_ctx,
+            estimated_tokens,
+            dropped,
+        }
+    }
+
+    pub fn was_trimmed(&self) -> bool {
+        !self.dropped.is_empty()
+    }
+}
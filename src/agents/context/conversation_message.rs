@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ConversationRole {
+    User,
+    Assistant,
+}
+
+/// A previous turn of the conversation, sent to the model before the current input.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ConversationMessage {
+    pub role: ConversationRole,
+    pub content: String,
+}
+
+impl ConversationMessage {
+    pub fn user(content: &str) -> Self {
+        Self {
+            role: ConversationRole::User,
+            content: content.to_string(),
+        }
+    }
+
+    pub fn assistant(content: &str) -> Self {
+        Self {
+            role: ConversationRole::Assistant,
+            content: content.to_string(),
+        }
+    }
+}
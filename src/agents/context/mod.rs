@@ -0,0 +1,17 @@
+pub mod budgeted_prompt;
+pub mod context_budget;
+pub mod context_error;
+pub mod context_report;
+pub mod conversation_message;
+pub mod prompt_section;
+pub mod section_priority;
+pub mod token_estimator;
+
+pub use budgeted_prompt::BudgetedPrompt;
+pub use context_budget::ContextBudget;
+pub use context_error::ContextError;
+pub use context_report::ContextReport;
+pub use conversation_message::{ConversationMessage, ConversationRole};
+pub use prompt_section::{PromptSection, join_message};
+pub use section_priority::SectionPriority;
+pub use token_estimator::TokenEstimator;
@@ -0,0 +1,67 @@
+use super::section_priority::SectionPriority;
+
+/// A named piece of a prompt (instruction, example, history turn or user input).
+/// Each section is sent as its own chat message unless
+/// [`in_message`](Self::in_message) joins it with others.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PromptSection {
+    name: String,
+    content: String,
+    priority: SectionPriority,
+    message: Option<String>,
+}
+
+impl PromptSection {
+    pub fn new(name: &str, content: &str, priority: SectionPriority) -> Self {
+        Self {
+            name: name.to_string(),
+            content: content.to_string(),
+            priority,
+            message: None,
+        }
+    }
+
+    /// Sends the section in the chat message `message` with the other
+    /// sections that name it, such as the instructions joined into one
+    /// system prompt.
+    pub fn in_message(mut self, message: &str) -> Self {
+        self.message = Some(message.to_string());
+        self
+    }
+
+    pub fn required(name: &str, content: &str) -> Self {
+        Self::new(name, content, SectionPriority::Required)
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn content(&self) -> &str {
+        &self.content
+    }
+
+    /// The chat message the section is sent in; its own name when alone.
+    pub fn message(&self) -> &str {
+        self.message.as_deref().unwrap_or(&self.name)
+    }
+
+    pub fn priority(&self) -> SectionPriority {
+        self.priority
+    }
+
+    pub fn is_trimmable(&self) -> bool {
+        self.priority != SectionPriority::Required
+    }
+}
+
+/// The contents of the sections sent in the chat message `message`, in order,
+/// joined with `separator`.
+pub fn join_message(sections: &[PromptSection], message: &str, separator: &str) -> String {
+    sections
+        .iter()
+        .filter(|section| section.message() == message)
+        .map(PromptSection::content)
+        .collect::<Vec<_>>()
+        .join(separator)
+}
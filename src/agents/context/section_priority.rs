@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+
+/// How important a prompt section is when the context window is tight.
+/// Lower priorities are trimmed first; `Required` sections are never trimmed.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum SectionPriority {
+    Low,
+    Medium,
+    High,
+    Required,
+}
@@ -0,0 +1,87 @@
+/// Approximate tokenizer used for budgeting; it does not need the model's vocabulary.
+///
+/// ASCII text averages about four characters per token, while accented and
+/// non-Latin characters are split far more often, so they are counted at two
+/// characters per token. Each message also carries a fixed framing overhead.
+#[derive(Debug, Clone, Copy)]
+pub struct TokenEstimator {
+    ascii_chars_per_token: usize,
+    non_ascii_chars_per_token: usize,
+    message_overhead: usize,
+}
+
+impl Default for TokenEstimator {
+    fn default() -> Self {
+        Self {
+            ascii_chars_per_token: 4,
+            non_ascii_chars_per_token: 2,
+            message_overhead: 4,
+        }
+    }
+}
+
+impl TokenEstimator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Estimated tokens for a piece of text.
+    pub fn estimate(&self, text: &str) -> usize {
+        text.split_whitespace()
+            .map(|word| {
+                let ascii = word.chars().filter(char::is_ascii).count();
+                let non_ascii = word.chars().count() - ascii;
+                ascii.div_ceil(self.ascii_chars_per_token)
+                    + non_ascii.div_ceil(self.non_ascii_chars_per_token)
+            })
+            .sum()
+    }
+
+    /// Estimated tokens for a text sent as its own chat message.
+    pub fn estimate_message(&self, text: &str) -> usize {
+        self.estimate(text) + self.message_overhead
+    }
+
+    /// Framing tokens every chat message costs, whatever its length.
+    pub fn message_overhead(&self) -> usize {
+        self.message_overhead
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimate_empty_text() {
+        assert_eq!(TokenEstimator::new().estimate(""), 0);
+        assert_eq!(TokenEstimator::new().estimate("   \n\t "), 0);
+    }
+
+    #[test]
+    fn test_estimate_short_words_count_as_one_token() {
+        assert_eq!(TokenEstimator::new().estimate("Send it to Eva now"), 5);
+    }
+
+    #[test]
+    fn test_estimate_long_words_split() {
+        // "internationalization" has 20 characters -> 5 tokens
+        assert_eq!(TokenEstimator::new().estimate("internationalization"), 5);
+    }
+
+    #[test]
+    fn test_estimate_non_ascii_counts_more() {
+        let estimator = TokenEstimator::new();
+        assert!(estimator.estimate("reunião") > estimator.estimate("reuniao"));
+        assert_eq!(estimator.estimate("模型模型"), 2);
+    }
+
+    #[test]
+    fn test_estimate_message_adds_overhead() {
+        let estimator = TokenEstimator::new();
+        assert_eq!(
+            estimator.estimate_message("hello"),
+            estimator.estimate("hello") + 4
+        );
+    }
+}
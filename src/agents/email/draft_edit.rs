@@ -0,0 +1,104 @@
+use serde::{Deserialize, Serialize};
+
+use super::email_draft::EmailDraft;
+
+/// Changes the user makes while reviewing a draft; unset fields stay as they are.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct DraftEdit {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cc: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bcc: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subject: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub html_body: Option<String>,
+}
+
+impl DraftEdit {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_to(mut self, to: Vec<String>) -> Self {
+        self.to = Some(to);
+        self
+    }
+
+    pub fn with_cc(mut self, cc: Vec<String>) -> Self {
+        self.cc = Some(cc);
+        self
+    }
+
+    pub fn with_bcc(mut self, bcc: Vec<String>) -> Self {
+        self.bcc = Some(bcc);
+        self
+    }
+
+    pub fn with_subject(mut self, subject: &str) -> Self {
+        self.subject = Some(subject.to_string());
+        self
+    }
+
+    pub fn with_body(mut self, body: &str) -> Self {
+        self.body = Some(body.to_string());
+        self
+    }
+
+    /// An empty HTML body removes it.
+    pub fn with_html_body(mut self, html_body: &str) -> Self {
+        self.html_body = Some(html_body.to_string());
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    pub fn apply(&self, draft: &mut EmailDraft) {
+        if let Some(to) = &self.to {
+            draft.to = to.clone();
+        }
+        if let Some(cc) = &self.cc {
+            draft.cc = cc.clone();
+        }
+        if let Some(bcc) = &self.bcc {
+            draft.bcc = bcc.clone();
+        }
+        if let Some(subject) = &self.subject {
+            draft.subject = subject.clone();
+        }
+        if let Some(body) = &self.body {
+            draft.body = body.clone();
+        }
+        if let Some(html_body) = &self.html_body {
+            draft.html_body = (!html_body.is_empty()).then(|| html_body.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_changes_only_set_fields() {
+        let mut draft = EmailDraft::new(vec!["eva@example.com".to_string()], "Hi", "Hello")
+            .with_html_body("<p>Hello</p>");
+
+        DraftEdit::new()
+            .with_subject("Meeting")
+            .with_html_body("")
+            .apply(&mut draft);
+
+        assert_eq!(draft.subject, "Meeting");
+        assert_eq!(draft.body, "Hello");
+        assert_eq!(draft.to, vec!["eva@example.com"]);
+        assert_eq!(draft.html_body, None);
+        assert!(DraftEdit::new().is_empty());
+    }
+}
@@ -0,0 +1,140 @@
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+use crate::agents::AgentError;
+
+/// Where a draft is in the approval workflow. Nothing is sent before the user
+/// approves it:
+///
+/// `draft` → `awaiting_approval` → `approved` → `sending` → `sent` | `failed`
+///
+/// `sending` claims the draft while it is delivered, so it is sent only once.
+/// Editing an approved or failed draft asks for approval again; any draft that
+/// is not being sent and was not sent can be cancelled.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum DraftStatus {
+    #[default]
+    Draft,
+    AwaitingApproval,
+    Approved,
+    Sending,
+    Sent,
+    Failed,
+    Cancelled,
+}
+
+impl DraftStatus {
+    pub fn to_str(&self) -> &str {
+        match self {
+            Self::Draft => DRAFT,
+            Self::AwaitingApproval => AWAITING_APPROVAL,
+            Self::Approved => APPROVED,
+            Self::Sending => SENDING,
+            Self::Sent => SENT,
+            Self::Failed => FAILED,
+            Self::Cancelled => CANCELLED,
+        }
+    }
+
+    /// Whether the draft still needs the user: reviewing, approving or retrying.
+    pub fn is_pending(&self) -> bool {
+        matches!(
+            self,
+            Self::Draft | Self::AwaitingApproval | Self::Approved | Self::Failed
+        )
+    }
+
+    /// Whether the draft can still be edited.
+    pub fn is_editable(&self) -> bool {
+        self.is_pending()
+    }
+
+    pub fn can_become(&self, next: DraftStatus) -> bool {
+        use DraftStatus::*;
+        matches!(
+            (self, next),
+            (Draft, AwaitingApproval)
+                | (AwaitingApproval, Approved)
+                | (Approved | Failed, AwaitingApproval)
+                | (Approved | Failed, Sending)
+                | (Sending, Sent | Failed)
+                | (Draft | AwaitingApproval | Approved | Failed, Cancelled)
+        )
+    }
+}
+
+impl FromStr for DraftStatus {
+    type Err = AgentError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input.trim().to_lowercase().as_str() {
+            DRAFT => Ok(Self::Draft),
+            AWAITING_APPROVAL => Ok(Self::AwaitingApproval),
+            APPROVED => Ok(Self::Approved),
+            SENDING => Ok(Self::Sending),
+            SENT => Ok(Self::Sent),
+            FAILED => Ok(Self::Failed),
+            CANCELLED => Ok(Self::Cancelled),
+            other => Err(AgentError::ParseError(format!(
+                "Unknown draft status: {other}"
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for DraftStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_str())
+    }
+}
+
+const DRAFT: &str = "draft";
+const AWAITING_APPROVAL: &str = "awaiting_approval";
+const APPROVED: &str = "approved";
+const SENDING: &str = "sending";
+const SENT: &str = "sent";
+const FAILED: &str = "failed";
+const CANCELLED: &str = "cancelled";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        for status in [
+            DraftStatus::Draft,
+            DraftStatus::AwaitingApproval,
+            DraftStatus::Approved,
+            DraftStatus::Sending,
+            DraftStatus::Sent,
+            DraftStatus::Failed,
+            DraftStatus::Cancelled,
+        ] {
+            assert_eq!(DraftStatus::from_str(status.to_str()).unwrap(), status);
+            assert_eq!(
+                serde_json::to_value(status).unwrap(),
+                serde_json::json!(status.to_str())
+            );
+        }
+        assert!(DraftStatus::from_str("queued").is_err());
+    }
+
+    #[test]
+    fn test_sending_requires_approval() {
+        assert!(!DraftStatus::Draft.can_become(DraftStatus::Sent));
+        assert!(!DraftStatus::AwaitingApproval.can_become(DraftStatus::Sent));
+        assert!(DraftStatus::Approved.can_become(DraftStatus::Sending));
+        assert!(DraftStatus::Failed.can_become(DraftStatus::Sending));
+        assert!(DraftStatus::Sending.can_become(DraftStatus::Sent));
+        assert!(DraftStatus::Sending.can_become(DraftStatus::Failed));
+        assert!(!DraftStatus::Approved.can_become(DraftStatus::Sent));
+        assert!(!DraftStatus::Sending.can_become(DraftStatus::Sending));
+        assert!(!DraftStatus::Sending.can_become(DraftStatus::Cancelled));
+        assert!(!DraftStatus::Sending.is_pending());
+        assert!(!DraftStatus::Sent.can_become(DraftStatus::Cancelled));
+        assert!(!DraftStatus::Cancelled.can_become(DraftStatus::AwaitingApproval));
+        assert!(!DraftStatus::Sent.is_pending());
+    }
+}
@@ -1,44 +1,1279 @@
-use crate::agents::{Agent, AgentError, AgentParam, email::EmailResult};
+use std::{
+    str::FromStr,
+    sync::{Mutex, MutexGuard},
+};
 
-#[derive(Debug, Default)]
-pub struct EmailAgent {}
+use ollama_oxide::ChatMessage;
+
+use crate::{
+    agents::{
+        Agent, AgentError, AgentParam,
+        assistant::{PersonaTone, build_assistant_name},
+        classifier::Params,
+        contact::ContactAgent,
+        context::{BudgetedPrompt, ContextBudget, PromptSection, SectionPriority, join_message},
+        email::{
+            DraftEdit, DraftStatus, EmailDraft, EmailResult, EmailTemplate, IdentityReason,
+            IdentitySelection, IdentitySelector, RecipientResolution, ResponseKind, SenderIdentity,
+            detect_language, forward_subject, is_long_thread, quote_original, reply_subject,
+            resolve_recipients, split_recipients, split_thread, text_to_html,
+        },
+        prompts::{PromptLibrary, PromptSet, prompt_library::DEFAULT_LANGUAGE},
+    },
+    config::Config,
+    infra::{
+        assistant_ollama_client::AssistantOllamaClient,
+        contacts::{ContactQuery, ContactStore},
+        email::{EmailSender, IncomingEmail, Mailbox},
+        persistence::{
+            Database, DraftRepository, InMemoryStore, SqliteStore, StoredDraft,
+            database::unix_timestamp,
+        },
+    },
+};
+
+/// Writes emails through the user's assistant model: the classifier's
+/// recipient and message become an [`EmailDraft`] in the user's language and
+/// tone, signed with the user's name from `[user.settings]`.
+///
+/// Drafts are stored awaiting the user's approval; only approved drafts are
+/// sent (see [`DraftStatus`]).
+#[derive(Debug)]
+pub struct EmailAgent<R: DraftRepository = InMemoryStore> {
+    drafts: Mutex<R>,
+}
 
 impl EmailAgent {
+    /// Keeps drafts in memory; use [`with_repository`](Self::with_repository) to persist them.
     pub fn new() -> Self {
-        Self {}
+        Self::with_repository(InMemoryStore::new())
+    }
+}
+
+impl EmailAgent<SqliteStore> {
+    /// Keeps drafts in the `[database] path` database, so they survive restarts.
+    pub fn from_config() -> Result<Self, AgentError> {
+        let database = Database::from_config(&Config::get().database).map_err(storage_error)?;
+        Ok(Self::with_repository(SqliteStore::new(database)))
+    }
+}
+
+impl Default for EmailAgent {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
-#[derive(Debug, Default)]
+impl<R: DraftRepository> EmailAgent<R> {
+    pub fn with_repository(repository: R) -> Self {
+        Self {
+            drafts: Mutex::new(repository),
+        }
+    }
+
+    /// Stores a draft the user is still composing, sent from the identity
+    /// `[[user.settings.identities]]` choose for it and with its signature
+    /// (see [`IdentitySelector::sign`]).
+    pub fn save_draft(&self, email: EmailDraft) -> Result<StoredDraft, AgentError> {
+        let email = IdentitySelector::from_config(&Config::get().user.settings)?.sign(email);
+        self.store(email, DraftStatus::Draft)
+    }
+
+    pub fn draft(&self, id: &str) -> Result<StoredDraft, AgentError> {
+        let drafts = self.repository()?;
+        find(&*drafts, id)
+    }
+
+    /// Drafts waiting for the user — to finish, approve or retry — most recent first.
+    pub fn pending_drafts(&self) -> Result<Vec<StoredDraft>, AgentError> {
+        let drafts = self.repository()?.drafts().map_err(storage_error)?;
+        Ok(drafts
+            .into_iter()
+            .filter(|draft| draft.status.is_pending())
+            .collect())
+    }
+
+    pub fn submit_for_approval(&self, id: &str) -> Result<StoredDraft, AgentError> {
+        self.transition(id, DraftStatus::AwaitingApproval, |_| {})
+    }
+
+    /// Applies the user's changes. An approved or failed draft has to be
+    /// approved again.
+    pub fn edit_draft(&self, id: &str, edit: &DraftEdit) -> Result<StoredDraft, AgentError> {
+        let mut drafts = self.repository()?;
+        let mut draft = find(&*drafts, id)?;
+        if !draft.status.is_editable() {
+            return Err(AgentError::ProcessingError(format!(
+                "Draft {id} is {} and cannot be edited",
+                draft.status
+            )));
+        }
+        let mut email = draft.email.clone().unwrap_or_else(|| {
+            EmailDraft::new(
+                draft.recipient.iter().cloned().collect(),
+                &draft.subject,
+                &draft.body,
+            )
+        });
+        edit.apply(&mut email);
+        if draft.status != DraftStatus::Draft {
+            draft.status = DraftStatus::AwaitingApproval;
+        }
+        draft = draft.with_email(email);
+        draft.reason = None;
+        draft.updated_at = unix_timestamp();
+        drafts.save_draft(&draft).map_err(storage_error)?;
+        Ok(draft)
+    }
+
+    /// Refuses drafts with recipients that are not valid addresses, such as a
+    /// name that was never resolved.
+    pub fn approve(&self, id: &str) -> Result<StoredDraft, AgentError> {
+        let draft = self.draft(id)?;
+        if let Some(email) = &draft.email {
+            let invalid: Vec<&str> = email
+                .recipients()
+                .filter(|recipient| Mailbox::parse(recipient).is_err())
+                .collect();
+            if !invalid.is_empty()
+                || email.to.is_empty() && email.cc.is_empty() && email.bcc.is_empty()
+            {
+                return Err(AgentError::ProcessingError(format!(
+                    "Draft {id} needs valid recipients before approval: {}",
+                    if invalid.is_empty() {
+                        "none set".to_string()
+                    } else {
+                        invalid.join(", ")
+                    }
+                )));
+            }
+        }
+        self.transition(id, DraftStatus::Approved, |_| {})
+    }
+
+    /// Validates, resolves and expands the recipients of `input` through the
+    /// user's contacts (see [`resolve_recipients`]). The references are the
+    /// `to` addresses when set, otherwise the classifier's `recipient` split
+    /// into names ("Eva and Carlos"). Pass the result to
+    /// [`EmailParam::with_recipients`] before drafting.
+    pub async fn resolve_recipients<S: ContactStore>(
+        &self,
+        input: &EmailParam,
+        contacts: &ContactAgent<S>,
+    ) -> Result<RecipientResolution, AgentError> {
+        let references = if input.to().is_empty() {
+            split_recipients(input.recipient().unwrap_or_default())
+        } else {
+            input.to().to_vec()
+        };
+        let message = input.message().unwrap_or(input.input());
+        resolve_recipients(contacts, &references, input.filter(), message).await
+    }
+
+    /// Cancels a draft that was not sent, keeping the user's reason.
+    pub fn reject(&self, id: &str, reason: Option<&str>) -> Result<StoredDraft, AgentError> {
+        self.transition(id, DraftStatus::Cancelled, |draft| {
+            draft.reason = reason.map(str::to_string);
+        })
+    }
+
+    /// Sends an approved (or previously failed) draft from its sender identity,
+    /// or from `from` when it has none. A delivery error marks the draft
+    /// `failed` with the reason instead of returning an error.
+    ///
+    /// The draft is claimed as `sending` before delivery, so a concurrent call
+    /// for the same draft fails instead of sending it twice. Saving the outcome
+    /// is retried; if it still fails, the error says whether the mail went out
+    /// and the draft stays `sending` until [`record_sent`](Self::record_sent).
+    pub async fn send_approved(
+        &self,
+        id: &str,
+        from: Mailbox,
+        sender: &impl EmailSender,
+    ) -> Result<StoredDraft, AgentError> {
+        let email = self.claim_for_sending(id)?;
+        let outcome = match email
+            .sender(from)
+            .and_then(|from| email.to_message(from))
+            .and_then(|message| message.build())
+        {
+            Ok(message) => sender.send(&message).await.map(|_| ()),
+            Err(e) => Err(e),
+        };
+        let (next, reason) = match outcome {
+            Ok(()) => (DraftStatus::Sent, None),
+            Err(e) => (DraftStatus::Failed, Some(e.to_string())),
+        };
+        let mut attempt = 1;
+        loop {
+            match self.transition(id, next, |draft| draft.reason = reason.clone()) {
+                Ok(draft) => return Ok(draft),
+                Err(e) if attempt >= STATUS_WRITE_ATTEMPTS => {
+                    let outcome = match next {
+                        DraftStatus::Sent => "was sent",
+                        _ => "was not sent",
+                    };
+                    return Err(AgentError::ProcessingError(format!(
+                        "Draft {id} {outcome} but is still recorded as sending: {e}"
+                    )));
+                }
+                Err(_) => {
+                    tokio::time::sleep(STATUS_WRITE_BACKOFF * attempt).await;
+                    attempt += 1;
+                }
+            }
+        }
+    }
+
+    /// Marks a draft left `sending` as sent, after [`send_approved`](Self::send_approved)
+    /// reported that the mail went out but its status could not be saved.
+    pub fn record_sent(&self, id: &str) -> Result<StoredDraft, AgentError> {
+        self.transition(id, DraftStatus::Sent, |draft| draft.reason = None)
+    }
+
+    /// Checks and marks the draft `sending` under one repository lock.
+    fn claim_for_sending(&self, id: &str) -> Result<EmailDraft, AgentError> {
+        let mut drafts = self.repository()?;
+        let mut draft = find(&*drafts, id)?;
+        if !draft.status.can_become(DraftStatus::Sending) {
+            return Err(AgentError::ProcessingError(format!(
+                "Draft {id} is {} and must be approved before sending",
+                draft.status
+            )));
+        }
+        let email = draft
+            .email
+            .clone()
+            .ok_or_else(|| AgentError::ProcessingError(format!("Draft {id} has no email")))?;
+        draft.status = DraftStatus::Sending;
+        draft.updated_at = unix_timestamp();
+        drafts.save_draft(&draft).map_err(storage_error)?;
+        Ok(email)
+    }
+
+    fn store(&self, email: EmailDraft, status: DraftStatus) -> Result<StoredDraft, AgentError> {
+        let mut drafts = self.repository()?;
+        let id = next_draft_id(&drafts.drafts().map_err(storage_error)?);
+        let draft = StoredDraft::from_email(&id, &email).with_status(status);
+        drafts.save_draft(&draft).map_err(storage_error)?;
+        Ok(draft)
+    }
+
+    fn transition(
+        &self,
+        id: &str,
+        next: DraftStatus,
+        change: impl FnOnce(&mut StoredDraft),
+    ) -> Result<StoredDraft, AgentError> {
+        let mut drafts = self.repository()?;
+        let mut draft = find(&*drafts, id)?;
+        if !draft.status.can_become(next) {
+            return Err(AgentError::ProcessingError(format!(
+                "Draft {id} is {} and cannot become {next}",
+                draft.status
+            )));
+        }
+        change(&mut draft);
+        draft.status = next;
+        draft.updated_at = unix_timestamp();
+        drafts.save_draft(&draft).map_err(storage_error)?;
+        Ok(draft)
+    }
+
+    fn repository(&self) -> Result<MutexGuard<'_, R>, AgentError> {
+        self.drafts
+            .lock()
+            .map_err(|e| AgentError::ProcessingError(e.to_string()))
+    }
+}
+
+fn find(drafts: &impl DraftRepository, id: &str) -> Result<StoredDraft, AgentError> {
+    drafts
+        .draft(id)
+        .map_err(storage_error)?
+        .ok_or_else(|| AgentError::ProcessingError(format!("Draft {id} not found")))
+}
+
+/// Tries at saving the outcome of a send; the wait grows by the backoff each time.
+const STATUS_WRITE_ATTEMPTS: u32 = 3;
+const STATUS_WRITE_BACKOFF: std::time::Duration = std::time::Duration::from_millis(50);
+
+/// Next free id in the `d001` sequence.
+fn next_draft_id(drafts: &[StoredDraft]) -> String {
+    let next = drafts
+        .iter()
+        .filter_map(|draft| draft.id.strip_prefix(DRAFT_ID_PREFIX)?.parse::<u32>().ok())
+        .max()
+        .unwrap_or(0)
+        + 1;
+    format!("{DRAFT_ID_PREFIX}{next:03}")
+}
+
+fn storage_error(e: impl std::fmt::Display) -> AgentError {
+    AgentError::ProcessingError(format!("Draft storage failed: {e}"))
+}
+
+#[derive(Debug, Default, Clone)]
 pub struct EmailParam {
     input: String,
+    recipient: Option<String>,
+    message: Option<String>,
+    to: Vec<String>,
+    cc: Vec<String>,
+    bcc: Vec<String>,
+    language: Option<String>,
+    tone: Option<PersonaTone>,
+    html: bool,
+    signer: Option<String>,
+    assistant: Option<String>,
+    filter: Option<ContactQuery>,
+    recipients: Option<RecipientResolution>,
+    original: Option<IncomingEmail>,
+    response: Option<ResponseKind>,
+    user_address: Option<String>,
+    template: Option<EmailTemplate>,
+    identity: Option<IdentitySelection>,
 }
 
 impl EmailParam {
     pub fn new(input: &String) -> Self {
         Self {
             input: input.to_owned(),
+            ..Self::default()
         }
     }
+
+    /// The user's request with the recipient and message the classifier found in it.
+    pub fn from_params(input: &str, params: &Params) -> Self {
+        let mut param = Self::new(&input.to_string());
+        param.recipient = params.recipient().map(str::to_string);
+        param.message = params.message().map(str::to_string);
+        param.filter = params.filter().cloned();
+        param
+    }
+
+    pub fn with_recipient(mut self, recipient: &str) -> Self {
+        self.recipient = Some(recipient.to_string());
+        self
+    }
+
+    pub fn with_message(mut self, message: &str) -> Self {
+        self.message = Some(message.to_string());
+        self
+    }
+
+    /// Resolved addresses, e.g. from the `ContactAgent`. Without them the draft
+    /// is addressed to the recipient as written.
+    pub fn with_to(mut self, to: Vec<String>) -> Self {
+        self.to = to;
+        self
+    }
+
+    pub fn with_cc(mut self, cc: Vec<String>) -> Self {
+        self.cc = cc;
+        self
+    }
+
+    pub fn with_bcc(mut self, bcc: Vec<String>) -> Self {
+        self.bcc = bcc;
+        self
+    }
+
+    /// Language code of the draft; defaults to the prompt library language.
+    pub fn with_language(mut self, language: &str) -> Self {
+        self.language = Some(language.to_string());
+        self
+    }
+
+    /// Defaults to the `[assistant.persona] tone`, or professional.
+    pub fn with_tone(mut self, tone: PersonaTone) -> Self {
+        self.tone = Some(tone);
+        self
+    }
+
+    /// Also ask for an HTML body.
+    pub fn with_html(mut self, html: bool) -> Self {
+        self.html = html;
+        self
+    }
+
+    /// Defaults to the user's name from `[user.settings]`.
+    pub fn with_signer(mut self, signer: &str) -> Self {
+        self.signer = Some(signer.to_string());
+        self
+    }
+
+    /// Defaults to the assistant from `[user.settings]`.
+    pub fn with_assistant(mut self, assistant: &str) -> Self {
+        self.assistant = Some(assistant.to_string());
+        self
+    }
+
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+
+    pub fn recipient(&self) -> Option<&str> {
+        self.recipient.as_deref()
+    }
+
+    pub fn message(&self) -> Option<&str> {
+        self.message.as_deref()
+    }
+
+    pub fn to(&self) -> &[String] {
+        &self.to
+    }
+
+    pub fn cc(&self) -> &[String] {
+        &self.cc
+    }
+
+    pub fn bcc(&self) -> &[String] {
+        &self.bcc
+    }
+
+    pub fn language(&self) -> Option<&str> {
+        self.language.as_deref()
+    }
+
+    pub fn tone(&self) -> Option<PersonaTone> {
+        self.tone
+    }
+
+    pub fn wants_html(&self) -> bool {
+        self.html
+    }
+
+    pub fn signer(&self) -> Option<&str> {
+        self.signer.as_deref()
+    }
+
+    pub fn assistant(&self) -> Option<&str> {
+        self.assistant.as_deref()
+    }
+
+    /// The classifier's group filter, e.g. for "everyone in Engineering".
+    pub fn with_filter(mut self, filter: ContactQuery) -> Self {
+        self.filter = Some(filter);
+        self
+    }
+
+    /// Addresses the draft to the resolved recipients and reports the
+    /// resolution in the [`EmailResult`].
+    pub fn with_recipients(mut self, recipients: RecipientResolution) -> Self {
+        self.to = recipients.addresses();
+        self.recipients = Some(recipients);
+        self
+    }
+
+    pub fn filter(&self) -> Option<&ContactQuery> {
+        self.filter.as_ref()
+    }
+
+    pub fn recipients(&self) -> Option<&RecipientResolution> {
+        self.recipients.as_ref()
+    }
+
+    /// Answers a received email: the draft gets the `Re:`/`Fwd:` subject and
+    /// threading headers, quotes the original and, unless a language is set,
+    /// is written in the original's language. Replies go to the sender; a
+    /// forward still needs recipients.
+    pub fn with_original(mut self, original: IncomingEmail, kind: ResponseKind) -> Self {
+        self.original = Some(original);
+        self.response = Some(kind);
+        self
+    }
+
+    /// The user's own address, left out of reply-all copies.
+    pub fn with_user_address(mut self, address: &str) -> Self {
+        self.user_address = Some(address.to_string());
+        self
+    }
+
+    pub fn original(&self) -> Option<&IncomingEmail> {
+        self.original.as_ref()
+    }
+
+    pub fn response_kind(&self) -> Option<ResponseKind> {
+        self.response
+    }
+
+    pub fn user_address(&self) -> Option<&str> {
+        self.user_address.as_deref()
+    }
+
+    /// A template the model follows as a style guide, e.g. from
+    /// `TemplateLibrary::for_request`. To send a template without the model,
+    /// render it with `EmailTemplate::to_draft` instead.
+    pub fn with_template(mut self, template: EmailTemplate) -> Self {
+        self.template = Some(template);
+        self
+    }
+
+    pub fn template(&self) -> Option<&EmailTemplate> {
+        self.template.as_ref()
+    }
+
+    /// Sends from `identity` instead of the one `[[user.settings.identities]]`
+    /// rules would choose.
+    pub fn with_identity(mut self, identity: SenderIdentity) -> Self {
+        self.identity = Some(IdentitySelection::new(
+            identity,
+            IdentityReason::Requested,
+            "chosen by the user",
+        ));
+        self
+    }
+
+    /// The identity the draft is sent from, once chosen.
+    pub fn identity(&self) -> Option<&IdentitySelection> {
+        self.identity.as_ref()
+    }
 }
 
 impl AgentParam for EmailParam {}
 
-impl Agent<EmailParam, EmailResult> for EmailAgent {
+impl<R: DraftRepository + Send> Agent<EmailParam, EmailResult> for EmailAgent<R> {
     fn process(
         &self,
-        _input: EmailParam,
+        input: EmailParam,
     ) -> impl std::future::Future<Output = Result<EmailResult, AgentError>> + Send {
         async move {
-            // TODO: Implement email sending logic
-            // - Parse input parameters (recipient, message)
-            // - Validate email address
-            // - Send email using email_sender
-            // - Return sending confirmation
-
-            Err(AgentError::ProcessingError(
-                "Email sending not implemented yet".to_string(),
+            let input = with_user_defaults(input)?;
+            let language = input.language().unwrap_or(DEFAULT_LANGUAGE);
+            let prompts = PromptLibrary::get().prompt_set_in(EMAIL_DRAFT_PROMPTS, language);
+            let model = build_assistant_name(input.assistant().unwrap_or_default());
+
+            let client = AssistantOllamaClient::new();
+            let budgeted = ContextBudget::for_model(&client, &model)
+                .await
+                .fit(build_draft_sections(&prompts, &input))
+                .map_err(|e| AgentError::ProcessingError(format!("Drafting failed: {e}")))?;
+            let response = client
+                .send_chat_message(build_messages(&budgeted), &model)
+                .await
+                .map_err(|e| AgentError::NetworkError(format!("Drafting failed: {e}")))?;
+            let content = response
+                .message
+                .and_then(|message| message.content().map(str::to_string))
+                .ok_or_else(|| {
+                    AgentError::ParseError("Drafting failed: empty response".to_string())
+                })?;
+
+            let draft = parse_draft(&content, &input)?;
+            let stored = self.store(draft.clone(), DraftStatus::AwaitingApproval)?;
+            let mut result = EmailResult::new(draft)
+                .with_stored(&stored)
+                .with_prompt_version(prompts.version());
+            if let Some(recipients) = input.recipients() {
+                result = result.with_recipients(recipients.clone());
+            }
+            if let Some(identity) = input.identity() {
+                result = result.with_identity(identity.clone());
+            }
+            Ok(result)
+        }
+    }
+}
+
+/// Fills what the user did not set from the config: the sender identity (see
+/// [`IdentitySelector`]) and, from it or the user settings, the signer,
+/// assistant, language and tone.
+fn with_user_defaults(mut input: EmailParam) -> Result<EmailParam, AgentError> {
+    let config = Config::get();
+    if input.identity.is_none() {
+        input.identity = IdentitySelector::from_config(&config.user.settings)?.select(&input);
+    }
+    let identity = input
+        .identity
+        .as_ref()
+        .map(|selection| selection.identity.clone());
+    input.signer = input
+        .signer
+        .or_else(|| identity.as_ref().map(|i| i.display_name().to_string()))
+        .or(Some(config.user.settings.name.clone()));
+    input.user_address = input
+        .user_address
+        .or_else(|| identity.as_ref().map(|i| i.mailbox.address.clone()));
+    input.assistant = input
+        .assistant
+        .or(Some(config.user.settings.assistant.clone()));
+    input.language = input
+        .language
+        .or_else(|| {
+            let original = input.original.as_ref()?;
+            detect_language(split_thread(&original.text_body).0).map(str::to_string)
+        })
+        .or_else(|| identity.and_then(|i| i.language))
+        .or(Some(PromptLibrary::get().language().to_string()));
+    input.tone = input.tone.or_else(|| {
+        config
+            .assistant
+            .persona
+            .tone
+            .as_deref()
+            .and_then(|tone| PersonaTone::from_str(tone).ok())
+    });
+    Ok(input)
+}
+
+fn build_messages(budgeted: &BudgetedPrompt) -> Vec<ChatMessage> {
+    vec![
+        ChatMessage::system(budgeted.message(SYSTEM_MESSAGE, SPACE)),
+        ChatMessage::user(budgeted.message(USER_MESSAGE, SPACE)),
+    ]
+}
+
+/// System and user prompts for drafting `input`, as sent to the model when
+/// nothing has to be trimmed.
+pub fn build_draft_prompts(prompts: &PromptSet, input: &EmailParam) -> (String, String) {
+    let sections = build_draft_sections(prompts, input);
+    (
+        join_message(&sections, SYSTEM_MESSAGE, SPACE),
+        join_message(&sections, USER_MESSAGE, SPACE),
+    )
+}
+
+/// Splits the drafting prompt into budgetable sections: the earlier messages
+/// of a long thread (with the request to summarise them) are trimmed first,
+/// then the template style guide; the rest is required.
+fn build_draft_sections(prompts: &PromptSet, input: &EmailParam) -> Vec<PromptSection> {
+    let signer = input.signer().unwrap_or_default();
+    let tone = input.tone().unwrap_or(PersonaTone::Professional);
+    let language = language_name(input.language().unwrap_or(DEFAULT_LANGUAGE));
+    let system = |name: &str, content: &str| {
+        PromptSection::required(name, content).in_message(SYSTEM_MESSAGE)
+    };
+    let user =
+        |name: &str, content: &str| PromptSection::required(name, content).in_message(USER_MESSAGE);
+
+    let mut sections = vec![
+        system(
+            "you_are",
+            &prompts.section("you_are", YOU_ARE).replace("{}", signer),
+        ),
+        system(
+            "output_format",
+            prompts.section("output_format", OUTPUT_FORMAT),
+        ),
+        system(
+            "tone",
+            &prompts
+                .section("tone", TONE)
+                .replace("{}", tone.instruction()),
+        ),
+        system(
+            "language",
+            &prompts
+                .section("language", LANGUAGE)
+                .replace("{}", language),
+        ),
+    ];
+    if signature(input).is_some() {
+        sections.push(system(
+            "identity_signature",
+            prompts.section("identity_signature", IDENTITY_SIGNATURE),
+        ));
+    } else {
+        sections.push(system(
+            "signature",
+            &prompts
+                .section("signature", SIGNATURE)
+                .replace("{}", signer),
+        ));
+    }
+    if input.wants_html() {
+        sections.push(system("html", prompts.section("html", HTML)));
+    }
+    sections.push(system("task", prompts.section("task", TASK)));
+    if let Some(template) = input.template() {
+        sections.push(
+            PromptSection::new(
+                "template",
+                &prompts
+                    .section("template", TEMPLATE)
+                    .replace("{}", &template.style_guide()),
+                SectionPriority::Medium,
+            )
+            .in_message(SYSTEM_MESSAGE),
+        );
+    }
+    let original = input.original().zip(input.response_kind());
+    if let Some((original, kind)) = original {
+        sections.push(match kind {
+            ResponseKind::Forward => system("forward", prompts.section("forward", FORWARD)),
+            _ => system("reply", prompts.section("reply", REPLY)),
+        });
+        if is_long_thread(original) {
+            sections.push(
+                PromptSection::new(
+                    "thread_summary",
+                    prompts.section("thread_summary", THREAD_SUMMARY),
+                    SectionPriority::Low,
+                )
+                .in_message(SYSTEM_MESSAGE),
+            );
+        }
+    }
+
+    if let Some(recipient) = input.recipient().filter(|r| !r.trim().is_empty()) {
+        sections.push(user(
+            "recipient",
+            &prompts
+                .section("recipient", RECIPIENT)
+                .replace("{}", recipient),
+        ));
+    }
+    if let Some(message) = input.message().filter(|m| !m.trim().is_empty()) {
+        sections.push(user(
+            "message",
+            &prompts.section("message", MESSAGE).replace("{}", message),
+        ));
+    }
+    if let Some((original, _)) = original {
+        let from = original
+            .from
+            .as_ref()
+            .map(ToString::to_string)
+            .unwrap_or_default();
+        let (latest, history) = split_thread(&original.text_body);
+        sections.push(user(
+            "original_from",
+            &prompts
+                .section("original_from", ORIGINAL_FROM)
+                .replace("{}", &from),
+        ));
+        sections.push(user(
+            "original_subject",
+            &prompts
+                .section("original_subject", ORIGINAL_SUBJECT)
+                .replace("{}", &original.subject),
+        ));
+        sections.push(user(
+            "original_body",
+            &prompts
+                .section("original_body", ORIGINAL_BODY)
+                .replace("{}", &truncate(latest, MAX_ORIGINAL_CHARS)),
+        ));
+        if is_long_thread(original) {
+            sections.push(
+                PromptSection::new(
+                    "earlier_messages",
+                    &prompts
+                        .section("earlier_messages", EARLIER_MESSAGES)
+                        .replace("{}", &truncate(history, MAX_ORIGINAL_CHARS)),
+                    SectionPriority::Low,
+                )
+                .in_message(USER_MESSAGE),
+            );
+        }
+    }
+    sections.push(user(
+        "input",
+        &prompts.section("input", INPUT).replace("{}", input.input()),
+    ));
+    sections.push(user("output", prompts.section("output", OUTPUT)));
+    sections
+}
+
+/// Reads the model's `{"subject","body","html"}` answer into a draft addressed
+/// as `input` asks. The signature is appended when the model left it out.
+pub fn parse_draft(content: &str, input: &EmailParam) -> Result<EmailDraft, AgentError> {
+    let json = extract_json(content).ok_or_else(|| {
+        AgentError::ParseError(format!("Drafting failed: no JSON in response: {content}"))
+    })?;
+    let value: serde_json::Value = serde_json::from_str(json)
+        .map_err(|e| AgentError::ParseError(format!("Drafting failed: {e}")))?;
+    let text = |key: &str| {
+        value
+            .get(key)
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .unwrap_or_default()
+            .to_string()
+    };
+
+    let subject = text("subject");
+    let mut body = text("body");
+    if body.is_empty() {
+        return Err(AgentError::ParseError(
+            "Drafting failed: the model returned an empty body".to_string(),
+        ));
+    }
+    if signature(input).is_none()
+        && let Some(signer) = input.signer().filter(|s| !s.trim().is_empty())
+        && !body.contains(signer)
+    {
+        body = format!("{body}\n\n{signer}");
+    }
+
+    let to = if input.to().is_empty() {
+        input
+            .recipient()
+            .filter(|r| !r.trim().is_empty())
+            .map(|r| vec![r.trim().to_string()])
+            .unwrap_or_default()
+    } else {
+        input.to().to_vec()
+    };
+    let mut draft = EmailDraft::new(to, &subject, &body)
+        .with_cc(input.cc().to_vec())
+        .with_bcc(input.bcc().to_vec())
+        .with_language(input.language().unwrap_or(DEFAULT_LANGUAGE))
+        .with_tone(input.tone().unwrap_or(PersonaTone::Professional));
+    let html = text("html");
+    if input.wants_html() && !html.is_empty() {
+        draft = draft.with_html_body(&html);
+    }
+    if let Some(selection) = input.identity() {
+        draft = draft
+            .with_signature(&selection.identity)
+            .with_identity(&selection.identity);
+    }
+    if let Some((original, kind)) = input.original().zip(input.response_kind()) {
+        draft = answer(draft, original, kind, input, &text("summary"));
+    }
+    Ok(draft)
+}
+
+/// The sender identity's plain signature, which replaces the signer's name.
+fn signature(input: &EmailParam) -> Option<&str> {
+    input.identity()?.identity.plain_signature()
+}
+
+/// Turns `draft` into an answer to `original`: subject prefix, threading
+/// headers, recipients when none were given, and the quoted original below the
+/// body in plain text and HTML.
+fn answer(
+    mut draft: EmailDraft,
+    original: &IncomingEmail,
+    kind: ResponseKind,
+    input: &EmailParam,
+    summary: &str,
+) -> EmailDraft {
+    let mut references = original.references.clone();
+    if let Some(id) = &original.message_id {
+        references.push(id.clone());
+    }
+    if kind.is_reply() {
+        draft.subject = reply_subject(&original.subject);
+        draft.in_reply_to = original.message_id.clone();
+        if draft.to.is_empty() {
+            let reply_to = if original.reply_to.is_empty() {
+                original.from.iter().cloned().collect()
+            } else {
+                original.reply_to.clone()
+            };
+            draft.to = reply_to.iter().map(ToString::to_string).collect();
+        }
+        if kind == ResponseKind::ReplyAll && input.cc().is_empty() {
+            let mut seen: Vec<String> = draft
+                .to
+                .iter()
+                .filter_map(|to| Mailbox::parse(to).ok())
+                .map(|to| to.address.to_lowercase())
+                .chain(input.user_address().map(str::to_lowercase))
+                .collect();
+            let mut cc: Vec<String> = Vec::new();
+            for mailbox in original.to.iter().chain(&original.cc) {
+                let address = mailbox.address.to_lowercase();
+                if !seen.contains(&address) {
+                    seen.push(address);
+                    cc.push(mailbox.to_string());
+                }
+            }
+            draft.cc = cc;
+        }
+    } else {
+        draft.subject = forward_subject(&original.subject);
+    }
+    draft.references = references;
+
+    let summary = (is_long_thread(original) && !summary.is_empty()).then_some(summary);
+    let (quoted, quoted_html) = quote_original(original, kind, &draft.language, summary);
+    let html = draft
+        .html_body
+        .take()
+        .unwrap_or_else(|| text_to_html(&draft.body));
+    draft.body = format!("{}\n\n{quoted}", draft.body);
+    draft.with_html_body(&format!("{html}\n{quoted_html}"))
+}
+
+/// The first `max` characters of `text`.
+fn truncate(text: &str, max: usize) -> String {
+    text.chars().take(max).collect()
+}
+
+/// The JSON object in a response, with or without a ```json fence.
+pub(crate) fn extract_json(content: &str) -> Option<&str> {
+    let start = content.find('{')?;
+    let end = content.rfind('}')?;
+    (start < end).then(|| &content[start..=end])
+}
+
+pub(crate) fn language_name(code: &str) -> &str {
+    match code {
+        "en" => "English",
+        "pt-BR" | "pt" => "Brazilian Portuguese",
+        "es" => "Spanish",
+        "fr" => "French",
+        other => other,
+    }
+}
+
+/// Prompt set name in the prompt library; the constants below are the fallback.
+pub const EMAIL_DRAFT_PROMPTS: &str = "email_draft";
+
+const DRAFT_ID_PREFIX: &str = "d";
+/// Longer originals are cut in the prompt; the draft still quotes them in full.
+const MAX_ORIGINAL_CHARS: usize = 4000;
+const SYSTEM_MESSAGE: &str = "system";
+const USER_MESSAGE: &str = "user";
+const SPACE: &str = "        ";
+const YOU_ARE: &str = "You are an assistant that writes emails on behalf of {}. Your response MUST be a JSON object. You will never use markdown notation in outputs.";
+const OUTPUT_FORMAT: &str = "Output-Format: {\"subject\":\"\",\"body\":\"\",\"html\":\"\"}";
+const TONE: &str = "Tone: {}.";
+const LANGUAGE: &str = "Write the subject and the body in {}.";
+const IDENTITY_SIGNATURE: &str =
+    "End the body with a short closing and no name: the signature is added automatically.";
+const SIGNATURE: &str = "End the body with a short closing followed by the name {}.";
+const HTML: &str = "Also fill html with the same body as simple HTML (p, br, ul, li, strong).";
+const TASK: &str = "Task: Write the email the user asked for. Keep every fact from the request and do not invent dates, names or commitments. Leave html empty unless asked for it.";
+const RECIPIENT: &str = "Recipient: \"{}\"";
+const MESSAGE: &str = "Message: \"{}\"";
+const TEMPLATE: &str = "Use this template as a style guide: keep its structure, greeting and closing, write in the requested language and replace the {variables} with what the request says: \"{}\"";
+const REPLY: &str = "The user is replying to the original email below. Write only the new message and do not quote the original: it is added below the body automatically. Answer what the original asks.";
+const FORWARD: &str = "The user is forwarding the original email below. Write only a short note to the new recipients and do not copy the original: it is added below the body automatically.";
+const THREAD_SUMMARY: &str = "The original quotes a long thread: also fill \"summary\" with two or three sentences summarising the earlier messages, in the language of the email.";
+const ORIGINAL_FROM: &str = "Original from: \"{}\"";
+const ORIGINAL_SUBJECT: &str = "Original subject: \"{}\"";
+const ORIGINAL_BODY: &str = "Original message: \"{}\"";
+const EARLIER_MESSAGES: &str = "Earlier messages: \"{}\"";
+const INPUT: &str = "Request: \"{}\"";
+const OUTPUT: &str = "Output: ";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{agents::context::TokenEstimator, infra::persistence::DatabaseError};
+
+    fn builtin() -> PromptSet {
+        PromptSet::builtin(EMAIL_DRAFT_PROMPTS)
+    }
+
+    fn param() -> EmailParam {
+        EmailParam::from_params(
+            "Envie um e-mail para Eva dizendo que não vou à reunião",
+            &Params::with_values("Eva".to_string(), "Não vou à reunião".to_string()),
+        )
+        .with_signer("Ana")
+        .with_language("pt-BR")
+        .with_tone(PersonaTone::Friendly)
+    }
+
+    #[test]
+    fn test_email_agent_new() {
+        let agent = EmailAgent::new();
+        assert!(format!("{:?}", agent).contains("EmailAgent"));
+    }
+
+    #[test]
+    fn test_draft_workflow_needs_approval() {
+        let agent = EmailAgent::new();
+        let email = EmailDraft::new(vec!["eva@example.com".to_string()], "Hi", "Hello");
+        let first = agent.save_draft(email.clone()).unwrap();
+        let second = agent.save_draft(email).unwrap();
+        assert_eq!((first.id.as_str(), second.id.as_str()), ("d001", "d002"));
+        assert_eq!(first.status, DraftStatus::Draft);
+
+        assert!(agent.approve("d001").is_err());
+        agent.submit_for_approval("d001").unwrap();
+        assert_eq!(agent.approve("d001").unwrap().status, DraftStatus::Approved);
+
+        let edited = agent
+            .edit_draft("d001", &DraftEdit::new().with_subject("Meeting"))
+            .unwrap();
+        assert_eq!(edited.status, DraftStatus::AwaitingApproval);
+        assert_eq!(edited.subject, "Meeting");
+
+        let rejected = agent.reject("d002", Some("Not needed")).unwrap();
+        assert_eq!(rejected.status, DraftStatus::Cancelled);
+        assert_eq!(rejected.reason.as_deref(), Some("Not needed"));
+        assert!(agent.edit_draft("d002", &DraftEdit::new()).is_err());
+
+        let pending = agent.pending_drafts().unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].id, "d001");
+        assert!(agent.draft("d003").is_err());
+    }
+
+    /// Counts deliveries and yields once, so a concurrent send can interleave.
+    #[derive(Default)]
+    struct CountingSender(std::sync::atomic::AtomicUsize);
+
+    impl EmailSender for CountingSender {
+        async fn send(
+            &self,
+            email: &crate::infra::email::OutgoingEmail,
+        ) -> Result<crate::infra::email::SendReceipt, crate::infra::email::EmailError> {
+            self.0.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            tokio::task::yield_now().await;
+            Ok(crate::infra::email::SendReceipt::new(
+                crate::infra::email::EmailTransportKind::Outbox,
+                email.recipients.clone(),
+                "sent",
             ))
         }
     }
+
+    #[tokio::test]
+    async fn test_concurrent_sends_deliver_a_draft_once() {
+        let agent = EmailAgent::new();
+        let email = EmailDraft::new(vec!["eva@example.com".to_string()], "Hi", "Hello");
+        let draft = agent.save_draft(email).unwrap();
+        agent.submit_for_approval(&draft.id).unwrap();
+        agent.approve(&draft.id).unwrap();
+        let sender = CountingSender::default();
+
+        let (first, second) = tokio::join!(
+            agent.send_approved(&draft.id, Mailbox::new("ana@example.com"), &sender),
+            agent.send_approved(&draft.id, Mailbox::new("ana@example.com"), &sender),
+        );
+
+        assert_eq!(sender.0.load(std::sync::atomic::Ordering::SeqCst), 1);
+        assert_eq!(first.unwrap().status, DraftStatus::Sent);
+        assert!(second.is_err());
+        assert_eq!(agent.draft(&draft.id).unwrap().status, DraftStatus::Sent);
+    }
+
+    /// Saves normally `healthy` times, then fails the next `failures` saves.
+    struct FlakyStore {
+        inner: InMemoryStore,
+        healthy: usize,
+        failures: usize,
+    }
+
+    impl DraftRepository for FlakyStore {
+        fn save_draft(&mut self, draft: &StoredDraft) -> Result<(), DatabaseError> {
+            if self.healthy > 0 {
+                self.healthy -= 1;
+            } else if self.failures > 0 {
+                self.failures -= 1;
+                return Err(DatabaseError::QueryError("disk I/O error".to_string()));
+            }
+            self.inner.save_draft(draft)
+        }
+
+        fn draft(&self, id: &str) -> Result<Option<StoredDraft>, DatabaseError> {
+            self.inner.draft(id)
+        }
+
+        fn drafts(&self) -> Result<Vec<StoredDraft>, DatabaseError> {
+            self.inner.drafts()
+        }
+
+        fn delete_draft(&mut self, id: &str) -> Result<bool, DatabaseError> {
+            self.inner.delete_draft(id)
+        }
+    }
+
+    /// Saves, submits, approves and then claims the draft before the store fails.
+    fn flaky_agent(failures: usize) -> (EmailAgent<FlakyStore>, String) {
+        let agent = EmailAgent::with_repository(FlakyStore {
+            inner: InMemoryStore::new(),
+            healthy: 4,
+            failures,
+        });
+        let email = EmailDraft::new(vec!["eva@example.com".to_string()], "Hi", "Hello");
+        let draft = agent.store(email, DraftStatus::Draft).unwrap();
+        agent.submit_for_approval(&draft.id).unwrap();
+        agent.approve(&draft.id).unwrap();
+        (agent, draft.id)
+    }
+
+    #[tokio::test]
+    async fn test_send_approved_retries_saving_the_outcome() {
+        let (agent, id) = flaky_agent(2);
+        let sender = CountingSender::default();
+
+        let sent = agent
+            .send_approved(&id, Mailbox::new("ana@example.com"), &sender)
+            .await
+            .unwrap();
+
+        assert_eq!(sent.status, DraftStatus::Sent);
+        assert_eq!(sender.0.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_unsaved_send_is_reported_and_can_be_recorded() {
+        let (agent, id) = flaky_agent(usize::MAX);
+        let sender = CountingSender::default();
+
+        let error = agent
+            .send_approved(&id, Mailbox::new("ana@example.com"), &sender)
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("was sent"), "{error}");
+        assert_eq!(agent.draft(&id).unwrap().status, DraftStatus::Sending);
+        assert!(
+            agent
+                .send_approved(&id, Mailbox::new("ana@example.com"), &sender)
+                .await
+                .is_err()
+        );
+        assert_eq!(sender.0.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+        agent.repository().unwrap().failures = 0;
+        assert_eq!(agent.record_sent(&id).unwrap().status, DraftStatus::Sent);
+    }
+
+    #[test]
+    fn test_every_template_language_has_a_name() {
+        for language in crate::agents::email::template_library::TEMPLATE_LANGUAGES {
+            assert_ne!(language_name(language), language, "{language}");
+        }
+    }
+
+    #[test]
+    fn test_email_param_from_params() {
+        let param = param();
+        assert_eq!(param.recipient(), Some("Eva"));
+        assert_eq!(param.message(), Some("Não vou à reunião"));
+        assert!(param.to().is_empty());
+        assert!(!param.wants_html());
+    }
+
+    #[test]
+    fn test_build_draft_prompts() {
+        let (system, user) = build_draft_prompts(&builtin(), &param());
+
+        assert!(system.starts_with("You are an assistant that writes emails on behalf of Ana."));
+        assert!(system.contains("Output-Format"));
+        assert!(system.contains(PersonaTone::Friendly.instruction()));
+        assert!(system.contains("Brazilian Portuguese"));
+        assert!(system.contains("followed by the name Ana"));
+        assert!(!system.contains(HTML));
+        assert!(user.contains("Recipient: \"Eva\""));
+        assert!(user.contains("Message: \"Não vou à reunião\""));
+        assert!(user.ends_with("Output: "));
+
+        let (system, user) = build_draft_prompts(
+            &builtin(),
+            &EmailParam::new(&"Email Carlos".to_string()).with_html(true),
+        );
+        assert!(system.contains(HTML));
+        assert!(!user.contains("Recipient:"));
+    }
+
+    #[test]
+    fn test_draft_budget_trims_earlier_messages_first() {
+        let original = IncomingEmail {
+            from: Some(Mailbox::new("eva@example.com")),
+            subject: "Budget".to_string(),
+            text_body: format!(
+                "Can we meet on Friday?\n\n> {}",
+                "earlier budget discussion ".repeat(200)
+            ),
+            ..IncomingEmail::default()
+        };
+        let input = param().with_original(original, ResponseKind::Reply);
+        let sections = build_draft_sections(&builtin(), &input);
+        let required: usize = sections
+            .iter()
+            .filter(|section| !section.is_trimmable())
+            .map(|section| TokenEstimator::new().estimate(section.content()))
+            .sum();
+
+        let budgeted = ContextBudget::new(required + 50)
+            .with_response_reserve(0)
+            .fit(sections)
+            .unwrap();
+
+        assert_eq!(
+            budgeted.report().dropped,
+            vec!["thread_summary".to_string(), "earlier_messages".to_string()]
+        );
+        let messages = build_messages(&budgeted);
+        assert_eq!(messages.len(), 2);
+        assert!(
+            budgeted
+                .message(USER_MESSAGE, SPACE)
+                .contains("Can we meet on Friday?")
+        );
+        assert!(
+            !budgeted
+                .message(USER_MESSAGE, SPACE)
+                .contains("earlier budget")
+        );
+    }
+
+    #[test]
+    fn test_parse_draft_addresses_and_signs() {
+        let content = "```json\n{\"subject\":\"Reunião\",\"body\":\"Oi Eva, não vou conseguir ir.\\n\\nAbraços\",\"html\":\"<p>Oi</p>\"}\n```";
+        let draft = parse_draft(content, &param()).unwrap();
+
+        assert_eq!(draft.to, vec!["Eva"]);
+        assert_eq!(draft.subject, "Reunião");
+        assert_eq!(
+            draft.body,
+            "Oi Eva, não vou conseguir ir.\n\nAbraços\n\nAna"
+        );
+        assert_eq!(draft.html_body, None);
+        assert_eq!(draft.language, "pt-BR");
+        assert_eq!(draft.tone, PersonaTone::Friendly);
+
+        let draft = parse_draft(
+            "{\"subject\":\"Hi\",\"body\":\"Hello\\n\\nBest,\\nAna\",\"html\":\"<p>Hello</p>\"}",
+            &param()
+                .with_to(vec!["eva.green@example.com".to_string()])
+                .with_cc(vec!["carlos@example.com".to_string()])
+                .with_html(true),
+        )
+        .unwrap();
+        assert_eq!(draft.to, vec!["eva.green@example.com"]);
+        assert_eq!(draft.cc, vec!["carlos@example.com"]);
+        assert_eq!(draft.body, "Hello\n\nBest,\nAna");
+        assert_eq!(draft.html_body.as_deref(), Some("<p>Hello</p>"));
+    }
+
+    #[test]
+    fn test_reply_all_keeps_every_other_recipient_once() {
+        let original = IncomingEmail {
+            from: Some(Mailbox::new("eva@x.com")),
+            to: vec![
+                Mailbox::new("anna@x.com").with_name("Anna"),
+                Mailbox::new("ana@example.com"),
+            ],
+            cc: vec![Mailbox::new("na@x.com"), Mailbox::new("ANNA@x.com")],
+            subject: "Budget".to_string(),
+            ..IncomingEmail::default()
+        };
+        let input =
+            EmailParam::new(&"Reply to all".to_string()).with_user_address("ana@example.com");
+
+        let draft = answer(
+            EmailDraft::new(Vec::new(), "", "Ok"),
+            &original,
+            ResponseKind::ReplyAll,
+            &input,
+            "",
+        );
+
+        assert_eq!(draft.to, vec!["eva@x.com"]);
+        assert_eq!(draft.cc, vec!["Anna <anna@x.com>", "na@x.com"]);
+    }
+
+    #[test]
+    fn test_parse_draft_rejects_unusable_responses() {
+        assert!(matches!(
+            parse_draft("I can't help with that", &param()),
+            Err(AgentError::ParseError(_))
+        ));
+        assert!(matches!(
+            parse_draft("{\"subject\":\"Hi\",\"body\":\"\"}", &param()),
+            Err(AgentError::ParseError(_))
+        ));
+    }
 }
@@ -0,0 +1,219 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    agents::assistant::PersonaTone,
+    infra::email::{EmailError, Mailbox, MessageBuilder},
+};
+
+use super::{quoting::text_to_html, sender_identity::SenderIdentity};
+
+/// An email written by the assistant, not sent yet.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct EmailDraft {
+    pub to: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cc: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub bcc: Vec<String>,
+    pub subject: String,
+    /// Plain text body, signed by the user.
+    pub body: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub html_body: Option<String>,
+    /// Language code the draft was written in, e.g. `en` or `pt-BR`.
+    pub language: String,
+    pub tone: PersonaTone,
+    /// `Message-ID` of the email this draft replies to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub in_reply_to: Option<String>,
+    /// The `References` header: the thread so far, oldest first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub references: Vec<String>,
+    /// The sender identity's `Name <address>`, when the draft was written for one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from: Option<String>,
+    /// The `[email.accounts.<name>]` the identity sends through.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub smtp_account: Option<String>,
+}
+
+impl EmailDraft {
+    pub fn new(to: Vec<String>, subject: &str, body: &str) -> Self {
+        Self {
+            to,
+            cc: Vec::new(),
+            bcc: Vec::new(),
+            subject: subject.to_string(),
+            body: body.to_string(),
+            html_body: None,
+            language: "en".to_string(),
+            tone: PersonaTone::Professional,
+            in_reply_to: None,
+            references: Vec::new(),
+            from: None,
+            smtp_account: None,
+        }
+    }
+
+    pub fn with_cc(mut self, cc: Vec<String>) -> Self {
+        self.cc = cc;
+        self
+    }
+
+    pub fn with_bcc(mut self, bcc: Vec<String>) -> Self {
+        self.bcc = bcc;
+        self
+    }
+
+    pub fn with_html_body(mut self, html_body: &str) -> Self {
+        self.html_body = Some(html_body.to_string());
+        self
+    }
+
+    pub fn with_language(mut self, language: &str) -> Self {
+        self.language = language.to_string();
+        self
+    }
+
+    pub fn with_tone(mut self, tone: PersonaTone) -> Self {
+        self.tone = tone;
+        self
+    }
+
+    pub fn with_in_reply_to(mut self, message_id: &str) -> Self {
+        self.in_reply_to = Some(message_id.to_string());
+        self
+    }
+
+    pub fn with_references(mut self, references: Vec<String>) -> Self {
+        self.references = references;
+        self
+    }
+
+    /// Sends the draft from `identity` and through its SMTP account.
+    pub fn with_identity(mut self, identity: &SenderIdentity) -> Self {
+        self.from = Some(identity.mailbox.to_string());
+        self.smtp_account = identity.smtp_account.clone();
+        self
+    }
+
+    /// Appends `identity`'s signature to the body, and to the HTML body when
+    /// there is one, unless they already contain it.
+    pub fn with_signature(mut self, identity: &SenderIdentity) -> Self {
+        let Some(signature) = identity.plain_signature() else {
+            return self;
+        };
+        if !self.body.contains(signature) {
+            self.body = format!("{}\n\n{signature}", self.body);
+        }
+        if let Some(html) = &self.html_body {
+            let signature_html = identity
+                .signature_html
+                .clone()
+                .unwrap_or_else(|| text_to_html(signature));
+            if !html.contains(&signature_html) {
+                self.html_body = Some(format!("{html}\n{signature_html}"));
+            }
+        }
+        self
+    }
+
+    /// The identity's mailbox the draft was written for, else `fallback`.
+    pub fn sender(&self, fallback: Mailbox) -> Result<Mailbox, EmailError> {
+        match &self.from {
+            Some(from) => Mailbox::parse(from),
+            None => Ok(fallback),
+        }
+    }
+
+    /// Every address the draft goes to: `to`, then `cc`, then `bcc`.
+    pub fn recipients(&self) -> impl Iterator<Item = &str> {
+        self.to
+            .iter()
+            .chain(&self.cc)
+            .chain(&self.bcc)
+            .map(String::as_str)
+    }
+
+    /// A [`MessageBuilder`] sending the draft from `from`; attachments and
+    /// threading headers can still be added before building.
+    pub fn to_message(&self, from: Mailbox) -> Result<MessageBuilder, EmailError> {
+        let mut message = MessageBuilder::new()
+            .with_from(from)
+            .with_subject(&self.subject)
+            .with_text_body(&self.body);
+        for address in &self.to {
+            message = message.with_to(Mailbox::parse(address)?);
+        }
+        for address in &self.cc {
+            message = message.with_cc(Mailbox::parse(address)?);
+        }
+        for address in &self.bcc {
+            message = message.with_bcc(Mailbox::parse(address)?);
+        }
+        if let Some(html) = &self.html_body {
+            message = message.with_html_body(html);
+        }
+        if let Some(in_reply_to) = &self.in_reply_to {
+            message = message.with_in_reply_to(in_reply_to);
+        }
+        if !self.references.is_empty() {
+            message = message.with_references(self.references.clone());
+        }
+        Ok(message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serialization_skips_empty_fields() {
+        let draft = EmailDraft::new(vec!["eva@example.com".to_string()], "Hi", "Hello\n\nAna");
+        let json = serde_json::to_value(&draft).unwrap();
+
+        assert_eq!(json["to"][0], "eva@example.com");
+        assert_eq!(json["tone"], "professional");
+        assert!(json.get("cc").is_none());
+        assert!(json.get("html_body").is_none());
+        assert_eq!(serde_json::from_value::<EmailDraft>(json).unwrap(), draft);
+    }
+
+    #[test]
+    fn test_recipients_in_order() {
+        let draft = EmailDraft::new(vec!["a@x.com".to_string()], "", "")
+            .with_cc(vec!["b@x.com".to_string()])
+            .with_bcc(vec!["c@x.com".to_string()]);
+        assert_eq!(
+            draft.recipients().collect::<Vec<_>>(),
+            vec!["a@x.com", "b@x.com", "c@x.com"]
+        );
+    }
+
+    #[test]
+    fn test_identity_sets_the_sender() {
+        let identity = SenderIdentity::new(
+            "work",
+            Mailbox::new("ana.souza@techsolutions.com").with_name("Ana Souza"),
+        )
+        .with_smtp_account("work");
+        let draft = EmailDraft::new(vec!["eva@example.com".to_string()], "Hi", "Hello");
+        let fallback = Mailbox::new("ana@example.com");
+
+        assert_eq!(draft.sender(fallback.clone()).unwrap(), fallback);
+
+        let draft = draft.with_identity(&identity);
+        assert_eq!(draft.sender(fallback).unwrap(), identity.mailbox);
+        assert_eq!(draft.smtp_account.as_deref(), Some("work"));
+    }
+
+    #[test]
+    fn test_to_message_rejects_invalid_addresses() {
+        let draft = EmailDraft::new(vec!["Eva".to_string()], "Hi", "Hello");
+        assert!(matches!(
+            draft.to_message(Mailbox::new("ana@example.com")),
+            Err(EmailError::InvalidMessage(_))
+        ));
+    }
+}
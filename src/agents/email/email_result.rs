@@ -2,12 +2,63 @@ use serde::{Deserialize, Serialize};
 
 use crate::agents::AgentResult;
 
+use crate::infra::persistence::StoredDraft;
+
+use super::{
+    draft_status::DraftStatus, email_draft::EmailDraft, identity_selection::IdentitySelection,
+    recipient_resolution::RecipientResolution,
+};
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct EmailResult {}
+pub struct EmailResult {
+    pub draft: EmailDraft,
+    /// Id of the stored draft, for approving, editing or rejecting it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub draft_id: Option<String>,
+    #[serde(default)]
+    pub status: DraftStatus,
+    /// How the recipients were resolved, when [`EmailAgent::resolve_recipients`](super::EmailAgent::resolve_recipients) ran.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recipients: Option<RecipientResolution>,
+    /// The sender identity the draft was written for and why.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub identity: Option<IdentitySelection>,
+    /// Version of the prompt set that produced the draft.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt_version: Option<String>,
+}
 
 impl EmailResult {
-    pub fn new() -> Self {
-        Self {}
+    pub fn new(draft: EmailDraft) -> Self {
+        Self {
+            draft,
+            draft_id: None,
+            status: DraftStatus::Draft,
+            recipients: None,
+            identity: None,
+            prompt_version: None,
+        }
+    }
+
+    pub fn with_stored(mut self, stored: &StoredDraft) -> Self {
+        self.draft_id = Some(stored.id.clone());
+        self.status = stored.status;
+        self
+    }
+
+    pub fn with_recipients(mut self, recipients: RecipientResolution) -> Self {
+        self.recipients = Some(recipients);
+        self
+    }
+
+    pub fn with_identity(mut self, identity: IdentitySelection) -> Self {
+        self.identity = Some(identity);
+        self
+    }
+
+    pub fn with_prompt_version(mut self, version: &str) -> Self {
+        self.prompt_version = Some(version.to_string());
+        self
     }
 }
 
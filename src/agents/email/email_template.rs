@@ -0,0 +1,222 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{agents::AgentError, config::Config};
+
+use super::{
+    email_agent::EmailParam,
+    email_draft::EmailDraft,
+    identity_selector::IdentitySelector,
+    template_kind::TemplateKind,
+    template_values::{MESSAGE, TemplateValues},
+};
+
+/// A formulaic email with `{name}` variables, in one language. Rendered as a
+/// draft directly ([`EmailTemplate::to_draft`]) or passed to the model as a
+/// style guide (`EmailParam::with_template`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EmailTemplate {
+    pub kind: TemplateKind,
+    pub language: String,
+    /// Version of the template file it came from, or `"builtin"`.
+    pub version: String,
+    pub subject: String,
+    pub body: String,
+}
+
+impl EmailTemplate {
+    /// The variables used in the subject and the body, in order of appearance.
+    pub fn variables(&self) -> Vec<&str> {
+        let mut names = Vec::new();
+        for name in placeholders(&self.subject).chain(placeholders(&self.body)) {
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+        names
+    }
+
+    /// Subject and body with every variable replaced. Fails naming the
+    /// variables `values` does not have.
+    pub fn render(&self, values: &TemplateValues) -> Result<(String, String), AgentError> {
+        let missing: Vec<&str> = self
+            .variables()
+            .into_iter()
+            .filter(|name| values.get(name).is_none())
+            .collect();
+        if !missing.is_empty() {
+            return Err(AgentError::ProcessingError(format!(
+                "Template {} is missing values for: {}",
+                self.kind,
+                missing.join(", ")
+            )));
+        }
+        Ok((fill(&self.subject, values), fill(&self.body, values)))
+    }
+
+    /// The rendered template as a draft to `to` in the template's language,
+    /// from the identity `[[user.settings.identities]]` choose for it.
+    pub fn to_draft(
+        &self,
+        to: Vec<String>,
+        values: &TemplateValues,
+    ) -> Result<EmailDraft, AgentError> {
+        let identities = IdentitySelector::from_config(&Config::get().user.settings)?;
+        self.to_draft_from(to, values, &identities)
+    }
+
+    /// [`to_draft`](Self::to_draft) choosing among `identities`. The chosen
+    /// identity's signature, else its display name, fills `{sender}`.
+    pub fn to_draft_from(
+        &self,
+        to: Vec<String>,
+        values: &TemplateValues,
+        identities: &IdentitySelector,
+    ) -> Result<EmailDraft, AgentError> {
+        let request = EmailParam::new(&values.get(MESSAGE).unwrap_or_default().to_string())
+            .with_to(to.clone());
+        let Some(selection) = identities.select(&request) else {
+            let (subject, body) = self.render(values)?;
+            return Ok(EmailDraft::new(to, &subject, &body).with_language(&self.language));
+        };
+        let identity = &selection.identity;
+        let sender = identity
+            .plain_signature()
+            .unwrap_or(identity.display_name());
+        let (subject, body) = self.render(&values.clone().with_sender(sender))?;
+        Ok(EmailDraft::new(to, &subject, &body)
+            .with_language(&self.language)
+            .with_signature(identity)
+            .with_identity(identity))
+    }
+
+    /// Subject and body as the model sees them, variables left in place.
+    pub fn style_guide(&self) -> String {
+        format!("Subject: {}\n{}", self.subject, self.body)
+    }
+}
+
+/// Names of the `{name}` placeholders in `text`; other braces are ignored.
+fn placeholders(text: &str) -> impl Iterator<Item = &str> {
+    text.split('{').skip(1).filter_map(|part| {
+        let (name, _) = part.split_once('}')?;
+        is_variable(name).then_some(name)
+    })
+}
+
+fn is_variable(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+fn fill(text: &str, values: &TemplateValues) -> String {
+    let mut filled = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('{') {
+        filled.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        match after
+            .split_once('}')
+            .filter(|(name, _)| is_variable(name))
+            .and_then(|(name, tail)| Some((values.get(name)?, tail)))
+        {
+            Some((value, tail)) => {
+                filled.push_str(value);
+                rest = tail;
+            }
+            None => {
+                filled.push('{');
+                rest = after;
+            }
+        }
+    }
+    filled.push_str(rest);
+    filled
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{agents::email::SenderIdentity, infra::email::Mailbox};
+
+    fn template() -> EmailTemplate {
+        EmailTemplate {
+            kind: TemplateKind::ThankYou,
+            language: "en".to_string(),
+            version: "1.0.0".to_string(),
+            subject: "Thank you, {recipient}".to_string(),
+            body: "Hi {recipient},\n\n{message} {not a variable}\n\n{sender}".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_identity_signature_fills_the_sender() {
+        let identity = SenderIdentity::new(
+            "work",
+            Mailbox::new("ana.souza@techsolutions.com").with_name("Ana Souza"),
+        )
+        .with_signature("Ana Souza\nTech Solutions", None);
+        let values = TemplateValues::new()
+            .with("recipient", "Eva")
+            .with("message", "Thanks for the review.")
+            .with_sender("Ana");
+
+        let draft = template()
+            .to_draft_from(
+                vec!["eva@example.com".to_string()],
+                &values,
+                &IdentitySelector::new(vec![identity]),
+            )
+            .unwrap();
+
+        assert!(
+            draft
+                .body
+                .ends_with("{not a variable}\n\nAna Souza\nTech Solutions")
+        );
+        assert_eq!(
+            draft.from.as_deref(),
+            Some("Ana Souza <ana.souza@techsolutions.com>")
+        );
+    }
+
+    #[test]
+    fn test_variables_in_order_without_repeats() {
+        assert_eq!(
+            template().variables(),
+            vec!["recipient", "message", "sender"]
+        );
+    }
+
+    #[test]
+    fn test_render_fills_every_variable() {
+        let values = TemplateValues::new()
+            .with("recipient", "Eva")
+            .with("message", "Thanks for the review.")
+            .with_sender("Ana");
+
+        let draft = template()
+            .to_draft(vec!["eva@example.com".to_string()], &values)
+            .unwrap();
+
+        assert_eq!(draft.subject, "Thank you, Eva");
+        assert_eq!(
+            draft.body,
+            "Hi Eva,\n\nThanks for the review. {not a variable}\n\nAna"
+        );
+        assert_eq!(draft.language, "en");
+    }
+
+    #[test]
+    fn test_render_names_missing_values() {
+        let values = TemplateValues::new().with("recipient", "Eva");
+
+        let error = template().render(&values).unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "Processing error: Template thank_you is missing values for: message, sender"
+        );
+    }
+}
@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Which rule chose a draft's sender identity.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum IdentityReason {
+    /// Set on the request with `EmailParam::with_identity`.
+    Requested,
+    /// A reply sent from the address the original was sent to.
+    OriginalRecipient,
+    /// A recipient is in one of the identity's `recipient_domains`.
+    RecipientDomain,
+    /// The request mentions one of the identity's `keywords`.
+    Keyword,
+    /// The request reads as work or personal, like the identity's `context`.
+    Context,
+    /// No rule matched: the default (or first) identity.
+    Default,
+}
+
+impl IdentityReason {
+    pub fn to_str(&self) -> &str {
+        match self {
+            Self::Requested => "requested",
+            Self::OriginalRecipient => "original_recipient",
+            Self::RecipientDomain => "recipient_domain",
+            Self::Keyword => "keyword",
+            Self::Context => "context",
+            Self::Default => "default",
+        }
+    }
+}
+
+impl fmt::Display for IdentityReason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_str())
+    }
+}
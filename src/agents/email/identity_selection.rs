@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+
+use super::{identity_reason::IdentityReason, sender_identity::SenderIdentity};
+
+/// The identity a draft is sent from and why it was chosen.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct IdentitySelection {
+    pub identity: SenderIdentity,
+    pub reason: IdentityReason,
+    /// E.g. `eva@techsolutions.com is in techsolutions.com`.
+    pub explanation: String,
+}
+
+impl IdentitySelection {
+    pub fn new(identity: SenderIdentity, reason: IdentityReason, explanation: &str) -> Self {
+        Self {
+            identity,
+            reason,
+            explanation: explanation.to_string(),
+        }
+    }
+}
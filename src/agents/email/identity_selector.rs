@@ -0,0 +1,283 @@
+use crate::{
+    agents::{AgentError, contact::AddressSelector},
+    config::UserSettings,
+    infra::email::Mailbox,
+};
+
+use super::{
+    email_agent::EmailParam, email_draft::EmailDraft, identity_reason::IdentityReason,
+    identity_selection::IdentitySelection, sender_identity::SenderIdentity,
+};
+
+/// Chooses which of the user's [`SenderIdentity`]s a draft is sent from. The
+/// first rule that matches wins:
+///
+/// 1. a reply is sent from the identity the original was addressed to
+/// 2. a recipient is in one of the identity's `recipient_domains`
+/// 3. the request mentions one of its `keywords`
+/// 4. the request reads as work or personal
+///    ([`AddressSelector::infer_from_text`]) and the identity has that `context`
+/// 5. the identity marked default, otherwise the first one
+#[derive(Debug, Clone, Default)]
+pub struct IdentitySelector {
+    identities: Vec<SenderIdentity>,
+}
+
+impl IdentitySelector {
+    pub fn new(identities: Vec<SenderIdentity>) -> Self {
+        Self { identities }
+    }
+
+    /// The identities in `[[user.settings.identities]]`; fails on the first
+    /// invalid one.
+    pub fn from_config(settings: &UserSettings) -> Result<Self, AgentError> {
+        let identities = settings
+            .identities
+            .iter()
+            .map(SenderIdentity::from_config)
+            .collect::<Result<_, _>>()?;
+        Ok(Self::new(identities))
+    }
+
+    pub fn identities(&self) -> &[SenderIdentity] {
+        &self.identities
+    }
+
+    pub fn get(&self, name: &str) -> Option<&SenderIdentity> {
+        self.identities
+            .iter()
+            .find(|identity| identity.name.eq_ignore_ascii_case(name))
+    }
+
+    /// The identity for `param`, or `None` when the user has none configured.
+    pub fn select(&self, param: &EmailParam) -> Option<IdentitySelection> {
+        self.by_original(param)
+            .or_else(|| self.by_recipient_domain(param))
+            .or_else(|| self.by_keyword(param))
+            .or_else(|| self.by_context(param))
+            .or_else(|| self.by_default())
+    }
+
+    /// The identity for a draft written without the model: its recipients,
+    /// subject and body stand in for the request.
+    pub fn select_for_draft(&self, draft: &EmailDraft) -> Option<IdentitySelection> {
+        let request = format!("{} {}", draft.subject, draft.body);
+        self.select(
+            &EmailParam::new(&request)
+                .with_to(draft.to.clone())
+                .with_cc(draft.cc.clone()),
+        )
+    }
+
+    /// Sends `draft` from the identity chosen for it, with that identity's
+    /// signature. A draft that already has a sender is left as it is.
+    pub fn sign(&self, draft: EmailDraft) -> EmailDraft {
+        if draft.from.is_some() {
+            return draft;
+        }
+        match self.select_for_draft(&draft) {
+            Some(selection) => draft
+                .with_signature(&selection.identity)
+                .with_identity(&selection.identity),
+            None => draft,
+        }
+    }
+
+    fn by_original(&self, param: &EmailParam) -> Option<IdentitySelection> {
+        let original = param.original()?;
+        let addressed = |identity: &&SenderIdentity| {
+            original
+                .to
+                .iter()
+                .chain(&original.cc)
+                .any(|to| to.address.eq_ignore_ascii_case(&identity.mailbox.address))
+        };
+        let identity = self.identities.iter().find(addressed)?;
+        Some(IdentitySelection::new(
+            identity.clone(),
+            IdentityReason::OriginalRecipient,
+            &format!("the original was sent to {}", identity.mailbox.address),
+        ))
+    }
+
+    fn by_recipient_domain(&self, param: &EmailParam) -> Option<IdentitySelection> {
+        let addresses: Vec<String> = recipient_addresses(param)
+            .iter()
+            .filter_map(|address| Mailbox::parse(address).ok())
+            .map(|mailbox| mailbox.address.to_lowercase())
+            .collect();
+        self.identities.iter().find_map(|identity| {
+            identity.recipient_domains.iter().find_map(|domain| {
+                let address = addresses.iter().find(|address| {
+                    let host = address.rsplit_once('@').map_or("", |(_, host)| host);
+                    host == domain || host.ends_with(&format!(".{domain}"))
+                })?;
+                Some(IdentitySelection::new(
+                    identity.clone(),
+                    IdentityReason::RecipientDomain,
+                    &format!("{address} is in {domain}"),
+                ))
+            })
+        })
+    }
+
+    fn by_keyword(&self, param: &EmailParam) -> Option<IdentitySelection> {
+        let text = request_text(param).to_lowercase();
+        self.identities.iter().find_map(|identity| {
+            let keyword = identity
+                .keywords
+                .iter()
+                .find(|keyword| text.contains(keyword.as_str()))?;
+            Some(IdentitySelection::new(
+                identity.clone(),
+                IdentityReason::Keyword,
+                &format!("it mentions \"{keyword}\""),
+            ))
+        })
+    }
+
+    fn by_context(&self, param: &EmailParam) -> Option<IdentitySelection> {
+        let signal = AddressSelector::new().infer_from_text(&request_text(param))?;
+        let identity = self
+            .identities
+            .iter()
+            .find(|identity| identity.context == Some(signal.context))?;
+        Some(IdentitySelection::new(
+            identity.clone(),
+            IdentityReason::Context,
+            &format!("a {} message: {}", signal.context, signal.evidence),
+        ))
+    }
+
+    fn by_default(&self) -> Option<IdentitySelection> {
+        let identity = self
+            .identities
+            .iter()
+            .find(|identity| identity.is_default)
+            .or(self.identities.first())?;
+        Some(IdentitySelection::new(
+            identity.clone(),
+            IdentityReason::Default,
+            "the default identity",
+        ))
+    }
+}
+
+/// What the draft is addressed to: the given or resolved addresses, else the
+/// classifier's recipient when it is an address.
+fn recipient_addresses(param: &EmailParam) -> Vec<String> {
+    let mut addresses: Vec<String> = param.to().iter().chain(param.cc()).cloned().collect();
+    if let Some(recipients) = param.recipients() {
+        addresses.extend(recipients.addresses());
+    }
+    if let Some(recipient) = param.recipient() {
+        addresses.push(recipient.to_string());
+    }
+    addresses
+}
+
+fn request_text(param: &EmailParam) -> String {
+    format!("{} {}", param.input(), param.message().unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        agents::{contact::MessageContext, email::ResponseKind},
+        infra::email::IncomingEmail,
+    };
+
+    fn selector() -> IdentitySelector {
+        let mut work = SenderIdentity::new(
+            "work",
+            Mailbox::new("ana.souza@techsolutions.com").with_name("Ana Souza"),
+        );
+        work.context = Some(MessageContext::Work);
+        work.recipient_domains = vec!["techsolutions.com".to_string()];
+        let mut personal = SenderIdentity::new("personal", Mailbox::new("ana@example.com"));
+        personal.context = Some(MessageContext::Personal);
+        personal.keywords = vec!["churrasco".to_string()];
+        personal.is_default = true;
+        IdentitySelector::new(vec![work, personal])
+    }
+
+    fn selected(param: &EmailParam) -> (String, IdentityReason) {
+        let selection = selector().select(param).unwrap();
+        (selection.identity.name, selection.reason)
+    }
+
+    #[test]
+    fn test_rules_in_order() {
+        let to_colleague = EmailParam::new(&"Tell Eva about the churrasco".to_string())
+            .with_to(vec!["Eva <eva@eng.techsolutions.com>".to_string()]);
+        assert_eq!(
+            selected(&to_colleague),
+            ("work".to_string(), IdentityReason::RecipientDomain)
+        );
+
+        let keyword = EmailParam::new(&"Invite Eva to the churrasco".to_string());
+        assert_eq!(
+            selected(&keyword),
+            ("personal".to_string(), IdentityReason::Keyword)
+        );
+
+        let context = EmailParam::new(&"Send Eva the project report".to_string());
+        assert_eq!(
+            selected(&context),
+            ("work".to_string(), IdentityReason::Context)
+        );
+
+        let neither = EmailParam::new(&"Write to Eva".to_string());
+        assert_eq!(
+            selected(&neither),
+            ("personal".to_string(), IdentityReason::Default)
+        );
+    }
+
+    #[test]
+    fn test_replies_come_from_the_address_written_to() {
+        let original = IncomingEmail {
+            to: vec![Mailbox::new("ANA.SOUZA@techsolutions.com")],
+            ..IncomingEmail::default()
+        };
+        let param = EmailParam::new(&"Reply about the churrasco".to_string())
+            .with_original(original, ResponseKind::Reply);
+
+        assert_eq!(
+            selected(&param),
+            ("work".to_string(), IdentityReason::OriginalRecipient)
+        );
+        assert!(IdentitySelector::default().select(&param).is_none());
+    }
+
+    #[test]
+    fn test_sign_applies_the_identity_to_a_manual_draft() {
+        let mut selector = selector();
+        selector.identities[0] = selector.identities[0]
+            .clone()
+            .with_signature("Ana Souza\nTech Solutions", Some("<p>Ana Souza</p>"));
+        let draft = EmailDraft::new(
+            vec!["eva@techsolutions.com".to_string()],
+            "Report",
+            "Hi Eva,\n\nHere it is.",
+        )
+        .with_html_body("<p>Hi Eva,</p>");
+
+        let signed = selector.sign(draft);
+
+        assert_eq!(
+            signed.body,
+            "Hi Eva,\n\nHere it is.\n\nAna Souza\nTech Solutions"
+        );
+        assert_eq!(
+            signed.html_body.as_deref(),
+            Some("<p>Hi Eva,</p>\n<p>Ana Souza</p>")
+        );
+        assert_eq!(
+            signed.from.as_deref(),
+            Some("Ana Souza <ana.souza@techsolutions.com>")
+        );
+        assert_eq!(selector.sign(signed.clone()), signed);
+    }
+}
@@ -0,0 +1,144 @@
+use unicode_normalization::UnicodeNormalization;
+
+/// Guesses the language of `text` among the ones the prompts ship in (`en`,
+/// `pt-BR`, `es`, `fr`) by counting common words. `None` when the text is too
+/// short or no language stands out.
+pub fn detect_language(text: &str) -> Option<&'static str> {
+    let words: Vec<String> = text
+        .split(|c: char| !c.is_alphabetic() && c != '\'')
+        .filter(|word| !word.is_empty())
+        .take(MAX_WORDS)
+        .map(|word| word.to_lowercase().nfc().collect())
+        .collect();
+
+    let mut scores: Vec<(&'static str, usize)> = COMMON_WORDS
+        .iter()
+        .map(|(language, common)| {
+            let hits = words
+                .iter()
+                .filter(|word| common.contains(&word.as_str()))
+                .count();
+            (*language, hits)
+        })
+        .collect();
+    scores.sort_by_key(|(_, hits)| std::cmp::Reverse(*hits));
+    let (best, hits) = scores[0];
+    (hits >= MIN_HITS && hits > scores[1].1).then_some(best)
+}
+
+const MAX_WORDS: usize = 400;
+const MIN_HITS: usize = 2;
+/// Frequent words that are rare in the other languages of the list; words
+/// shared by all of them ("a", "me", "no") are left out.
+const COMMON_WORDS: &[(&str, &[&str])] = &[
+    (
+        "en",
+        &[
+            "the", "and", "is", "are", "you", "to", "of", "for", "with", "this", "that", "will",
+            "can", "please", "thanks", "thank", "we", "i", "my", "your", "have", "be", "it", "on",
+            "at", "would", "could", "hi", "hello", "regards", "best",
+        ],
+    ),
+    (
+        "pt-BR",
+        &[
+            "você",
+            "vocês",
+            "não",
+            "está",
+            "são",
+            "obrigado",
+            "obrigada",
+            "olá",
+            "oi",
+            "para",
+            "com",
+            "uma",
+            "um",
+            "que",
+            "do",
+            "da",
+            "dos",
+            "das",
+            "em",
+            "na",
+            "no",
+            "por",
+            "favor",
+            "abraço",
+            "abraços",
+            "atenciosamente",
+            "também",
+            "mas",
+            "eu",
+            "seu",
+            "sua",
+        ],
+    ),
+    (
+        "es",
+        &[
+            "usted", "el", "los", "las", "está", "gracias", "hola", "para", "con", "una", "que",
+            "del", "en", "por", "favor", "saludos", "también", "pero", "yo", "su", "es", "muy",
+            "reunión", "mañana", "semana", "puedes", "puede",
+        ],
+    ),
+    (
+        "fr",
+        &[
+            "vous",
+            "le",
+            "les",
+            "des",
+            "est",
+            "merci",
+            "bonjour",
+            "pour",
+            "avec",
+            "une",
+            "que",
+            "du",
+            "dans",
+            "par",
+            "je",
+            "nous",
+            "votre",
+            "vos",
+            "cordialement",
+            "aussi",
+            "mais",
+            "être",
+            "pas",
+            "très",
+            "réunion",
+            "demain",
+            "semaine",
+        ],
+    ),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_language() {
+        assert_eq!(
+            detect_language("Hi Ana, can we move the meeting to Thursday? Thanks!"),
+            Some("en")
+        );
+        assert_eq!(
+            detect_language("Olá Ana, você pode enviar os números até sexta? Abraços"),
+            Some("pt-BR")
+        );
+        assert_eq!(
+            detect_language("Hola Ana, ¿puedes enviarme los números del informe? Gracias"),
+            Some("es")
+        );
+        assert_eq!(
+            detect_language("Bonjour Ana, pouvez-vous nous envoyer le rapport demain ? Merci"),
+            Some("fr")
+        );
+        assert_eq!(detect_language("OK"), None);
+    }
+}
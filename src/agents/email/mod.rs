@@ -1,5 +1,47 @@
+pub mod draft_edit;
+pub mod draft_status;
 pub mod email_agent;
+pub mod email_draft;
 pub mod email_result;
+pub mod email_template;
+pub mod identity_reason;
+pub mod identity_selection;
+pub mod identity_selector;
+pub mod language_detector;
+pub mod quoting;
+pub mod recipient_problem;
+pub mod recipient_resolution;
+pub mod recipient_resolver;
+pub mod recipient_source;
+pub mod resolved_recipient;
+pub mod response_kind;
+pub mod sender_identity;
+pub mod template_kind;
+pub mod template_library;
+pub mod template_values;
+pub mod unresolved_recipient;
 
-pub use email_agent::EmailAgent;
+pub use draft_edit::DraftEdit;
+pub use draft_status::DraftStatus;
+pub use email_agent::{EmailAgent, EmailParam, build_draft_prompts, parse_draft};
+pub use email_draft::EmailDraft;
 pub use email_result::EmailResult;
+pub use email_template::EmailTemplate;
+pub use identity_reason::IdentityReason;
+pub use identity_selection::IdentitySelection;
+pub use identity_selector::IdentitySelector;
+pub use language_detector::detect_language;
+pub use quoting::{
+    forward_subject, is_long_thread, quote_original, reply_subject, split_thread, text_to_html,
+};
+pub use recipient_problem::RecipientProblem;
+pub use recipient_resolution::RecipientResolution;
+pub use recipient_resolver::{resolve_recipients, split_recipients};
+pub use recipient_source::RecipientSource;
+pub use resolved_recipient::ResolvedRecipient;
+pub use response_kind::ResponseKind;
+pub use sender_identity::SenderIdentity;
+pub use template_kind::TemplateKind;
+pub use template_library::TemplateLibrary;
+pub use template_values::TemplateValues;
+pub use unresolved_recipient::UnresolvedRecipient;
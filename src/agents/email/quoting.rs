@@ -0,0 +1,323 @@
+use crate::infra::email::IncomingEmail;
+
+use super::response_kind::ResponseKind;
+
+/// `Re: ` plus the original subject, without piling up prefixes: a reply to
+/// `RE: Re: Budget` is `Re: Budget`.
+pub fn reply_subject(subject: &str) -> String {
+    format!("Re: {}", strip_prefixes(subject, REPLY_PREFIXES))
+}
+
+/// `Fwd: ` plus the original subject, dropping earlier forward prefixes.
+pub fn forward_subject(subject: &str) -> String {
+    format!("Fwd: {}", strip_prefixes(subject, FORWARD_PREFIXES))
+}
+
+fn strip_prefixes<'a>(subject: &'a str, prefixes: &[&str]) -> &'a str {
+    let mut subject = subject.trim();
+    loop {
+        let Some((head, rest)) = subject.split_once(':') else {
+            return subject;
+        };
+        // `Re[2]` and `AW` count as well as `Re`.
+        let head = head.trim().split('[').next().unwrap_or_default();
+        if !prefixes.iter().any(|p| p.eq_ignore_ascii_case(head)) {
+            return subject;
+        }
+        subject = rest.trim_start();
+    }
+}
+
+/// Splits a message body into what the sender wrote and the earlier messages
+/// they quoted, at the first quoted line (`>`) or attribution line such as
+/// "On …, Ana wrote:".
+pub fn split_thread(text: &str) -> (&str, &str) {
+    let mut offset = 0;
+    for line in text.split_inclusive('\n') {
+        let trimmed = line.trim();
+        if trimmed.starts_with('>')
+            || THREAD_MARKERS
+                .iter()
+                .any(|marker| trimmed.ends_with(marker) || trimmed.starts_with(marker))
+        {
+            return (text[..offset].trim_end(), text[offset..].trim());
+        }
+        offset += line.len();
+    }
+    (text.trim_end(), "")
+}
+
+/// Whether quoting `original` in full would make the draft too long; the
+/// earlier messages are then summarised instead.
+pub fn is_long_thread(original: &IncomingEmail) -> bool {
+    let (_, history) = split_thread(&original.text_body);
+    !history.is_empty() && original.text_body.chars().count() > LONG_THREAD_CHARS
+}
+
+/// The original below a reply or forward, in plain text and HTML, with the
+/// attribution in `language`. With `history_summary`, only the sender's latest
+/// message is quoted and the earlier ones are replaced by the summary.
+pub fn quote_original(
+    original: &IncomingEmail,
+    kind: ResponseKind,
+    language: &str,
+    history_summary: Option<&str>,
+) -> (String, String) {
+    let labels = labels(language);
+    let (latest, _) = split_thread(&original.text_body);
+    let (quoted, summary) = match history_summary.filter(|s| !s.trim().is_empty()) {
+        Some(summary) => (latest, Some(summary.trim())),
+        None => (original.text_body.trim_end(), None),
+    };
+    let from = original
+        .from
+        .as_ref()
+        .map(ToString::to_string)
+        .unwrap_or_default();
+    let date = original
+        .date
+        .map(|date| date.format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_default();
+
+    let mut plain = Vec::new();
+    let mut html = Vec::new();
+    match kind {
+        ResponseKind::Forward => {
+            let to = original
+                .to
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", ");
+            plain.push(labels.forwarded.to_string());
+            let mut header = Vec::new();
+            for (label, value) in [
+                (labels.from, &from),
+                (labels.date, &date),
+                (labels.subject, &original.subject),
+                (labels.to, &to),
+            ] {
+                if !value.is_empty() {
+                    plain.push(format!("{label} {value}"));
+                    header.push(format!("{label} {}", escape_html(value)));
+                }
+            }
+            plain.push(String::new());
+            plain.push(quoted.to_string());
+            html.push(format!(
+                "<div>{}<br>{}</div><br>",
+                escape_html(labels.forwarded),
+                header.join("<br>")
+            ));
+            html.push(format!("<div>{}</div>", text_to_html(quoted)));
+        }
+        _ => {
+            let attribution = if date.is_empty() {
+                labels.wrote_undated.replace("{from}", &from)
+            } else {
+                labels
+                    .wrote
+                    .replace("{date}", &date)
+                    .replace("{from}", &from)
+            };
+            plain.push(attribution.clone());
+            plain.extend(quoted.lines().map(|line| {
+                if line.is_empty() || line.starts_with('>') {
+                    format!(">{line}")
+                } else {
+                    format!("> {line}")
+                }
+            }));
+            html.push(format!("<div>{}</div>", escape_html(&attribution)));
+            html.push(format!(
+                "<blockquote type=\"cite\">{}</blockquote>",
+                text_to_html(quoted)
+            ));
+        }
+    }
+    if let Some(summary) = summary {
+        plain.push(String::new());
+        plain.push(labels.summary.to_string());
+        plain.push(summary.to_string());
+        html.push(format!(
+            "<p><em>{}</em><br>{}</p>",
+            escape_html(labels.summary),
+            escape_html(summary)
+        ));
+    }
+    (plain.join("\n"), html.join("\n"))
+}
+
+/// Plain text as HTML paragraphs, with line breaks kept.
+pub fn text_to_html(text: &str) -> String {
+    text.trim()
+        .split("\n\n")
+        .filter(|paragraph| !paragraph.trim().is_empty())
+        .map(|paragraph| {
+            let lines: Vec<String> = paragraph.trim().lines().map(escape_html).collect();
+            format!("<p>{}</p>", lines.join("<br>"))
+        })
+        .collect()
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Quoting labels in one language.
+struct Labels {
+    wrote: &'static str,
+    wrote_undated: &'static str,
+    forwarded: &'static str,
+    from: &'static str,
+    date: &'static str,
+    subject: &'static str,
+    to: &'static str,
+    summary: &'static str,
+}
+
+fn labels(language: &str) -> &'static Labels {
+    let language = language.to_lowercase();
+    if language.starts_with("pt") {
+        &PORTUGUESE
+    } else if language.starts_with("es") {
+        &SPANISH
+    } else if language.starts_with("fr") {
+        &FRENCH
+    } else {
+        &ENGLISH
+    }
+}
+
+const ENGLISH: Labels = Labels {
+    wrote: "On {date}, {from} wrote:",
+    wrote_undated: "{from} wrote:",
+    forwarded: "---------- Forwarded message ---------",
+    from: "From:",
+    date: "Date:",
+    subject: "Subject:",
+    to: "To:",
+    summary: "Summary of the earlier messages:",
+};
+const PORTUGUESE: Labels = Labels {
+    wrote: "Em {date}, {from} escreveu:",
+    wrote_undated: "{from} escreveu:",
+    forwarded: "---------- Mensagem encaminhada ---------",
+    from: "De:",
+    date: "Data:",
+    subject: "Assunto:",
+    to: "Para:",
+    summary: "Resumo das mensagens anteriores:",
+};
+const SPANISH: Labels = Labels {
+    wrote: "El {date}, {from} escribió:",
+    wrote_undated: "{from} escribió:",
+    forwarded: "---------- Mensaje reenviado ---------",
+    from: "De:",
+    date: "Fecha:",
+    subject: "Asunto:",
+    to: "Para:",
+    summary: "Resumen de los mensajes anteriores:",
+};
+const FRENCH: Labels = Labels {
+    wrote: "Le {date}, {from} a écrit :",
+    wrote_undated: "{from} a écrit :",
+    forwarded: "---------- Message transféré ---------",
+    from: "De :",
+    date: "Date :",
+    subject: "Objet :",
+    to: "À :",
+    summary: "Résumé des messages précédents :",
+};
+
+/// Bodies longer than this, in characters, are summarised when they quote a thread.
+const LONG_THREAD_CHARS: usize = 3000;
+const REPLY_PREFIXES: &[&str] = &["re", "aw", "res", "sv"];
+const FORWARD_PREFIXES: &[&str] = &["fwd", "fw", "enc", "tr", "rv", "wg"];
+/// Lines that start the quoted part of a body.
+const THREAD_MARKERS: &[&str] = &[
+    "wrote:",
+    "escreveu:",
+    "escribió:",
+    "a écrit :",
+    "a écrit:",
+    "-----Original Message-----",
+    "---------- Forwarded message",
+    "---------- Mensagem encaminhada",
+    "---------- Mensaje reenviado",
+    "---------- Message transféré",
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infra::email::Mailbox;
+
+    fn original() -> IncomingEmail {
+        IncomingEmail {
+            from: Some(Mailbox::new("carlos@example.com").with_name("Carlos")),
+            to: vec![Mailbox::new("ana@example.com")],
+            subject: "Budget".to_string(),
+            date: chrono::DateTime::parse_from_rfc3339("2025-09-02T09:30:00-03:00").ok(),
+            text_body:
+                "Can you send the numbers?\n\nOn 2025-09-01 10:00, Ana wrote:\n> Here is the draft."
+                    .to_string(),
+            ..IncomingEmail::default()
+        }
+    }
+
+    #[test]
+    fn test_subjects_keep_one_prefix() {
+        assert_eq!(reply_subject("Budget"), "Re: Budget");
+        assert_eq!(reply_subject("RE: Re[2]: Budget"), "Re: Budget");
+        assert_eq!(reply_subject("AW: Budget"), "Re: Budget");
+        assert_eq!(reply_subject("Fwd: Budget"), "Re: Fwd: Budget");
+        assert_eq!(forward_subject("Fw: FWD: Budget"), "Fwd: Budget");
+        assert_eq!(forward_subject("Re: Budget"), "Fwd: Re: Budget");
+        assert_eq!(
+            reply_subject("Meeting: Q3 budget"),
+            "Re: Meeting: Q3 budget"
+        );
+    }
+
+    #[test]
+    fn test_split_thread() {
+        let original = original();
+        let (latest, history) = split_thread(&original.text_body);
+        assert_eq!(latest, "Can you send the numbers?");
+        assert!(history.starts_with("On 2025-09-01"));
+        assert_eq!(split_thread("Just this"), ("Just this", ""));
+    }
+
+    #[test]
+    fn test_quote_reply_in_the_reply_language() {
+        let (plain, html) = quote_original(&original(), ResponseKind::Reply, "pt-BR", None);
+
+        assert!(plain.starts_with("Em 2025-09-02 09:30, Carlos <carlos@example.com> escreveu:\n> Can you send the numbers?\n>\n> On 2025-09-01"));
+        assert!(plain.ends_with(">> Here is the draft."));
+        assert!(html.contains("<blockquote type=\"cite\"><p>Can you send the numbers?</p>"));
+        assert!(html.contains("Carlos &lt;carlos@example.com&gt; escreveu:"));
+    }
+
+    #[test]
+    fn test_quote_forward_with_summary() {
+        let (plain, html) = quote_original(
+            &original(),
+            ResponseKind::Forward,
+            "en",
+            Some("Ana shared a draft budget."),
+        );
+
+        assert!(plain.starts_with("---------- Forwarded message ---------\nFrom: Carlos <carlos@example.com>\nDate: 2025-09-02 09:30\nSubject: Budget\nTo: ana@example.com\n\nCan you send the numbers?"));
+        assert!(!plain.contains("Here is the draft"));
+        assert!(plain.ends_with("Summary of the earlier messages:\nAna shared a draft budget."));
+        assert!(html.contains("<em>Summary of the earlier messages:</em>"));
+        assert_eq!(
+            text_to_html("Hi <Eva>,\nline two\n\nBye"),
+            "<p>Hi &lt;Eva&gt;,<br>line two</p><p>Bye</p>"
+        );
+    }
+}
@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Why a recipient could not be turned into an address.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RecipientProblem {
+    /// Looks like an address but is not a valid one.
+    InvalidAddress,
+    NotFound,
+    /// Several contacts match.
+    Ambiguous,
+    /// The contact has no email address.
+    NoEmail,
+}
+
+impl RecipientProblem {
+    pub fn to_str(&self) -> &str {
+        match self {
+            Self::InvalidAddress => INVALID_ADDRESS,
+            Self::NotFound => NOT_FOUND,
+            Self::Ambiguous => AMBIGUOUS,
+            Self::NoEmail => NO_EMAIL,
+        }
+    }
+}
+
+impl fmt::Display for RecipientProblem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_str())
+    }
+}
+
+const INVALID_ADDRESS: &str = "invalid_address";
+const NOT_FOUND: &str = "not_found";
+const AMBIGUOUS: &str = "ambiguous";
+const NO_EMAIL: &str = "no_email";
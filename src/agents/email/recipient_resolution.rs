@@ -0,0 +1,47 @@
+use serde::{Deserialize, Serialize};
+
+use super::{resolved_recipient::ResolvedRecipient, unresolved_recipient::UnresolvedRecipient};
+
+/// The outcome of resolving an email's recipients: valid addresses with their
+/// provenance, and the recipients still needing the user.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct RecipientResolution {
+    pub resolved: Vec<ResolvedRecipient>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unresolved: Vec<UnresolvedRecipient>,
+}
+
+impl RecipientResolution {
+    /// Every resolved address once, in the order found.
+    pub fn addresses(&self) -> Vec<String> {
+        let mut addresses: Vec<String> = Vec::new();
+        for recipient in &self.resolved {
+            if !addresses
+                .iter()
+                .any(|known| known.eq_ignore_ascii_case(&recipient.address))
+            {
+                addresses.push(recipient.address.clone());
+            }
+        }
+        addresses
+    }
+
+    /// Whether every recipient has an address and there is at least one.
+    pub fn is_complete(&self) -> bool {
+        self.unresolved.is_empty() && !self.resolved.is_empty()
+    }
+
+    /// The resolved recipients the user did not write as addresses.
+    pub fn inferred(&self) -> impl Iterator<Item = &ResolvedRecipient> {
+        self.resolved
+            .iter()
+            .filter(|recipient| recipient.is_inferred())
+    }
+
+    pub fn follow_up_questions(&self) -> Vec<&str> {
+        self.unresolved
+            .iter()
+            .map(|recipient| recipient.question.as_str())
+            .collect()
+    }
+}
@@ -0,0 +1,243 @@
+use crate::{
+    agents::{
+        Agent, AgentError,
+        contact::{
+            AddressSelector, ContactAgent, ContactChannel, ContactParam, ContactResult,
+            ContextSignal, MatchField, RecipientCandidate, ResolutionStatus, parse_contact_query,
+        },
+    },
+    infra::{
+        contacts::{ContactQuery, ContactStore},
+        email::normalize_address,
+    },
+};
+
+use super::{
+    recipient_problem::RecipientProblem, recipient_resolution::RecipientResolution,
+    recipient_source::RecipientSource, resolved_recipient::ResolvedRecipient,
+    unresolved_recipient::UnresolvedRecipient,
+};
+
+/// Turns what the classifier put in `recipient` ("Eva", "eva@company.com",
+/// "the team") into addresses:
+///
+/// - addresses are validated ([`normalize_address`]) and, when they belong to a
+///   contact, attributed to it
+/// - names are resolved by the [`ContactAgent`], which also expands groups;
+///   `filter` is the classifier's group filter for a single reference
+/// - the address of each contact is chosen by the [`AddressSelector`] for the
+///   context of `message` (work or personal)
+///
+/// References that cannot be resolved come back as [`UnresolvedRecipient`]s
+/// with the question to ask.
+pub async fn resolve_recipients<S: ContactStore>(
+    contacts: &ContactAgent<S>,
+    references: &[String],
+    filter: Option<&ContactQuery>,
+    message: &str,
+) -> Result<RecipientResolution, AgentError> {
+    let signal = AddressSelector::new().infer_from_text(message);
+    let mut resolution = RecipientResolution::default();
+    for reference in references {
+        if reference.contains('@') {
+            resolve_address(contacts, reference, &mut resolution).await?;
+            continue;
+        }
+        let mut param = ContactParam::new(reference);
+        if let Some(filter) = filter.filter(|_| references.len() == 1) {
+            param = param.with_query(filter.clone());
+        }
+        let result = contacts.process(param).await?;
+        match result.status {
+            ResolutionStatus::Unique | ResolutionStatus::Group => {
+                let source = if result.status == ResolutionStatus::Group {
+                    RecipientSource::Group
+                } else {
+                    RecipientSource::Contact
+                };
+                for candidate in result.recipients() {
+                    resolve_contact(
+                        contacts,
+                        reference,
+                        candidate,
+                        source,
+                        signal.as_ref(),
+                        &mut resolution,
+                    )?;
+                }
+            }
+            ResolutionStatus::Ambiguous => {
+                resolution
+                    .unresolved
+                    .push(unresolved(&result, RecipientProblem::Ambiguous));
+            }
+            ResolutionStatus::NotFound => {
+                resolution
+                    .unresolved
+                    .push(unresolved(&result, RecipientProblem::NotFound));
+            }
+        }
+    }
+    Ok(resolution)
+}
+
+/// "Eva, Carlos and Tiger" as three references; a group description
+/// ("everyone in Sales and Marketing") stays whole.
+pub fn split_recipients(text: &str) -> Vec<String> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Vec::new();
+    }
+    if parse_contact_query(text).is_some() {
+        return vec![text.to_string()];
+    }
+    let mut references = Vec::new();
+    for part in text.split([',', ';']) {
+        let mut rest = part.trim();
+        for conjunction in CONJUNCTIONS {
+            if let Some(stripped) = rest.strip_prefix(conjunction.trim_start()) {
+                rest = stripped.trim();
+            }
+        }
+        let mut pieces = vec![rest.to_string()];
+        for conjunction in CONJUNCTIONS {
+            pieces = pieces
+                .iter()
+                .flat_map(|piece| piece.split(conjunction).map(str::trim))
+                .map(str::to_string)
+                .collect();
+        }
+        references.extend(pieces.into_iter().filter(|piece| !piece.is_empty()));
+    }
+    references
+}
+
+async fn resolve_address<S: ContactStore>(
+    contacts: &ContactAgent<S>,
+    reference: &str,
+    resolution: &mut RecipientResolution,
+) -> Result<(), AgentError> {
+    let address = match normalize_address(reference) {
+        Ok(address) => address,
+        Err(_) => {
+            resolution.unresolved.push(UnresolvedRecipient {
+                input: reference.to_string(),
+                problem: RecipientProblem::InvalidAddress,
+                candidates: Vec::new(),
+                question: format!(
+                    "\"{reference}\" is not a valid email address. What is the correct one?"
+                ),
+            });
+            return Ok(());
+        }
+    };
+    let owner = contacts
+        .process(ContactParam::new(&reference.to_string()))
+        .await?
+        .resolved()
+        .filter(|candidate| candidate.matched_on == MatchField::EmailAddress)
+        .cloned();
+    let explanation = match &owner {
+        Some(owner) => format!(
+            "{address} was written as an address; it is {}'s.",
+            owner.name
+        ),
+        None => format!("{address} was written as an address."),
+    };
+    resolution.resolved.push(ResolvedRecipient {
+        input: reference.to_string(),
+        address,
+        source: RecipientSource::Address,
+        contact_id: owner.as_ref().map(|owner| owner.contact_id.clone()),
+        name: owner.map(|owner| owner.name),
+        matched_on: None,
+        selection: None,
+        explanation,
+    });
+    Ok(())
+}
+
+fn resolve_contact<S: ContactStore>(
+    contacts: &ContactAgent<S>,
+    reference: &str,
+    candidate: &RecipientCandidate,
+    source: RecipientSource,
+    signal: Option<&ContextSignal>,
+    resolution: &mut RecipientResolution,
+) -> Result<(), AgentError> {
+    let name = &candidate.name;
+    let no_email = |problem, question: String| UnresolvedRecipient {
+        input: name.clone(),
+        problem,
+        candidates: Vec::new(),
+        question,
+    };
+    let Some(selection) =
+        contacts.select_address(&candidate.contact_id, ContactChannel::Email, signal)?
+    else {
+        resolution.unresolved.push(no_email(
+            RecipientProblem::NoEmail,
+            format!("{name} has no email address. What address should I use?"),
+        ));
+        return Ok(());
+    };
+    let Ok(address) = normalize_address(&selection.address) else {
+        resolution.unresolved.push(no_email(
+            RecipientProblem::InvalidAddress,
+            format!(
+                "{name}'s email {} is not a valid address. What address should I use?",
+                selection.address
+            ),
+        ));
+        return Ok(());
+    };
+    let found = match source {
+        RecipientSource::Group => format!("{name} is in \"{reference}\"."),
+        _ => format!(
+            "\"{reference}\" matched {name} ({}).",
+            candidate.matched_on.to_str().replace('_', " ")
+        ),
+    };
+    resolution.resolved.push(ResolvedRecipient {
+        input: reference.to_string(),
+        address,
+        source,
+        contact_id: Some(candidate.contact_id.clone()),
+        name: Some(name.clone()),
+        matched_on: Some(candidate.matched_on),
+        selection: Some(selection.reason),
+        explanation: format!("{found} {}", selection.explanation),
+    });
+    Ok(())
+}
+
+fn unresolved(result: &ContactResult, problem: RecipientProblem) -> UnresolvedRecipient {
+    UnresolvedRecipient {
+        input: result.query.clone(),
+        problem,
+        candidates: result.candidates.iter().map(|c| c.label()).collect(),
+        question: result.follow_up_question().unwrap_or_default(),
+    }
+}
+
+const CONJUNCTIONS: [&str; 3] = [" and ", " e ", " & "];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_recipients() {
+        assert_eq!(
+            split_recipients("Eva, Carlos and tiger@example.com"),
+            vec!["Eva", "Carlos", "tiger@example.com"]
+        );
+        assert_eq!(split_recipients("Eva e Carlos"), vec!["Eva", "Carlos"]);
+        assert_eq!(split_recipients("Eva, and Carlos"), vec!["Eva", "Carlos"]);
+        assert_eq!(
+            split_recipients("everyone in Engineering at Tech Solutions"),
+            vec!["everyone in Engineering at Tech Solutions"]
+        );
+        assert!(split_recipients("  ").is_empty());
+    }
+}
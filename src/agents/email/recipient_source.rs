@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Where a recipient's address came from.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RecipientSource {
+    /// The user wrote the address.
+    Address,
+    /// A contact matched by name; the address was chosen by the `AddressSelector`.
+    Contact,
+    /// A member of a group such as "the engineering team".
+    Group,
+}
+
+impl RecipientSource {
+    pub fn to_str(&self) -> &str {
+        match self {
+            Self::Address => ADDRESS,
+            Self::Contact => CONTACT,
+            Self::Group => GROUP,
+        }
+    }
+}
+
+impl fmt::Display for RecipientSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_str())
+    }
+}
+
+const ADDRESS: &str = "address";
+const CONTACT: &str = "contact";
+const GROUP: &str = "group";
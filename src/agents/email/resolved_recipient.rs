@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+
+use crate::agents::contact::{MatchField, SelectionReason};
+
+use super::recipient_source::RecipientSource;
+
+/// A recipient turned into a valid address, with where the address came from.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ResolvedRecipient {
+    /// The recipient as the user wrote it: "Eva", "the team", an address.
+    pub input: String,
+    /// Validated, with an internationalised domain in ASCII form.
+    pub address: String,
+    pub source: RecipientSource,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub contact_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// The contact field `input` matched, for contacts found by name.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub matched_on: Option<MatchField>,
+    /// Why this one of the contact's addresses was used.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub selection: Option<SelectionReason>,
+    pub explanation: String,
+}
+
+impl ResolvedRecipient {
+    /// Whether the address was not written by the user but looked up.
+    pub fn is_inferred(&self) -> bool {
+        self.source != RecipientSource::Address
+    }
+}
@@ -0,0 +1,55 @@
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+use crate::agents::AgentError;
+
+/// How a draft answers a received email.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ResponseKind {
+    /// To the sender (or its `Reply-To`).
+    Reply,
+    /// To the sender, with the other recipients in copy.
+    ReplyAll,
+    /// To new recipients, with the original quoted.
+    Forward,
+}
+
+impl ResponseKind {
+    pub fn to_str(&self) -> &str {
+        match self {
+            Self::Reply => REPLY,
+            Self::ReplyAll => REPLY_ALL,
+            Self::Forward => FORWARD,
+        }
+    }
+
+    pub fn is_reply(&self) -> bool {
+        matches!(self, Self::Reply | Self::ReplyAll)
+    }
+}
+
+impl FromStr for ResponseKind {
+    type Err = AgentError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input.trim().to_lowercase().as_str() {
+            REPLY => Ok(Self::Reply),
+            REPLY_ALL => Ok(Self::ReplyAll),
+            FORWARD => Ok(Self::Forward),
+            other => Err(AgentError::ParseError(format!(
+                "Unknown response kind: {other}"
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for ResponseKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_str())
+    }
+}
+
+const REPLY: &str = "reply";
+const REPLY_ALL: &str = "reply_all";
+const FORWARD: &str = "forward";
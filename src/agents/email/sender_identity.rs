@@ -0,0 +1,186 @@
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    agents::{AgentError, contact::MessageContext},
+    config::IdentityConfig,
+    infra::email::Mailbox,
+};
+
+/// An address the user sends from, with its signature, SMTP account and the
+/// rules that choose it for a draft (see [`IdentitySelector`](super::IdentitySelector)).
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SenderIdentity {
+    pub name: String,
+    /// The display name and address drafts are sent from.
+    pub mailbox: Mailbox,
+    /// An `[email.accounts.<name>]` section; `None` uses `[email.smtp]`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub smtp_account: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature_html: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context: Option<MessageContext>,
+    /// Lowercase; subdomains match too.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub recipient_domains: Vec<String>,
+    /// Lowercase words or phrases looked for in the request.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub keywords: Vec<String>,
+    #[serde(default)]
+    pub is_default: bool,
+}
+
+impl SenderIdentity {
+    pub fn new(name: &str, mailbox: Mailbox) -> Self {
+        Self {
+            name: name.to_string(),
+            mailbox,
+            smtp_account: None,
+            signature: None,
+            signature_html: None,
+            language: None,
+            context: None,
+            recipient_domains: Vec::new(),
+            keywords: Vec::new(),
+            is_default: false,
+        }
+    }
+
+    /// Validates a `[[user.settings.identities]]` entry: the address must be
+    /// valid and the context `work` or `personal`.
+    pub fn from_config(config: &IdentityConfig) -> Result<Self, AgentError> {
+        let invalid = |reason: String| {
+            AgentError::ProcessingError(format!("Identity '{}': {reason}", config.name))
+        };
+        let mut mailbox = Mailbox::parse(&config.address).map_err(|e| invalid(e.to_string()))?;
+        if !config.display_name.trim().is_empty() {
+            mailbox = mailbox.with_name(config.display_name.trim());
+        }
+        let context = config
+            .context
+            .as_deref()
+            .filter(|context| !context.trim().is_empty())
+            .map(MessageContext::from_str)
+            .transpose()
+            .map_err(|e| invalid(e.to_string()))?;
+        let name = if config.name.trim().is_empty() {
+            mailbox.address.clone()
+        } else {
+            config.name.trim().to_string()
+        };
+
+        Ok(Self {
+            name,
+            mailbox,
+            smtp_account: non_empty(&config.smtp_account),
+            signature: non_empty(&config.signature),
+            signature_html: non_empty(&config.signature_html),
+            language: non_empty(&config.language),
+            context,
+            recipient_domains: lowercase(&config.recipient_domains),
+            keywords: lowercase(&config.keywords),
+            is_default: config.default,
+        })
+    }
+
+    /// The name drafts are signed with: the display name, else the address.
+    pub fn display_name(&self) -> &str {
+        self.mailbox
+            .name
+            .as_deref()
+            .unwrap_or(&self.mailbox.address)
+    }
+
+    /// The plain signature, when it is not blank.
+    pub fn plain_signature(&self) -> Option<&str> {
+        self.signature
+            .as_deref()
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+    }
+
+    pub fn with_signature(mut self, plain: &str, html: Option<&str>) -> Self {
+        self.signature = Some(plain.to_string());
+        self.signature_html = html.map(str::to_string);
+        self
+    }
+
+    pub fn with_language(mut self, language: &str) -> Self {
+        self.language = Some(language.to_string());
+        self
+    }
+
+    pub fn with_smtp_account(mut self, account: &str) -> Self {
+        self.smtp_account = Some(account.to_string());
+        self
+    }
+}
+
+fn non_empty(value: &Option<String>) -> Option<String> {
+    value
+        .as_deref()
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(str::to_string)
+}
+
+fn lowercase(values: &[String]) -> Vec<String> {
+    values
+        .iter()
+        .map(|v| v.trim().to_lowercase())
+        .filter(|v| !v.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_config_validates_and_normalises() {
+        let config = IdentityConfig {
+            name: "work".to_string(),
+            display_name: "Ana Souza".to_string(),
+            address: "ana.souza@techsolutions.com".to_string(),
+            signature: Some("  ".to_string()),
+            context: Some("Work".to_string()),
+            recipient_domains: vec!["TechSolutions.com".to_string()],
+            ..IdentityConfig::default()
+        };
+
+        let identity = SenderIdentity::from_config(&config).unwrap();
+
+        assert_eq!(
+            identity.mailbox.to_string(),
+            "Ana Souza <ana.souza@techsolutions.com>"
+        );
+        assert_eq!(identity.display_name(), "Ana Souza");
+        assert_eq!(identity.signature, None);
+        assert_eq!(identity.context, Some(MessageContext::Work));
+        assert_eq!(identity.recipient_domains, vec!["techsolutions.com"]);
+    }
+
+    #[test]
+    fn test_from_config_rejects_bad_addresses_and_contexts() {
+        let config = IdentityConfig {
+            name: "work".to_string(),
+            address: "not an address".to_string(),
+            ..IdentityConfig::default()
+        };
+        assert!(SenderIdentity::from_config(&config).is_err());
+
+        let config = IdentityConfig {
+            address: "ana@example.com".to_string(),
+            context: Some("weekend".to_string()),
+            ..config
+        };
+        let error = SenderIdentity::from_config(&config).unwrap_err();
+        assert!(error.to_string().contains("Identity 'work'"), "{error}");
+    }
+}
@@ -0,0 +1,64 @@
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+use crate::agents::AgentError;
+
+/// The formulaic emails the template library covers.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum TemplateKind {
+    /// Apologising for missing a meeting.
+    MeetingApology,
+    /// Asking to move a meeting to another date.
+    Reschedule,
+    /// Checking in on something still open.
+    FollowUp,
+    /// Thanking someone.
+    ThankYou,
+}
+
+impl TemplateKind {
+    /// Every kind, in the order ties are broken when selecting by keywords.
+    pub const ALL: [TemplateKind; 4] = [
+        Self::MeetingApology,
+        Self::Reschedule,
+        Self::FollowUp,
+        Self::ThankYou,
+    ];
+
+    pub fn to_str(&self) -> &str {
+        match self {
+            Self::MeetingApology => MEETING_APOLOGY,
+            Self::Reschedule => RESCHEDULE,
+            Self::FollowUp => FOLLOW_UP,
+            Self::ThankYou => THANK_YOU,
+        }
+    }
+}
+
+impl FromStr for TemplateKind {
+    type Err = AgentError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input.trim().to_lowercase().as_str() {
+            MEETING_APOLOGY => Ok(Self::MeetingApology),
+            RESCHEDULE => Ok(Self::Reschedule),
+            FOLLOW_UP => Ok(Self::FollowUp),
+            THANK_YOU => Ok(Self::ThankYou),
+            other => Err(AgentError::ParseError(format!(
+                "Unknown email template: {other}"
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for TemplateKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_str())
+    }
+}
+
+const MEETING_APOLOGY: &str = "meeting_apology";
+const RESCHEDULE: &str = "reschedule";
+const FOLLOW_UP: &str = "follow_up";
+const THANK_YOU: &str = "thank_you";
@@ -0,0 +1,203 @@
+use unicode_normalization::UnicodeNormalization;
+
+use crate::agents::{
+    Intent,
+    prompts::{PromptLibrary, prompt_library::DEFAULT_LANGUAGE},
+};
+
+use super::{
+    email_template::EmailTemplate, language_detector::detect_language, template_kind::TemplateKind,
+};
+
+/// Email templates stored with the prompts, one `email_templates.toml` per
+/// language, so they share the prompt library's versioning, per-user overrides
+/// and fallback to English:
+///
+/// ```toml
+/// version = "1.0.0"
+///
+/// [sections]
+/// thank_you_subject = "Thank you"
+/// thank_you_body = "Hi {recipient},\n\n{message}\n\n{sender}"
+/// thank_you_keywords = "thank, thanks, grateful"
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct TemplateLibrary<'a> {
+    prompts: &'a PromptLibrary,
+}
+
+impl TemplateLibrary<'static> {
+    /// The templates of the prompt library loaded from `[prompts]`.
+    pub fn get() -> Self {
+        Self::new(PromptLibrary::get())
+    }
+}
+
+impl<'a> TemplateLibrary<'a> {
+    pub fn new(prompts: &'a PromptLibrary) -> Self {
+        Self { prompts }
+    }
+
+    /// The `kind` template in `language`, or in English when that language
+    /// does not define it; [`EmailTemplate::language`] tells which one it is.
+    pub fn template(&self, kind: TemplateKind, language: &str) -> EmailTemplate {
+        let set = self.prompts.prompt_set_in(EMAIL_TEMPLATES, language);
+        let (subject, body, _) = builtin(kind);
+        let body = set.section(&key(kind, BODY), body);
+        let english = self
+            .prompts
+            .prompt_set_in(EMAIL_TEMPLATES, DEFAULT_LANGUAGE);
+        let language = if body == english.section(&key(kind, BODY), body) {
+            DEFAULT_LANGUAGE
+        } else {
+            language
+        };
+        EmailTemplate {
+            kind,
+            language: language.to_string(),
+            version: set.version().to_string(),
+            subject: set.section(&key(kind, SUBJECT), subject).to_string(),
+            body: body.to_string(),
+        }
+    }
+
+    /// The template whose keywords, in any of the shipped languages, best
+    /// match the request. Only intents that send email have templates.
+    pub fn select(&self, intent: &Intent, text: &str) -> Option<TemplateKind> {
+        if !matches!(intent, Intent::SendEmail | Intent::ScheduleMeeting) {
+            return None;
+        }
+        let text = normalize(text);
+        let mut best: Option<(TemplateKind, usize)> = None;
+        for kind in TemplateKind::ALL {
+            let hits = self.keywords(kind).filter(|k| text.contains(k)).count();
+            if hits > best.map_or(0, |(_, most)| most) {
+                best = Some((kind, hits));
+            }
+        }
+        best.map(|(kind, _)| kind)
+    }
+
+    /// The template for a classified request, in `language` or else in the
+    /// language the request was written in.
+    pub fn for_request(
+        &self,
+        intent: &Intent,
+        text: &str,
+        language: Option<&str>,
+    ) -> Option<EmailTemplate> {
+        let kind = self.select(intent, text)?;
+        let language = language
+            .or_else(|| detect_language(text))
+            .unwrap_or(self.prompts.language());
+        Some(self.template(kind, language))
+    }
+
+    fn keywords(&self, kind: TemplateKind) -> impl Iterator<Item = String> {
+        let (_, _, fallback) = builtin(kind);
+        let mut keywords: Vec<String> = TEMPLATE_LANGUAGES
+            .iter()
+            .flat_map(|language| {
+                let set = self.prompts.prompt_set_in(EMAIL_TEMPLATES, language);
+                set.section(&key(kind, KEYWORDS), fallback)
+                    .split(',')
+                    .map(normalize)
+                    .filter(|k| !k.is_empty())
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        keywords.sort();
+        keywords.dedup();
+        keywords.into_iter()
+    }
+}
+
+fn key(kind: TemplateKind, part: &str) -> String {
+    format!("{kind}_{part}")
+}
+
+fn normalize(text: &str) -> String {
+    text.trim().to_lowercase().nfc().collect()
+}
+
+/// English subject, body and keywords compiled into the binary.
+fn builtin(kind: TemplateKind) -> (&'static str, &'static str, &'static str) {
+    match kind {
+        TemplateKind::MeetingApology => (
+            MEETING_APOLOGY_SUBJECT,
+            MEETING_APOLOGY_BODY,
+            MEETING_APOLOGY_KEYWORDS,
+        ),
+        TemplateKind::Reschedule => (RESCHEDULE_SUBJECT, RESCHEDULE_BODY, RESCHEDULE_KEYWORDS),
+        TemplateKind::FollowUp => (FOLLOW_UP_SUBJECT, FOLLOW_UP_BODY, FOLLOW_UP_KEYWORDS),
+        TemplateKind::ThankYou => (THANK_YOU_SUBJECT, THANK_YOU_BODY, THANK_YOU_KEYWORDS),
+    }
+}
+
+pub const EMAIL_TEMPLATES: &str = "email_templates";
+/// Languages the templates ship in; selection reads the keywords of all of them.
+pub const TEMPLATE_LANGUAGES: [&str; 4] = ["en", "pt-BR", "es", "fr"];
+
+const SUBJECT: &str = "subject";
+const BODY: &str = "body";
+const KEYWORDS: &str = "keywords";
+
+const MEETING_APOLOGY_SUBJECT: &str = "Unable to attend the meeting";
+const MEETING_APOLOGY_BODY: &str = "Hi {recipient},\n\n{message}\n\nI'm sorry for the short notice. I will catch up on what was discussed, and please let me know if there is anything I should prepare.\n\nBest regards,\n{sender}";
+const MEETING_APOLOGY_KEYWORDS: &str = "can't attend, cannot attend, won't be able to attend, can't make it, won't make it, miss the meeting, apologise, apologize, sorry";
+const RESCHEDULE_SUBJECT: &str = "Rescheduling our meeting";
+const RESCHEDULE_BODY: &str = "Hi {recipient},\n\n{message}\n\nCould you let me know which times work for you? I will send an updated invitation.\n\nBest regards,\n{sender}";
+const RESCHEDULE_KEYWORDS: &str =
+    "reschedule, postpone, move the meeting, push back, another time, new date";
+const FOLLOW_UP_SUBJECT: &str = "Following up";
+const FOLLOW_UP_BODY: &str = "Hi {recipient},\n\nI'm following up on my previous message. {message}\n\nThanks in advance,\n{sender}";
+const FOLLOW_UP_KEYWORDS: &str =
+    "follow up, follow-up, following up, checking in, any update, reminder, haven't heard";
+const THANK_YOU_SUBJECT: &str = "Thank you";
+const THANK_YOU_BODY: &str = "Hi {recipient},\n\n{message}\n\nThank you again,\n{sender}";
+const THANK_YOU_KEYWORDS: &str = "thank, thanks, grateful, appreciate";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_templates_select_by_keywords() {
+        let prompts = PromptLibrary::builtin("en");
+        let library = TemplateLibrary::new(&prompts);
+
+        assert_eq!(
+            library.select(
+                &Intent::SendEmail,
+                "Tell Eva I can't attend the meeting, sorry"
+            ),
+            Some(TemplateKind::MeetingApology)
+        );
+        assert_eq!(
+            library.select(
+                &Intent::ScheduleMeeting,
+                "Postpone the review to a new date"
+            ),
+            Some(TemplateKind::Reschedule)
+        );
+        assert_eq!(
+            library.select(&Intent::SendEmail, "Send Carlos the budget"),
+            None
+        );
+        assert_eq!(
+            library.select(&Intent::ContactManagement, "Thanks, add Eva's new phone"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_builtin_template_is_english() {
+        let prompts = PromptLibrary::builtin("pt-BR");
+        let template = TemplateLibrary::new(&prompts).template(TemplateKind::ThankYou, "pt-BR");
+
+        assert_eq!(template.language, "en");
+        assert_eq!(template.version, "builtin");
+        assert_eq!(template.subject, THANK_YOU_SUBJECT);
+        assert_eq!(template.variables(), vec!["recipient", "message", "sender"]);
+    }
+}
@@ -0,0 +1,111 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{agents::classifier::Params, infra::contacts::Contact};
+
+/// Values for the `{name}` variables of an [`EmailTemplate`](super::EmailTemplate).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TemplateValues {
+    values: BTreeMap<String, String>,
+}
+
+impl TemplateValues {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// `{recipient}` and `{message}` from the classifier. The message becomes a
+    /// sentence: capitalised and ending in a full stop.
+    pub fn from_params(params: &Params) -> Self {
+        let mut values = Self::new();
+        if let Some(recipient) = params.recipient().filter(|r| !r.trim().is_empty()) {
+            values = values.with(RECIPIENT, recipient.trim());
+        }
+        if let Some(message) = params.message().filter(|m| !m.trim().is_empty()) {
+            values = values.with(MESSAGE, &sentence(message));
+        }
+        values
+    }
+
+    /// `{recipient}` as the contact is greeted (nickname, else first name) and
+    /// `{recipient_full_name}`, replacing what the classifier wrote.
+    pub fn with_contact(self, contact: &Contact) -> Self {
+        let greeting = contact
+            .nickname
+            .as_deref()
+            .filter(|n| !n.trim().is_empty())
+            .or(Some(contact.first_name.as_str()).filter(|n| !n.trim().is_empty()))
+            .unwrap_or(&contact.display_name)
+            .to_string();
+        self.with(RECIPIENT, &greeting)
+            .with(RECIPIENT_FULL_NAME, &contact.display_name)
+    }
+
+    /// `{sender}`, the name the email is signed with.
+    pub fn with_sender(self, sender: &str) -> Self {
+        self.with(SENDER, sender)
+    }
+
+    pub fn with(mut self, name: &str, value: &str) -> Self {
+        self.values.insert(name.to_string(), value.to_string());
+        self
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.values.get(name).map(String::as_str)
+    }
+}
+
+/// `text` capitalised and ending with punctuation.
+fn sentence(text: &str) -> String {
+    let text = text.trim();
+    let mut chars = text.chars();
+    let mut sentence: String = chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect())
+        .unwrap_or_default();
+    if !sentence.ends_with(['.', '!', '?']) {
+        sentence.push('.');
+    }
+    sentence
+}
+
+pub const RECIPIENT: &str = "recipient";
+pub const RECIPIENT_FULL_NAME: &str = "recipient_full_name";
+pub const SENDER: &str = "sender";
+pub const MESSAGE: &str = "message";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_params_makes_the_message_a_sentence() {
+        let params = Params::with_values(
+            "Eva".to_string(),
+            "não vou poder comparecer à reunião".to_string(),
+        );
+
+        let values = TemplateValues::from_params(&params);
+
+        assert_eq!(values.get(RECIPIENT), Some("Eva"));
+        assert_eq!(
+            values.get(MESSAGE),
+            Some("Não vou poder comparecer à reunião.")
+        );
+        assert_eq!(values.get(SENDER), None);
+    }
+
+    #[test]
+    fn test_contact_greeting_prefers_the_nickname() {
+        let mut contact = Contact::new("c1", "Evelyn", "Green");
+        let values = TemplateValues::new().with_contact(&contact);
+        assert_eq!(values.get(RECIPIENT), Some("Evelyn"));
+
+        contact.nickname = Some("Eva".to_string());
+        let values = TemplateValues::new().with_contact(&contact);
+        assert_eq!(values.get(RECIPIENT), Some("Eva"));
+        assert_eq!(values.get(RECIPIENT_FULL_NAME), Some("Evelyn Green"));
+    }
+}
@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+
+use super::recipient_problem::RecipientProblem;
+
+/// A recipient that needs the user before the email can be sent.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct UnresolvedRecipient {
+    pub input: String,
+    pub problem: RecipientProblem,
+    /// Contacts that might be meant, as `Name <email>`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub candidates: Vec<String>,
+    pub question: String,
+}
@@ -0,0 +1,11 @@
+use crate::agents::AgentError;
+
+use super::classifier_call::ClassifierCall;
+
+/// Where experiment runs send classifier prompts; returns the raw model output.
+pub trait ClassifierBackend {
+    fn classify(
+        &self,
+        call: &ClassifierCall,
+    ) -> impl std::future::Future<Output = Result<String, AgentError>> + Send;
+}
@@ -0,0 +1,68 @@
+use serde::{Deserialize, Serialize};
+
+/// One classifier request issued by an experiment run.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ClassifierCall {
+    pub variant: String,
+    pub system: String,
+    pub user: String,
+    pub seed: u64,
+}
+
+impl ClassifierCall {
+    pub fn new(variant: &str, system: &str, user: &str, seed: u64) -> Self {
+        Self {
+            variant: variant.to_string(),
+            system: system.to_string(),
+            user: user.to_string(),
+            seed,
+        }
+    }
+
+    /// Stable key for replaying a recorded response: identical prompts and seed share
+    /// a response regardless of the variant name.
+    pub fn replay_key(&self) -> String {
+        let mut hash = FNV_OFFSET_BASIS;
+        for byte in self
+            .system
+            .bytes()
+            .chain([0])
+            .chain(self.user.bytes())
+            .chain([0])
+            .chain(self.seed.to_le_bytes())
+        {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(FNV_PRIME);
+        }
+        format!("{hash:016x}")
+    }
+}
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_replay_key_ignores_variant_name() {
+        let a = ClassifierCall::new("a", "system", "user", 1);
+        let b = ClassifierCall::new("b", "system", "user", 1);
+        assert_eq!(a.replay_key(), b.replay_key());
+    }
+
+    #[test]
+    fn test_replay_key_depends_on_prompts_and_seed() {
+        let base = ClassifierCall::new("a", "system", "user", 1);
+        assert_ne!(
+            base.replay_key(),
+            ClassifierCall::new("a", "system", "user", 2).replay_key()
+        );
+        assert_ne!(
+            base.replay_key(),
+            ClassifierCall::new("a", "systemuser", "", 1).replay_key()
+        );
+        assert_eq!(base.replay_key().len(), 16);
+    }
+}
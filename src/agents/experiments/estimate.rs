@@ -0,0 +1,109 @@
+use serde::{Deserialize, Serialize};
+
+/// Sample mean with a two-sided 95% confidence interval (Student's t).
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct Estimate {
+    pub mean: f64,
+    pub low: f64,
+    pub high: f64,
+    pub samples: usize,
+}
+
+impl Estimate {
+    /// With fewer than two samples the interval collapses to the mean.
+    pub fn from_samples(samples: &[f64]) -> Self {
+        let n = samples.len();
+        if n == 0 {
+            return Self {
+                mean: 0.0,
+                low: 0.0,
+                high: 0.0,
+                samples: 0,
+            };
+        }
+
+        let mean = samples.iter().sum::<f64>() / n as f64;
+        let margin = if n < 2 {
+            0.0
+        } else {
+            let variance = samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1) as f64;
+            t_critical_95(n - 1) * (variance / n as f64).sqrt()
+        };
+
+        Self {
+            mean,
+            low: mean - margin,
+            high: mean + margin,
+            samples: n,
+        }
+    }
+
+    /// True when the interval excludes zero.
+    pub fn is_significant(&self) -> bool {
+        self.low > 0.0 || self.high < 0.0
+    }
+
+    pub fn margin(&self) -> f64 {
+        (self.high - self.low) / 2.0
+    }
+}
+
+/// Two-sided 95% critical value of Student's t for `df` degrees of freedom.
+fn t_critical_95(df: usize) -> f64 {
+    match df {
+        0 => f64::INFINITY,
+        1..=30 => T_TABLE_95[df - 1],
+        31..=60 => 2.000,
+        61..=120 => 1.980,
+        _ => 1.960,
+    }
+}
+
+const T_TABLE_95: [f64; 30] = [
+    12.706, 4.303, 3.182, 2.776, 2.571, 2.447, 2.365, 2.306, 2.262, 2.228, 2.201, 2.179, 2.160,
+    2.145, 2.131, 2.120, 2.110, 2.101, 2.093, 2.086, 2.080, 2.074, 2.069, 2.064, 2.060, 2.056,
+    2.052, 2.048, 2.045, 2.042,
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_samples_mean_and_interval() {
+        let estimate = Estimate::from_samples(&[0.8, 0.9, 1.0]);
+
+        assert!((estimate.mean - 0.9).abs() < 1e-9);
+        // sd = 0.1, se = 0.1 / sqrt(3), t(2) = 4.303
+        assert!((estimate.margin() - 4.303 * 0.1 / 3f64.sqrt()).abs() < 1e-9);
+        assert_eq!(estimate.samples, 3);
+    }
+
+    #[test]
+    fn test_single_sample_collapses_interval() {
+        let estimate = Estimate::from_samples(&[0.75]);
+        assert_eq!(estimate.low, 0.75);
+        assert_eq!(estimate.high, 0.75);
+    }
+
+    #[test]
+    fn test_empty_samples() {
+        let estimate = Estimate::from_samples(&[]);
+        assert_eq!(estimate.samples, 0);
+        assert!(!estimate.is_significant());
+    }
+
+    #[test]
+    fn test_is_significant() {
+        assert!(Estimate::from_samples(&[1.0; 40]).is_significant());
+        assert!(!Estimate::from_samples(&[1.0, -1.0, 0.0, 1.0, -1.0]).is_significant());
+        assert!(Estimate::from_samples(&[-1.0, -1.0, -1.0, 0.0, -1.0, -1.0]).is_significant());
+    }
+
+    #[test]
+    fn test_t_critical_values() {
+        assert_eq!(t_critical_95(1), 12.706);
+        assert_eq!(t_critical_95(30), 2.042);
+        assert_eq!(t_critical_95(1000), 1.960);
+    }
+}
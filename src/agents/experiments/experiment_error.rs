@@ -0,0 +1,21 @@
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug)]
+pub enum ExperimentError {
+    IoError(String),
+    DatasetError(String),
+    InvalidExperiment(String),
+}
+
+impl fmt::Display for ExperimentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExperimentError::IoError(msg) => write!(f, "Experiment IO error: {msg}"),
+            ExperimentError::DatasetError(msg) => write!(f, "Experiment dataset error: {msg}"),
+            ExperimentError::InvalidExperiment(msg) => write!(f, "Invalid experiment: {msg}"),
+        }
+    }
+}
+
+impl Error for ExperimentError {}
@@ -0,0 +1,80 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use super::{variant_comparison::VariantComparison, variant_report::VariantReport};
+
+/// Result of an experiment: the first variant is the baseline the others are compared to.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ExperimentReport {
+    pub dataset_size: usize,
+    pub seeds: Vec<u64>,
+    pub variants: Vec<VariantReport>,
+    pub comparisons: Vec<VariantComparison>,
+}
+
+impl ExperimentReport {
+    pub fn baseline(&self) -> Option<&VariantReport> {
+        self.variants.first()
+    }
+
+    pub fn variant(&self, name: &str) -> Option<&VariantReport> {
+        self.variants.iter().find(|variant| variant.name == name)
+    }
+
+    pub fn comparison(&self, name: &str) -> Option<&VariantComparison> {
+        self.comparisons
+            .iter()
+            .find(|comparison| comparison.variant == name)
+    }
+
+    /// The variant with the largest significant improvement over the baseline, if any.
+    pub fn winner(&self) -> Option<&VariantReport> {
+        self.comparisons
+            .iter()
+            .filter(|comparison| comparison.is_improvement())
+            .max_by(|a, b| a.delta.mean.total_cmp(&b.delta.mean))
+            .and_then(|comparison| self.variant(&comparison.variant))
+    }
+}
+
+impl fmt::Display for ExperimentReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "{} examples x {} seeds",
+            self.dataset_size,
+            self.seeds.len()
+        )?;
+        for variant in &self.variants {
+            write!(
+                f,
+                "{} ({}): accuracy {:.3} [{:.3}, {:.3}], failures {}",
+                variant.name,
+                variant.prompt_version,
+                variant.accuracy.mean,
+                variant.accuracy.low,
+                variant.accuracy.high,
+                variant.failures
+            )?;
+            if let Some(comparison) = self.comparison(&variant.name) {
+                write!(
+                    f,
+                    ", delta {:+.3} [{:+.3}, {:+.3}]",
+                    comparison.delta.mean, comparison.delta.low, comparison.delta.high
+                )?;
+                for regression in &comparison.regressions {
+                    write!(
+                        f,
+                        ", {} {:.3} -> {:.3}",
+                        regression.intent,
+                        regression.baseline_accuracy,
+                        regression.variant_accuracy
+                    )?;
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,293 @@
+use std::collections::BTreeMap;
+
+use crate::agents::classifier::{
+    FromMarkdownJson, ollama_intent_response_parser::OllamaIntentResponseParser,
+};
+
+use super::{
+    classifier_backend::ClassifierBackend, classifier_call::ClassifierCall, estimate::Estimate,
+    experiment_error::ExperimentError, experiment_report::ExperimentReport,
+    intent_score::IntentScore, labelled_dataset::LabelledDataset, prompt_variant::PromptVariant,
+    variant_comparison::VariantComparison, variant_report::VariantReport,
+};
+
+/// Seeds used when none are configured.
+pub const DEFAULT_SEEDS: [u64; 3] = [1, 2, 3];
+
+/// Runs every prompt variant against the same labelled dataset and seeds.
+pub struct ExperimentRunner<B> {
+    backend: B,
+    dataset: LabelledDataset,
+    seeds: Vec<u64>,
+}
+
+impl<B: ClassifierBackend> ExperimentRunner<B> {
+    pub fn new(backend: B, dataset: LabelledDataset) -> Self {
+        Self {
+            backend,
+            dataset,
+            seeds: DEFAULT_SEEDS.to_vec(),
+        }
+    }
+
+    pub fn with_seeds(mut self, seeds: &[u64]) -> Self {
+        self.seeds = seeds.to_vec();
+        self
+    }
+
+    pub fn backend(&self) -> &B {
+        &self.backend
+    }
+
+    pub fn into_backend(self) -> B {
+        self.backend
+    }
+
+    /// Scores each variant; the first one is the baseline for the comparisons.
+    pub async fn run(
+        &self,
+        variants: &[PromptVariant],
+    ) -> Result<ExperimentReport, ExperimentError> {
+        if variants.len() < 2 {
+            return Err(ExperimentError::InvalidExperiment(
+                "at least a baseline and one variant are required".to_string(),
+            ));
+        }
+        if self.dataset.is_empty() {
+            return Err(ExperimentError::InvalidExperiment(
+                "the labelled dataset is empty".to_string(),
+            ));
+        }
+        if self.seeds.is_empty() {
+            return Err(ExperimentError::InvalidExperiment(
+                "at least one seed is required".to_string(),
+            ));
+        }
+
+        let mut reports = Vec::with_capacity(variants.len());
+        for variant in variants {
+            reports.push(self.run_variant(variant).await);
+        }
+
+        let comparisons = reports[1..]
+            .iter()
+            .map(|report| VariantComparison::between(&reports[0], report))
+            .collect();
+
+        Ok(ExperimentReport {
+            dataset_size: self.dataset.len(),
+            seeds: self.seeds.clone(),
+            variants: reports,
+            comparisons,
+        })
+    }
+
+    async fn run_variant(&self, variant: &PromptVariant) -> VariantReport {
+        let mut per_intent: BTreeMap<String, IntentScore> = BTreeMap::new();
+        let mut outcomes = Vec::with_capacity(self.seeds.len() * self.dataset.len());
+        let mut correct = vec![0usize; self.dataset.len()];
+        let mut failures = 0;
+
+        for seed in &self.seeds {
+            for (index, example) in self.dataset.examples().iter().enumerate() {
+                let (system, user) = variant.build_prompts(&example.input);
+                let call = ClassifierCall::new(variant.name(), &system, &user, *seed);
+
+                let predicted = match self.backend.classify(&call).await {
+                    Ok(response) => OllamaIntentResponseParser::from_markdown_text(&response)
+                        .map(|result| result.intent)
+                        .ok(),
+                    Err(_) => None,
+                };
+                if predicted.is_none() {
+                    failures += 1;
+                }
+
+                let is_correct = predicted.as_ref() == Some(&example.intent);
+                per_intent
+                    .entry(example.intent.to_str().to_string())
+                    .or_default()
+                    .record(is_correct);
+                outcomes.push(is_correct);
+                if is_correct {
+                    correct[index] += 1;
+                }
+            }
+        }
+        // Repeated seeds of one example are not independent, so the example
+        // is the sample unit.
+        let per_example: Vec<f64> = correct
+            .iter()
+            .map(|&count| count as f64 / self.seeds.len() as f64)
+            .collect();
+
+        VariantReport {
+            name: variant.name().to_string(),
+            prompt_version: variant.prompts().version().to_string(),
+            accuracy: Estimate::from_samples(&per_example),
+            per_intent,
+            failures,
+            outcomes,
+            per_example,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agents::{
+        AgentError, Intent,
+        experiments::LabelledExample,
+        prompts::{PromptFile, PromptSet},
+    };
+
+    struct ScriptedBackend<F>(F);
+
+    impl<F> ClassifierBackend for ScriptedBackend<F>
+    where
+        F: Fn(&ClassifierCall) -> Result<String, AgentError> + Sync,
+    {
+        fn classify(
+            &self,
+            call: &ClassifierCall,
+        ) -> impl std::future::Future<Output = Result<String, AgentError>> + Send {
+            let response = (self.0)(call);
+            async move { response }
+        }
+    }
+
+    fn intent_json(intent: &str) -> String {
+        format!("{{\"intent\":\"{intent}\",\"params\":{{\"recipient\":\"\",\"message\":\"\"}}}}")
+    }
+
+    /// Classifies meetings only when the task mentions them; fails emails for "broken".
+    fn scripted(call: &ClassifierCall) -> Result<String, AgentError> {
+        let is_meeting = call.user.contains("meeting");
+        if call.system.contains("broken") && !is_meeting {
+            return Ok("not json".to_string());
+        }
+        if is_meeting && call.system.contains("schedule_meeting too") {
+            Ok(intent_json("schedule_meeting"))
+        } else if is_meeting {
+            Ok(intent_json("no_action"))
+        } else {
+            Ok(intent_json("send_email"))
+        }
+    }
+
+    fn dataset() -> LabelledDataset {
+        LabelledDataset::new(vec![
+            LabelledExample::new("Send an email to Eva", Intent::SendEmail),
+            LabelledExample::new("Email Carlos about the delay", Intent::SendEmail),
+            LabelledExample::new("Write to Sofia", Intent::SendEmail),
+            LabelledExample::new("Reply to Ana with the invoice", Intent::SendEmail),
+            LabelledExample::new("Send the report to Bruno", Intent::SendEmail),
+            LabelledExample::new("Set up a meeting with Eva", Intent::ScheduleMeeting),
+            LabelledExample::new("Book a meeting room for Monday", Intent::ScheduleMeeting),
+            LabelledExample::new("Move the meeting to 3pm", Intent::ScheduleMeeting),
+            LabelledExample::new("Plan a meeting with the team", Intent::ScheduleMeeting),
+            LabelledExample::new("Cancel Friday's meeting", Intent::ScheduleMeeting),
+        ])
+    }
+
+    fn variant(name: &str, task: &str) -> PromptVariant {
+        PromptVariant::with_override(
+            name,
+            &PromptSet::builtin("classifier"),
+            PromptFile {
+                version: name.to_string(),
+                sections: [("task".to_string(), task.to_string())].into(),
+            },
+        )
+    }
+
+    fn variants() -> Vec<PromptVariant> {
+        vec![
+            PromptVariant::new("baseline", PromptSet::builtin("classifier")),
+            variant(
+                "meetings",
+                "Task: detect send_email and schedule_meeting too",
+            ),
+            variant("broken", "Task: broken"),
+        ]
+    }
+
+    #[tokio::test]
+    async fn test_run_scores_variants_and_picks_winner() {
+        let runner = ExperimentRunner::new(ScriptedBackend(scripted), dataset());
+        let report = runner.run(&variants()).await.unwrap();
+
+        assert_eq!(report.dataset_size, 10);
+        assert_eq!(report.seeds, DEFAULT_SEEDS.to_vec());
+        assert_eq!(report.baseline().unwrap().name, "baseline");
+        assert!((report.variant("baseline").unwrap().accuracy.mean - 0.5).abs() < 1e-9);
+        assert!((report.variant("meetings").unwrap().accuracy.mean - 1.0).abs() < 1e-9);
+
+        let meetings = report.comparison("meetings").unwrap();
+        assert!((meetings.delta.mean - 0.5).abs() < 1e-9);
+        assert!(meetings.is_improvement());
+        assert!(meetings.regressions.is_empty());
+        assert_eq!(report.winner().unwrap().name, "meetings");
+    }
+
+    #[tokio::test]
+    async fn test_seeds_are_averaged_per_example_before_estimating() {
+        let runner = ExperimentRunner::new(ScriptedBackend(scripted), dataset());
+        let report = runner.run(&variants()).await.unwrap();
+
+        let baseline = report.baseline().unwrap();
+        assert_eq!(baseline.outcomes.len(), 10 * DEFAULT_SEEDS.len());
+        assert_eq!(baseline.per_example.len(), 10);
+        assert_eq!(baseline.accuracy.samples, 10);
+        assert_eq!(report.comparison("meetings").unwrap().delta.samples, 10);
+    }
+
+    #[tokio::test]
+    async fn test_run_reports_failures_and_per_intent_regressions() {
+        let runner = ExperimentRunner::new(ScriptedBackend(scripted), dataset()).with_seeds(&[42]);
+        let report = runner.run(&variants()).await.unwrap();
+
+        let broken = report.variant("broken").unwrap();
+        assert_eq!(broken.failures, 5);
+        assert_eq!(broken.per_intent["send_email"].correct, 0);
+        assert_eq!(broken.outcomes.len(), 10);
+
+        let comparison = report.comparison("broken").unwrap();
+        assert_eq!(comparison.regressions.len(), 1);
+        assert_eq!(comparison.regressions[0].intent, "send_email");
+        assert_eq!(comparison.regressions[0].delta(), -1.0);
+        assert!(!comparison.is_improvement());
+        assert!(report.to_string().contains("send_email 1.000 -> 0.000"));
+    }
+
+    #[tokio::test]
+    async fn test_backend_errors_count_as_failures() {
+        let backend =
+            ScriptedBackend(|_: &ClassifierCall| Err(AgentError::NetworkError("down".to_string())));
+        let report = ExperimentRunner::new(backend, dataset())
+            .with_seeds(&[1])
+            .run(&variants()[..2])
+            .await
+            .unwrap();
+
+        assert_eq!(report.variants[0].failures, 10);
+        assert_eq!(report.variants[0].accuracy.mean, 0.0);
+        assert!(report.winner().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_run_rejects_invalid_experiments() {
+        let runner = ExperimentRunner::new(ScriptedBackend(scripted), dataset());
+        assert!(matches!(
+            runner.run(&variants()[..1]).await,
+            Err(ExperimentError::InvalidExperiment(_))
+        ));
+
+        let empty = ExperimentRunner::new(ScriptedBackend(scripted), LabelledDataset::default());
+        assert!(empty.run(&variants()).await.is_err());
+
+        let no_seeds = ExperimentRunner::new(ScriptedBackend(scripted), dataset()).with_seeds(&[]);
+        assert!(no_seeds.run(&variants()).await.is_err());
+    }
+}
@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+
+/// An intent whose accuracy dropped in a variant compared to the baseline.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct IntentRegression {
+    pub intent: String,
+    pub baseline_accuracy: f64,
+    pub variant_accuracy: f64,
+}
+
+impl IntentRegression {
+    pub fn delta(&self) -> f64 {
+        self.variant_accuracy - self.baseline_accuracy
+    }
+}
@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+
+/// Correct classifications out of all attempts for one expected intent.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub struct IntentScore {
+    pub correct: usize,
+    pub total: usize,
+}
+
+impl IntentScore {
+    pub fn record(&mut self, correct: bool) {
+        self.total += 1;
+        if correct {
+            self.correct += 1;
+        }
+    }
+
+    pub fn accuracy(&self) -> f64 {
+        if self.total == 0 {
+            0.0
+        } else {
+            self.correct as f64 / self.total as f64
+        }
+    }
+}
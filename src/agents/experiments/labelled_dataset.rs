@@ -0,0 +1,77 @@
+use std::{fs, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use super::{experiment_error::ExperimentError, labelled_example::LabelledExample};
+
+/// The labelled inputs every prompt variant is scored against, stored as a JSON array:
+///
+/// ```json
+/// [{ "input": "Send an email to Eva", "intent": "send_email" }]
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+#[serde(transparent)]
+pub struct LabelledDataset {
+    examples: Vec<LabelledExample>,
+}
+
+impl LabelledDataset {
+    pub fn new(examples: Vec<LabelledExample>) -> Self {
+        Self { examples }
+    }
+
+    pub fn from_json_str(content: &str) -> Result<Self, ExperimentError> {
+        serde_json::from_str(content).map_err(|e| ExperimentError::DatasetError(e.to_string()))
+    }
+
+    pub fn load(path: &Path) -> Result<Self, ExperimentError> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| ExperimentError::IoError(format!("{}: {e}", path.display())))?;
+        Self::from_json_str(&content)
+            .map_err(|e| ExperimentError::DatasetError(format!("{}: {e}", path.display())))
+    }
+
+    pub fn examples(&self) -> &[LabelledExample] {
+        &self.examples
+    }
+
+    pub fn len(&self) -> usize {
+        self.examples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.examples.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agents::Intent;
+
+    #[test]
+    fn test_from_json_str() {
+        let dataset = LabelledDataset::from_json_str(
+            r#"[
+                {"input": "Send an email to Eva", "intent": "send_email"},
+                {"input": "Book a call with Carlos", "intent": "schedule_meeting"}
+            ]"#,
+        )
+        .unwrap();
+
+        assert_eq!(dataset.len(), 2);
+        assert_eq!(dataset.examples()[1].intent, Intent::ScheduleMeeting);
+    }
+
+    #[test]
+    fn test_from_json_str_rejects_unknown_intent() {
+        let result = LabelledDataset::from_json_str(r#"[{"input": "hi", "intent": "dance"}]"#);
+        assert!(matches!(result, Err(ExperimentError::DatasetError(_))));
+    }
+
+    #[test]
+    fn test_load_missing_file_is_io_error() {
+        let result = LabelledDataset::load(Path::new("does/not/exist.json"));
+        assert!(matches!(result, Err(ExperimentError::IoError(_))));
+    }
+}
@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+
+use crate::agents::Intent;
+
+/// A user input paired with the intent the classifier is expected to return.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct LabelledExample {
+    pub input: String,
+    pub intent: Intent,
+}
+
+impl LabelledExample {
+    pub fn new(input: &str, intent: Intent) -> Self {
+        Self {
+            input: input.to_string(),
+            intent,
+        }
+    }
+}
@@ -0,0 +1,33 @@
+pub mod classifier_backend;
+pub mod classifier_call;
+pub mod estimate;
+pub mod experiment_error;
+pub mod experiment_report;
+pub mod experiment_runner;
+pub mod intent_regression;
+pub mod intent_score;
+pub mod labelled_dataset;
+pub mod labelled_example;
+pub mod ollama_backend;
+pub mod prompt_variant;
+pub mod recording_backend;
+pub mod replay_backend;
+pub mod variant_comparison;
+pub mod variant_report;
+
+pub use classifier_backend::ClassifierBackend;
+pub use classifier_call::ClassifierCall;
+pub use estimate::Estimate;
+pub use experiment_error::ExperimentError;
+pub use experiment_report::ExperimentReport;
+pub use experiment_runner::ExperimentRunner;
+pub use intent_regression::IntentRegression;
+pub use intent_score::IntentScore;
+pub use labelled_dataset::LabelledDataset;
+pub use labelled_example::LabelledExample;
+pub use ollama_backend::OllamaBackend;
+pub use prompt_variant::PromptVariant;
+pub use recording_backend::RecordingBackend;
+pub use replay_backend::ReplayBackend;
+pub use variant_comparison::VariantComparison;
+pub use variant_report::VariantReport;
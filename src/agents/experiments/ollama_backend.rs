@@ -0,0 +1,48 @@
+use ollama_oxide::ChatMessage;
+
+use crate::{agents::AgentError, infra::assistant_ollama_client::AssistantOllamaClient};
+
+use super::{classifier_backend::ClassifierBackend, classifier_call::ClassifierCall};
+
+/// Sends experiment calls to a live Ollama model, seeding each request.
+pub struct OllamaBackend {
+    model: String,
+}
+
+impl OllamaBackend {
+    pub fn new(model: &str) -> Self {
+        Self {
+            model: model.to_string(),
+        }
+    }
+
+    pub fn model(&self) -> &str {
+        &self.model
+    }
+}
+
+impl ClassifierBackend for OllamaBackend {
+    fn classify(
+        &self,
+        call: &ClassifierCall,
+    ) -> impl std::future::Future<Output = Result<String, AgentError>> + Send {
+        let messages = vec![
+            ChatMessage::system(call.system.clone()),
+            ChatMessage::user(call.user.clone()),
+        ];
+        let model = self.model.clone();
+        let seed = call.seed;
+        async move {
+            let response = AssistantOllamaClient::new()
+                .send_classifier_message_with_seed(messages, &model, seed)
+                .await
+                .map_err(|e| AgentError::NetworkError(format!("Classification failed: {e}")))?;
+            response
+                .message
+                .and_then(|message| message.content().map(str::to_string))
+                .ok_or_else(|| {
+                    AgentError::ParseError("Classification failed: empty response".to_string())
+                })
+        }
+    }
+}
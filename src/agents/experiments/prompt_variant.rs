@@ -0,0 +1,188 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    agents::{
+        classifier::build_classifier_prompts,
+        prompts::{PromptError, PromptFile, PromptSet},
+    },
+    config::PromptsConfig,
+};
+
+/// A candidate classifier prompt: a name plus the prompt set it resolves sections from.
+#[derive(Debug, Clone)]
+pub struct PromptVariant {
+    name: String,
+    prompts: PromptSet,
+    /// The sections this variant changes; `None` for a set used as is.
+    overrides: Option<PromptFile>,
+}
+
+impl PromptVariant {
+    pub fn new(name: &str, prompts: PromptSet) -> Self {
+        Self {
+            name: name.to_string(),
+            prompts,
+            overrides: None,
+        }
+    }
+
+    /// `base` with the sections of `file` layered on top, e.g. a new task or few-shot set.
+    pub fn with_override(name: &str, base: &PromptSet, file: PromptFile) -> Self {
+        Self {
+            overrides: Some(file.clone()),
+            ..Self::new(name, base.with_override(file))
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn prompts(&self) -> &PromptSet {
+        &self.prompts
+    }
+
+    /// System and user prompts the classifier would send for `input`.
+    pub fn build_prompts(&self, input: &str) -> (String, String) {
+        build_classifier_prompts(&self.prompts, input)
+    }
+
+    /// Writes this variant's own sections into the prompt library file of its
+    /// prompt set and language, as `version`. The file's other sections are
+    /// kept; user overrides and sections the set falls back to in other
+    /// languages are not copied into it.
+    pub fn promote(&self, config: &PromptsConfig, version: &str) -> Result<PathBuf, PromptError> {
+        let Some(overrides) = &self.overrides else {
+            return Err(PromptError::ValidationError(format!(
+                "Variant '{}' has no sections of its own to promote",
+                self.name
+            )));
+        };
+        let path = Path::new(&config.path)
+            .join(self.prompts.language())
+            .join(format!("{}.toml", self.prompts.name()));
+        let mut sections = if path.exists() {
+            let content = fs::read_to_string(&path)
+                .map_err(|e| PromptError::IoError(format!("{}: {e}", path.display())))?;
+            PromptFile::from_toml_str(&content)?.sections
+        } else {
+            Default::default()
+        };
+        sections.extend(overrides.sections.clone());
+        let file = PromptFile {
+            version: version.to_string(),
+            sections,
+        };
+        file.validate(&path.display().to_string())?;
+
+        let content = toml::to_string(&file).map_err(|e| PromptError::ParseError(e.to_string()))?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| PromptError::IoError(format!("{}: {e}", parent.display())))?;
+        }
+        fs::write(&path, content)
+            .map_err(|e| PromptError::IoError(format!("{}: {e}", path.display())))?;
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agents::prompts::PromptLibrary;
+
+    fn variant_file(version: &str, task: &str) -> PromptFile {
+        PromptFile {
+            version: version.to_string(),
+            sections: [("task".to_string(), task.to_string())].into(),
+        }
+    }
+
+    #[test]
+    fn test_with_override_changes_only_overridden_sections() {
+        let base = PromptSet::builtin("classifier");
+        let variant = PromptVariant::with_override(
+            "short-task",
+            &base,
+            variant_file("exp-1", "Task: be brief"),
+        );
+
+        let (system, _) = variant.build_prompts("Email Eva");
+        let (baseline, _) = build_classifier_prompts(&base, "Email Eva");
+
+        assert_eq!(variant.name(), "short-task");
+        assert_eq!(variant.prompts().version(), "exp-1");
+        assert!(system.contains("Task: be brief"));
+        assert!(!baseline.contains("Task: be brief"));
+    }
+
+    #[test]
+    fn test_promote_writes_a_loadable_prompt_file() {
+        let root = std::env::temp_dir().join(format!("prompt_variant_{}", std::process::id()));
+        let config = PromptsConfig {
+            path: root.display().to_string(),
+            language: "en".to_string(),
+        };
+        let variant = PromptVariant::with_override(
+            "short-task",
+            &PromptSet::builtin("classifier"),
+            variant_file("exp-1", "Task: be brief"),
+        );
+
+        let path = variant.promote(&config, "1.1.0").unwrap();
+        let library = PromptLibrary::load(&config, "nobody").unwrap();
+        let _ = fs::remove_dir_all(&root);
+
+        assert!(path.ends_with("en/classifier.toml"));
+        let promoted = library.prompt_set("classifier");
+        assert_eq!(promoted.version(), "1.1.0");
+        assert_eq!(promoted.section("task", ""), "Task: be brief");
+    }
+
+    #[test]
+    fn test_promote_writes_only_the_variant_sections() {
+        let root = std::env::temp_dir().join(format!("prompt_variant_own_{}", std::process::id()));
+        let config = PromptsConfig {
+            path: root.display().to_string(),
+            language: "pt-BR".to_string(),
+        };
+        fs::create_dir_all(root.join("pt-BR")).unwrap();
+        fs::write(
+            root.join("pt-BR/classifier.toml"),
+            "version = \"1.0.0-pt-BR\"\n[sections]\ntask = \"Tarefa\"\nyou_are = \"Você é\"\n",
+        )
+        .unwrap();
+        let file = |version: &str, sections: &[(&str, &str)]| PromptFile {
+            version: version.to_string(),
+            sections: sections
+                .iter()
+                .map(|(key, text)| (key.to_string(), text.to_string()))
+                .collect(),
+        };
+        let base = PromptSet::new(
+            "classifier",
+            "pt-BR",
+            vec![
+                file("ana-1", &[("you_are", "Você é a Tereza")]),
+                file("1.0.0-pt-BR", &[("task", "Tarefa"), ("you_are", "Você é")]),
+                file("1.0.0", &[("output", "Output-Format")]),
+            ],
+        );
+        let variant =
+            PromptVariant::with_override("short", &base, variant_file("exp-1", "Tarefa curta"));
+
+        let path = variant.promote(&config, "1.1.0-pt-BR").unwrap();
+        let promoted = PromptFile::from_toml_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        let baseline = PromptVariant::new("baseline", base).promote(&config, "1.2.0");
+        let _ = fs::remove_dir_all(&root);
+
+        assert_eq!(promoted.version, "1.1.0-pt-BR");
+        assert_eq!(promoted.section("task"), Some("Tarefa curta"));
+        assert_eq!(promoted.section("you_are"), Some("Você é"));
+        assert_eq!(promoted.section("output"), None);
+        assert!(matches!(baseline, Err(PromptError::ValidationError(_))));
+    }
+}
@@ -0,0 +1,71 @@
+use std::sync::Mutex;
+
+use crate::agents::AgentError;
+
+use super::{
+    classifier_backend::ClassifierBackend, classifier_call::ClassifierCall,
+    replay_backend::ReplayBackend,
+};
+
+/// Forwards to another backend and records every successful response for replay.
+pub struct RecordingBackend<B> {
+    inner: B,
+    recorded: Mutex<ReplayBackend>,
+}
+
+impl<B: ClassifierBackend + Sync> RecordingBackend<B> {
+    pub fn new(inner: B) -> Self {
+        Self {
+            inner,
+            recorded: Mutex::new(ReplayBackend::new()),
+        }
+    }
+
+    /// The responses recorded so far.
+    pub fn into_replay(self) -> ReplayBackend {
+        self.recorded
+            .into_inner()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl<B: ClassifierBackend + Sync> ClassifierBackend for RecordingBackend<B> {
+    fn classify(
+        &self,
+        call: &ClassifierCall,
+    ) -> impl std::future::Future<Output = Result<String, AgentError>> + Send {
+        let call = call.clone();
+        async move {
+            let response = self.inner.classify(&call).await?;
+            self.recorded
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .record(&call, &response);
+            Ok(response)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_records_responses_from_inner_backend() {
+        let call = ClassifierCall::new("baseline", "system", "user", 3);
+        let recording =
+            RecordingBackend::new(ReplayBackend::new().with_response(&call, "recorded"));
+
+        assert_eq!(recording.classify(&call).await.unwrap(), "recorded");
+        assert!(
+            recording
+                .classify(&ClassifierCall::new("baseline", "other", "user", 3))
+                .await
+                .is_err()
+        );
+
+        let replay = recording.into_replay();
+        assert_eq!(replay.len(), 1);
+        assert_eq!(replay.response(&call), Some("recorded"));
+    }
+}
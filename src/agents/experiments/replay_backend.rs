@@ -0,0 +1,115 @@
+use std::{collections::BTreeMap, fs, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use crate::agents::AgentError;
+
+use super::{
+    classifier_backend::ClassifierBackend, classifier_call::ClassifierCall,
+    experiment_error::ExperimentError,
+};
+
+/// Answers from recorded responses so an experiment can be re-run without a model.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(transparent)]
+pub struct ReplayBackend {
+    responses: BTreeMap<String, String>,
+}
+
+impl ReplayBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_response(mut self, call: &ClassifierCall, response: &str) -> Self {
+        self.record(call, response);
+        self
+    }
+
+    pub fn record(&mut self, call: &ClassifierCall, response: &str) {
+        self.responses
+            .insert(call.replay_key(), response.to_string());
+    }
+
+    pub fn response(&self, call: &ClassifierCall) -> Option<&str> {
+        self.responses.get(&call.replay_key()).map(String::as_str)
+    }
+
+    pub fn len(&self) -> usize {
+        self.responses.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.responses.is_empty()
+    }
+
+    pub fn load(path: &Path) -> Result<Self, ExperimentError> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| ExperimentError::IoError(format!("{}: {e}", path.display())))?;
+        serde_json::from_str(&content)
+            .map_err(|e| ExperimentError::DatasetError(format!("{}: {e}", path.display())))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), ExperimentError> {
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| ExperimentError::DatasetError(e.to_string()))?;
+        fs::write(path, content)
+            .map_err(|e| ExperimentError::IoError(format!("{}: {e}", path.display())))
+    }
+}
+
+impl ClassifierBackend for ReplayBackend {
+    fn classify(
+        &self,
+        call: &ClassifierCall,
+    ) -> impl std::future::Future<Output = Result<String, AgentError>> + Send {
+        let response = self.response(call).map(str::to_string);
+        let variant = call.variant.clone();
+        let seed = call.seed;
+        async move {
+            response.ok_or_else(|| {
+                AgentError::ProcessingError(format!(
+                    "No recorded response for variant '{variant}' with seed {seed}"
+                ))
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_replays_recorded_response() {
+        let call = ClassifierCall::new("baseline", "system", "user", 7);
+        let backend = ReplayBackend::new().with_response(&call, "{\"intent\":\"no_action\"}");
+
+        assert_eq!(
+            backend.classify(&call).await.unwrap(),
+            "{\"intent\":\"no_action\"}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_missing_response_is_an_error() {
+        let call = ClassifierCall::new("baseline", "system", "user", 7);
+        let result = ReplayBackend::new().classify(&call).await;
+        assert!(matches!(result, Err(AgentError::ProcessingError(_))));
+    }
+
+    #[test]
+    fn test_save_and_load_round_trip() {
+        let path = std::env::temp_dir().join(format!("replay_backend_{}.json", std::process::id()));
+        let backend = ReplayBackend::new()
+            .with_response(&ClassifierCall::new("a", "s", "u", 1), "one")
+            .with_response(&ClassifierCall::new("a", "s", "u", 2), "two");
+
+        backend.save(&path).unwrap();
+        let restored = ReplayBackend::load(&path).unwrap();
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(restored, backend);
+        assert_eq!(restored.len(), 2);
+    }
+}
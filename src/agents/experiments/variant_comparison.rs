@@ -0,0 +1,55 @@
+use serde::{Deserialize, Serialize};
+
+use super::{
+    estimate::Estimate, intent_regression::IntentRegression, variant_report::VariantReport,
+};
+
+/// Accuracy difference between a variant and the baseline, paired per example
+/// (each averaged over the seeds), the same unit as [`VariantReport::accuracy`].
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct VariantComparison {
+    pub baseline: String,
+    pub variant: String,
+    pub delta: Estimate,
+    /// Intents with lower accuracy than the baseline, largest drop first.
+    pub regressions: Vec<IntentRegression>,
+}
+
+impl VariantComparison {
+    pub fn between(baseline: &VariantReport, variant: &VariantReport) -> Self {
+        let differences: Vec<f64> = baseline
+            .per_example
+            .iter()
+            .zip(&variant.per_example)
+            .map(|(base, candidate)| candidate - base)
+            .collect();
+
+        let mut regressions: Vec<IntentRegression> = baseline
+            .per_intent
+            .iter()
+            .map(|(intent, score)| IntentRegression {
+                intent: intent.clone(),
+                baseline_accuracy: score.accuracy(),
+                variant_accuracy: variant
+                    .per_intent
+                    .get(intent)
+                    .map(|score| score.accuracy())
+                    .unwrap_or(0.0),
+            })
+            .filter(|regression| regression.delta() < 0.0)
+            .collect();
+        regressions.sort_by(|a, b| a.delta().total_cmp(&b.delta()));
+
+        Self {
+            baseline: baseline.name.clone(),
+            variant: variant.name.clone(),
+            delta: Estimate::from_samples(&differences),
+            regressions,
+        }
+    }
+
+    /// True when the whole interval of the accuracy delta is above zero.
+    pub fn is_improvement(&self) -> bool {
+        self.delta.low > 0.0
+    }
+}
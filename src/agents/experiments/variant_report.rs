@@ -0,0 +1,25 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::{estimate::Estimate, intent_score::IntentScore};
+
+/// How one prompt variant scored on the dataset across all seeds.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct VariantReport {
+    pub name: String,
+    pub prompt_version: String,
+    /// Accuracy over the examples (each one averaged over the seeds), with a
+    /// 95% interval.
+    pub accuracy: Estimate,
+    pub per_intent: BTreeMap<String, IntentScore>,
+    /// Calls whose response could not be obtained or parsed; counted as incorrect.
+    pub failures: usize,
+    /// Correctness per (seed, example), seed-major.
+    #[serde(default)]
+    pub outcomes: Vec<bool>,
+    /// Accuracy of each example over the seeds, in dataset order; the unit
+    /// both the accuracy and the paired comparison are computed over.
+    #[serde(default)]
+    pub per_example: Vec<f64>,
+}
@@ -6,6 +6,7 @@ use std::{fmt, str::FromStr};
 pub enum Intent {
     SendEmail,
     ScheduleMeeting,
+    ContactManagement,
     NoAction,
 }
 
@@ -17,6 +18,7 @@ impl FromStr for Intent {
         match input.trim().to_lowercase().as_str() {
             SEND_EMAIL => Ok(Intent::SendEmail),
             SCHEDULE_MEETING => Ok(Intent::ScheduleMeeting),
+            CONTACT_MANAGEMENT => Ok(Intent::ContactManagement),
             _ => Ok(Intent::NoAction),
         }
     }
@@ -29,6 +31,7 @@ impl Intent {
         match self {
             Self::SendEmail => SEND_EMAIL,
             Self::ScheduleMeeting => SCHEDULE_MEETING,
+            Self::ContactManagement => CONTACT_MANAGEMENT,
             Self::NoAction => NO_ACTION,
         }
     }
@@ -39,6 +42,7 @@ impl fmt::Display for Intent {
         match self {
             Intent::SendEmail => write!(f, "{SEND_EMAIL}"),
             Intent::ScheduleMeeting => write!(f, "{SCHEDULE_MEETING}"),
+            Intent::ContactManagement => write!(f, "{CONTACT_MANAGEMENT}"),
             Intent::NoAction => write!(f, "{NO_ACTION}"),
         }
     }
@@ -46,4 +50,5 @@ impl fmt::Display for Intent {
 
 const SEND_EMAIL: &str = "send_email";
 const SCHEDULE_MEETING: &str = "schedule_meeting";
+const CONTACT_MANAGEMENT: &str = "contact_management";
 const NO_ACTION: &str = "no_action";
@@ -7,8 +7,13 @@ pub mod agent_result;
 pub mod assistant;
 pub mod classifier;
 pub mod contact;
+pub mod context;
 pub mod email;
+pub mod experiments;
 pub mod intent;
+pub mod prompts;
+pub mod reminders;
+pub mod triage;
 
 pub use agent::Agent;
 pub use agent_error::AgentError;
@@ -0,0 +1,9 @@
+pub mod prompt_error;
+pub mod prompt_file;
+pub mod prompt_library;
+pub mod prompt_set;
+
+pub use prompt_error::PromptError;
+pub use prompt_file::PromptFile;
+pub use prompt_library::PromptLibrary;
+pub use prompt_set::PromptSet;
@@ -0,0 +1,21 @@
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug, Clone)]
+pub enum PromptError {
+    IoError(String),
+    ParseError(String),
+    ValidationError(String),
+}
+
+impl fmt::Display for PromptError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PromptError::IoError(msg) => write!(f, "Prompt IO error: {msg}"),
+            PromptError::ParseError(msg) => write!(f, "Prompt parse error: {msg}"),
+            PromptError::ValidationError(msg) => write!(f, "Prompt validation error: {msg}"),
+        }
+    }
+}
+
+impl Error for PromptError {}
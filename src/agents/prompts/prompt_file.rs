@@ -0,0 +1,143 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::prompt_error::PromptError;
+
+/// A versioned set of named prompt sections, stored as one TOML file.
+///
+/// ```toml
+/// version = "1.0.0"
+///
+/// [sections]
+/// task = "Task: Return JSON with: ..."
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct PromptFile {
+    pub version: String,
+    #[serde(default)]
+    pub sections: BTreeMap<String, String>,
+}
+
+impl PromptFile {
+    pub fn from_toml_str(content: &str) -> Result<Self, PromptError> {
+        toml::from_str(content).map_err(|e| PromptError::ParseError(e.to_string()))
+    }
+
+    /// Checks the version identifier and every section, naming `origin` in errors.
+    pub fn validate(&self, origin: &str) -> Result<(), PromptError> {
+        if self.version.trim().is_empty() || self.version.chars().any(char::is_whitespace) {
+            return Err(PromptError::ValidationError(format!(
+                "{origin}: version must be a non-empty identifier without spaces"
+            )));
+        }
+        if self.sections.is_empty() {
+            return Err(PromptError::ValidationError(format!(
+                "{origin}: no sections defined"
+            )));
+        }
+        for (key, text) in &self.sections {
+            if !key
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+            {
+                return Err(PromptError::ValidationError(format!(
+                    "{origin}: section '{key}' must be snake_case"
+                )));
+            }
+            if text.trim().is_empty() {
+                return Err(PromptError::ValidationError(format!(
+                    "{origin}: section '{key}' is empty"
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn section(&self, key: &str) -> Option<&str> {
+        self.sections.get(key).map(String::as_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prompt_file_from_toml_str() {
+        let file = PromptFile::from_toml_str(
+            r#"
+version = "1.2.0"
+
+[sections]
+task = "Classify the input"
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(file.version, "1.2.0");
+        assert_eq!(file.section("task"), Some("Classify the input"));
+        assert_eq!(file.section("missing"), None);
+        assert!(file.validate("classifier.toml").is_ok());
+    }
+
+    #[test]
+    fn test_prompt_file_invalid_toml() {
+        let result = PromptFile::from_toml_str("version = ");
+        assert!(matches!(result, Err(PromptError::ParseError(_))));
+    }
+
+    #[test]
+    fn test_prompt_file_validate_rejects_blank_version() {
+        let file = PromptFile::from_toml_str(
+            r#"
+version = " "
+[sections]
+task = "Classify"
+"#,
+        )
+        .unwrap();
+
+        let error = file.validate("classifier.toml").unwrap_err();
+        assert!(error.to_string().contains("classifier.toml"));
+        assert!(error.to_string().contains("version"));
+    }
+
+    #[test]
+    fn test_prompt_file_validate_rejects_empty_sections() {
+        let file = PromptFile::from_toml_str(r#"version = "1""#).unwrap();
+        assert!(matches!(
+            file.validate("empty.toml"),
+            Err(PromptError::ValidationError(_))
+        ));
+    }
+
+    #[test]
+    fn test_prompt_file_validate_rejects_blank_section() {
+        let file = PromptFile::from_toml_str(
+            r#"
+version = "1"
+[sections]
+task = "   "
+"#,
+        )
+        .unwrap();
+
+        let error = file.validate("blank.toml").unwrap_err();
+        assert!(error.to_string().contains("'task' is empty"));
+    }
+
+    #[test]
+    fn test_prompt_file_validate_rejects_bad_section_key() {
+        let file = PromptFile::from_toml_str(
+            r#"
+version = "1"
+[sections]
+"Task Name" = "Classify"
+"#,
+        )
+        .unwrap();
+
+        assert!(file.validate("keys.toml").is_err());
+    }
+}
@@ -0,0 +1,346 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use once_cell::sync::Lazy;
+
+use crate::config::{Config, PromptsConfig};
+
+use super::{prompt_error::PromptError, prompt_file::PromptFile, prompt_set::PromptSet};
+
+pub const DEFAULT_LANGUAGE: &str = "en";
+const USERS_DIR: &str = "users";
+const PROMPT_EXTENSION: &str = "toml";
+
+/// Prompt files loaded from the directory configured in `[prompts]`.
+///
+/// Layout:
+/// ```text
+/// prompts/
+/// ├── en/classifier.toml
+/// ├── pt-BR/classifier.toml
+/// └── users/Ana/en/classifier.toml
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct PromptLibrary {
+    language: String,
+    user: Option<String>,
+    files: HashMap<String, PromptFile>,
+    errors: Vec<PromptError>,
+}
+
+static PROMPT_LIBRARY: Lazy<PromptLibrary> = Lazy::new(|| {
+    let config = Config::get();
+    PromptLibrary::load_skipping_invalid(&config.prompts, &config.user.settings.name)
+});
+
+impl PromptLibrary {
+    /// A library without files: every prompt falls back to the compiled-in text.
+    pub fn builtin(language: &str) -> Self {
+        Self {
+            language: language.to_string(),
+            user: None,
+            files: HashMap::new(),
+            errors: Vec::new(),
+        }
+    }
+
+    /// Loads and validates every prompt file. A missing directory is not an error.
+    pub fn load(config: &PromptsConfig, user: &str) -> Result<Self, PromptError> {
+        let mut library = Self::load_skipping_invalid(config, user);
+        if library.errors.is_empty() {
+            Ok(library)
+        } else {
+            Err(library.errors.remove(0))
+        }
+    }
+
+    /// Like [`PromptLibrary::load`], but a file that cannot be read or is
+    /// invalid is skipped, so only its overrides are lost. The skipped files are
+    /// listed in [`errors`](Self::errors).
+    pub fn load_skipping_invalid(config: &PromptsConfig, user: &str) -> Self {
+        let mut library = Self::builtin(&config.language);
+        let root = Path::new(&config.path);
+        if !root.is_dir() {
+            return library;
+        }
+
+        for (language, dir) in library.language_dirs(root) {
+            if language != USERS_DIR {
+                library.load_dir(&dir, &language);
+            }
+        }
+
+        let user_root = root.join(USERS_DIR).join(user);
+        if user_root.is_dir() {
+            for (language, dir) in library.language_dirs(&user_root) {
+                library.load_dir(&dir, &user_key(user, &language));
+            }
+            library.user = Some(user.to_string());
+        }
+
+        library
+    }
+
+    pub fn get() -> &'static PromptLibrary {
+        &PROMPT_LIBRARY
+    }
+
+    pub fn language(&self) -> &str {
+        &self.language
+    }
+
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// Why files were skipped by [`load_skipping_invalid`](Self::load_skipping_invalid).
+    pub fn errors(&self) -> &[PromptError] {
+        &self.errors
+    }
+
+    /// Resolves `name` in the configured language.
+    pub fn prompt_set(&self, name: &str) -> PromptSet {
+        self.prompt_set_in(name, &self.language)
+    }
+
+    /// Resolves `name` in `language`, layering user overrides over shared files
+    /// and falling back to the default language.
+    pub fn prompt_set_in(&self, name: &str, language: &str) -> PromptSet {
+        let mut prefixes = Vec::new();
+        for lang in [language, DEFAULT_LANGUAGE] {
+            if let Some(user) = &self.user {
+                prefixes.push(user_key(user, lang));
+            }
+            prefixes.push(lang.to_string());
+        }
+        prefixes.dedup();
+
+        let layers = prefixes
+            .iter()
+            .filter_map(|prefix| self.files.get(&format!("{prefix}/{name}")))
+            .cloned()
+            .collect();
+
+        PromptSet::new(name, language, layers)
+    }
+
+    fn load_dir(&mut self, dir: &Path, prefix: &str) {
+        for path in self.entries(dir) {
+            if path.extension().and_then(|e| e.to_str()) != Some(PROMPT_EXTENSION) {
+                continue;
+            }
+            let Some(name) = path.file_stem().and_then(|s| s.to_str()) else {
+                continue;
+            };
+
+            match load_file(&path) {
+                Ok(file) => {
+                    self.files.insert(format!("{prefix}/{name}"), file);
+                }
+                Err(e) => self.errors.push(e),
+            }
+        }
+    }
+
+    fn language_dirs(&mut self, root: &Path) -> Vec<(String, PathBuf)> {
+        self.entries(root)
+            .into_iter()
+            .filter(|path| path.is_dir())
+            .filter_map(|path| {
+                let name = path.file_name()?.to_str()?.to_string();
+                Some((name, path))
+            })
+            .collect()
+    }
+
+    /// The paths in `dir`; a directory that cannot be read is recorded and skipped.
+    fn entries(&mut self, dir: &Path) -> Vec<PathBuf> {
+        let paths = fs::read_dir(dir).and_then(|entries| {
+            entries
+                .map(|entry| entry.map(|entry| entry.path()))
+                .collect::<Result<Vec<_>, _>>()
+        });
+        paths.unwrap_or_else(|e| {
+            self.errors.push(io_error(dir, e));
+            Vec::new()
+        })
+    }
+}
+
+fn load_file(path: &Path) -> Result<PromptFile, PromptError> {
+    let content = fs::read_to_string(path).map_err(|e| io_error(path, e))?;
+    let origin = path.display().to_string();
+    let file = PromptFile::from_toml_str(&content)
+        .map_err(|e| PromptError::ParseError(format!("{origin}: {e}")))?;
+    file.validate(&origin)?;
+    Ok(file)
+}
+
+fn user_key(user: &str, language: &str) -> String {
+    format!("{USERS_DIR}/{user}/{language}")
+}
+
+fn io_error(path: &Path, error: std::io::Error) -> PromptError {
+    PromptError::IoError(format!("{}: {error}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct TestPromptDir {
+        root: PathBuf,
+    }
+
+    impl TestPromptDir {
+        fn new(name: &str) -> Self {
+            let root = std::env::temp_dir().join(format!(
+                "prompt_library_{name}_{}",
+                std::process::id()
+            ));
+            let _ = fs::remove_dir_all(&root);
+            fs::create_dir_all(&root).unwrap();
+            Self { root }
+        }
+
+        fn write(&self, relative: &str, content: &str) {
+            let path = self.root.join(relative);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+
+        fn config(&self, language: &str) -> PromptsConfig {
+            PromptsConfig {
+                path: self.root.display().to_string(),
+                language: language.to_string(),
+            }
+        }
+    }
+
+    impl Drop for TestPromptDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.root);
+        }
+    }
+
+    const EN_CLASSIFIER: &str = r#"
+version = "1.0.0"
+[sections]
+task = "Task in English"
+you_are = "Intro in English"
+"#;
+
+    const PT_CLASSIFIER: &str = r#"
+version = "1.1.0-pt"
+[sections]
+task = "Tarefa em português"
+"#;
+
+    #[test]
+    fn test_load_missing_directory_falls_back_to_builtin() {
+        let config = PromptsConfig {
+            path: "nonexistent_prompts_dir".to_string(),
+            language: "en".to_string(),
+        };
+
+        let library = PromptLibrary::load(&config, "Ana").unwrap();
+        assert!(library.is_empty());
+
+        let set = library.prompt_set("classifier");
+        assert!(set.is_builtin());
+        assert_eq!(set.section("task", "compiled"), "compiled");
+    }
+
+    #[test]
+    fn test_load_language_variant_with_default_fallback() {
+        let dir = TestPromptDir::new("language");
+        dir.write("en/classifier.toml", EN_CLASSIFIER);
+        dir.write("pt-BR/classifier.toml", PT_CLASSIFIER);
+
+        let library = PromptLibrary::load(&dir.config("pt-BR"), "Ana").unwrap();
+        assert_eq!(library.len(), 2);
+        assert_eq!(library.language(), "pt-BR");
+
+        let set = library.prompt_set("classifier");
+        assert_eq!(set.version(), "1.1.0-pt");
+        assert_eq!(set.section("task", "compiled"), "Tarefa em português");
+        assert_eq!(set.section("you_are", "compiled"), "Intro in English");
+
+        let english = library.prompt_set_in("classifier", "en");
+        assert_eq!(english.version(), "1.0.0");
+        assert_eq!(english.section("task", "compiled"), "Task in English");
+    }
+
+    #[test]
+    fn test_load_user_override_takes_precedence() {
+        let dir = TestPromptDir::new("user");
+        dir.write("en/classifier.toml", EN_CLASSIFIER);
+        dir.write(
+            "users/Ana/en/classifier.toml",
+            "version = \"ana-1\"\n[sections]\ntask = \"Ana's task\"\n",
+        );
+        dir.write(
+            "users/Bruno/en/classifier.toml",
+            "version = \"bruno-1\"\n[sections]\ntask = \"Bruno's task\"\n",
+        );
+
+        let library = PromptLibrary::load(&dir.config("en"), "Ana").unwrap();
+        let set = library.prompt_set("classifier");
+
+        assert_eq!(set.version(), "ana-1");
+        assert_eq!(set.section("task", "compiled"), "Ana's task");
+        assert_eq!(set.section("you_are", "compiled"), "Intro in English");
+    }
+
+    #[test]
+    fn test_load_rejects_invalid_prompt_file() {
+        let dir = TestPromptDir::new("invalid");
+        dir.write("en/classifier.toml", "version = \"\"\n[sections]\ntask = \"x\"\n");
+
+        let result = PromptLibrary::load(&dir.config("en"), "Ana");
+        assert!(matches!(result, Err(PromptError::ValidationError(_))));
+    }
+
+    #[test]
+    fn test_load_skipping_invalid_keeps_the_valid_files() {
+        let dir = TestPromptDir::new("skipping");
+        dir.write("en/classifier.toml", EN_CLASSIFIER);
+        dir.write("en/email_draft.toml", "[sections\ntask = ");
+
+        let library = PromptLibrary::load_skipping_invalid(&dir.config("en"), "Ana");
+        assert_eq!(library.len(), 1);
+        assert_eq!(library.prompt_set("classifier").version(), "1.0.0");
+        assert_eq!(library.errors().len(), 1);
+        assert!(library.errors()[0].to_string().contains("email_draft.toml"));
+        assert_eq!(
+            library.prompt_set("email_draft").version(),
+            PromptSet::builtin("email_draft").version()
+        );
+    }
+
+    #[test]
+    fn test_load_rejects_malformed_toml() {
+        let dir = TestPromptDir::new("malformed");
+        dir.write("en/classifier.toml", "[sections\ntask = ");
+
+        let error = PromptLibrary::load(&dir.config("en"), "Ana").unwrap_err();
+        assert!(error.to_string().contains("classifier.toml"));
+    }
+
+    #[test]
+    fn test_load_ignores_non_toml_files() {
+        let dir = TestPromptDir::new("non_toml");
+        dir.write("en/classifier.toml", EN_CLASSIFIER);
+        dir.write("en/README.md", "# notes");
+
+        let library = PromptLibrary::load(&dir.config("en"), "Ana").unwrap();
+        assert_eq!(library.len(), 1);
+    }
+}
@@ -0,0 +1,125 @@
+use super::prompt_file::PromptFile;
+
+/// Version reported when no prompt file was found and the compiled-in text is used.
+pub const BUILTIN_VERSION: &str = "builtin";
+
+/// A resolved prompt set: prompt files ordered from the most to the least specific
+/// layer (user override, requested language, default language).
+#[derive(Debug, Clone)]
+pub struct PromptSet {
+    name: String,
+    language: String,
+    layers: Vec<PromptFile>,
+}
+
+impl PromptSet {
+    pub fn new(name: &str, language: &str, layers: Vec<PromptFile>) -> Self {
+        Self {
+            name: name.to_string(),
+            language: language.to_string(),
+            layers,
+        }
+    }
+
+    /// A prompt set with no files, so every section resolves to its fallback.
+    pub fn builtin(name: &str) -> Self {
+        Self::new(name, super::prompt_library::DEFAULT_LANGUAGE, Vec::new())
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn language(&self) -> &str {
+        &self.language
+    }
+
+    /// Version of the most specific prompt file, or `"builtin"`.
+    pub fn version(&self) -> &str {
+        self.layers
+            .first()
+            .map(|layer| layer.version.as_str())
+            .unwrap_or(BUILTIN_VERSION)
+    }
+
+    pub fn is_builtin(&self) -> bool {
+        self.layers.is_empty()
+    }
+
+    /// A copy of this set with `file` as its most specific layer.
+    pub fn with_override(&self, file: PromptFile) -> Self {
+        let mut layers = vec![file];
+        layers.extend(self.layers.iter().cloned());
+        Self::new(&self.name, &self.language, layers)
+    }
+
+    /// Returns the section from the first layer that defines it, or `fallback`.
+    pub fn section<'a>(&'a self, key: &str, fallback: &'a str) -> &'a str {
+        self.layers
+            .iter()
+            .find_map(|layer| layer.section(key))
+            .unwrap_or(fallback)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn prompt_file(version: &str, sections: &[(&str, &str)]) -> PromptFile {
+        PromptFile {
+            version: version.to_string(),
+            sections: sections
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_builtin_prompt_set_uses_fallbacks() {
+        let set = PromptSet::builtin("classifier");
+
+        assert!(set.is_builtin());
+        assert_eq!(set.name(), "classifier");
+        assert_eq!(set.version(), BUILTIN_VERSION);
+        assert_eq!(set.section("task", "compiled task"), "compiled task");
+    }
+
+    #[test]
+    fn test_prompt_set_most_specific_layer_wins() {
+        let set = PromptSet::new(
+            "classifier",
+            "pt-BR",
+            vec![
+                prompt_file("user-2", &[("task", "user task")]),
+                prompt_file("1.0.0", &[("task", "language task"), ("you_are", "language intro")]),
+            ],
+        );
+
+        assert!(!set.is_builtin());
+        assert_eq!(set.version(), "user-2");
+        assert_eq!(set.language(), "pt-BR");
+        assert_eq!(set.section("task", "compiled"), "user task");
+        assert_eq!(set.section("you_are", "compiled"), "language intro");
+        assert_eq!(set.section("output", "compiled"), "compiled");
+    }
+
+    #[test]
+    fn test_with_override_takes_precedence_and_reports_its_version() {
+        let base = PromptSet::new(
+            "classifier",
+            "en",
+            vec![prompt_file(
+                "1.0.0",
+                &[("task", "base task"), ("you_are", "base")],
+            )],
+        );
+        let variant = base.with_override(prompt_file("exp-a", &[("task", "variant task")]));
+
+        assert_eq!(variant.version(), "exp-a");
+        assert_eq!(variant.section("task", ""), "variant task");
+        assert_eq!(variant.section("you_are", ""), "base");
+        assert_eq!(base.section("task", ""), "base task");
+    }
+}
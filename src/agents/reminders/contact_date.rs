@@ -0,0 +1,137 @@
+use chrono::{Datelike, NaiveDate};
+
+use super::leap_day_policy::LeapDayPolicy;
+
+/// A birthday or anniversary as written on a contact: `YYYY-MM-DD`, or `--MM-DD` when the
+/// year is unknown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ContactDate {
+    pub year: Option<i32>,
+    pub month: u32,
+    pub day: u32,
+}
+
+impl ContactDate {
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        if let Some(month_day) = value.strip_prefix("--") {
+            let (month, day) = month_day.split_once('-')?;
+            let date = Self {
+                year: None,
+                month: month.parse().ok()?,
+                day: day.parse().ok()?,
+            };
+            // 2000 is a leap year, so this accepts --02-29 and rejects --02-30.
+            NaiveDate::from_ymd_opt(2000, date.month, date.day)?;
+            return Some(date);
+        }
+
+        let date = NaiveDate::parse_from_str(value, "%Y-%m-%d").ok()?;
+        Some(Self {
+            year: Some(date.year()),
+            month: date.month(),
+            day: date.day(),
+        })
+    }
+
+    /// The date in `year`, moving February 29 according to `policy` outside leap years.
+    pub fn in_year(&self, year: i32, policy: LeapDayPolicy) -> Option<NaiveDate> {
+        NaiveDate::from_ymd_opt(year, self.month, self.day).or_else(|| {
+            if (self.month, self.day) != (2, 29) {
+                return None;
+            }
+            match policy {
+                LeapDayPolicy::February28 => NaiveDate::from_ymd_opt(year, 2, 28),
+                LeapDayPolicy::March1 => NaiveDate::from_ymd_opt(year, 3, 1),
+            }
+        })
+    }
+
+    /// First occurrence on or after `from`.
+    pub fn next_occurrence(&self, from: NaiveDate, policy: LeapDayPolicy) -> Option<NaiveDate> {
+        (from.year()..=from.year() + 1)
+            .filter_map(|year| self.in_year(year, policy))
+            .find(|date| *date >= from)
+    }
+
+    /// Years completed on `date`, when the original year is known and not after it.
+    pub fn years_at(&self, date: NaiveDate) -> Option<u32> {
+        let years = date.year() - self.year?;
+        u32::try_from(years).ok().filter(|years| *years > 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_parse_accepts_full_and_yearless_dates() {
+        assert_eq!(
+            ContactDate::parse("1980-11-08"),
+            Some(ContactDate {
+                year: Some(1980),
+                month: 11,
+                day: 8
+            })
+        );
+        assert_eq!(ContactDate::parse("--02-29").unwrap().year, None);
+        assert_eq!(ContactDate::parse("--02-30"), None);
+        assert_eq!(ContactDate::parse("1981-02-29"), None);
+        assert_eq!(ContactDate::parse("next week"), None);
+    }
+
+    #[test]
+    fn test_next_occurrence_wraps_into_next_year() {
+        let birthday = ContactDate::parse("1980-01-05").unwrap();
+        let policy = LeapDayPolicy::default();
+        assert_eq!(
+            birthday.next_occurrence(date(2025, 12, 20), policy),
+            Some(date(2026, 1, 5))
+        );
+        assert_eq!(
+            birthday.next_occurrence(date(2026, 1, 5), policy),
+            Some(date(2026, 1, 5))
+        );
+    }
+
+    #[test]
+    fn test_leap_day_follows_policy_outside_leap_years() {
+        let birthday = ContactDate::parse("1980-02-29").unwrap();
+        let from = date(2025, 2, 1);
+        assert_eq!(
+            birthday.next_occurrence(from, LeapDayPolicy::February28),
+            Some(date(2025, 2, 28))
+        );
+        assert_eq!(
+            birthday.next_occurrence(from, LeapDayPolicy::March1),
+            Some(date(2025, 3, 1))
+        );
+        assert_eq!(
+            birthday.next_occurrence(date(2028, 2, 1), LeapDayPolicy::March1),
+            Some(date(2028, 2, 29))
+        );
+    }
+
+    #[test]
+    fn test_years_at_needs_a_known_year() {
+        let birthday = ContactDate::parse("1980-11-08").unwrap();
+        assert_eq!(birthday.years_at(date(2025, 11, 8)), Some(45));
+        assert_eq!(
+            ContactDate::parse("--11-08")
+                .unwrap()
+                .years_at(date(2025, 11, 8)),
+            None
+        );
+        assert_eq!(
+            ContactDate::parse("2025-11-08")
+                .unwrap()
+                .years_at(date(2025, 11, 8)),
+            None
+        );
+    }
+}
@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+
+use crate::agents::email::email_agent::EmailParam;
+
+/// A congratulations message for an occasion, ready to hand to the `EmailAgent`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct GreetingDraft {
+    pub contact_id: String,
+    pub name: String,
+    /// Primary e-mail of the contact, if it has one.
+    pub to: Option<String>,
+    pub subject: String,
+    pub body: String,
+    pub language: String,
+}
+
+impl GreetingDraft {
+    /// The send request the `EmailAgent` expects, addressed by e-mail when known and by name
+    /// otherwise so recipient resolution can take over.
+    pub fn to_email_param(&self) -> EmailParam {
+        let recipient = self.to.as_deref().unwrap_or(&self.name);
+        let param = EmailParam::new(&format!(
+            "Send an email to {recipient} with the subject \"{}\":\n\n{}",
+            self.subject, self.body
+        ))
+        .with_recipient(&self.name)
+        .with_message(&self.body)
+        .with_language(&self.language);
+        match &self.to {
+            Some(to) => param.with_to(vec![to.clone()]),
+            None => param,
+        }
+    }
+}
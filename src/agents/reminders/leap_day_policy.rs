@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Where a February 29 date falls in years that do not have one.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum LeapDayPolicy {
+    #[default]
+    February28,
+    March1,
+}
+
+impl LeapDayPolicy {
+    pub fn to_str(&self) -> &str {
+        match self {
+            Self::February28 => "february28",
+            Self::March1 => "march1",
+        }
+    }
+}
+
+impl fmt::Display for LeapDayPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_str())
+    }
+}
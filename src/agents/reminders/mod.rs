@@ -0,0 +1,13 @@
+pub mod contact_date;
+pub mod greeting_draft;
+pub mod leap_day_policy;
+pub mod occasion_kind;
+pub mod occasion_planner;
+pub mod upcoming_occasion;
+
+pub use contact_date::ContactDate;
+pub use greeting_draft::GreetingDraft;
+pub use leap_day_policy::LeapDayPolicy;
+pub use occasion_kind::OccasionKind;
+pub use occasion_planner::OccasionPlanner;
+pub use upcoming_occasion::UpcomingOccasion;
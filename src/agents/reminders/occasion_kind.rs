@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Which contact date an occasion comes from.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum OccasionKind {
+    Birthday,
+    Anniversary,
+}
+
+impl OccasionKind {
+    pub fn to_str(&self) -> &str {
+        match self {
+            Self::Birthday => "birthday",
+            Self::Anniversary => "anniversary",
+        }
+    }
+
+    /// Heading used in calendar titles, e.g. `Birthday - Dr. Dolphin Wise`.
+    pub fn title(&self) -> &str {
+        match self {
+            Self::Birthday => "Birthday",
+            Self::Anniversary => "Anniversary",
+        }
+    }
+}
+
+impl fmt::Display for OccasionKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_str())
+    }
+}
@@ -0,0 +1,404 @@
+use chrono::{FixedOffset, NaiveDate};
+use std::collections::BTreeMap;
+
+use crate::infra::{
+    calendar::{CalendarEvent, EventAttendee, EventCalendar, EventRecurrence, EventReminder},
+    contacts::Contact,
+};
+
+use super::{
+    contact_date::ContactDate, greeting_draft::GreetingDraft, leap_day_policy::LeapDayPolicy,
+    occasion_kind::OccasionKind, upcoming_occasion::UpcomingOccasion,
+};
+
+/// Derives birthdays and anniversaries from contacts and turns them into reminders, yearly
+/// calendar entries and greetings.
+#[derive(Debug, Clone)]
+pub struct OccasionPlanner {
+    horizon_days: i64,
+    lead_days: i64,
+    leap_day_policy: LeapDayPolicy,
+    utc_offset: FixedOffset,
+    language: String,
+}
+
+impl Default for OccasionPlanner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OccasionPlanner {
+    pub fn new() -> Self {
+        Self {
+            horizon_days: DEFAULT_HORIZON_DAYS,
+            lead_days: DEFAULT_LEAD_DAYS,
+            leap_day_policy: LeapDayPolicy::default(),
+            utc_offset: FixedOffset::east_opt(0).expect("UTC is a valid offset"),
+            language: DEFAULT_LANGUAGE.to_string(),
+        }
+    }
+
+    /// How far ahead `upcoming` looks, in days.
+    pub fn with_horizon_days(mut self, days: u32) -> Self {
+        self.horizon_days = i64::from(days);
+        self
+    }
+
+    /// How many days before the occasion the reminder fires.
+    pub fn with_lead_days(mut self, days: u32) -> Self {
+        self.lead_days = i64::from(days);
+        self
+    }
+
+    pub fn with_leap_day_policy(mut self, policy: LeapDayPolicy) -> Self {
+        self.leap_day_policy = policy;
+        self
+    }
+
+    /// Offset written on calendar entries, e.g. `-05:00` for `America/New_York` in winter.
+    pub fn with_utc_offset(mut self, offset: FixedOffset) -> Self {
+        self.utc_offset = offset;
+        self
+    }
+
+    /// Greeting language; `pt-*` writes Portuguese, anything else English.
+    pub fn with_language(mut self, language: &str) -> Self {
+        self.language = language.to_string();
+        self
+    }
+
+    /// Occasions within the horizon, soonest first.
+    pub fn upcoming(&self, contacts: &[Contact], today: NaiveDate) -> Vec<UpcomingOccasion> {
+        let mut occasions: Vec<UpcomingOccasion> = contacts
+            .iter()
+            .flat_map(|contact| self.occasions_of(contact, today))
+            .filter(|occasion| occasion.days_until <= self.horizon_days)
+            .collect();
+        occasions.sort_by(|a, b| {
+            (a.date, a.kind, &a.name, &a.contact_id).cmp(&(b.date, b.kind, &b.name, &b.contact_id))
+        });
+        occasions
+    }
+
+    /// Upcoming occasions whose reminder day has come.
+    pub fn due_reminders(&self, contacts: &[Contact], today: NaiveDate) -> Vec<UpcomingOccasion> {
+        self.upcoming(contacts, today)
+            .into_iter()
+            .filter(|occasion| occasion.remind_on <= today)
+            .collect()
+    }
+
+    /// The next occurrence of each birthday and anniversary of `contact`. Dates that cannot
+    /// be read are skipped.
+    pub fn occasions_of(&self, contact: &Contact, today: NaiveDate) -> Vec<UpcomingOccasion> {
+        [
+            (OccasionKind::Birthday, &contact.birthday),
+            (OccasionKind::Anniversary, &contact.anniversary),
+        ]
+        .into_iter()
+        .filter_map(|(kind, value)| {
+            let source = ContactDate::parse(value.as_deref()?)?;
+            let date = source.next_occurrence(today, self.leap_day_policy)?;
+            Some(UpcomingOccasion {
+                contact_id: contact.id.clone(),
+                name: contact.name(),
+                kind,
+                date,
+                days_until: (date - today).num_days(),
+                turning: source.years_at(date),
+                remind_on: date - chrono::Duration::days(self.lead_days),
+            })
+        })
+        .collect()
+    }
+
+    /// A yearly all-day entry in the style of the birthday events of `spec/calendar.json`.
+    /// The id is derived from the contact, so writing it again replaces the previous entry.
+    pub fn calendar_event(&self, occasion: &UpcomingOccasion) -> CalendarEvent {
+        let day = occasion.date.format("%Y-%m-%d");
+        let offset = self.utc_offset;
+        CalendarEvent {
+            id: format!("{}_{}", occasion.kind.to_str(), occasion.contact_id),
+            title: format!("{} - {}", occasion.kind.title(), occasion.name),
+            description: Some(format!(
+                "{}'s {}. Send congratulations card.",
+                occasion.name,
+                occasion.kind.to_str()
+            )),
+            start_date_time: format!("{day}T00:00:00{offset}"),
+            end_date_time: format!("{day}T23:59:59{offset}"),
+            attendees: vec![EventAttendee {
+                contact_id: Some(occasion.contact_id.clone()),
+                name: occasion.name.clone(),
+                email: None,
+                status: "none".to_string(),
+                attendee_type: occasion.kind.to_str().to_string(),
+            }],
+            category: occasion.kind.to_str().to_string(),
+            priority: "low".to_string(),
+            status: "confirmed".to_string(),
+            all_day: true,
+            recurrence: Some(EventRecurrence::yearly()),
+            reminders: vec![EventReminder::notification(
+                u32::try_from(self.lead_days * MINUTES_PER_DAY).unwrap_or(u32::MAX),
+            )],
+            color: Some(OCCASION_COLOR.to_string()),
+            created_at: None,
+            updated_at: None,
+            extra: BTreeMap::new(),
+        }
+    }
+
+    /// Writes a recurring entry for every birthday and anniversary in `contacts`, whatever
+    /// the horizon. Entries written earlier are refreshed; occasions the calendar already
+    /// tracks under another id (such as `evt_008` in the spec) are left alone. Returns how
+    /// many entries were new.
+    pub fn materialize(
+        &self,
+        calendar: &mut EventCalendar,
+        contacts: &[Contact],
+        today: NaiveDate,
+    ) -> usize {
+        let events: Vec<CalendarEvent> = contacts
+            .iter()
+            .flat_map(|contact| self.occasions_of(contact, today))
+            .map(|occasion| self.calendar_event(&occasion))
+            .collect();
+        let mut inserted = 0;
+        for event in events {
+            if calendar.find(&event.id).is_none() && tracked_elsewhere(calendar, &event) {
+                continue;
+            }
+            if calendar.upsert_event(event) {
+                inserted += 1;
+            }
+        }
+        inserted
+    }
+
+    /// A congratulations e-mail for `occasion`, signed by `signer`.
+    pub fn greeting(
+        &self,
+        occasion: &UpcomingOccasion,
+        contact: &Contact,
+        signer: &str,
+    ) -> GreetingDraft {
+        let first_name = if contact.first_name.trim().is_empty() {
+            occasion.name.clone()
+        } else {
+            contact.first_name.clone()
+        };
+        let portuguese = self.language.to_lowercase().starts_with("pt");
+        let (subject, wish, closing) = match (occasion.kind, portuguese) {
+            (OccasionKind::Birthday, false) => (
+                format!("Happy birthday, {first_name}!"),
+                match occasion.turning {
+                    Some(age) => format!("Happy {} birthday!", ordinal(age)),
+                    None => "Happy birthday!".to_string(),
+                },
+                "Wishing you a wonderful year ahead.\n\nBest,",
+            ),
+            (OccasionKind::Anniversary, false) => (
+                format!("Happy anniversary, {first_name}!"),
+                match occasion.turning {
+                    Some(years) => format!("Congratulations on {years} years!"),
+                    None => "Happy anniversary!".to_string(),
+                },
+                "Wishing you many more happy years.\n\nBest,",
+            ),
+            (OccasionKind::Birthday, true) => (
+                format!("Feliz aniversário, {first_name}!"),
+                match occasion.turning {
+                    Some(age) => format!("Parabéns pelos seus {age} anos!"),
+                    None => "Feliz aniversário!".to_string(),
+                },
+                "Desejo um ótimo ano pela frente.\n\nAbraços,",
+            ),
+            (OccasionKind::Anniversary, true) => (
+                format!("Parabéns, {first_name}!"),
+                match occasion.turning {
+                    Some(years) => format!("Parabéns pelos {years} anos de união!"),
+                    None => "Feliz aniversário de união!".to_string(),
+                },
+                "Desejo muitos outros anos felizes.\n\nAbraços,",
+            ),
+        };
+        let greeting = if portuguese { "Olá" } else { "Hi" };
+
+        GreetingDraft {
+            contact_id: occasion.contact_id.clone(),
+            name: occasion.name.clone(),
+            to: contact.primary_email().map(|email| email.address.clone()),
+            subject,
+            body: format!("{greeting} {first_name},\n\n{wish} {closing}\n{signer}"),
+            language: if portuguese { "pt-BR" } else { "en" }.to_string(),
+        }
+    }
+}
+
+/// Whether an event with the same category already has the contact as an attendee.
+fn tracked_elsewhere(calendar: &EventCalendar, event: &CalendarEvent) -> bool {
+    calendar.events.iter().any(|existing| {
+        existing.category == event.category
+            && existing.attendees.iter().any(|attendee| {
+                attendee.contact_id.is_some()
+                    && attendee.contact_id == event.attendees[0].contact_id
+            })
+    })
+}
+
+fn ordinal(n: u32) -> String {
+    let suffix = match (n % 10, n % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    format!("{n}{suffix}")
+}
+
+const DEFAULT_HORIZON_DAYS: i64 = 30;
+const DEFAULT_LEAD_DAYS: i64 = 1;
+const DEFAULT_LANGUAGE: &str = "en";
+const MINUTES_PER_DAY: i64 = 24 * 60;
+/// The `birthday` category color in the spec calendar settings.
+const OCCASION_COLOR: &str = "#FF69B4";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infra::contacts::{ContactEmail, ContactLabel};
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn contacts() -> Vec<Contact> {
+        vec![
+            Contact::new("c001", "Eva", "Green").with_birthday("1990-06-15"),
+            Contact::new("c002", "Leap", "Day").with_birthday("1980-02-29"),
+            Contact::new("c003", "Noah", "Park")
+                .with_birthday("--06-20")
+                .with_anniversary("2015-06-10"),
+            Contact::new("c004", "No", "Date"),
+            Contact::new("c005", "Bad", "Date").with_birthday("sometime in May"),
+        ]
+    }
+
+    #[test]
+    fn test_upcoming_sorts_within_the_horizon() {
+        let planner = OccasionPlanner::new().with_horizon_days(14);
+        let upcoming = planner.upcoming(&contacts(), date(2025, 6, 8));
+
+        let summary: Vec<(&str, OccasionKind, i64, Option<u32>)> = upcoming
+            .iter()
+            .map(|o| (o.contact_id.as_str(), o.kind, o.days_until, o.turning))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("c003", OccasionKind::Anniversary, 2, Some(10)),
+                ("c001", OccasionKind::Birthday, 7, Some(35)),
+                ("c003", OccasionKind::Birthday, 12, None),
+            ]
+        );
+        assert_eq!(upcoming[0].remind_on, date(2025, 6, 9));
+    }
+
+    #[test]
+    fn test_leap_day_birthdays_follow_the_policy() {
+        let today = date(2025, 2, 20);
+        let default = OccasionPlanner::new().upcoming(&contacts(), today);
+        assert_eq!(default[0].date, date(2025, 2, 28));
+        assert_eq!(default[0].turning, Some(45));
+
+        let march = OccasionPlanner::new()
+            .with_leap_day_policy(LeapDayPolicy::March1)
+            .upcoming(&contacts(), today);
+        assert_eq!(march[0].date, date(2025, 3, 1));
+    }
+
+    #[test]
+    fn test_due_reminders_honour_lead_days() {
+        let planner = OccasionPlanner::new().with_lead_days(3);
+        let due = planner.due_reminders(&contacts(), date(2025, 6, 12));
+        let ids: Vec<&str> = due.iter().map(|o| o.contact_id.as_str()).collect();
+        assert_eq!(ids, vec!["c001"]);
+        assert!(
+            planner
+                .due_reminders(&contacts(), date(2025, 6, 11))
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_calendar_event_matches_the_spec_birthday_style() {
+        let planner =
+            OccasionPlanner::new().with_utc_offset(FixedOffset::west_opt(4 * 3600).unwrap());
+        let occasion = &planner.occasions_of(&contacts()[0], date(2025, 6, 1))[0];
+        let event = planner.calendar_event(occasion);
+
+        assert_eq!(event.id, "birthday_c001");
+        assert_eq!(event.title, "Birthday - Eva Green");
+        assert_eq!(event.start_date_time, "2025-06-15T00:00:00-04:00");
+        assert_eq!(event.end_date_time, "2025-06-15T23:59:59-04:00");
+        assert_eq!(event.attendees[0].attendee_type, "birthday");
+        assert_eq!(event.recurrence, Some(EventRecurrence::yearly()));
+        assert_eq!(event.reminders, vec![EventReminder::notification(1440)]);
+        assert!(event.all_day);
+    }
+
+    #[test]
+    fn test_materialize_is_idempotent() {
+        let planner = OccasionPlanner::new();
+        let mut calendar = EventCalendar::default();
+        assert_eq!(
+            planner.materialize(&mut calendar, &contacts(), date(2025, 6, 1)),
+            4
+        );
+        assert_eq!(
+            planner.materialize(&mut calendar, &contacts(), date(2025, 6, 1)),
+            0
+        );
+        assert_eq!(calendar.events.len(), 4);
+    }
+
+    #[test]
+    fn test_greeting_addresses_the_primary_email() {
+        let mut contact = contacts().remove(0);
+        contact
+            .emails
+            .push(ContactEmail::new(ContactLabel::Personal, "eva@example.com"));
+        let planner = OccasionPlanner::new();
+        let occasion = &planner.occasions_of(&contact, date(2025, 6, 14))[0];
+
+        let draft = planner.greeting(occasion, &contact, "Alice");
+        assert_eq!(draft.to.as_deref(), Some("eva@example.com"));
+        assert_eq!(draft.subject, "Happy birthday, Eva!");
+        assert!(draft.body.starts_with("Hi Eva,\n\nHappy 35th birthday!"));
+        assert!(draft.body.ends_with("Alice"));
+
+        let draft = planner
+            .with_language("pt-BR")
+            .greeting(occasion, &contact, "Alice");
+        assert_eq!(draft.subject, "Feliz aniversário, Eva!");
+        assert_eq!(draft.language, "pt-BR");
+    }
+
+    #[test]
+    fn test_ordinal_suffixes() {
+        let suffixes: Vec<String> = [1, 2, 3, 4, 11, 12, 13, 21, 22, 101, 111]
+            .into_iter()
+            .map(ordinal)
+            .collect();
+        assert_eq!(
+            suffixes,
+            vec![
+                "1st", "2nd", "3rd", "4th", "11th", "12th", "13th", "21st", "22nd", "101st",
+                "111th"
+            ]
+        );
+    }
+}
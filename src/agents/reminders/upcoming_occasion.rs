@@ -0,0 +1,18 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use super::occasion_kind::OccasionKind;
+
+/// The next birthday or anniversary of a contact.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct UpcomingOccasion {
+    pub contact_id: String,
+    pub name: String,
+    pub kind: OccasionKind,
+    pub date: NaiveDate,
+    pub days_until: i64,
+    /// Age or years of marriage reached on `date`; `None` when the contact date has no year.
+    pub turning: Option<u32>,
+    /// Day the reminder fires, `lead_days` before `date`.
+    pub remind_on: NaiveDate,
+}
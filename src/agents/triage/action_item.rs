@@ -0,0 +1,24 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+/// Something the user has to do because of an email.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ActionItem {
+    pub description: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub due: Option<NaiveDate>,
+}
+
+impl ActionItem {
+    pub fn new(description: &str) -> Self {
+        Self {
+            description: description.to_string(),
+            due: None,
+        }
+    }
+
+    pub fn with_due(mut self, due: NaiveDate) -> Self {
+        self.due = Some(due);
+        self
+    }
+}
@@ -0,0 +1,15 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+/// A task suggested from an email's action items, for the user to accept or drop.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CandidateTask {
+    pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub due: Option<NaiveDate>,
+    /// The sender's address.
+    pub from: String,
+    /// `Message-ID` of the email the task came from.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message_id: Option<String>,
+}
@@ -0,0 +1,18 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+/// A date an email mentions as a limit, such as "registration closes on May 3".
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Deadline {
+    pub description: String,
+    pub date: NaiveDate,
+}
+
+impl Deadline {
+    pub fn new(description: &str, date: NaiveDate) -> Self {
+        Self {
+            description: description.to_string(),
+            date,
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+/// The meeting a `meeting_request` email proposes. Times are in the sender's time zone.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct MeetingProposal {
+    pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start: Option<NaiveDateTime>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end: Option<NaiveDateTime>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+}
+
+impl MeetingProposal {
+    pub fn new(title: &str) -> Self {
+        Self {
+            title: title.to_string(),
+            start: None,
+            end: None,
+            location: None,
+        }
+    }
+
+    pub fn with_start(mut self, start: NaiveDateTime) -> Self {
+        self.start = Some(start);
+        self
+    }
+
+    pub fn with_end(mut self, end: NaiveDateTime) -> Self {
+        self.end = Some(end);
+        self
+    }
+
+    pub fn with_location(mut self, location: &str) -> Self {
+        self.location = Some(location.to_string());
+        self
+    }
+}
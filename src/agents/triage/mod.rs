@@ -0,0 +1,19 @@
+pub mod action_item;
+pub mod candidate_task;
+pub mod deadline;
+pub mod meeting_proposal;
+pub mod triage_agent;
+pub mod triage_category;
+pub mod triage_result;
+pub mod triage_run;
+
+pub use action_item::ActionItem;
+pub use candidate_task::CandidateTask;
+pub use deadline::Deadline;
+pub use meeting_proposal::MeetingProposal;
+pub use triage_agent::{
+    TriageAgent, TriageParam, build_triage_prompts, heuristic_category, parse_triage,
+};
+pub use triage_category::TriageCategory;
+pub use triage_result::TriageResult;
+pub use triage_run::TriageRun;
@@ -0,0 +1,523 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use ollama_oxide::ChatMessage;
+
+use crate::{
+    agents::{
+        Agent, AgentError, AgentParam,
+        assistant::build_assistant_name,
+        context::{ContextBudget, PromptSection, SectionPriority, join_message},
+        email::email_agent::{extract_json, language_name},
+        prompts::{PromptLibrary, PromptSet, prompt_library::DEFAULT_LANGUAGE},
+        triage::{ActionItem, Deadline, MeetingProposal, TriageCategory, TriageResult, TriageRun},
+    },
+    config::Config,
+    infra::{
+        assistant_ollama_client::AssistantOllamaClient,
+        email::{IncomingEmail, MailSource},
+    },
+};
+
+/// Reads incoming email for the user through the assistant model: each message
+/// is classified ([`TriageCategory`]), summarised, and its action items,
+/// deadlines and proposed meeting extracted into a [`TriageResult`].
+///
+/// Messages the receiving server flagged as spam never reach the model.
+#[derive(Debug, Default)]
+pub struct TriageAgent {}
+
+impl TriageAgent {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    /// Triages every new message of `source`. A message the model could not
+    /// triage is still returned, categorised from its headers, with the reason
+    /// in [`TriageResult::error`]; messages that could not be parsed are listed
+    /// in [`TriageRun::unreadable`].
+    pub async fn triage_source(
+        &self,
+        source: &mut impl MailSource,
+    ) -> Result<TriageRun, AgentError> {
+        let batch = source
+            .fetch()
+            .map_err(|e| AgentError::ProcessingError(format!("Reading mail failed: {e}")))?;
+        let mut results = Vec::new();
+        for email in batch.emails {
+            let result = match self.process(TriageParam::new(email.clone())).await {
+                Ok(result) => result,
+                Err(e) => {
+                    TriageResult::new(email.clone(), heuristic_category(&email), &email.subject)
+                        .with_error(&e.to_string())
+                }
+            };
+            results.push(result);
+        }
+        Ok(TriageRun {
+            results,
+            unreadable: batch.unreadable,
+        })
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct TriageParam {
+    email: IncomingEmail,
+    language: Option<String>,
+    user: Option<String>,
+    assistant: Option<String>,
+}
+
+impl TriageParam {
+    pub fn new(email: IncomingEmail) -> Self {
+        Self {
+            email,
+            ..Self::default()
+        }
+    }
+
+    /// Language of the summary; defaults to the prompt library language.
+    pub fn with_language(mut self, language: &str) -> Self {
+        self.language = Some(language.to_string());
+        self
+    }
+
+    /// Whose mail it is; defaults to the user's name from `[user.settings]`.
+    pub fn with_user(mut self, user: &str) -> Self {
+        self.user = Some(user.to_string());
+        self
+    }
+
+    /// Defaults to the assistant from `[user.settings]`.
+    pub fn with_assistant(mut self, assistant: &str) -> Self {
+        self.assistant = Some(assistant.to_string());
+        self
+    }
+
+    pub fn email(&self) -> &IncomingEmail {
+        &self.email
+    }
+
+    pub fn language(&self) -> Option<&str> {
+        self.language.as_deref()
+    }
+
+    pub fn user(&self) -> Option<&str> {
+        self.user.as_deref()
+    }
+
+    pub fn assistant(&self) -> Option<&str> {
+        self.assistant.as_deref()
+    }
+}
+
+impl AgentParam for TriageParam {}
+
+impl Agent<TriageParam, TriageResult> for TriageAgent {
+    fn process(
+        &self,
+        input: TriageParam,
+    ) -> impl std::future::Future<Output = Result<TriageResult, AgentError>> + Send {
+        async move {
+            if input.email().spam_flag {
+                let summary = input.email().subject.clone();
+                return Ok(TriageResult::new(
+                    input.email,
+                    TriageCategory::Spam,
+                    &summary,
+                ));
+            }
+            let input = with_user_defaults(input);
+            let language = input.language().unwrap_or(DEFAULT_LANGUAGE);
+            let prompts = PromptLibrary::get().prompt_set_in(EMAIL_TRIAGE_PROMPTS, language);
+            let model = build_assistant_name(input.assistant().unwrap_or_default());
+
+            let client = AssistantOllamaClient::new();
+            let budgeted = ContextBudget::for_model(&client, &model)
+                .await
+                .fit(build_triage_sections(&prompts, &input))
+                .map_err(|e| AgentError::ProcessingError(format!("Triage failed: {e}")))?;
+            let response = client
+                .send_chat_message(
+                    vec![
+                        ChatMessage::system(budgeted.message(SYSTEM_MESSAGE, SPACE)),
+                        ChatMessage::user(budgeted.message(USER_MESSAGE, SPACE)),
+                    ],
+                    &model,
+                )
+                .await
+                .map_err(|e| AgentError::NetworkError(format!("Triage failed: {e}")))?;
+            let content = response
+                .message
+                .and_then(|message| message.content().map(str::to_string))
+                .ok_or_else(|| {
+                    AgentError::ParseError("Triage failed: empty response".to_string())
+                })?;
+
+            Ok(parse_triage(&content, input.email)?.with_prompt_version(prompts.version()))
+        }
+    }
+}
+
+fn with_user_defaults(mut input: TriageParam) -> TriageParam {
+    let config = Config::get();
+    input.user = input.user.or(Some(config.user.settings.name.clone()));
+    input.assistant = input
+        .assistant
+        .or(Some(config.user.settings.assistant.clone()));
+    input.language = input
+        .language
+        .or(Some(PromptLibrary::get().language().to_string()));
+    input
+}
+
+/// The category an email gets without the model: spam when the server flagged
+/// it, a meeting request when it carries an invitation (`.ics`), FYI for
+/// mailing lists, and otherwise "needs reply" when it asks a question.
+pub fn heuristic_category(email: &IncomingEmail) -> TriageCategory {
+    if email.spam_flag {
+        TriageCategory::Spam
+    } else if email
+        .attachments
+        .iter()
+        .any(|name| name.to_lowercase().ends_with(".ics"))
+    {
+        TriageCategory::MeetingRequest
+    } else if email.list_id.is_some() {
+        TriageCategory::Fyi
+    } else if email.text_body.contains('?') || email.subject.contains('?') {
+        TriageCategory::NeedsReply
+    } else {
+        TriageCategory::Fyi
+    }
+}
+
+/// System and user prompts for triaging `input`, as sent to the model when
+/// nothing has to be trimmed.
+pub fn build_triage_prompts(prompts: &PromptSet, input: &TriageParam) -> (String, String) {
+    let sections = build_triage_sections(prompts, input);
+    (
+        join_message(&sections, SYSTEM_MESSAGE, SPACE),
+        join_message(&sections, USER_MESSAGE, SPACE),
+    )
+}
+
+/// Splits the triage prompt into budgetable sections: only the attachment
+/// names can be trimmed; the body is already cut at `MAX_BODY_CHARS`.
+fn build_triage_sections(prompts: &PromptSet, input: &TriageParam) -> Vec<PromptSection> {
+    let user = input.user().unwrap_or_default();
+    let language = language_name(input.language().unwrap_or(DEFAULT_LANGUAGE));
+    let email = input.email();
+    let system = |name: &str, content: &str| {
+        PromptSection::required(name, content).in_message(SYSTEM_MESSAGE)
+    };
+    let message =
+        |name: &str, content: &str| PromptSection::required(name, content).in_message(USER_MESSAGE);
+
+    let mut sections = vec![
+        system(
+            "you_are",
+            &prompts.section("you_are", YOU_ARE).replace("{}", user),
+        ),
+        system(
+            "output_format",
+            prompts.section("output_format", OUTPUT_FORMAT),
+        ),
+        system("categories", prompts.section("categories", CATEGORIES)),
+        system("dates", prompts.section("dates", DATES)),
+        system(
+            "language",
+            &prompts
+                .section("language", LANGUAGE)
+                .replace("{}", language),
+        ),
+        system("task", &prompts.section("task", TASK).replace("{}", user)),
+    ];
+
+    let from = email
+        .from
+        .as_ref()
+        .map(ToString::to_string)
+        .unwrap_or_default();
+    let sent = email
+        .date
+        .map(|date| date.format("%Y-%m-%d %H:%M (%A) %:z").to_string())
+        .unwrap_or_default();
+    let body: String = email.text_body.chars().take(MAX_BODY_CHARS).collect();
+    sections.push(message(
+        "from",
+        &prompts.section("from", FROM).replace("{}", &from),
+    ));
+    sections.push(message(
+        "subject",
+        &prompts
+            .section("subject", SUBJECT)
+            .replace("{}", &email.subject),
+    ));
+    if !sent.is_empty() {
+        sections.push(message(
+            "sent",
+            &prompts.section("sent", SENT).replace("{}", &sent),
+        ));
+    }
+    if !email.attachments.is_empty() {
+        sections.push(
+            PromptSection::new(
+                "attachments",
+                &prompts
+                    .section("attachments", ATTACHMENTS)
+                    .replace("{}", &email.attachments.join(", ")),
+                SectionPriority::Low,
+            )
+            .in_message(USER_MESSAGE),
+        );
+    }
+    sections.push(message(
+        "body",
+        &prompts.section("body", BODY).replace("{}", &body),
+    ));
+    sections.push(message("output", prompts.section("output", OUTPUT)));
+    sections
+}
+
+/// Reads the model's answer about `email`. Dates that cannot be read are
+/// dropped: an action item keeps its description without a due date, a
+/// deadline without a date is left out.
+pub fn parse_triage(content: &str, email: IncomingEmail) -> Result<TriageResult, AgentError> {
+    let json = extract_json(content).ok_or_else(|| {
+        AgentError::ParseError(format!("Triage failed: no JSON in response: {content}"))
+    })?;
+    let value: serde_json::Value = serde_json::from_str(json)
+        .map_err(|e| AgentError::ParseError(format!("Triage failed: {e}")))?;
+    let category = value
+        .get("category")
+        .and_then(|v| v.as_str())
+        .unwrap_or_default()
+        .parse::<TriageCategory>()?;
+    let summary = text(&value, "summary");
+    let summary = if summary.is_empty() {
+        email.subject.clone()
+    } else {
+        summary
+    };
+
+    let entries = |key: &str| {
+        value
+            .get(key)
+            .and_then(|v| v.as_array())
+            .cloned()
+            .unwrap_or_default()
+    };
+    let action_items = entries("action_items")
+        .iter()
+        .filter_map(|item| {
+            let description = text(item, "description");
+            if description.is_empty() {
+                return None;
+            }
+            let action = ActionItem::new(&description);
+            Some(match date(&text(item, "due")) {
+                Some(due) => action.with_due(due),
+                None => action,
+            })
+        })
+        .collect();
+    let deadlines = entries("deadlines")
+        .iter()
+        .filter_map(|item| {
+            let description = text(item, "description");
+            let date = date(&text(item, "date"))?;
+            (!description.is_empty()).then(|| Deadline::new(&description, date))
+        })
+        .collect();
+
+    let mut result = TriageResult::new(email, category, &summary)
+        .with_action_items(action_items)
+        .with_deadlines(deadlines);
+    if let Some(meeting) = value.get("meeting").filter(|m| m.is_object()) {
+        let start = date_time(&text(meeting, "start"));
+        let title = text(meeting, "title");
+        if start.is_some() || !title.is_empty() {
+            let title = if title.is_empty() {
+                result.email.subject.clone()
+            } else {
+                title
+            };
+            let mut proposal = MeetingProposal::new(&title);
+            proposal.start = start;
+            proposal.end = date_time(&text(meeting, "end"));
+            let location = text(meeting, "location");
+            if !location.is_empty() {
+                proposal = proposal.with_location(&location);
+            }
+            result = result.with_meeting(proposal);
+        }
+    }
+    Ok(result)
+}
+
+fn text(value: &serde_json::Value, key: &str) -> String {
+    value
+        .get(key)
+        .and_then(|v| v.as_str())
+        .map(str::trim)
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// `YYYY-MM-DD`, also when followed by a time.
+fn date(value: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(value.get(..10)?, "%Y-%m-%d").ok()
+}
+
+/// `YYYY-MM-DDTHH:MM`, with or without seconds; a space also separates date and time.
+fn date_time(value: &str) -> Option<NaiveDateTime> {
+    let value = value.get(..16)?.replace(' ', "T");
+    NaiveDateTime::parse_from_str(&value, "%Y-%m-%dT%H:%M").ok()
+}
+
+/// Prompt set name in the prompt library; the constants below are the fallback.
+pub const EMAIL_TRIAGE_PROMPTS: &str = "email_triage";
+
+/// Longer bodies are cut, keeping the prompt within small context windows.
+const MAX_BODY_CHARS: usize = 4000;
+const SYSTEM_MESSAGE: &str = "system";
+const USER_MESSAGE: &str = "user";
+const SPACE: &str = "        ";
+const YOU_ARE: &str = "You are an assistant that triages the incoming email of {}. Your response MUST be a JSON object. You will never use markdown notation in outputs.";
+const OUTPUT_FORMAT: &str = "Output-Format: {\"category\":\"\",\"summary\":\"\",\"action_items\":[{\"description\":\"\",\"due\":\"\"}],\"deadlines\":[{\"description\":\"\",\"date\":\"\"}],\"meeting\":{\"title\":\"\",\"start\":\"\",\"end\":\"\",\"location\":\"\"}}";
+const CATEGORIES: &str = "Categories: needs_reply (the sender expects an answer or a decision), fyi (information only: newsletters, notifications, receipts), meeting_request (an invitation or a proposal to meet at a given time), spam (unsolicited advertising, phishing).";
+const DATES: &str = "Write dates as YYYY-MM-DD and times as YYYY-MM-DDTHH:MM in the sender's time zone. Resolve relative dates such as \"next Friday\" from the date the email was sent. Leave due empty when there is none.";
+const LANGUAGE: &str = "Write the summary and the descriptions in {}.";
+const TASK: &str = "Task: Classify the email, summarise it in one or two sentences, list what {} has to do and every deadline it mentions. Fill meeting only for meeting_request. Do not invent facts.";
+const FROM: &str = "From: \"{}\"";
+const SUBJECT: &str = "Subject: \"{}\"";
+const SENT: &str = "Sent: {}";
+const ATTACHMENTS: &str = "Attachments: {}";
+const BODY: &str = "Body: \"{}\"";
+const OUTPUT: &str = "Output: ";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{agents::context::context_budget::DEFAULT_NUM_CTX, infra::email::Mailbox};
+
+    fn email() -> IncomingEmail {
+        IncomingEmail {
+            message_id: Some("<42@example.com>".to_string()),
+            from: Some(Mailbox::new("carlos@example.com").with_name("Carlos")),
+            subject: "Budget review".to_string(),
+            date: chrono::DateTime::parse_from_rfc3339("2025-09-02T09:30:00-03:00").ok(),
+            text_body: "Can we meet on Thursday at 3pm? Please send the numbers by Friday."
+                .to_string(),
+            ..IncomingEmail::default()
+        }
+    }
+
+    #[test]
+    fn test_build_triage_prompts() {
+        let param = TriageParam::new(email())
+            .with_user("Ana")
+            .with_language("pt-BR");
+
+        let (system, user) =
+            build_triage_prompts(&PromptSet::builtin(EMAIL_TRIAGE_PROMPTS), &param);
+
+        assert!(system.starts_with("You are an assistant that triages the incoming email of Ana."));
+        assert!(system.contains("meeting_request"));
+        assert!(system.contains("Brazilian Portuguese"));
+        assert!(user.contains("From: \"Carlos <carlos@example.com>\""));
+        assert!(user.contains("Sent: 2025-09-02 09:30 (Tuesday) -03:00"));
+        assert!(!user.contains("Attachments:"));
+        assert!(user.ends_with("Output: "));
+    }
+
+    #[test]
+    fn test_triage_budget_keeps_the_body_and_fails_when_it_cannot() {
+        let mut email = email();
+        email.attachments = vec!["numbers.xlsx".to_string()];
+        let param = TriageParam::new(email).with_user("Ana");
+        let sections = build_triage_sections(&PromptSet::builtin(EMAIL_TRIAGE_PROMPTS), &param);
+        let fits = ContextBudget::new(DEFAULT_NUM_CTX)
+            .fit(sections.clone())
+            .unwrap();
+        assert!(
+            fits.message(USER_MESSAGE, SPACE)
+                .contains("Attachments: numbers.xlsx")
+        );
+        assert_eq!(fits.report().dropped, Vec::<String>::new());
+
+        let tight = ContextBudget::new(fits.estimated_tokens() - 1)
+            .with_response_reserve(0)
+            .fit(sections.clone())
+            .unwrap();
+        assert_eq!(tight.report().dropped, vec!["attachments".to_string()]);
+        assert!(
+            tight
+                .message(USER_MESSAGE, SPACE)
+                .contains("Can we meet on Thursday")
+        );
+
+        assert!(
+            ContextBudget::new(50)
+                .with_response_reserve(0)
+                .fit(sections)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_parse_triage_drops_unreadable_dates() {
+        let content = r#"{"category":"meeting_request","summary":"Carlos wants to review the budget.","action_items":[{"description":"Send the numbers","due":"2025-09-05"},{"description":"Book a room","due":"soon"},{"description":"","due":""}],"deadlines":[{"description":"Numbers due","date":"2025-09-05"},{"description":"Someday","date":""}],"meeting":{"title":"","start":"2025-09-04 15:00","end":"","location":"Room A"}}"#;
+
+        let result = parse_triage(content, email()).unwrap();
+
+        assert_eq!(result.category, TriageCategory::MeetingRequest);
+        assert_eq!(result.action_items.len(), 2);
+        assert_eq!(
+            result.action_items[0].due,
+            NaiveDate::from_ymd_opt(2025, 9, 5)
+        );
+        assert_eq!(result.action_items[1].due, None);
+        assert_eq!(result.deadlines.len(), 1);
+        let meeting = result.meeting.unwrap();
+        assert_eq!(meeting.title, "Budget review");
+        assert_eq!(meeting.start.unwrap().to_string(), "2025-09-04 15:00:00");
+        assert_eq!(meeting.location.as_deref(), Some("Room A"));
+        assert!(parse_triage(r#"{"category":"urgent"}"#, email()).is_err());
+        assert!(parse_triage("no json", email()).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_flagged_spam_skips_the_model() {
+        let spam = IncomingEmail {
+            spam_flag: true,
+            ..email()
+        };
+
+        let result = TriageAgent::new()
+            .process(TriageParam::new(spam))
+            .await
+            .unwrap();
+
+        assert_eq!(result.category, TriageCategory::Spam);
+        assert!(result.candidate_tasks().is_empty());
+    }
+
+    #[test]
+    fn test_heuristic_category() {
+        assert_eq!(heuristic_category(&email()), TriageCategory::NeedsReply);
+        let invitation = IncomingEmail {
+            attachments: vec!["invite.ics".to_string()],
+            ..email()
+        };
+        assert_eq!(
+            heuristic_category(&invitation),
+            TriageCategory::MeetingRequest
+        );
+        let newsletter = IncomingEmail {
+            list_id: Some("<news.example.com>".to_string()),
+            ..email()
+        };
+        assert_eq!(heuristic_category(&newsletter), TriageCategory::Fyi);
+    }
+}
@@ -0,0 +1,90 @@
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+use crate::agents::AgentError;
+
+/// What an incoming email asks of the user.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum TriageCategory {
+    /// The sender expects an answer or a decision.
+    NeedsReply,
+    /// Information only: newsletters, notifications, receipts.
+    Fyi,
+    /// An invitation or a proposal to meet at a given time.
+    MeetingRequest,
+    Spam,
+}
+
+impl TriageCategory {
+    pub fn to_str(&self) -> &str {
+        match self {
+            Self::NeedsReply => NEEDS_REPLY,
+            Self::Fyi => FYI,
+            Self::MeetingRequest => MEETING_REQUEST,
+            Self::Spam => SPAM,
+        }
+    }
+}
+
+impl FromStr for TriageCategory {
+    type Err = AgentError;
+
+    /// Also accepts the spellings models tend to use, such as `FYI` or `meeting request`.
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input
+            .trim()
+            .to_lowercase()
+            .replace([' ', '-'], "_")
+            .as_str()
+        {
+            NEEDS_REPLY => Ok(Self::NeedsReply),
+            FYI => Ok(Self::Fyi),
+            MEETING_REQUEST => Ok(Self::MeetingRequest),
+            SPAM => Ok(Self::Spam),
+            other => Err(AgentError::ParseError(format!(
+                "Unknown triage category: {other}"
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for TriageCategory {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_str())
+    }
+}
+
+const NEEDS_REPLY: &str = "needs_reply";
+const FYI: &str = "fyi";
+const MEETING_REQUEST: &str = "meeting_request";
+const SPAM: &str = "spam";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        for category in [
+            TriageCategory::NeedsReply,
+            TriageCategory::Fyi,
+            TriageCategory::MeetingRequest,
+            TriageCategory::Spam,
+        ] {
+            assert_eq!(
+                TriageCategory::from_str(category.to_str()).unwrap(),
+                category
+            );
+        }
+        assert_eq!(
+            TriageCategory::from_str("Meeting request").unwrap(),
+            TriageCategory::MeetingRequest
+        );
+        assert_eq!(
+            TriageCategory::from_str("FYI").unwrap(),
+            TriageCategory::Fyi
+        );
+        assert!(TriageCategory::from_str("urgent").is_err());
+    }
+}
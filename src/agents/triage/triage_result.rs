@@ -0,0 +1,204 @@
+use std::collections::BTreeMap;
+
+use chrono::{FixedOffset, NaiveDateTime, TimeDelta};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    agents::AgentResult,
+    infra::{
+        calendar::{CalendarEvent, EventAttendee, EventReminder},
+        email::IncomingEmail,
+    },
+};
+
+use super::{
+    action_item::ActionItem, candidate_task::CandidateTask, deadline::Deadline,
+    meeting_proposal::MeetingProposal, triage_category::TriageCategory,
+};
+
+/// The triage of one incoming email. Nothing is written anywhere: the
+/// [`candidate_tasks`](Self::candidate_tasks) and
+/// [`candidate_events`](Self::candidate_events) are suggestions for the user.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct TriageResult {
+    pub email: IncomingEmail,
+    pub category: TriageCategory,
+    pub summary: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub action_items: Vec<ActionItem>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub deadlines: Vec<Deadline>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub meeting: Option<MeetingProposal>,
+    /// Why the model could not triage the email; the category then comes from
+    /// the headers (see [`heuristic_category`](super::heuristic_category)).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt_version: Option<String>,
+}
+
+impl TriageResult {
+    pub fn new(email: IncomingEmail, category: TriageCategory, summary: &str) -> Self {
+        Self {
+            email,
+            category,
+            summary: summary.to_string(),
+            action_items: Vec::new(),
+            deadlines: Vec::new(),
+            meeting: None,
+            error: None,
+            prompt_version: None,
+        }
+    }
+
+    pub fn with_action_items(mut self, action_items: Vec<ActionItem>) -> Self {
+        self.action_items = action_items;
+        self
+    }
+
+    pub fn with_deadlines(mut self, deadlines: Vec<Deadline>) -> Self {
+        self.deadlines = deadlines;
+        self
+    }
+
+    pub fn with_meeting(mut self, meeting: MeetingProposal) -> Self {
+        self.meeting = Some(meeting);
+        self
+    }
+
+    pub fn with_error(mut self, error: &str) -> Self {
+        self.error = Some(error.to_string());
+        self
+    }
+
+    pub fn with_prompt_version(mut self, version: &str) -> Self {
+        self.prompt_version = Some(version.to_string());
+        self
+    }
+
+    /// A task per action item; none for spam.
+    pub fn candidate_tasks(&self) -> Vec<CandidateTask> {
+        if self.category == TriageCategory::Spam {
+            return Vec::new();
+        }
+        self.action_items
+            .iter()
+            .map(|item| CandidateTask {
+                title: item.description.clone(),
+                due: item.due,
+                from: self.email.sender().to_string(),
+                message_id: self.email.message_id.clone(),
+            })
+            .collect()
+    }
+
+    /// A tentative entry for the proposed meeting, when it has a start time,
+    /// and an all-day entry per deadline; none for spam. Ids are derived from
+    /// the `Message-ID`, so triaging the same email again replaces its entries.
+    pub fn candidate_events(&self) -> Vec<CalendarEvent> {
+        if self.category == TriageCategory::Spam {
+            return Vec::new();
+        }
+        let offset = self
+            .email
+            .date
+            .map(|date| *date.offset())
+            .unwrap_or_else(|| FixedOffset::east_opt(0).expect("UTC is a valid offset"));
+        let id = event_id_prefix(&self.email);
+        let mut events = Vec::new();
+
+        if let Some(meeting) = &self.meeting
+            && let Some(start) = meeting.start
+        {
+            let end = meeting
+                .end
+                .filter(|end| *end > start)
+                .unwrap_or(start + TimeDelta::minutes(DEFAULT_MEETING_MINUTES));
+            let mut extra = BTreeMap::new();
+            if let Some(location) = &meeting.location {
+                extra.insert(
+                    "location".to_string(),
+                    serde_json::json!({ "name": location }),
+                );
+            }
+            events.push(CalendarEvent {
+                id: format!("{id}_meeting"),
+                title: meeting.title.clone(),
+                description: Some(self.summary.clone()),
+                start_date_time: timestamp(start, offset),
+                end_date_time: timestamp(end, offset),
+                attendees: self
+                    .email
+                    .from
+                    .iter()
+                    .map(|from| EventAttendee {
+                        contact_id: None,
+                        name: from.name.clone().unwrap_or_else(|| from.address.clone()),
+                        email: Some(from.address.clone()),
+                        status: "accepted".to_string(),
+                        attendee_type: "organizer".to_string(),
+                    })
+                    .collect(),
+                category: EVENT_CATEGORY.to_string(),
+                priority: "medium".to_string(),
+                status: "tentative".to_string(),
+                all_day: false,
+                recurrence: None,
+                reminders: vec![EventReminder::notification(MEETING_REMINDER_MINUTES)],
+                color: None,
+                created_at: None,
+                updated_at: None,
+                extra,
+            });
+        }
+
+        for (n, deadline) in self.deadlines.iter().enumerate() {
+            let day = deadline.date.format("%Y-%m-%d");
+            events.push(CalendarEvent {
+                id: format!("{id}_deadline_{}", n + 1),
+                title: deadline.description.clone(),
+                description: Some(format!("{} ({})", self.email.subject, self.email.sender())),
+                start_date_time: format!("{day}T00:00:00{offset}"),
+                end_date_time: format!("{day}T23:59:59{offset}"),
+                attendees: Vec::new(),
+                category: EVENT_CATEGORY.to_string(),
+                priority: "high".to_string(),
+                status: "tentative".to_string(),
+                all_day: true,
+                recurrence: None,
+                reminders: vec![EventReminder::notification(DEADLINE_REMINDER_MINUTES)],
+                color: None,
+                created_at: None,
+                updated_at: None,
+                extra: BTreeMap::new(),
+            });
+        }
+        events
+    }
+}
+
+impl AgentResult for TriageResult {}
+
+fn timestamp(time: NaiveDateTime, offset: FixedOffset) -> String {
+    format!("{}{offset}", time.format("%Y-%m-%dT%H:%M:%S"))
+}
+
+/// `email_` followed by the `Message-ID` reduced to letters, digits and `_`.
+fn event_id_prefix(email: &IncomingEmail) -> String {
+    let source = email
+        .message_id
+        .clone()
+        .unwrap_or_else(|| format!("{} {}", email.sender(), email.subject));
+    let id: String = source
+        .trim_matches(|c| c == '<' || c == '>')
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    format!("email_{id}")
+}
+
+const EVENT_CATEGORY: &str = "work";
+const DEFAULT_MEETING_MINUTES: i64 = 60;
+const MEETING_REMINDER_MINUTES: u32 = 30;
+const DEADLINE_REMINDER_MINUTES: u32 = 1440;
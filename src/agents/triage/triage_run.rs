@@ -0,0 +1,11 @@
+use crate::infra::email::UnreadableMail;
+
+use super::triage_result::TriageResult;
+
+/// What [`TriageAgent::triage_source`](super::TriageAgent::triage_source) did
+/// with one fetch: a result per message, and the messages it could not read.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TriageRun {
+    pub results: Vec<TriageResult>,
+    pub unreadable: Vec<UnreadableMail>,
+}
@@ -1,6 +1,6 @@
 use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
-use std::fs;
+use std::{collections::BTreeMap, fs};
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
 pub struct Config {
@@ -8,6 +8,10 @@ pub struct Config {
     pub user: UserConfig,
     pub ollama: OllamaConfig,
     pub assistant: AssistantConfig,
+    #[serde(default)]
+    pub prompts: PromptsConfig,
+    #[serde(default)]
+    pub email: EmailConfig,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
@@ -24,6 +28,40 @@ pub struct UserConfig {
 pub struct UserSettings {
     pub name: String,
     pub assistant: String,
+    /// `[[user.settings.identities]]`: the addresses the user sends from.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub identities: Vec<IdentityConfig>,
+}
+
+/// A sender identity: who an email is from, how it is signed and which SMTP
+/// account sends it. Replies go out from the identity the original was sent
+/// to; otherwise its rules choose it, in order: `recipient_domains`,
+/// `keywords`, then `context` (`work` or `personal`, as inferred from the
+/// request); otherwise the one marked `default`, otherwise the first.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+#[serde(default)]
+pub struct IdentityConfig {
+    /// How the identity is referred to, e.g. `work`.
+    pub name: String,
+    pub display_name: String,
+    pub address: String,
+    /// Name of an `[email.accounts.<name>]` section.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub smtp_account: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signature_html: Option<String>,
+    /// Language of drafts sent from this identity, e.g. `pt-BR`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub context: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub recipient_domains: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub keywords: Vec<String>,
+    pub default: bool,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
@@ -45,11 +83,16 @@ pub struct ApiConfig {
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
 pub struct ApiOptions {
     pub temperature: f32,
+    /// Context window used when the model's `show` response does not report one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub num_ctx: Option<usize>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
 pub struct AssistantConfig {
     pub root: AssistantRootConfig,
+    #[serde(default)]
+    pub persona: PersonaConfig,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
@@ -57,6 +100,122 @@ pub struct AssistantRootConfig {
     pub name: String,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct PromptsConfig {
+    pub path: String,
+    pub language: String,
+}
+
+/// Optional `[assistant.persona]` overrides applied on top of the selected preset.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+#[serde(default)]
+pub struct PersonaConfig {
+    pub preset: Option<String>,
+    pub domain: Option<String>,
+    pub tone: Option<String>,
+    pub formality: Option<String>,
+    pub primary_language: Option<String>,
+    pub secondary_languages: Option<Vec<String>>,
+    pub capabilities: Option<Vec<String>>,
+    pub working_hours: Option<String>,
+    pub working_days: Option<String>,
+    pub timezone: Option<String>,
+    pub signature: Option<String>,
+}
+
+/// `[email]`: where sent messages go. `transport` is `outbox` (the default:
+/// `.eml` files in `path`), `maildir` (a Maildir at `path`) or `smtp`.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(default)]
+pub struct EmailConfig {
+    pub transport: String,
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub smtp: Option<SmtpConfig>,
+    /// `[email.accounts.<name>]`: further SMTP accounts, referred to by
+    /// sender identities. `[email.smtp]` stays the default account.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub accounts: BTreeMap<String, SmtpConfig>,
+    pub queue: EmailQueueConfig,
+}
+
+/// `[email.queue]`: retries of the persistent send queue and how often its
+/// worker looks for due messages.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(default)]
+pub struct EmailQueueConfig {
+    pub max_attempts: u32,
+    pub retry_after_secs: u64,
+    pub max_retry_after_secs: u64,
+    pub poll_interval_secs: u64,
+}
+
+/// `[email.smtp]`. The password is read from the environment variable named by
+/// `password_env` when set, so it can stay out of `config.toml`.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(default)]
+pub struct SmtpConfig {
+    pub host: String,
+    /// Defaults to 587 for `starttls`, 465 for `tls` and 25 for `none`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub port: Option<u16>,
+    pub security: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub password_env: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+}
+
+impl Default for EmailConfig {
+    fn default() -> Self {
+        Self {
+            transport: "outbox".to_string(),
+            path: "outbox".to_string(),
+            smtp: None,
+            accounts: BTreeMap::new(),
+            queue: EmailQueueConfig::default(),
+        }
+    }
+}
+
+impl Default for EmailQueueConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            retry_after_secs: 60,
+            max_retry_after_secs: 3600,
+            poll_interval_secs: 30,
+        }
+    }
+}
+
+impl Default for SmtpConfig {
+    fn default() -> Self {
+        Self {
+            host: "localhost".to_string(),
+            port: None,
+            security: "starttls".to_string(),
+            username: None,
+            password: None,
+            password_env: None,
+            timeout_secs: None,
+        }
+    }
+}
+
+impl Default for PromptsConfig {
+    fn default() -> Self {
+        Self {
+            path: "prompts".to_string(),
+            language: "en".to_string(),
+        }
+    }
+}
+
 impl AssistantRootConfig {
     pub fn to_name(&self, named_to: &str) -> String {
         format!("{}-{}", self.name, named_to)
@@ -223,6 +382,7 @@ path = "/test/database.db"
                 settings: UserSettings {
                     name: "Test User".to_string(),
                     assistant: "Test Assistant".to_string(),
+                    identities: Vec::new(),
                 },
             },
             ollama: OllamaConfig {
@@ -233,14 +393,20 @@ path = "/test/database.db"
                     show: "/show".to_string(),
                     load: "/generate".to_string(),
                     model: "test-model".to_string(),
-                    options: ApiOptions { temperature: 0.0 },
+                    options: ApiOptions {
+                        temperature: 0.0,
+                        num_ctx: None,
+                    },
                 },
             },
             assistant: AssistantConfig {
                 root: AssistantRootConfig {
                     name: "test-prefix-".to_string(),
                 },
+                persona: PersonaConfig::default(),
             },
+            prompts: PromptsConfig::default(),
+            email: EmailConfig::default(),
         };
 
         let serialized = toml::to_string(&original_config).expect("Serialization should succeed");
@@ -439,7 +605,10 @@ name = "助理-prefix-café"
             show: "/show".to_string(),
             load: "/generate".to_string(),
             model: "test-model".to_string(),
-            options: ApiOptions { temperature: 0.0 },
+            options: ApiOptions {
+                temperature: 0.0,
+                num_ctx: None,
+            },
         };
 
         assert_eq!(api_config.url, "http://test.com/api");
@@ -461,7 +630,10 @@ name = "助理-prefix-café"
                 show: "/show".to_string(),
                 load: "/generate".to_string(),
                 model: "test-model".to_string(),
-                options: ApiOptions { temperature: 0.0 },
+                options: ApiOptions {
+                    temperature: 0.0,
+                    num_ctx: None,
+                },
             },
         };
 
@@ -484,6 +656,7 @@ name = "助理-prefix-café"
                 settings: UserSettings {
                     name: "Test User".to_string(),
                     assistant: "Test Assistant".to_string(),
+                    identities: Vec::new(),
                 },
             },
             ollama: OllamaConfig {
@@ -494,14 +667,20 @@ name = "助理-prefix-café"
                     show: "/show".to_string(),
                     load: "/generate".to_string(),
                     model: "test-model".to_string(),
-                    options: ApiOptions { temperature: 0.0 },
+                    options: ApiOptions {
+                        temperature: 0.0,
+                        num_ctx: None,
+                    },
                 },
             },
             assistant: AssistantConfig {
                 root: AssistantRootConfig {
                     name: "test-assistant-".to_string(),
                 },
+                persona: PersonaConfig::default(),
             },
+            prompts: PromptsConfig::default(),
+            email: EmailConfig::default(),
         };
 
         assert_eq!(config.database.path, "/test/db.db");
@@ -527,6 +706,7 @@ name = "助理-prefix-café"
                 settings: UserSettings {
                     name: "Debug User".to_string(),
                     assistant: "Debug Assistant".to_string(),
+                    identities: Vec::new(),
                 },
             },
             ollama: OllamaConfig {
@@ -537,14 +717,20 @@ name = "助理-prefix-café"
                     show: "/show".to_string(),
                     load: "/generate".to_string(),
                     model: "test-model".to_string(),
-                    options: ApiOptions { temperature: 0.0 },
+                    options: ApiOptions {
+                        temperature: 0.0,
+                        num_ctx: None,
+                    },
                 },
             },
             assistant: AssistantConfig {
                 root: AssistantRootConfig {
                     name: "debug-assistant-".to_string(),
                 },
+                persona: PersonaConfig::default(),
             },
+            prompts: PromptsConfig::default(),
+            email: EmailConfig::default(),
         };
 
         let debug_string = format!("{:?}", config);
@@ -568,6 +754,7 @@ name = "助理-prefix-café"
             root: AssistantRootConfig {
                 name: "custom-assistant-".to_string(),
             },
+            persona: PersonaConfig::default(),
         };
 
         assert_eq!(assistant_config.root.name, "custom-assistant-");
@@ -588,6 +775,7 @@ name = "助理-prefix-café"
             root: AssistantRootConfig {
                 name: "".to_string(),
             },
+            persona: PersonaConfig::default(),
         };
 
         assert_eq!(assistant_config.root.name, "");
@@ -599,6 +787,7 @@ name = "助理-prefix-café"
             root: AssistantRootConfig {
                 name: "special-chars_123-".to_string(),
             },
+            persona: PersonaConfig::default(),
         };
 
         assert_eq!(assistant_config.root.name, "special-chars_123-");
@@ -610,6 +799,7 @@ name = "助理-prefix-café"
             root: AssistantRootConfig {
                 name: "助理-prefix-café-".to_string(),
             },
+            persona: PersonaConfig::default(),
         };
 
         assert_eq!(assistant_config.root.name, "助理-prefix-café-");
@@ -621,6 +811,7 @@ name = "助理-prefix-café"
             root: AssistantRootConfig {
                 name: "serialization-test-".to_string(),
             },
+            persona: PersonaConfig::default(),
         };
 
         let serialized = toml::to_string(&assistant_config).expect("Serialization should succeed");
@@ -700,7 +891,10 @@ model = "test-model"
             show: "/show".to_string(),
             load: "/generate".to_string(),
             model: "qwen3:0.6b".to_string(),
-            options: ApiOptions { temperature: 0.0 },
+            options: ApiOptions {
+                temperature: 0.0,
+                num_ctx: None,
+            },
         };
 
         assert_eq!(api_config.chat_url(), "http://localhost:11434/api/chat");
@@ -715,7 +909,10 @@ model = "test-model"
             show: "/show".to_string(),
             load: "/generate".to_string(),
             model: "qwen3:0.6b".to_string(),
-            options: ApiOptions { temperature: 0.0 },
+            options: ApiOptions {
+                temperature: 0.0,
+                num_ctx: None,
+            },
         };
 
         assert_eq!(api_config.create_url(), "http://localhost:11434/api/create");
@@ -730,7 +927,10 @@ model = "test-model"
             show: "/show".to_string(),
             load: "/generate".to_string(),
             model: "qwen3:0.6b".to_string(),
-            options: ApiOptions { temperature: 0.0 },
+            options: ApiOptions {
+                temperature: 0.0,
+                num_ctx: None,
+            },
         };
 
         assert_eq!(api_config.show_url(), "http://localhost:11434/api/show");
@@ -745,7 +945,10 @@ model = "test-model"
             show: "/show".to_string(),
             load: "/generate".to_string(),
             model: "qwen3:0.6b".to_string(),
-            options: ApiOptions { temperature: 0.0 },
+            options: ApiOptions {
+                temperature: 0.0,
+                num_ctx: None,
+            },
         };
 
         assert_eq!(api_config.base_url(), "http://localhost:11434/api");
@@ -760,7 +963,10 @@ model = "test-model"
             show: "/show".to_string(),
             load: "/generate".to_string(),
             model: "qwen3:0.6b".to_string(),
-            options: ApiOptions { temperature: 0.0 },
+            options: ApiOptions {
+                temperature: 0.0,
+                num_ctx: None,
+            },
         };
 
         assert_eq!(
@@ -782,7 +988,10 @@ model = "test-model"
             show: "/show".to_string(),
             load: "/generate".to_string(),
             model: "qwen3:0.6b".to_string(),
-            options: ApiOptions { temperature: 0.0 },
+            options: ApiOptions {
+                temperature: 0.0,
+                num_ctx: None,
+            },
         };
 
         assert_eq!(api_config.chat_url(), "http://localhost:11434/api//chat");
@@ -802,7 +1011,10 @@ model = "test-model"
             show: "/show?details=true".to_string(),
             load: "/generate?details=true".to_string(),
             model: "qwen3:0.6b".to_string(),
-            options: ApiOptions { temperature: 0.0 },
+            options: ApiOptions {
+                temperature: 0.0,
+                num_ctx: None,
+            },
         };
 
         assert_eq!(
@@ -828,7 +1040,10 @@ model = "test-model"
             show: "/显示".to_string(),
             load: "/显示".to_string(),
             model: "测试模型".to_string(),
-            options: ApiOptions { temperature: 0.0 },
+            options: ApiOptions {
+                temperature: 0.0,
+                num_ctx: None,
+            },
         };
 
         assert_eq!(api_config.chat_url(), "http://localhost:11434/api/聊天");
@@ -846,7 +1061,10 @@ model = "test-model"
             show: "".to_string(),
             load: "".to_string(),
             model: "qwen3:0.6b".to_string(),
-            options: ApiOptions { temperature: 0.0 },
+            options: ApiOptions {
+                temperature: 0.0,
+                num_ctx: None,
+            },
         };
 
         assert_eq!(api_config.chat_url(), "http://localhost:11434/api");
@@ -920,6 +1138,7 @@ name = "test-assistant-"
         let user_settings = UserSettings {
             name: "Ana".to_string(),
             assistant: "Tereza".to_string(),
+            identities: Vec::new(),
         };
 
         assert_eq!(user_settings.name, "Ana");
@@ -932,6 +1151,7 @@ name = "test-assistant-"
             settings: UserSettings {
                 name: "Test User".to_string(),
                 assistant: "Test Assistant".to_string(),
+                identities: Vec::new(),
             },
         };
 
@@ -944,6 +1164,7 @@ name = "test-assistant-"
         let user_settings = UserSettings {
             name: "".to_string(),
             assistant: "".to_string(),
+            identities: Vec::new(),
         };
 
         assert_eq!(user_settings.name, "");
@@ -955,6 +1176,7 @@ name = "test-assistant-"
         let user_settings = UserSettings {
             name: "用户-María".to_string(),
             assistant: "助理-José".to_string(),
+            identities: Vec::new(),
         };
 
         assert_eq!(user_settings.name, "用户-María");
@@ -966,6 +1188,7 @@ name = "test-assistant-"
         let user_settings = UserSettings {
             name: "Serialization Test".to_string(),
             assistant: "Test Assistant".to_string(),
+            identities: Vec::new(),
         };
 
         let serialized = toml::to_string(&user_settings).expect("Serialization should succeed");
@@ -1006,4 +1229,315 @@ name = "test-assistant-"
 
         cleanup_test_file(test_path);
     }
+
+    #[test]
+    fn test_config_prompts_section_defaults_when_missing() {
+        let test_path = "test_config_no_prompts.toml";
+        let test_content = r#"
+[database]
+path = "/test/database.db"
+
+[user.settings]
+name = "Test User"
+assistant = "Test Assistant"
+
+[ollama.api]
+url = "http://localhost:8080/api"
+chat = "/chat"
+create = "/create"
+show = "/show"
+load = "/generate"
+model = "test-model"
+[ollama.api.options]
+temperature = 0
+
+[assistant.root]
+name = "test-assistant-"
+"#;
+
+        create_test_config_file(test_path, test_content).expect("Failed to create test file");
+
+        let config = Config::load_from_file(test_path).unwrap();
+        assert_eq!(config.prompts, PromptsConfig::default());
+        assert_eq!(config.prompts.path, "prompts");
+        assert_eq!(config.prompts.language, "en");
+
+        cleanup_test_file(test_path);
+    }
+
+    #[test]
+    fn test_config_prompts_section_custom_values() {
+        let test_path = "test_config_custom_prompts.toml";
+        let test_content = r#"
+[database]
+path = "/test/database.db"
+
+[user.settings]
+name = "Test User"
+assistant = "Test Assistant"
+
+[ollama.api]
+url = "http://localhost:8080/api"
+chat = "/chat"
+create = "/create"
+show = "/show"
+load = "/generate"
+model = "test-model"
+[ollama.api.options]
+temperature = 0
+
+[assistant.root]
+name = "test-assistant-"
+
+[prompts]
+path = "/opt/assistant/prompts"
+language = "pt-BR"
+"#;
+
+        create_test_config_file(test_path, test_content).expect("Failed to create test file");
+
+        let config = Config::load_from_file(test_path).unwrap();
+        assert_eq!(config.prompts.path, "/opt/assistant/prompts");
+        assert_eq!(config.prompts.language, "pt-BR");
+
+        cleanup_test_file(test_path);
+    }
+
+    #[test]
+    fn test_config_email_section_defaults_to_outbox() {
+        let test_path = "test_config_no_email.toml";
+        let test_content = r#"
+[database]
+path = "/test/database.db"
+
+[user.settings]
+name = "Test User"
+assistant = "Test Assistant"
+
+[ollama.api]
+url = "http://localhost:8080/api"
+chat = "/chat"
+create = "/create"
+show = "/show"
+load = "/generate"
+model = "test-model"
+[ollama.api.options]
+temperature = 0
+
+[assistant.root]
+name = "test-assistant-"
+"#;
+
+        create_test_config_file(test_path, test_content).expect("Failed to create test file");
+
+        let config = Config::load_from_file(test_path).unwrap();
+        assert_eq!(config.email, EmailConfig::default());
+        assert_eq!(config.email.transport, "outbox");
+        assert_eq!(config.email.smtp, None);
+
+        cleanup_test_file(test_path);
+    }
+
+    #[test]
+    fn test_config_email_smtp_section() {
+        let test_path = "test_config_email_smtp.toml";
+        let test_content = r#"
+[database]
+path = "/test/database.db"
+
+[user.settings]
+name = "Test User"
+assistant = "Test Assistant"
+
+[ollama.api]
+url = "http://localhost:8080/api"
+chat = "/chat"
+create = "/create"
+show = "/show"
+load = "/generate"
+model = "test-model"
+[ollama.api.options]
+temperature = 0
+
+[assistant.root]
+name = "test-assistant-"
+
+[email]
+transport = "smtp"
+
+[email.smtp]
+host = "smtp.example.com"
+security = "tls"
+username = "ana@example.com"
+password_env = "ASSISTANT_SMTP_PASSWORD"
+
+[email.queue]
+max_attempts = 3
+"#;
+
+        create_test_config_file(test_path, test_content).expect("Failed to create test file");
+
+        let config = Config::load_from_file(test_path).unwrap();
+        let smtp = config.email.smtp.unwrap();
+        assert_eq!(config.email.transport, "smtp");
+        assert_eq!(config.email.path, "outbox");
+        assert_eq!(smtp.host, "smtp.example.com");
+        assert_eq!(smtp.port, None);
+        assert_eq!(smtp.security, "tls");
+        assert_eq!(smtp.username.as_deref(), Some("ana@example.com"));
+        assert_eq!(smtp.password, None);
+        assert_eq!(
+            smtp.password_env.as_deref(),
+            Some("ASSISTANT_SMTP_PASSWORD")
+        );
+        assert_eq!(config.email.queue.max_attempts, 3);
+        assert_eq!(config.email.queue.retry_after_secs, 60);
+
+        cleanup_test_file(test_path);
+    }
+
+    #[test]
+    fn test_config_sender_identities_and_smtp_accounts() {
+        let test_path = "test_config_identities.toml";
+        let test_content = r#"
+[database]
+path = "/test/database.db"
+
+[user.settings]
+name = "Ana"
+assistant = "Tereza"
+
+[[user.settings.identities]]
+name = "work"
+display_name = "Ana Souza"
+address = "ana.souza@techsolutions.com"
+smtp_account = "work"
+signature = "Ana Souza\nTech Solutions"
+context = "work"
+recipient_domains = ["techsolutions.com"]
+
+[[user.settings.identities]]
+name = "personal"
+display_name = "Ana"
+address = "ana@example.com"
+language = "pt-BR"
+default = true
+
+[ollama.api]
+url = "http://localhost:8080/api"
+chat = "/chat"
+create = "/create"
+show = "/show"
+load = "/generate"
+model = "test-model"
+[ollama.api.options]
+temperature = 0
+
+[assistant.root]
+name = "test-assistant-"
+
+[email.accounts.work]
+host = "smtp.techsolutions.com"
+username = "ana.souza@techsolutions.com"
+"#;
+
+        create_test_config_file(test_path, test_content).expect("Failed to create test file");
+
+        let config = Config::load_from_file(test_path).unwrap();
+        let identities = &config.user.settings.identities;
+        assert_eq!(identities.len(), 2);
+        assert_eq!(identities[0].smtp_account.as_deref(), Some("work"));
+        assert_eq!(
+            identities[0].signature.as_deref(),
+            Some("Ana Souza\nTech Solutions")
+        );
+        assert_eq!(identities[0].recipient_domains, vec!["techsolutions.com"]);
+        assert!(!identities[0].default);
+        assert_eq!(identities[1].language.as_deref(), Some("pt-BR"));
+        assert!(identities[1].default);
+        assert!(identities[1].keywords.is_empty());
+        assert_eq!(config.email.smtp, None);
+        assert_eq!(config.email.accounts["work"].host, "smtp.techsolutions.com");
+        assert_eq!(config.email.accounts["work"].security, "starttls");
+
+        cleanup_test_file(test_path);
+    }
+
+    #[test]
+    fn test_config_assistant_persona_section() {
+        let test_path = "test_config_assistant_persona.toml";
+        let test_content = r#"
+[database]
+path = "/test/database.db"
+
+[user.settings]
+name = "Test User"
+assistant = "Test Assistant"
+
+[ollama.api]
+url = "http://localhost:8080/api"
+chat = "/chat"
+create = "/create"
+show = "/show"
+load = "/generate"
+model = "test-model"
+[ollama.api.options]
+temperature = 0
+
+[assistant.root]
+name = "test-assistant-"
+
+[assistant.persona]
+preset = "code_reviewer"
+tone = "friendly"
+secondary_languages = ["pt-BR"]
+working_hours = "08:00-16:00"
+"#;
+
+        create_test_config_file(test_path, test_content).expect("Failed to create test file");
+
+        let config = Config::load_from_file(test_path).unwrap();
+        let persona = &config.assistant.persona;
+        assert_eq!(persona.preset.as_deref(), Some("code_reviewer"));
+        assert_eq!(persona.tone.as_deref(), Some("friendly"));
+        assert_eq!(persona.secondary_languages, Some(vec!["pt-BR".to_string()]));
+        assert_eq!(persona.working_hours.as_deref(), Some("08:00-16:00"));
+        assert_eq!(persona.formality, None);
+        assert_eq!(persona.signature, None);
+
+        cleanup_test_file(test_path);
+    }
+
+    #[test]
+    fn test_config_assistant_persona_defaults_when_missing() {
+        let test_path = "test_config_no_persona.toml";
+        let test_content = r#"
+[database]
+path = "/test/database.db"
+
+[user.settings]
+name = "Test User"
+assistant = "Test Assistant"
+
+[ollama.api]
+url = "http://localhost:8080/api"
+chat = "/chat"
+create = "/create"
+show = "/show"
+load = "/generate"
+model = "test-model"
+[ollama.api.options]
+temperature = 0
+
+[assistant.root]
+name = "test-assistant-"
+"#;
+
+        create_test_config_file(test_path, test_content).expect("Failed to create test file");
+
+        let config = Config::load_from_file(test_path).unwrap();
+        assert_eq!(config.assistant.persona, PersonaConfig::default());
+
+        cleanup_test_file(test_path);
+    }
 }
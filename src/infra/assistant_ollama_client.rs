@@ -1,4 +1,9 @@
-use crate::{agents::assistant::CheckResult, config::Config};
+use crate::{
+    agents::assistant::{CheckResult, Modelfile},
+    config::Config,
+};
+
+use serde::{Serialize, de::DeserializeOwned};
 
 use ollama_oxide::{
     ChatMessage, ChatRequest, ChatResponse, CreateRequest, CreateResponse, ModelOptions, OllamaApiAsync, OllamaClient, ShowRequest
@@ -32,6 +37,18 @@ impl AssistantOllamaClient {
         }
     }
 
+    /// Returns the context length reported by `show` for `model`, if any.
+    pub async fn context_length(
+        &self,
+        model: &str,
+    ) -> Result<Option<usize>, Box<dyn std::error::Error>> {
+        let ollama_client = OllamaClient::with_base_url(&Config::get().ollama.api.url.clone())?;
+        let response = ollama_client
+            .show_model(&ShowRequest::new(model.to_string()))
+            .await?;
+        Ok(context_length_from_show(&serde_json::to_value(&response)?))
+    }
+
     async fn send_chat_request(
         &self,
         chat_request: ChatRequest,
@@ -77,6 +94,34 @@ impl AssistantOllamaClient {
         }
     }
 
+    /// Sends a free-form chat (e.g. an email draft) with the configured temperature.
+    pub async fn send_chat_message(
+        &self,
+        messages: Vec<ChatMessage>,
+        model: &str,
+    ) -> Result<ChatResponse, Box<dyn std::error::Error>> {
+        let options =
+            ModelOptions::new().with_temperature(Config::get().ollama.api.options.temperature);
+        self.send_chat_request(ChatRequest::new(model, messages).with_options(options))
+            .await
+    }
+
+    /// Like `send_classifier_message`, with a fixed sampling seed for reproducible runs.
+    /// Fails rather than sending the request without the seed.
+    pub async fn send_classifier_message_with_seed(
+        &self,
+        messages: Vec<ChatMessage>,
+        model: &str,
+        seed: u64,
+    ) -> Result<ChatResponse, Box<dyn std::error::Error>> {
+        let options: ModelOptions = carried(serde_json::json!({
+            "temperature": Config::get().ollama.api.options.temperature,
+            "seed": seed,
+        }))?;
+        self.send_chat_request(ChatRequest::new(model, messages).with_options(options))
+            .await
+    }
+
     pub async fn create_assistant(
         &self,
         system: String,
@@ -103,6 +148,179 @@ impl AssistantOllamaClient {
             }
         }
     }
+
+    /// Creates `name` from every Modelfile instruction, not only FROM and SYSTEM.
+    /// A Modelfile with just those two uses the typed `CreateRequest` builder.
+    pub async fn create_from_modelfile(
+        &self,
+        name: &str,
+        modelfile: &Modelfile,
+    ) -> Result<CreateResponse, Box<dyn std::error::Error>> {
+        let create_request = if is_from_and_system(modelfile) {
+            let request = CreateRequest::from_model(name.to_string(), modelfile.from.clone());
+            match &modelfile.system {
+                Some(system) => request.with_system(system.clone()),
+                None => request,
+            }
+        } else {
+            carried(create_request_body(name, modelfile))?
+        };
+        let ollama_client = OllamaClient::with_base_url(&Config::get().ollama.api.url.clone())?;
+        Ok(ollama_client.create_model(&create_request).await?)
+    }
+}
+
+fn is_from_and_system(modelfile: &Modelfile) -> bool {
+    modelfile.template.is_none()
+        && modelfile.license.is_none()
+        && modelfile.parameters.is_empty()
+        && modelfile.messages.is_empty()
+}
+
+/// `/api/create` body for `modelfile`; repeated `stop` parameters become a list.
+fn create_request_body(name: &str, modelfile: &Modelfile) -> serde_json::Value {
+    let mut body = serde_json::Map::new();
+    body.insert("model".to_string(), name.into());
+    body.insert("from".to_string(), modelfile.from.as_str().into());
+    for (key, value) in [
+        ("system", &modelfile.system),
+        ("template", &modelfile.template),
+        ("license", &modelfile.license),
+    ] {
+        if let Some(value) = value {
+            body.insert(key.to_string(), value.as_str().into());
+        }
+    }
+
+    let mut parameters = serde_json::Map::new();
+    for (key, value) in &modelfile.parameters {
+        let value = parameter_value(value);
+        if key == "stop" {
+            let stops = parameters
+                .entry(key.clone())
+                .or_insert_with(|| serde_json::Value::Array(Vec::new()));
+            if let Some(stops) = stops.as_array_mut() {
+                stops.push(value);
+            }
+        } else {
+            parameters.insert(key.clone(), value);
+        }
+    }
+    if !parameters.is_empty() {
+        body.insert("parameters".to_string(), parameters.into());
+    }
+
+    if !modelfile.messages.is_empty() {
+        let messages = modelfile
+            .messages
+            .iter()
+            .map(|message| {
+                serde_json::json!({ "role": message.role.to_str(), "content": message.content })
+            })
+            .collect::<Vec<serde_json::Value>>();
+        body.insert("messages".to_string(), messages.into());
+    }
+
+    body.into()
+}
+
+/// Builds an ollama-oxide request type from its JSON body, failing when the
+/// type has no field for one of the body's keys, at any depth, instead of
+/// letting serde drop it.
+fn carried<T: Serialize + DeserializeOwned>(
+    body: serde_json::Value,
+) -> Result<T, Box<dyn std::error::Error>> {
+    let request: T = serde_json::from_value(body.clone())?;
+    let built = serde_json::to_value(&request)?;
+    let mut dropped = Vec::new();
+    dropped_keys(&body, &built, "", &mut dropped);
+    if !dropped.is_empty() {
+        return Err(format!(
+            "{} cannot carry: {}",
+            std::any::type_name::<T>(),
+            dropped.join(", ")
+        )
+        .into());
+    }
+    Ok(request)
+}
+
+/// Collects the paths (`parameters.stop`, `messages[0].role`) of `sent` that
+/// are missing or null in `built`.
+fn dropped_keys(
+    sent: &serde_json::Value,
+    built: &serde_json::Value,
+    path: &str,
+    dropped: &mut Vec<String>,
+) {
+    match (sent, built) {
+        (serde_json::Value::Object(sent), serde_json::Value::Object(built)) => {
+            for (key, value) in sent {
+                let key_path = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{path}.{key}")
+                };
+                match built.get(key) {
+                    Some(built) if !built.is_null() => {
+                        dropped_keys(value, built, &key_path, dropped)
+                    }
+                    _ => dropped.push(key_path),
+                }
+            }
+        }
+        (serde_json::Value::Array(sent), serde_json::Value::Array(built)) => {
+            for (index, value) in sent.iter().enumerate() {
+                let index_path = format!("{path}[{index}]");
+                match built.get(index) {
+                    Some(built) => dropped_keys(value, built, &index_path, dropped),
+                    None => dropped.push(index_path),
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Modelfile parameters are text; the API expects numbers and booleans typed.
+fn parameter_value(value: &str) -> serde_json::Value {
+    if let Ok(integer) = value.parse::<i64>() {
+        integer.into()
+    } else if let Ok(float) = value.parse::<f64>() {
+        float.into()
+    } else if let Ok(boolean) = value.parse::<bool>() {
+        boolean.into()
+    } else {
+        value.into()
+    }
+}
+
+/// Reads `num_ctx` from the Modelfile parameters, falling back to the
+/// architecture's `*.context_length` in `model_info`.
+fn context_length_from_show(show: &serde_json::Value) -> Option<usize> {
+    let from_parameters = show
+        .get("parameters")
+        .and_then(|parameters| parameters.as_str())
+        .and_then(|parameters| {
+            parameters.lines().find_map(|line| {
+                let mut parts = line.split_whitespace();
+                match (parts.next(), parts.next()) {
+                    (Some("num_ctx"), Some(value)) => value.parse().ok(),
+                    _ => None,
+                }
+            })
+        });
+
+    from_parameters.or_else(|| {
+        show.get("model_info")
+            .and_then(|info| info.as_object())
+            .and_then(|info| {
+                info.iter()
+                    .find(|(key, _)| key.ends_with(".context_length"))
+                    .and_then(|(_, value)| value.as_u64())
+            })
+            .map(|value| value as usize)
+    })
 }
 
 #[cfg(test)]
@@ -340,4 +558,132 @@ mod tests {
             assert!(result.is_err(), "Model {} should return an error", model);
         }
     }
+
+    #[test]
+    fn test_context_length_from_show_parameters() {
+        let show = serde_json::json!({
+            "parameters": "temperature 0\nnum_ctx 8192\nstop \"<|im_end|>\"",
+            "model_info": { "qwen3.context_length": 40960 }
+        });
+        assert_eq!(context_length_from_show(&show), Some(8192));
+    }
+
+    #[test]
+    fn test_context_length_from_show_model_info() {
+        let show = serde_json::json!({
+            "parameters": "temperature 0",
+            "model_info": { "general.architecture": "qwen3", "qwen3.context_length": 40960 }
+        });
+        assert_eq!(context_length_from_show(&show), Some(40960));
+    }
+
+    #[test]
+    fn test_context_length_from_show_missing() {
+        let show = serde_json::json!({ "modelfile": "FROM qwen3:0.6b" });
+        assert_eq!(context_length_from_show(&show), None);
+    }
+
+    #[test]
+    fn test_create_request_body_maps_every_instruction() {
+        use crate::agents::assistant::{ModelfileMessage, ModelfileRole};
+
+        let modelfile = Modelfile::new("qwen3:0.6b")
+            .with_system("You are Tereza")
+            .with_template("{{ .Prompt }}")
+            .with_parameter("temperature", "0.2")
+            .with_parameter("num_ctx", "4096")
+            .with_parameter("stop", "<|im_end|>")
+            .with_parameter("stop", "<|endoftext|>")
+            .with_message(ModelfileMessage::new(ModelfileRole::User, "Hi"))
+            .with_message(ModelfileMessage::new(ModelfileRole::Assistant, "Hello!"));
+
+        let body = create_request_body("assistant-tereza", &modelfile);
+
+        assert_eq!(body["model"], "assistant-tereza");
+        assert_eq!(body["from"], "qwen3:0.6b");
+        assert_eq!(body["system"], "You are Tereza");
+        assert_eq!(body["template"], "{{ .Prompt }}");
+        assert_eq!(body["parameters"]["temperature"], 0.2);
+        assert_eq!(body["parameters"]["num_ctx"], 4096);
+        assert_eq!(
+            body["parameters"]["stop"],
+            serde_json::json!(["<|im_end|>", "<|endoftext|>"])
+        );
+        assert_eq!(body["messages"][1]["role"], "assistant");
+        assert_eq!(body["messages"][1]["content"], "Hello!");
+        assert!(body.get("license").is_none());
+    }
+
+    #[test]
+    fn test_carried_fails_on_fields_the_type_drops() {
+        #[derive(Serialize, serde::Deserialize)]
+        struct Narrow {
+            model: String,
+            #[serde(default, skip_serializing_if = "Option::is_none")]
+            seed: Option<u64>,
+        }
+
+        let narrow: Narrow = carried(serde_json::json!({ "model": "m", "seed": 7 })).unwrap();
+        assert_eq!(narrow.seed, Some(7));
+
+        let error = carried::<Narrow>(serde_json::json!({
+            "model": "m",
+            "template": "{{ .Prompt }}",
+            "messages": [],
+        }))
+        .err()
+        .unwrap();
+        assert!(
+            error.to_string().ends_with("cannot carry: messages, template"),
+            "{error}"
+        );
+    }
+
+    #[test]
+    fn test_carried_fails_on_nested_fields_the_type_drops() {
+        #[derive(Serialize, serde::Deserialize)]
+        struct Parameters {
+            #[serde(default, skip_serializing_if = "Option::is_none")]
+            temperature: Option<f64>,
+        }
+
+        #[derive(Serialize, serde::Deserialize)]
+        struct Narrow {
+            model: String,
+            parameters: Parameters,
+        }
+
+        let error = carried::<Narrow>(serde_json::json!({
+            "model": "m",
+            "parameters": { "temperature": 0.2, "num_ctx": 4096, "stop": ["<|im_end|>"] },
+        }))
+        .err()
+        .unwrap();
+        assert!(
+            error
+                .to_string()
+                .ends_with("cannot carry: parameters.num_ctx, parameters.stop"),
+            "{error}"
+        );
+    }
+
+    #[test]
+    fn test_only_from_and_system_uses_the_typed_builder() {
+        let modelfile = Modelfile::new("qwen3:0.6b").with_system("You are Tereza");
+        assert!(is_from_and_system(&modelfile));
+        assert!(!is_from_and_system(
+            &modelfile.clone().with_parameter("num_ctx", "4096")
+        ));
+        assert!(!is_from_and_system(
+            &modelfile.with_template("{{ .Prompt }}")
+        ));
+    }
+
+    #[test]
+    fn test_create_request_body_omits_empty_sections() {
+        let body = create_request_body("assistant-tereza", &Modelfile::new("qwen3:0.6b"));
+        assert!(body.get("parameters").is_none());
+        assert!(body.get("messages").is_none());
+        assert!(body.get("system").is_none());
+    }
 }
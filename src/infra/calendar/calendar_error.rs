@@ -0,0 +1,19 @@
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug)]
+pub enum CalendarError {
+    IoError(String),
+    ParseError(String),
+}
+
+impl fmt::Display for CalendarError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CalendarError::IoError(msg) => write!(f, "Calendar IO error: {msg}"),
+            CalendarError::ParseError(msg) => write!(f, "Calendar parse error: {msg}"),
+        }
+    }
+}
+
+impl Error for CalendarError {}
@@ -0,0 +1,40 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+use super::{
+    event_attendee::EventAttendee, event_recurrence::EventRecurrence, event_reminder::EventReminder,
+};
+
+/// An event as stored in `spec/calendar.json`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CalendarEvent {
+    pub id: String,
+    pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// RFC 3339 timestamp as written in the source.
+    pub start_date_time: String,
+    pub end_date_time: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attendees: Vec<EventAttendee>,
+    pub category: String,
+    pub priority: String,
+    pub status: String,
+    #[serde(default)]
+    pub all_day: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recurrence: Option<EventRecurrence>,
+    #[serde(default)]
+    pub reminders: Vec<EventReminder>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<String>,
+    /// Fields without a typed column (`location`, `attachments`, `cost`, `notes`), kept as written.
+    #[serde(flatten)]
+    pub extra: BTreeMap<String, Value>,
+}
@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CalendarMetadata {
+    #[serde(default)]
+    pub total_events: usize,
+    #[serde(flatten)]
+    pub extra: BTreeMap<String, Value>,
+}
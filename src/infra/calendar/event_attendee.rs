@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+
+/// An entry of `attendees` in `spec/calendar.json`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct EventAttendee {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub contact_id: Option<String>,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    /// `accepted`, `tentative`, `pending` or `none` for occasions such as birthdays.
+    pub status: String,
+    /// `required`, `optional`, `birthday`, ...
+    #[serde(rename = "type")]
+    pub attendee_type: String,
+}
@@ -0,0 +1,93 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::{calendar_event::CalendarEvent, calendar_metadata::CalendarMetadata};
+
+/// The `calendar` object of `spec/calendar.json`.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct EventCalendar {
+    #[serde(default)]
+    pub name: String,
+    /// IANA zone name, e.g. `America/New_York`.
+    #[serde(default)]
+    pub time_zone: String,
+    #[serde(default)]
+    pub owner: String,
+    #[serde(default)]
+    pub events: Vec<CalendarEvent>,
+    #[serde(default, skip_serializing_if = "Value::is_null")]
+    pub settings: Value,
+    #[serde(default)]
+    pub metadata: CalendarMetadata,
+}
+
+impl EventCalendar {
+    pub fn find(&self, id: &str) -> Option<&CalendarEvent> {
+        self.events.iter().find(|event| event.id == id)
+    }
+
+    /// Replaces the event with the same id, or appends it. Returns `true` when it was new.
+    pub fn upsert_event(&mut self, event: CalendarEvent) -> bool {
+        let inserted = match self.events.iter_mut().find(|e| e.id == event.id) {
+            Some(existing) => {
+                *existing = event;
+                false
+            }
+            None => {
+                self.events.push(event);
+                true
+            }
+        };
+        self.metadata.total_events = self.events.len();
+        inserted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infra::calendar::{EventAttendee, EventRecurrence};
+    use std::collections::BTreeMap;
+
+    fn event(id: &str, title: &str) -> CalendarEvent {
+        CalendarEvent {
+            id: id.to_string(),
+            title: title.to_string(),
+            description: None,
+            start_date_time: "2025-11-08T00:00:00Z".to_string(),
+            end_date_time: "2025-11-08T23:59:59Z".to_string(),
+            attendees: vec![EventAttendee {
+                contact_id: Some("c003".to_string()),
+                name: "Dolphin Wise".to_string(),
+                email: None,
+                status: "none".to_string(),
+                attendee_type: "birthday".to_string(),
+            }],
+            category: "birthday".to_string(),
+            priority: "low".to_string(),
+            status: "confirmed".to_string(),
+            all_day: true,
+            recurrence: Some(EventRecurrence::yearly()),
+            reminders: Vec::new(),
+            color: None,
+            created_at: None,
+            updated_at: None,
+            extra: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_upsert_event_replaces_by_id() {
+        let mut calendar = EventCalendar::default();
+        assert!(calendar.upsert_event(event("birthday_c003", "Birthday")));
+        assert!(!calendar.upsert_event(event("birthday_c003", "Birthday - Dolphin Wise")));
+
+        assert_eq!(calendar.events.len(), 1);
+        assert_eq!(calendar.metadata.total_events, 1);
+        assert_eq!(
+            calendar.find("birthday_c003").map(|e| e.title.as_str()),
+            Some("Birthday - Dolphin Wise")
+        );
+    }
+}
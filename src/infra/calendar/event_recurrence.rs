@@ -0,0 +1,21 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct EventRecurrence {
+    /// `daily`, `weekly`, `monthly` or `yearly`.
+    pub frequency: String,
+    pub interval: u32,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub days_of_week: Vec<String>,
+}
+
+impl EventRecurrence {
+    pub fn yearly() -> Self {
+        Self {
+            frequency: "yearly".to_string(),
+            interval: 1,
+            days_of_week: Vec::new(),
+        }
+    }
+}
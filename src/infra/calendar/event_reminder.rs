@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct EventReminder {
+    /// `notification` or `email`.
+    pub method: String,
+    /// Minutes before the event starts.
+    pub minutes: u32,
+}
+
+impl EventReminder {
+    pub fn notification(minutes: u32) -> Self {
+        Self {
+            method: "notification".to_string(),
+            minutes,
+        }
+    }
+}
@@ -0,0 +1,17 @@
+pub mod calendar_error;
+pub mod calendar_event;
+pub mod calendar_metadata;
+pub mod event_attendee;
+pub mod event_calendar;
+pub mod event_recurrence;
+pub mod event_reminder;
+pub mod user_calendar;
+
+pub use calendar_error::CalendarError;
+pub use calendar_event::CalendarEvent;
+pub use calendar_metadata::CalendarMetadata;
+pub use event_attendee::EventAttendee;
+pub use event_calendar::EventCalendar;
+pub use event_recurrence::EventRecurrence;
+pub use event_reminder::EventReminder;
+pub use user_calendar::UserCalendar;
@@ -0,0 +1,68 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+use super::{calendar_error::CalendarError, event_calendar::EventCalendar};
+
+/// The calendar document: the root object of `spec/calendar.json`.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct UserCalendar {
+    #[serde(default)]
+    pub calendar: EventCalendar,
+}
+
+impl UserCalendar {
+    pub fn from_json_str(content: &str) -> Result<Self, CalendarError> {
+        serde_json::from_str(content).map_err(|e| CalendarError::ParseError(e.to_string()))
+    }
+
+    pub fn to_json_string(&self) -> Result<String, CalendarError> {
+        serde_json::to_string_pretty(self).map_err(|e| CalendarError::ParseError(e.to_string()))
+    }
+
+    pub fn read_from(path: impl AsRef<Path>) -> Result<Self, CalendarError> {
+        let content =
+            fs::read_to_string(path).map_err(|e| CalendarError::IoError(e.to_string()))?;
+        Self::from_json_str(&content)
+    }
+
+    pub fn write_to(&self, path: impl AsRef<Path>) -> Result<(), CalendarError> {
+        fs::write(path, self.to_json_string()?).map_err(|e| CalendarError::IoError(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spec_calendar_round_trips() {
+        let content = fs::read_to_string("spec/calendar.json").unwrap();
+        let document = UserCalendar::from_json_str(&content).unwrap();
+        assert_eq!(document.calendar.events.len(), 12);
+        assert_eq!(document.calendar.metadata.total_events, 12);
+
+        let birthday = document.calendar.find("evt_008").unwrap();
+        assert!(birthday.all_day);
+        assert_eq!(birthday.attendees[0].attendee_type, "birthday");
+        assert!(
+            document
+                .calendar
+                .find("evt_001")
+                .unwrap()
+                .extra
+                .contains_key("location")
+        );
+
+        let written: serde_json::Value =
+            serde_json::from_str(&document.to_json_string().unwrap()).unwrap();
+        let original: serde_json::Value = serde_json::from_str(&content).unwrap();
+        assert_eq!(written, original);
+    }
+
+    #[test]
+    fn test_from_json_str_rejects_invalid_documents() {
+        let result = UserCalendar::from_json_str("{\"calendar\": {\"events\": 3}}");
+        assert!(matches!(result, Err(CalendarError::ParseError(_))));
+    }
+}
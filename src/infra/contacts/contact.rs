@@ -0,0 +1,258 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use super::{
+    contact_address::ContactAddress, contact_email::ContactEmail, contact_phone::ContactPhone,
+    social_profile::SocialProfile,
+};
+
+/// A contact as stored in `spec/contacts.json`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Contact {
+    pub id: String,
+    #[serde(default)]
+    pub first_name: String,
+    #[serde(default)]
+    pub last_name: String,
+    #[serde(default)]
+    pub display_name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nickname: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub company: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub job_title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub department: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub phone_numbers: Vec<ContactPhone>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub emails: Vec<ContactEmail>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub addresses: Vec<ContactAddress>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub social_profiles: Vec<SocialProfile>,
+    /// `YYYY-MM-DD` as written in the source.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub birthday: Option<String>,
+    /// Same forms as `birthday`: `YYYY-MM-DD`, or `--MM-DD` without the year.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub anniversary: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub photo: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub favorite: bool,
+    /// RFC 3339 timestamp as written in the source.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<String>,
+    /// Source-specific fields with no column of their own, such as vCard `X-` properties.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub extensions: BTreeMap<String, String>,
+}
+
+impl Contact {
+    pub fn new(id: &str, first_name: &str, last_name: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            first_name: first_name.to_string(),
+            last_name: last_name.to_string(),
+            display_name: format!("{first_name} {last_name}").trim().to_string(),
+            nickname: None,
+            company: None,
+            job_title: None,
+            department: None,
+            phone_numbers: Vec::new(),
+            emails: Vec::new(),
+            addresses: Vec::new(),
+            social_profiles: Vec::new(),
+            birthday: None,
+            anniversary: None,
+            notes: None,
+            photo: None,
+            tags: Vec::new(),
+            favorite: false,
+            created_at: None,
+            updated_at: None,
+            extensions: BTreeMap::new(),
+        }
+    }
+
+    pub fn with_display_name(mut self, display_name: &str) -> Self {
+        self.display_name = display_name.to_string();
+        self
+    }
+
+    pub fn with_nickname(mut self, nickname: &str) -> Self {
+        self.nickname = Some(nickname.to_string());
+        self
+    }
+
+    pub fn with_company(mut self, company: &str) -> Self {
+        self.company = Some(company.to_string());
+        self
+    }
+
+    pub fn with_job_title(mut self, job_title: &str) -> Self {
+        self.job_title = Some(job_title.to_string());
+        self
+    }
+
+    pub fn with_department(mut self, department: &str) -> Self {
+        self.department = Some(department.to_string());
+        self
+    }
+
+    pub fn with_phone(mut self, phone: ContactPhone) -> Self {
+        self.phone_numbers.push(phone);
+        self
+    }
+
+    pub fn with_email(mut self, email: ContactEmail) -> Self {
+        self.emails.push(email);
+        self
+    }
+
+    pub fn with_address(mut self, address: ContactAddress) -> Self {
+        self.addresses.push(address);
+        self
+    }
+
+    pub fn with_social_profile(mut self, profile: SocialProfile) -> Self {
+        self.social_profiles.push(profile);
+        self
+    }
+
+    pub fn with_birthday(mut self, birthday: &str) -> Self {
+        self.birthday = Some(birthday.to_string());
+        self
+    }
+
+    pub fn with_anniversary(mut self, anniversary: &str) -> Self {
+        self.anniversary = Some(anniversary.to_string());
+        self
+    }
+
+    pub fn with_notes(mut self, notes: &str) -> Self {
+        self.notes = Some(notes.to_string());
+        self
+    }
+
+    pub fn with_tag(mut self, tag: &str) -> Self {
+        self.tags.push(tag.to_string());
+        self
+    }
+
+    pub fn with_favorite(mut self, favorite: bool) -> Self {
+        self.favorite = favorite;
+        self
+    }
+
+    /// Display name, falling back to first and last name when it is blank.
+    pub fn name(&self) -> String {
+        if self.display_name.trim().is_empty() {
+            format!("{} {}", self.first_name, self.last_name)
+                .trim()
+                .to_string()
+        } else {
+            self.display_name.clone()
+        }
+    }
+
+    /// Email flagged as primary, or the first one listed.
+    pub fn primary_email(&self) -> Option<&ContactEmail> {
+        self.emails
+            .iter()
+            .find(|email| email.primary)
+            .or_else(|| self.emails.first())
+    }
+
+    /// Phone number flagged as primary, or the first one listed.
+    pub fn primary_phone(&self) -> Option<&ContactPhone> {
+        self.phone_numbers
+            .iter()
+            .find(|phone| phone.primary)
+            .or_else(|| self.phone_numbers.first())
+    }
+
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t.eq_ignore_ascii_case(tag))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infra::contacts::ContactLabel;
+
+    #[test]
+    fn test_deserialize_spec_contact() {
+        let json = r#"{
+            "id": "c003",
+            "firstName": "Dolphin",
+            "lastName": "Wise",
+            "displayName": "Dr. Dolphin Wise",
+            "company": "City Medical Center",
+            "phoneNumbers": [{"type": "work", "number": "+1-555-111-2222", "primary": true}],
+            "emails": [{"type": "work", "address": "d.wise@citymedical.com", "primary": true}],
+            "tags": ["doctor"],
+            "favorite": true,
+            "createdAt": "2023-01-15T10:30:00Z"
+        }"#;
+        let contact: Contact = serde_json::from_str(json).unwrap();
+
+        assert_eq!(contact.name(), "Dr. Dolphin Wise");
+        assert_eq!(contact.company.as_deref(), Some("City Medical Center"));
+        assert_eq!(contact.phone_numbers[0].label, ContactLabel::Work);
+        assert_eq!(
+            contact.primary_email().map(|e| e.address.as_str()),
+            Some("d.wise@citymedical.com")
+        );
+        assert!(contact.has_tag("Doctor"));
+        assert!(contact.addresses.is_empty());
+        assert_eq!(contact.updated_at, None);
+    }
+
+    #[test]
+    fn test_serialize_uses_camel_case_and_skips_missing_fields() {
+        let contact = Contact::new("c100", "Eva", "Green")
+            .with_job_title("Architect")
+            .with_email(ContactEmail::new(ContactLabel::Personal, "eva@example.com"));
+        let json = serde_json::to_value(&contact).unwrap();
+
+        assert_eq!(json["displayName"], "Eva Green");
+        assert_eq!(json["jobTitle"], "Architect");
+        assert_eq!(json["emails"][0]["type"], "personal");
+        assert!(json.get("nickname").is_none());
+        assert!(json.get("phoneNumbers").is_none());
+    }
+
+    #[test]
+    fn test_primary_falls_back_to_first_entry() {
+        let contact = Contact::new("c100", "Eva", "Green")
+            .with_phone(ContactPhone::new(ContactLabel::Work, "+1"))
+            .with_phone(ContactPhone::new(ContactLabel::Mobile, "+2").with_primary(true))
+            .with_email(ContactEmail::new(ContactLabel::Work, "eva@work.com"));
+
+        assert_eq!(
+            contact.primary_phone().map(|p| p.number.as_str()),
+            Some("+2")
+        );
+        assert_eq!(
+            contact.primary_email().map(|e| e.address.as_str()),
+            Some("eva@work.com")
+        );
+    }
+
+    #[test]
+    fn test_name_falls_back_to_first_and_last_name() {
+        let contact = Contact::new("c100", "Eva", "Green").with_display_name(" ");
+        assert_eq!(contact.name(), "Eva Green");
+    }
+}
@@ -0,0 +1,66 @@
+use serde::{Deserialize, Serialize};
+
+use super::contact_label::ContactLabel;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ContactAddress {
+    #[serde(rename = "type")]
+    pub label: ContactLabel,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub street: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub city: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub postal_code: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub country: Option<String>,
+    #[serde(default)]
+    pub primary: bool,
+}
+
+impl ContactAddress {
+    pub fn new(label: ContactLabel) -> Self {
+        Self {
+            label,
+            street: None,
+            city: None,
+            state: None,
+            postal_code: None,
+            country: None,
+            primary: false,
+        }
+    }
+
+    pub fn with_street(mut self, street: &str) -> Self {
+        self.street = Some(street.to_string());
+        self
+    }
+
+    pub fn with_city(mut self, city: &str) -> Self {
+        self.city = Some(city.to_string());
+        self
+    }
+
+    pub fn with_state(mut self, state: &str) -> Self {
+        self.state = Some(state.to_string());
+        self
+    }
+
+    pub fn with_postal_code(mut self, postal_code: &str) -> Self {
+        self.postal_code = Some(postal_code.to_string());
+        self
+    }
+
+    pub fn with_country(mut self, country: &str) -> Self {
+        self.country = Some(country.to_string());
+        self
+    }
+
+    pub fn with_primary(mut self, primary: bool) -> Self {
+        self.primary = primary;
+        self
+    }
+}
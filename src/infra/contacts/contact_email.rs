@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+
+use super::contact_label::ContactLabel;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ContactEmail {
+    #[serde(rename = "type")]
+    pub label: ContactLabel,
+    pub address: String,
+    #[serde(default)]
+    pub primary: bool,
+}
+
+impl ContactEmail {
+    pub fn new(label: ContactLabel, address: &str) -> Self {
+        Self {
+            label,
+            address: address.to_string(),
+            primary: false,
+        }
+    }
+
+    pub fn with_primary(mut self, primary: bool) -> Self {
+        self.primary = primary;
+        self
+    }
+}
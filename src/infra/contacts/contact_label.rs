@@ -0,0 +1,56 @@
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+use super::contact_store_error::ContactStoreError;
+
+/// The `type` of a phone number, email or address in `spec/contacts.json`.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum ContactLabel {
+    Mobile,
+    Home,
+    Work,
+    Personal,
+    Other,
+}
+
+impl ContactLabel {
+    pub fn to_str(&self) -> &str {
+        match self {
+            Self::Mobile => MOBILE,
+            Self::Home => HOME,
+            Self::Work => WORK,
+            Self::Personal => PERSONAL,
+            Self::Other => OTHER,
+        }
+    }
+}
+
+impl FromStr for ContactLabel {
+    type Err = ContactStoreError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input.trim().to_lowercase().as_str() {
+            MOBILE => Ok(Self::Mobile),
+            HOME => Ok(Self::Home),
+            WORK => Ok(Self::Work),
+            PERSONAL => Ok(Self::Personal),
+            OTHER => Ok(Self::Other),
+            other => Err(ContactStoreError::ParseError(format!(
+                "Unknown contact label: {other}"
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for ContactLabel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_str())
+    }
+}
+
+const MOBILE: &str = "mobile";
+const HOME: &str = "home";
+const WORK: &str = "work";
+const PERSONAL: &str = "personal";
+const OTHER: &str = "other";
@@ -0,0 +1,56 @@
+use serde::{Deserialize, Serialize};
+
+use super::contact_label::ContactLabel;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ContactPhone {
+    #[serde(rename = "type")]
+    pub label: ContactLabel,
+    pub number: String,
+    #[serde(default)]
+    pub primary: bool,
+}
+
+impl ContactPhone {
+    pub fn new(label: ContactLabel, number: &str) -> Self {
+        Self {
+            label,
+            number: number.to_string(),
+            primary: false,
+        }
+    }
+
+    pub fn with_primary(mut self, primary: bool) -> Self {
+        self.primary = primary;
+        self
+    }
+
+    /// The number without formatting: `+1 (555) 444-7890` -> `15554447890`.
+    pub fn digits(&self) -> String {
+        self.number.chars().filter(char::is_ascii_digit).collect()
+    }
+
+    /// Same digits, or one number is the other with a country code prepended.
+    /// Numbers shorter than seven digits never match.
+    pub fn same_number(&self, other: &ContactPhone) -> bool {
+        let (a, b) = (self.digits(), other.digits());
+        let (shorter, longer) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+        shorter.len() >= MIN_DIGITS && longer.ends_with(&shorter)
+    }
+}
+
+const MIN_DIGITS: usize = 7;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_number_ignores_formatting_and_country_code() {
+        let phone = ContactPhone::new(ContactLabel::Mobile, "+1-555-444-7890");
+        assert_eq!(phone.digits(), "15554447890");
+        assert!(phone.same_number(&ContactPhone::new(ContactLabel::Work, "(555) 444-7890")));
+        assert!(!phone.same_number(&ContactPhone::new(ContactLabel::Work, "555-444-7891")));
+        assert!(!phone.same_number(&ContactPhone::new(ContactLabel::Work, "7890")));
+    }
+}
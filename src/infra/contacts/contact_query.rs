@@ -0,0 +1,277 @@
+use serde::{Deserialize, Serialize};
+
+use crate::agents::contact::normalize_name;
+
+use super::contact::Contact;
+
+/// Filters over the contact store: "everyone in Engineering at Tech Solutions",
+/// "my favourite contacts in New York". Text filters compare without accents or
+/// case and match part of the value, so `Tech Solutions` finds `Tech Solutions
+/// Inc.`; every filter that is set must match. Empty strings count as unset, as
+/// the classifier fills absent fields with them.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ContactQuery {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub company: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub department: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub job_title: Option<String>,
+    /// All of them must be on the contact; a plural ("friends") also finds the singular tag.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub favorite: Option<bool>,
+    /// Matched against the city of any address.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub city: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub country: Option<String>,
+    /// Free text searched in the notes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+}
+
+impl ContactQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_company(mut self, company: &str) -> Self {
+        self.company = Some(company.to_string());
+        self
+    }
+
+    pub fn with_department(mut self, department: &str) -> Self {
+        self.department = Some(department.to_string());
+        self
+    }
+
+    pub fn with_job_title(mut self, job_title: &str) -> Self {
+        self.job_title = Some(job_title.to_string());
+        self
+    }
+
+    pub fn with_tag(mut self, tag: &str) -> Self {
+        self.tags.push(tag.to_string());
+        self
+    }
+
+    pub fn with_favorite(mut self, favorite: bool) -> Self {
+        self.favorite = Some(favorite);
+        self
+    }
+
+    pub fn with_city(mut self, city: &str) -> Self {
+        self.city = Some(city.to_string());
+        self
+    }
+
+    pub fn with_country(mut self, country: &str) -> Self {
+        self.country = Some(country.to_string());
+        self
+    }
+
+    pub fn with_notes(mut self, text: &str) -> Self {
+        self.notes = Some(text.to_string());
+        self
+    }
+
+    /// No filter set; such a query would match every contact.
+    pub fn is_empty(&self) -> bool {
+        self.favorite.is_none()
+            && self.tags.iter().all(|tag| normalize_name(tag).is_empty())
+            && [
+                &self.company,
+                &self.department,
+                &self.job_title,
+                &self.city,
+                &self.country,
+                &self.notes,
+            ]
+            .into_iter()
+            .all(|filter| normalized(filter).is_none())
+    }
+
+    pub fn matches(&self, contact: &Contact) -> bool {
+        let cities = contact.addresses.iter().filter_map(|a| a.city.as_deref());
+        let countries = contact
+            .addresses
+            .iter()
+            .filter_map(|a| a.country.as_deref());
+
+        self.favorite
+            .is_none_or(|favorite| contact.favorite == favorite)
+            && self.tags.iter().all(|tag| has_tag(contact, tag))
+            && text_matches(&self.company, contact.company.as_deref())
+            && text_matches(&self.department, contact.department.as_deref())
+            && text_matches(&self.job_title, contact.job_title.as_deref())
+            && text_matches(&self.city, cities)
+            && text_matches(&self.country, countries)
+            && text_matches(&self.notes, contact.notes.as_deref())
+    }
+
+    /// The matching contacts, in the order given.
+    pub fn apply(&self, contacts: &[Contact]) -> Vec<Contact> {
+        contacts
+            .iter()
+            .filter(|contact| self.matches(contact))
+            .cloned()
+            .collect()
+    }
+
+    /// The filters in words, e.g. `department Engineering, company Tech Solutions`.
+    pub fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if self.favorite == Some(true) {
+            parts.push("favourites".to_string());
+        }
+        if self.favorite == Some(false) {
+            parts.push("not favourites".to_string());
+        }
+        for (label, value) in [
+            ("department", &self.department),
+            ("company", &self.company),
+            ("job title", &self.job_title),
+            ("city", &self.city),
+            ("country", &self.country),
+            ("notes mentioning", &self.notes),
+        ] {
+            if let Some(value) = value.as_deref().filter(|value| !value.trim().is_empty()) {
+                parts.push(format!("{label} {}", value.trim()));
+            }
+        }
+        let tags: Vec<&str> = self
+            .tags
+            .iter()
+            .map(|tag| tag.trim())
+            .filter(|tag| !tag.is_empty())
+            .collect();
+        if !tags.is_empty() {
+            parts.push(format!("tagged {}", tags.join(", ")));
+        }
+        parts.join(", ")
+    }
+}
+
+fn normalized(filter: &Option<String>) -> Option<String> {
+    filter
+        .as_deref()
+        .map(normalize_name)
+        .filter(|value| !value.is_empty())
+}
+
+/// Unset filters match; set ones must be part of one of the values.
+fn text_matches<'a>(filter: &Option<String>, values: impl IntoIterator<Item = &'a str>) -> bool {
+    match normalized(filter) {
+        Some(filter) => values
+            .into_iter()
+            .any(|value| normalize_name(value).contains(&filter)),
+        None => true,
+    }
+}
+
+fn has_tag(contact: &Contact, tag: &str) -> bool {
+    let tag = normalize_name(tag);
+    if tag.is_empty() {
+        return true;
+    }
+    let singular = tag.strip_suffix('s').unwrap_or(&tag);
+    contact
+        .tags
+        .iter()
+        .map(|t| normalize_name(t))
+        .any(|t| t == tag || t == singular)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infra::contacts::{ContactAddress, ContactLabel};
+
+    fn contacts() -> Vec<Contact> {
+        vec![
+            Contact::new("c001", "Tiger", "Brilliant")
+                .with_company("Tech Solutions Inc.")
+                .with_department("Engineering")
+                .with_job_title("Software Engineer")
+                .with_tag("colleague")
+                .with_tag("developer")
+                .with_favorite(true)
+                .with_notes("Interested in AI projects.")
+                .with_address(ContactAddress::new(ContactLabel::Work).with_city("New York")),
+            Contact::new("c005", "Roo", "Cheerful")
+                .with_tag("friend")
+                .with_favorite(true),
+            Contact::new("c020", "Eva", "Green")
+                .with_company("Tech Solutions Inc.")
+                .with_department("Sales")
+                .with_address(ContactAddress::new(ContactLabel::Home).with_city("São Paulo")),
+        ]
+    }
+
+    fn ids(query: &ContactQuery) -> Vec<String> {
+        query.apply(&contacts()).into_iter().map(|c| c.id).collect()
+    }
+
+    #[test]
+    fn test_filters_combine() {
+        let query = ContactQuery::new()
+            .with_department("engineering")
+            .with_company("Tech Solutions");
+        assert_eq!(ids(&query), vec!["c001"]);
+        assert_eq!(
+            ids(&ContactQuery::new().with_company("tech solutions")),
+            vec!["c001", "c020"]
+        );
+        assert_eq!(
+            ids(&ContactQuery::new()
+                .with_favorite(true)
+                .with_city("new york")),
+            vec!["c001"]
+        );
+        assert_eq!(
+            ids(&ContactQuery::new().with_city("Sao Paulo")),
+            vec!["c020"]
+        );
+        assert_eq!(
+            ids(&ContactQuery::new().with_job_title("engineer")),
+            vec!["c001"]
+        );
+        assert_eq!(ids(&ContactQuery::new().with_notes("AI")), vec!["c001"]);
+    }
+
+    #[test]
+    fn test_tags_accept_plurals() {
+        assert_eq!(ids(&ContactQuery::new().with_tag("friends")), vec!["c005"]);
+        assert!(ids(&ContactQuery::new().with_tag("friend").with_tag("colleague")).is_empty());
+    }
+
+    #[test]
+    fn test_blank_filters_are_ignored() {
+        let query: ContactQuery =
+            serde_json::from_str(r#"{"company": "", "department": " ", "tags": [""]}"#).unwrap();
+        assert!(query.is_empty());
+        assert_eq!(ids(&query).len(), 3);
+        assert!(!ContactQuery::new().with_favorite(false).is_empty());
+    }
+
+    #[test]
+    fn test_describe_lists_the_filters() {
+        let query = ContactQuery::new()
+            .with_company("Tech Solutions")
+            .with_department("Engineering")
+            .with_tag("vip");
+        assert_eq!(
+            query.describe(),
+            "department Engineering, company Tech Solutions, tagged vip"
+        );
+    }
+
+    #[test]
+    fn test_serializes_in_camel_case() {
+        let json = serde_json::to_string(&ContactQuery::new().with_job_title("Mechanic")).unwrap();
+        assert_eq!(json, r#"{"jobTitle":"Mechanic"}"#);
+    }
+}
@@ -0,0 +1,22 @@
+use super::{
+    contact::Contact, contact_query::ContactQuery, contact_store_error::ContactStoreError,
+};
+
+/// Persistence for the user's contacts.
+pub trait ContactStore {
+    fn list(&self) -> Result<Vec<Contact>, ContactStoreError>;
+
+    fn get(&self, id: &str) -> Result<Option<Contact>, ContactStoreError>;
+
+    /// Stores a new contact, assigning an id when `contact.id` is empty.
+    fn create(&mut self, contact: Contact) -> Result<Contact, ContactStoreError>;
+
+    fn update(&mut self, contact: Contact) -> Result<Contact, ContactStoreError>;
+
+    fn delete(&mut self, id: &str) -> Result<Contact, ContactStoreError>;
+
+    /// Contacts matching every filter of `query`, in store order.
+    fn query(&self, query: &ContactQuery) -> Result<Vec<Contact>, ContactStoreError> {
+        Ok(query.apply(&self.list()?))
+    }
+}
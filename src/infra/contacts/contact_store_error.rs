@@ -0,0 +1,23 @@
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug)]
+pub enum ContactStoreError {
+    IoError(String),
+    ParseError(String),
+    NotFound(String),
+    DuplicateId(String),
+}
+
+impl fmt::Display for ContactStoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ContactStoreError::IoError(msg) => write!(f, "Contact store IO error: {msg}"),
+            ContactStoreError::ParseError(msg) => write!(f, "Contact store parse error: {msg}"),
+            ContactStoreError::NotFound(id) => write!(f, "Contact not found: {id}"),
+            ContactStoreError::DuplicateId(id) => write!(f, "Contact already exists: {id}"),
+        }
+    }
+}
+
+impl Error for ContactStoreError {}
@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+
+/// The `metadata` block of the contacts document.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ContactsMetadata {
+    #[serde(default)]
+    pub total_contacts: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_sync: Option<String>,
+    #[serde(default = "default_version")]
+    pub version: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+}
+
+impl Default for ContactsMetadata {
+    fn default() -> Self {
+        Self {
+            total_contacts: 0,
+            last_sync: None,
+            version: default_version(),
+            source: None,
+        }
+    }
+}
+
+fn default_version() -> String {
+    VERSION.to_string()
+}
+
+const VERSION: &str = "1.1";
@@ -0,0 +1,56 @@
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+use super::contact_store_error::ContactStoreError;
+
+/// Component of a [`ContactAddress`](super::ContactAddress) a CSV column fills.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum CsvAddressPart {
+    Street,
+    City,
+    State,
+    PostalCode,
+    Country,
+}
+
+impl CsvAddressPart {
+    pub fn to_str(&self) -> &str {
+        match self {
+            Self::Street => STREET,
+            Self::City => CITY,
+            Self::State => STATE,
+            Self::PostalCode => POSTAL_CODE,
+            Self::Country => COUNTRY,
+        }
+    }
+}
+
+impl FromStr for CsvAddressPart {
+    type Err = ContactStoreError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input.trim().to_lowercase().as_str() {
+            STREET => Ok(Self::Street),
+            CITY => Ok(Self::City),
+            STATE => Ok(Self::State),
+            POSTAL_CODE => Ok(Self::PostalCode),
+            COUNTRY => Ok(Self::Country),
+            other => Err(ContactStoreError::ParseError(format!(
+                "Unknown address part: {other}"
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for CsvAddressPart {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_str())
+    }
+}
+
+const STREET: &str = "street";
+const CITY: &str = "city";
+const STATE: &str = "state";
+const POSTAL_CODE: &str = "postal_code";
+const COUNTRY: &str = "country";
@@ -0,0 +1,273 @@
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fs, path::Path};
+
+use super::{
+    contact_label::ContactLabel, contact_store_error::ContactStoreError,
+    csv_address_part::CsvAddressPart, csv_field::CsvField,
+};
+
+/// Which CSV header feeds which [`CsvField`]. Headers match case-insensitively,
+/// several headers may feed the same field, and unmapped columns are skipped.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CsvColumnMapping {
+    pub name: String,
+    pub columns: BTreeMap<String, CsvField>,
+}
+
+impl CsvColumnMapping {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            columns: BTreeMap::new(),
+        }
+    }
+
+    pub fn with_column(mut self, header: &str, field: CsvField) -> Self {
+        self.columns.insert(header.trim().to_lowercase(), field);
+        self
+    }
+
+    pub fn field(&self, header: &str) -> Option<CsvField> {
+        self.columns.get(&header.trim().to_lowercase()).copied()
+    }
+
+    /// A user mapping in JSON: `{"name": "crm", "columns": {"Mail": "email.1:work"}}`.
+    pub fn from_json_str(content: &str) -> Result<Self, ContactStoreError> {
+        let mapping: Self = serde_json::from_str(content)
+            .map_err(|e| ContactStoreError::ParseError(e.to_string()))?;
+        Ok(mapping
+            .columns
+            .into_iter()
+            .fold(Self::new(&mapping.name), |mapping, (header, field)| {
+                mapping.with_column(&header, field)
+            }))
+    }
+
+    pub fn load(path: &Path) -> Result<Self, ContactStoreError> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| ContactStoreError::IoError(format!("{}: {e}", path.display())))?;
+        Self::from_json_str(&content)
+            .map_err(|e| ContactStoreError::ParseError(format!("{}: {e}", path.display())))
+    }
+
+    /// Google Contacts export, current ("First Name", "E-mail 1 - Label") and
+    /// legacy ("Given Name", "E-mail 1 - Type") headers.
+    pub fn google() -> Self {
+        let mut mapping = Self::new(GOOGLE)
+            .with_column("First Name", CsvField::FirstName)
+            .with_column("Given Name", CsvField::FirstName)
+            .with_column("Last Name", CsvField::LastName)
+            .with_column("Family Name", CsvField::LastName)
+            .with_column("Name", CsvField::DisplayName)
+            .with_column("File As", CsvField::DisplayName)
+            .with_column("Nickname", CsvField::Nickname)
+            .with_column("Organization Name", CsvField::Company)
+            .with_column("Organization 1 - Name", CsvField::Company)
+            .with_column("Organization Title", CsvField::JobTitle)
+            .with_column("Organization 1 - Title", CsvField::JobTitle)
+            .with_column("Organization Department", CsvField::Department)
+            .with_column("Organization 1 - Department", CsvField::Department)
+            .with_column("Birthday", CsvField::Birthday)
+            .with_column("Notes", CsvField::Notes)
+            .with_column("Labels", CsvField::Tags)
+            .with_column("Group Membership", CsvField::Tags);
+
+        for slot in 1..=SLOTS {
+            mapping = mapping
+                .with_column(
+                    &format!("E-mail {slot} - Value"),
+                    CsvField::Email { slot, label: None },
+                )
+                .with_column(
+                    &format!("E-mail {slot} - Label"),
+                    CsvField::EmailType { slot },
+                )
+                .with_column(
+                    &format!("E-mail {slot} - Type"),
+                    CsvField::EmailType { slot },
+                )
+                .with_column(
+                    &format!("Phone {slot} - Value"),
+                    CsvField::Phone { slot, label: None },
+                )
+                .with_column(
+                    &format!("Phone {slot} - Label"),
+                    CsvField::PhoneType { slot },
+                )
+                .with_column(
+                    &format!("Phone {slot} - Type"),
+                    CsvField::PhoneType { slot },
+                )
+                .with_column(
+                    &format!("Address {slot} - Label"),
+                    CsvField::AddressType { slot },
+                )
+                .with_column(
+                    &format!("Address {slot} - Type"),
+                    CsvField::AddressType { slot },
+                );
+            for (suffix, part) in [
+                ("Street", CsvAddressPart::Street),
+                ("City", CsvAddressPart::City),
+                ("Region", CsvAddressPart::State),
+                ("Postal Code", CsvAddressPart::PostalCode),
+                ("Country", CsvAddressPart::Country),
+            ] {
+                mapping = mapping.with_column(
+                    &format!("Address {slot} - {suffix}"),
+                    CsvField::Address {
+                        slot,
+                        part,
+                        label: None,
+                    },
+                );
+            }
+        }
+        mapping
+    }
+
+    /// Outlook export: one column per kind of email, phone and address.
+    pub fn outlook() -> Self {
+        let mut mapping = Self::new(OUTLOOK)
+            .with_column("First Name", CsvField::FirstName)
+            .with_column("Last Name", CsvField::LastName)
+            .with_column("Nickname", CsvField::Nickname)
+            .with_column("Company", CsvField::Company)
+            .with_column("Job Title", CsvField::JobTitle)
+            .with_column("Department", CsvField::Department)
+            .with_column("Birthday", CsvField::Birthday)
+            .with_column("Notes", CsvField::Notes)
+            .with_column("Categories", CsvField::Tags)
+            .with_column(
+                "E-mail Address",
+                CsvField::Email {
+                    slot: 1,
+                    label: None,
+                },
+            )
+            .with_column(
+                "E-mail 2 Address",
+                CsvField::Email {
+                    slot: 2,
+                    label: None,
+                },
+            )
+            .with_column(
+                "E-mail 3 Address",
+                CsvField::Email {
+                    slot: 3,
+                    label: None,
+                },
+            );
+
+        for (header, slot, label) in [
+            ("Mobile Phone", 1, ContactLabel::Mobile),
+            ("Business Phone", 2, ContactLabel::Work),
+            ("Home Phone", 3, ContactLabel::Home),
+            ("Primary Phone", 4, ContactLabel::Other),
+        ] {
+            mapping = mapping.with_column(
+                header,
+                CsvField::Phone {
+                    slot,
+                    label: Some(label),
+                },
+            );
+        }
+        for (prefix, slot, label) in [
+            ("Home", 1, ContactLabel::Home),
+            ("Business", 2, ContactLabel::Work),
+        ] {
+            for (suffix, part) in [
+                ("Street", CsvAddressPart::Street),
+                ("City", CsvAddressPart::City),
+                ("State", CsvAddressPart::State),
+                ("Postal Code", CsvAddressPart::PostalCode),
+                ("Country/Region", CsvAddressPart::Country),
+            ] {
+                mapping = mapping.with_column(
+                    &format!("{prefix} {suffix}"),
+                    CsvField::Address {
+                        slot,
+                        part,
+                        label: Some(label),
+                    },
+                );
+            }
+        }
+        mapping
+    }
+
+    /// Picks the built-in layout whose marker headers appear in `headers`.
+    pub fn detect(headers: &[String]) -> Option<Self> {
+        let has = |marker: &str| {
+            headers
+                .iter()
+                .any(|h| h.trim().eq_ignore_ascii_case(marker))
+        };
+        if GOOGLE_MARKERS.iter().any(|marker| has(marker)) {
+            Some(Self::google())
+        } else if OUTLOOK_MARKERS.iter().any(|marker| has(marker)) {
+            Some(Self::outlook())
+        } else {
+            None
+        }
+    }
+}
+
+const GOOGLE: &str = "google";
+const OUTLOOK: &str = "outlook";
+const SLOTS: u8 = 3;
+const GOOGLE_MARKERS: &[&str] = &["E-mail 1 - Value", "Given Name", "Organization Name"];
+const OUTLOOK_MARKERS: &[&str] = &["E-mail Address", "Business Phone", "Mobile Phone"];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn test_detect_built_in_layouts() {
+        let google = headers(&["First Name", "E-mail 1 - Label", "E-mail 1 - Value"]);
+        let outlook = headers(&["First Name", "E-mail Address", "Mobile Phone"]);
+        assert_eq!(CsvColumnMapping::detect(&google).unwrap().name, "google");
+        assert_eq!(CsvColumnMapping::detect(&outlook).unwrap().name, "outlook");
+        assert!(CsvColumnMapping::detect(&headers(&["Nome", "Telefone"])).is_none());
+    }
+
+    #[test]
+    fn test_fields_match_headers_case_insensitively() {
+        let mapping = CsvColumnMapping::google();
+        assert_eq!(
+            mapping.field(" e-mail 2 - value "),
+            Some(CsvField::Email {
+                slot: 2,
+                label: None
+            })
+        );
+        assert_eq!(mapping.field("Unknown"), None);
+    }
+
+    #[test]
+    fn test_user_mapping_from_json() {
+        let mapping = CsvColumnMapping::from_json_str(
+            r#"{"name": "crm", "columns": {"Nome": "first_name", "E-mail Trabalho": "email.1:work"}}"#,
+        )
+        .unwrap();
+        assert_eq!(mapping.field("nome"), Some(CsvField::FirstName));
+        assert_eq!(
+            mapping.field("e-mail trabalho"),
+            Some(CsvField::Email {
+                slot: 1,
+                label: Some(ContactLabel::Work)
+            })
+        );
+        assert!(
+            CsvColumnMapping::from_json_str(r#"{"name": "x", "columns": {"A": "salary"}}"#)
+                .is_err()
+        );
+    }
+}
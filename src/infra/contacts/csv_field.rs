@@ -0,0 +1,210 @@
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+use super::{
+    contact_label::ContactLabel, contact_store_error::ContactStoreError,
+    csv_address_part::CsvAddressPart,
+};
+
+/// The `Contact` field a CSV column is imported into.
+///
+/// Written as text in column mappings: `first_name`, `email.1`, `email.1:work`
+/// (fixed label), `email.1.type` (label read from the column), `phone.2:mobile`,
+/// `address.1.city:home`, `address.1.type`. Columns sharing a slot number build
+/// the same email, phone or address.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(try_from = "String", into = "String")]
+pub enum CsvField {
+    FirstName,
+    LastName,
+    DisplayName,
+    Nickname,
+    Company,
+    JobTitle,
+    Department,
+    Birthday,
+    Notes,
+    Tags,
+    Email {
+        slot: u8,
+        label: Option<ContactLabel>,
+    },
+    EmailType {
+        slot: u8,
+    },
+    Phone {
+        slot: u8,
+        label: Option<ContactLabel>,
+    },
+    PhoneType {
+        slot: u8,
+    },
+    Address {
+        slot: u8,
+        part: CsvAddressPart,
+        label: Option<ContactLabel>,
+    },
+    AddressType {
+        slot: u8,
+    },
+}
+
+impl FromStr for CsvField {
+    type Err = ContactStoreError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let input = input.trim().to_lowercase();
+        let (path, label) = match input.split_once(':') {
+            Some((path, label)) => (path, Some(label.parse::<ContactLabel>()?)),
+            None => (input.as_str(), None),
+        };
+        let parts: Vec<&str> = path.split('.').collect();
+        let slot = |index: usize| -> Result<u8, ContactStoreError> {
+            parts
+                .get(index)
+                .and_then(|slot| slot.parse().ok())
+                .ok_or_else(|| parse_error(&input))
+        };
+
+        let field = match parts.as_slice() {
+            [FIRST_NAME] => Self::FirstName,
+            [LAST_NAME] => Self::LastName,
+            [DISPLAY_NAME] => Self::DisplayName,
+            [NICKNAME] => Self::Nickname,
+            [COMPANY] => Self::Company,
+            [JOB_TITLE] => Self::JobTitle,
+            [DEPARTMENT] => Self::Department,
+            [BIRTHDAY] => Self::Birthday,
+            [NOTES] => Self::Notes,
+            [TAGS] => Self::Tags,
+            [EMAIL, _] => Self::Email {
+                slot: slot(1)?,
+                label,
+            },
+            [EMAIL, _, TYPE] => Self::EmailType { slot: slot(1)? },
+            [PHONE, _] => Self::Phone {
+                slot: slot(1)?,
+                label,
+            },
+            [PHONE, _, TYPE] => Self::PhoneType { slot: slot(1)? },
+            [ADDRESS, _, TYPE] => Self::AddressType { slot: slot(1)? },
+            [ADDRESS, _, part] => Self::Address {
+                slot: slot(1)?,
+                part: part.parse()?,
+                label,
+            },
+            _ => return Err(parse_error(&input)),
+        };
+        Ok(field)
+    }
+}
+
+impl fmt::Display for CsvField {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let with_label = |path: String, label: &Option<ContactLabel>| match label {
+            Some(label) => format!("{path}:{label}"),
+            None => path,
+        };
+        let text = match self {
+            Self::FirstName => FIRST_NAME.to_string(),
+            Self::LastName => LAST_NAME.to_string(),
+            Self::DisplayName => DISPLAY_NAME.to_string(),
+            Self::Nickname => NICKNAME.to_string(),
+            Self::Company => COMPANY.to_string(),
+            Self::JobTitle => JOB_TITLE.to_string(),
+            Self::Department => DEPARTMENT.to_string(),
+            Self::Birthday => BIRTHDAY.to_string(),
+            Self::Notes => NOTES.to_string(),
+            Self::Tags => TAGS.to_string(),
+            Self::Email { slot, label } => with_label(format!("{EMAIL}.{slot}"), label),
+            Self::EmailType { slot } => format!("{EMAIL}.{slot}.{TYPE}"),
+            Self::Phone { slot, label } => with_label(format!("{PHONE}.{slot}"), label),
+            Self::PhoneType { slot } => format!("{PHONE}.{slot}.{TYPE}"),
+            Self::Address { slot, part, label } => {
+                with_label(format!("{ADDRESS}.{slot}.{part}"), label)
+            }
+            Self::AddressType { slot } => format!("{ADDRESS}.{slot}.{TYPE}"),
+        };
+        write!(f, "{text}")
+    }
+}
+
+impl TryFrom<String> for CsvField {
+    type Error = ContactStoreError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<CsvField> for String {
+    fn from(field: CsvField) -> Self {
+        field.to_string()
+    }
+}
+
+fn parse_error(input: &str) -> ContactStoreError {
+    ContactStoreError::ParseError(format!("Unknown CSV field: {input}"))
+}
+
+const FIRST_NAME: &str = "first_name";
+const LAST_NAME: &str = "last_name";
+const DISPLAY_NAME: &str = "display_name";
+const NICKNAME: &str = "nickname";
+const COMPANY: &str = "company";
+const JOB_TITLE: &str = "job_title";
+const DEPARTMENT: &str = "department";
+const BIRTHDAY: &str = "birthday";
+const NOTES: &str = "notes";
+const TAGS: &str = "tags";
+const EMAIL: &str = "email";
+const PHONE: &str = "phone";
+const ADDRESS: &str = "address";
+const TYPE: &str = "type";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_display_round_trip() {
+        for text in [
+            "first_name",
+            "tags",
+            "email.1",
+            "email.2:work",
+            "email.1.type",
+            "phone.3:mobile",
+            "phone.1.type",
+            "address.1.postal_code:home",
+            "address.2.type",
+        ] {
+            assert_eq!(text.parse::<CsvField>().unwrap().to_string(), text);
+        }
+    }
+
+    #[test]
+    fn test_parse_reads_slots_labels_and_parts() {
+        assert_eq!(
+            "Address.2.City:Work".parse::<CsvField>().unwrap(),
+            CsvField::Address {
+                slot: 2,
+                part: CsvAddressPart::City,
+                label: Some(ContactLabel::Work)
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_fields() {
+        for text in [
+            "salary",
+            "email",
+            "email.x",
+            "phone.1:fax",
+            "address.1.planet",
+        ] {
+            assert!(text.parse::<CsvField>().is_err(), "{text}");
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+
+use super::{
+    contact::Contact, contact_store::ContactStore, contact_store_error::ContactStoreError,
+    csv_import_row::CsvImportRow, csv_row_status::CsvRowStatus,
+};
+
+/// Dry-run result of a CSV import: nothing is written until [`commit`](Self::commit).
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CsvImportPreview {
+    /// Name of the column mapping used.
+    pub mapping: String,
+    pub rows: Vec<CsvImportRow>,
+    /// Headers the mapping does not know, skipped during import.
+    pub unmapped_columns: Vec<String>,
+}
+
+impl CsvImportPreview {
+    pub fn rows_with_status(&self, status: CsvRowStatus) -> impl Iterator<Item = &CsvImportRow> {
+        self.rows.iter().filter(move |row| row.status == status)
+    }
+
+    pub fn count(&self, status: CsvRowStatus) -> usize {
+        self.rows_with_status(status).count()
+    }
+
+    /// Creates the valid rows in `store` and returns the stored contacts.
+    pub fn commit(&self, store: &mut impl ContactStore) -> Result<Vec<Contact>, ContactStoreError> {
+        self.rows_with_status(CsvRowStatus::Valid)
+            .filter_map(|row| row.contact.clone())
+            .map(|contact| store.create(contact))
+            .collect()
+    }
+}
@@ -0,0 +1,16 @@
+use serde::{Deserialize, Serialize};
+
+use super::{contact::Contact, csv_row_status::CsvRowStatus};
+
+/// One data row of a CSV import preview.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CsvImportRow {
+    /// Line number in the file, counting the header as line 1.
+    pub line: usize,
+    pub status: CsvRowStatus,
+    /// The contact built from the row, also kept for invalid rows when it could be built.
+    pub contact: Option<Contact>,
+    pub errors: Vec<String>,
+    /// Id of the existing contact, or `line N` of the earlier row, this row duplicates.
+    pub duplicate_of: Option<String>,
+}
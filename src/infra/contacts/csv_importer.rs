@@ -0,0 +1,511 @@
+use std::{collections::BTreeMap, fs, path::Path};
+
+use super::{
+    contact::Contact, contact_address::ContactAddress, contact_email::ContactEmail,
+    contact_label::ContactLabel, contact_phone::ContactPhone,
+    contact_store_error::ContactStoreError, csv_address_part::CsvAddressPart,
+    csv_column_mapping::CsvColumnMapping, csv_field::CsvField,
+    csv_import_preview::CsvImportPreview, csv_import_row::CsvImportRow,
+    csv_row_status::CsvRowStatus,
+};
+
+/// Imports contacts from CSV exports in two steps: [`preview`](Self::preview)
+/// validates every row and flags duplicates, then
+/// [`CsvImportPreview::commit`] writes the valid rows.
+#[derive(Debug, Clone, Default)]
+pub struct CsvImporter {
+    mapping: Option<CsvColumnMapping>,
+    delimiter: Option<u8>,
+}
+
+impl CsvImporter {
+    /// Detects the Google or Outlook layout from the headers.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_mapping(mut self, mapping: CsvColumnMapping) -> Self {
+        self.mapping = Some(mapping);
+        self
+    }
+
+    /// Field delimiter; `,` unless set.
+    pub fn with_delimiter(mut self, delimiter: u8) -> Self {
+        self.delimiter = Some(delimiter);
+        self
+    }
+
+    pub fn preview_file(
+        &self,
+        path: &Path,
+        existing: &[Contact],
+    ) -> Result<CsvImportPreview, ContactStoreError> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| ContactStoreError::IoError(format!("{}: {e}", path.display())))?;
+        self.preview(&content, existing)
+    }
+
+    /// Validates `content` against `existing` contacts without writing anything.
+    pub fn preview(
+        &self,
+        content: &str,
+        existing: &[Contact],
+    ) -> Result<CsvImportPreview, ContactStoreError> {
+        let content = content.trim_start_matches(BOM);
+        let mut reader = csv::ReaderBuilder::new()
+            .delimiter(self.delimiter.unwrap_or(b','))
+            .flexible(true)
+            .from_reader(content.as_bytes());
+
+        let headers: Vec<String> = reader
+            .headers()
+            .map_err(|e| ContactStoreError::ParseError(e.to_string()))?
+            .iter()
+            .map(|header| header.trim().to_string())
+            .collect();
+        let mapping = match &self.mapping {
+            Some(mapping) => mapping.clone(),
+            None => CsvColumnMapping::detect(&headers).ok_or_else(|| {
+                ContactStoreError::ParseError(
+                    "Unknown CSV layout; supply a column mapping".to_string(),
+                )
+            })?,
+        };
+        let fields: Vec<Option<CsvField>> = headers.iter().map(|h| mapping.field(h)).collect();
+        let unmapped_columns = headers
+            .iter()
+            .zip(&fields)
+            .filter(|(header, field)| field.is_none() && !header.is_empty())
+            .map(|(header, _)| header.clone())
+            .collect();
+
+        let mut rows: Vec<CsvImportRow> = Vec::new();
+        for record in reader.records() {
+            let record = record.map_err(|e| ContactStoreError::ParseError(e.to_string()))?;
+            let line = record
+                .position()
+                .map(|p| p.line() as usize)
+                .unwrap_or_default();
+            let values: Vec<(&str, CsvField, &str)> = headers
+                .iter()
+                .zip(&fields)
+                .zip(record.iter())
+                .filter_map(|((header, field), value)| {
+                    let value = value.trim();
+                    field
+                        .filter(|_| !value.is_empty())
+                        .map(|field| (header.as_str(), field, value))
+                })
+                .collect();
+            if values.is_empty() {
+                continue;
+            }
+
+            let (contact, errors) = build_contact(&values);
+            let duplicate_of = if errors.is_empty() {
+                find_duplicate(&contact, existing, &rows)
+            } else {
+                None
+            };
+            let status = match (&duplicate_of, errors.is_empty()) {
+                (_, false) => CsvRowStatus::Invalid,
+                (Some(_), true) => CsvRowStatus::Duplicate,
+                (None, true) => CsvRowStatus::Valid,
+            };
+            rows.push(CsvImportRow {
+                line,
+                status,
+                contact: Some(contact),
+                errors,
+                duplicate_of,
+            });
+        }
+
+        Ok(CsvImportPreview {
+            mapping: mapping.name,
+            rows,
+            unmapped_columns,
+        })
+    }
+}
+
+#[derive(Default)]
+struct SlotValues {
+    values: Vec<String>,
+    type_text: Option<String>,
+    label: Option<ContactLabel>,
+    parts: BTreeMap<CsvAddressPart, String>,
+}
+
+fn build_contact(values: &[(&str, CsvField, &str)]) -> (Contact, Vec<String>) {
+    let mut contact = Contact::new("", "", "");
+    let mut errors = Vec::new();
+    let mut emails: BTreeMap<u8, SlotValues> = BTreeMap::new();
+    let mut phones: BTreeMap<u8, SlotValues> = BTreeMap::new();
+    let mut addresses: BTreeMap<u8, SlotValues> = BTreeMap::new();
+
+    for &(header, field, value) in values {
+        match field {
+            CsvField::FirstName => contact.first_name = value.to_string(),
+            CsvField::LastName => contact.last_name = value.to_string(),
+            CsvField::DisplayName => contact.display_name = value.to_string(),
+            CsvField::Nickname => contact.nickname = Some(value.to_string()),
+            CsvField::Company => contact.company = Some(value.to_string()),
+            CsvField::JobTitle => contact.job_title = Some(value.to_string()),
+            CsvField::Department => contact.department = Some(value.to_string()),
+            CsvField::Notes => contact.notes = Some(value.to_string()),
+            CsvField::Tags => contact.tags.extend(split_tags(value)),
+            CsvField::Birthday => match normalize_birthday(value) {
+                Some(Some(birthday)) => contact.birthday = Some(birthday),
+                Some(None) => {}
+                None => errors.push(format!("Invalid birthday '{value}' in column '{header}'")),
+            },
+            CsvField::Email { slot, label } => {
+                let slot = emails.entry(slot).or_default();
+                slot.values.extend(split_multi(value));
+                slot.label = slot.label.or(label);
+            }
+            CsvField::EmailType { slot } => {
+                emails.entry(slot).or_default().type_text = Some(value.to_string())
+            }
+            CsvField::Phone { slot, label } => {
+                let slot = phones.entry(slot).or_default();
+                slot.values.extend(split_multi(value));
+                slot.label = slot.label.or(label);
+            }
+            CsvField::PhoneType { slot } => {
+                phones.entry(slot).or_default().type_text = Some(value.to_string())
+            }
+            CsvField::Address { slot, part, label } => {
+                let slot = addresses.entry(slot).or_default();
+                slot.parts.insert(part, value.to_string());
+                slot.label = slot.label.or(label);
+            }
+            CsvField::AddressType { slot } => {
+                addresses.entry(slot).or_default().type_text = Some(value.to_string())
+            }
+        }
+    }
+
+    for slot in emails.values() {
+        let (label, primary) = slot_label(slot, true);
+        for address in &slot.values {
+            if !is_valid_email(address) {
+                errors.push(format!("Invalid email '{address}'"));
+            }
+            contact
+                .emails
+                .push(ContactEmail::new(label, address).with_primary(primary));
+        }
+    }
+    for slot in phones.values() {
+        let (label, primary) = slot_label(slot, false);
+        for number in &slot.values {
+            if !number.chars().any(|c| c.is_ascii_digit()) {
+                errors.push(format!("Invalid phone number '{number}'"));
+            }
+            contact
+                .phone_numbers
+                .push(ContactPhone::new(label, number).with_primary(primary));
+        }
+    }
+    for slot in addresses.values().filter(|slot| !slot.parts.is_empty()) {
+        let (label, primary) = slot_label(slot, false);
+        let part = |part: CsvAddressPart| slot.parts.get(&part).cloned();
+        contact.addresses.push(ContactAddress {
+            label,
+            street: part(CsvAddressPart::Street),
+            city: part(CsvAddressPart::City),
+            state: part(CsvAddressPart::State),
+            postal_code: part(CsvAddressPart::PostalCode),
+            country: part(CsvAddressPart::Country),
+            primary,
+        });
+    }
+
+    if contact.display_name.is_empty() {
+        contact.display_name = contact.name();
+    }
+    if contact.display_name.is_empty() && contact.emails.is_empty() {
+        errors.push("Row has no name or email".to_string());
+    }
+    (contact, errors)
+}
+
+/// Fixed label from the mapping, else the label column; Google marks the
+/// primary value with a leading `* `.
+fn slot_label(slot: &SlotValues, is_email: bool) -> (ContactLabel, bool) {
+    let type_text = slot.type_text.as_deref().unwrap_or_default();
+    let primary = type_text.starts_with('*');
+    let label = slot
+        .label
+        .unwrap_or_else(|| label_from_text(type_text, is_email));
+    (label, primary)
+}
+
+fn label_from_text(text: &str, is_email: bool) -> ContactLabel {
+    let text = text.trim_start_matches('*').trim().to_lowercase();
+    if text.contains("mobile") || text.contains("cell") {
+        ContactLabel::Mobile
+    } else if text.contains("work") || text.contains("business") || text.contains("office") {
+        ContactLabel::Work
+    } else if text.contains("home") && is_email {
+        ContactLabel::Personal
+    } else if text.contains("home") {
+        ContactLabel::Home
+    } else if text.contains("personal") {
+        ContactLabel::Personal
+    } else {
+        ContactLabel::Other
+    }
+}
+
+/// Google packs several values into one cell separated by ` ::: `.
+fn split_multi(value: &str) -> Vec<String> {
+    value
+        .split(MULTI_VALUE_SEPARATOR)
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Splits Google labels (` ::: `) and Outlook categories (`;`), dropping
+/// system groups such as `* myContacts`.
+fn split_tags(value: &str) -> Vec<String> {
+    value
+        .split(MULTI_VALUE_SEPARATOR)
+        .flat_map(|tag| tag.split(';'))
+        .map(str::trim)
+        .filter(|tag| !tag.is_empty() && !tag.starts_with('*'))
+        .map(str::to_string)
+        .collect()
+}
+
+fn is_valid_email(address: &str) -> bool {
+    match address.split_once('@') {
+        Some((local, domain)) => {
+            !local.is_empty()
+                && domain.contains('.')
+                && !domain.starts_with('.')
+                && !domain.ends_with('.')
+                && !address.contains(char::is_whitespace)
+        }
+        None => false,
+    }
+}
+
+/// `YYYY-MM-DD`, `--MM-DD` or Outlook's `M/D/YYYY`. `Some(None)` is Outlook's
+/// empty `0/0/00`; `None` means the value is not a date.
+fn normalize_birthday(value: &str) -> Option<Option<String>> {
+    let numbers = |parts: &[&str]| -> Option<Vec<u32>> {
+        parts.iter().map(|part| part.parse().ok()).collect()
+    };
+    let valid = |month: u32, day: u32| (1..=12).contains(&month) && (1..=31).contains(&day);
+
+    if let Some(month_day) = value.strip_prefix("--") {
+        let parts = numbers(&month_day.split('-').collect::<Vec<_>>())?;
+        return match parts.as_slice() {
+            [month, day] if valid(*month, *day) => Some(Some(format!("--{month:02}-{day:02}"))),
+            _ => None,
+        };
+    }
+    if value.contains('/') {
+        let parts = numbers(&value.split('/').collect::<Vec<_>>())?;
+        return match parts.as_slice() {
+            [0, 0, _] => Some(None),
+            [month, day, year] if valid(*month, *day) && *year >= 1000 => {
+                Some(Some(format!("{year:04}-{month:02}-{day:02}")))
+            }
+            _ => None,
+        };
+    }
+    let parts = numbers(&value.split('-').collect::<Vec<_>>())?;
+    match parts.as_slice() {
+        [year, month, day] if valid(*month, *day) && *year >= 1000 => {
+            Some(Some(format!("{year:04}-{month:02}-{day:02}")))
+        }
+        _ => None,
+    }
+}
+
+/// An existing contact or earlier valid row sharing an email, a phone number,
+/// or the same name at the same company.
+fn find_duplicate(
+    contact: &Contact,
+    existing: &[Contact],
+    rows: &[CsvImportRow],
+) -> Option<String> {
+    if let Some(duplicate) = existing.iter().find(|other| is_duplicate(contact, other)) {
+        return Some(duplicate.id.clone());
+    }
+    rows.iter()
+        .filter(|row| row.status == CsvRowStatus::Valid)
+        .find(|row| {
+            row.contact
+                .as_ref()
+                .is_some_and(|other| is_duplicate(contact, other))
+        })
+        .map(|row| format!("line {}", row.line))
+}
+
+fn is_duplicate(a: &Contact, b: &Contact) -> bool {
+    let shares_email = a.emails.iter().any(|email| {
+        b.emails
+            .iter()
+            .any(|other| other.address.eq_ignore_ascii_case(&email.address))
+    });
+    let shares_phone = a
+        .phone_numbers
+        .iter()
+        .any(|phone| b.phone_numbers.iter().any(|other| phone.same_number(other)));
+    let same_person = comparable_name(a) == comparable_name(b)
+        && !comparable_name(a).is_empty()
+        && a.company.as_deref().map(str::to_lowercase)
+            == b.company.as_deref().map(str::to_lowercase);
+    shares_email || shares_phone || same_person
+}
+
+fn comparable_name(contact: &Contact) -> String {
+    contact
+        .name()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+const BOM: char = '\u{feff}';
+const MULTI_VALUE_SEPARATOR: &str = ":::";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GOOGLE_CSV: &str = "\u{feff}First Name,Last Name,Organization Name,Birthday,Labels,E-mail 1 - Label,E-mail 1 - Value,E-mail 2 - Label,E-mail 2 - Value,Phone 1 - Label,Phone 1 - Value,Address 1 - Label,Address 1 - City,Address 1 - Country,Photo
+Eva,Green,Green Studio,1990-03-22,* myContacts ::: Friends,* Work,eva@green.studio,Home,eva.green@gmail.com,Mobile,+55 11 98888-7777,Home,São Paulo,Brasil,https://example.com/eva.jpg
+Carlos,Silva,,--07-04,,Work,carlos@acme.com ::: carlos.silva@acme.com,,,,,,,,
+Broken,Row,,31/31/2020,,,not-an-email,,,,,,,,
+,,,,,,,,,,,,,,
+";
+
+    #[test]
+    fn test_preview_google_export() {
+        let preview = CsvImporter::new().preview(GOOGLE_CSV, &[]).unwrap();
+        assert_eq!(preview.mapping, "google");
+        assert_eq!(preview.unmapped_columns, vec!["Photo"]);
+        assert_eq!(preview.rows.len(), 3);
+
+        let eva = preview.rows[0].contact.as_ref().unwrap();
+        assert_eq!(preview.rows[0].line, 2);
+        assert_eq!(preview.rows[0].status, CsvRowStatus::Valid);
+        assert_eq!(eva.display_name, "Eva Green");
+        assert_eq!(eva.tags, vec!["Friends"]);
+        assert_eq!(eva.emails[0].label, ContactLabel::Work);
+        assert!(eva.emails[0].primary);
+        assert_eq!(eva.emails[1].label, ContactLabel::Personal);
+        assert_eq!(eva.phone_numbers[0].label, ContactLabel::Mobile);
+        assert_eq!(eva.addresses[0].city.as_deref(), Some("São Paulo"));
+
+        let carlos = preview.rows[1].contact.as_ref().unwrap();
+        assert_eq!(carlos.emails.len(), 2);
+        assert_eq!(carlos.birthday.as_deref(), Some("--07-04"));
+    }
+
+    #[test]
+    fn test_preview_reports_row_errors() {
+        let preview = CsvImporter::new().preview(GOOGLE_CSV, &[]).unwrap();
+        let broken = &preview.rows[2];
+        assert_eq!(broken.status, CsvRowStatus::Invalid);
+        assert_eq!(broken.line, 4);
+        assert_eq!(
+            broken.errors,
+            vec![
+                "Invalid birthday '31/31/2020' in column 'Birthday'",
+                "Invalid email 'not-an-email'"
+            ]
+        );
+        assert_eq!(preview.count(CsvRowStatus::Valid), 2);
+    }
+
+    #[test]
+    fn test_preview_outlook_export_with_fixed_labels() {
+        let csv = "First Name,Last Name,E-mail Address,Mobile Phone,Business Phone,Business City,Birthday,Categories\n\
+                   Fox,Clever,fox@clever.com,+1 555 111 2222,+1 555 333 4444,Chicago,6/15/1985,Clients;VIP\n\
+                   Bear,Strong,,,,,0/0/00,\n";
+        let preview = CsvImporter::new().preview(csv, &[]).unwrap();
+        assert_eq!(preview.mapping, "outlook");
+
+        let fox = preview.rows[0].contact.as_ref().unwrap();
+        assert_eq!(fox.phone_numbers[0].label, ContactLabel::Mobile);
+        assert_eq!(fox.phone_numbers[1].label, ContactLabel::Work);
+        assert_eq!(fox.addresses[0].label, ContactLabel::Work);
+        assert_eq!(fox.birthday.as_deref(), Some("1985-06-15"));
+        assert_eq!(fox.tags, vec!["Clients", "VIP"]);
+
+        let bear = preview.rows[1].contact.as_ref().unwrap();
+        assert_eq!(bear.birthday, None);
+        assert_eq!(preview.rows[1].status, CsvRowStatus::Valid);
+    }
+
+    #[test]
+    fn test_duplicates_against_existing_and_earlier_rows() {
+        let existing = vec![
+            Contact::new("c001", "Eva", "Green")
+                .with_email(ContactEmail::new(ContactLabel::Work, "EVA@green.studio")),
+        ];
+        let csv = "Given Name,Family Name,E-mail 1 - Value,Phone 1 - Value\n\
+                   Eva,G.,eva@green.studio,\n\
+                   Rabbit,Quick,,(555) 901-2345\n\
+                   R.,Quick,,+555-901-2345\n";
+        let preview = CsvImporter::new().preview(csv, &existing).unwrap();
+
+        assert_eq!(preview.rows[0].status, CsvRowStatus::Duplicate);
+        assert_eq!(preview.rows[0].duplicate_of.as_deref(), Some("c001"));
+        assert_eq!(preview.rows[1].status, CsvRowStatus::Valid);
+        assert_eq!(preview.rows[2].status, CsvRowStatus::Duplicate);
+        assert_eq!(preview.rows[2].duplicate_of.as_deref(), Some("line 3"));
+    }
+
+    #[test]
+    fn test_custom_mapping_and_delimiter() {
+        let mapping = CsvColumnMapping::new("crm")
+            .with_column("Nome", CsvField::FirstName)
+            .with_column("E-mail", "email.1:work".parse().unwrap());
+        let preview = CsvImporter::new()
+            .with_mapping(mapping)
+            .with_delimiter(b';')
+            .preview("Nome;E-mail;Cargo\nJoão;joao@acme.com;Dev\n", &[])
+            .unwrap();
+
+        assert_eq!(preview.unmapped_columns, vec!["Cargo"]);
+        let joao = preview.rows[0].contact.as_ref().unwrap();
+        assert_eq!(joao.name(), "João");
+        assert_eq!(joao.emails[0].label, ContactLabel::Work);
+    }
+
+    #[test]
+    fn test_unknown_layout_needs_a_mapping() {
+        let result = CsvImporter::new().preview("Nome,Telefone\nJoão,123\n", &[]);
+        assert!(matches!(result, Err(ContactStoreError::ParseError(_))));
+    }
+
+    #[test]
+    fn test_normalize_birthday_formats() {
+        assert_eq!(
+            normalize_birthday("1985-6-5"),
+            Some(Some("1985-06-05".to_string()))
+        );
+        assert_eq!(
+            normalize_birthday("--02-29"),
+            Some(Some("--02-29".to_string()))
+        );
+        assert_eq!(
+            normalize_birthday("12/25/1990"),
+            Some(Some("1990-12-25".to_string()))
+        );
+        assert_eq!(normalize_birthday("0/0/00"), Some(None));
+        assert_eq!(normalize_birthday("yesterday"), None);
+        assert_eq!(normalize_birthday("1985-13-01"), None);
+    }
+}
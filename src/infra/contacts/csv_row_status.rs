@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CsvRowStatus {
+    /// Will be created on commit.
+    Valid,
+    /// Failed validation; see the row errors.
+    Invalid,
+    /// Matches an existing contact or an earlier row and is skipped.
+    Duplicate,
+}
+
+impl CsvRowStatus {
+    pub fn to_str(&self) -> &str {
+        match self {
+            Self::Valid => "valid",
+            Self::Invalid => "invalid",
+            Self::Duplicate => "duplicate",
+        }
+    }
+}
+
+impl fmt::Display for CsvRowStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_str())
+    }
+}
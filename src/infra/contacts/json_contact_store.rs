@@ -0,0 +1,229 @@
+use std::{
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use super::{
+    contact::Contact, contact_store::ContactStore, contact_store_error::ContactStoreError,
+    contacts_metadata::ContactsMetadata, user_contacts::UserContacts,
+};
+
+/// Contact store backed by a JSON file in the `spec/contacts.json` format.
+///
+/// Every write goes to a temporary file next to the target and is renamed
+/// over it, so a crash never leaves a half-written contacts file.
+#[derive(Debug)]
+pub struct JsonContactStore {
+    path: PathBuf,
+    document: UserContacts,
+}
+
+impl JsonContactStore {
+    /// Opens the store at `path`, starting empty when the file does not exist yet.
+    pub fn open(path: &Path) -> Result<Self, ContactStoreError> {
+        let mut store = Self {
+            path: path.to_path_buf(),
+            document: UserContacts::default(),
+        };
+        if path.exists() {
+            store.load()?;
+        }
+        Ok(store)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn metadata(&self) -> &ContactsMetadata {
+        &self.document.metadata
+    }
+
+    /// Re-reads the file, discarding the in-memory document.
+    pub fn load(&mut self) -> Result<(), ContactStoreError> {
+        let content = fs::read_to_string(&self.path).map_err(|e| self.io_error(&self.path, e))?;
+        self.document = UserContacts::from_json_str(&content)
+            .map_err(|e| ContactStoreError::ParseError(format!("{}: {e}", self.path.display())))?;
+        Ok(())
+    }
+
+    pub fn save(&self) -> Result<(), ContactStoreError> {
+        self.write(&self.document)
+    }
+
+    fn write(&self, document: &UserContacts) -> Result<(), ContactStoreError> {
+        if let Some(parent) = self
+            .path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            fs::create_dir_all(parent).map_err(|e| self.io_error(parent, e))?;
+        }
+
+        let content = document.to_json_string()?;
+        let temp_path = self.temp_path();
+        let mut file = fs::File::create(&temp_path).map_err(|e| self.io_error(&temp_path, e))?;
+        file.write_all(content.as_bytes())
+            .and_then(|_| file.sync_all())
+            .map_err(|e| self.io_error(&temp_path, e))?;
+        fs::rename(&temp_path, &self.path).map_err(|e| self.io_error(&self.path, e))
+    }
+
+    /// Persists `document` and only then makes it the current state.
+    fn commit(&mut self, mut document: UserContacts) -> Result<(), ContactStoreError> {
+        document.refresh_metadata();
+        self.write(&document)?;
+        self.document = document;
+        Ok(())
+    }
+
+    fn temp_path(&self) -> PathBuf {
+        let mut name = self
+            .path
+            .file_name()
+            .map(|name| name.to_os_string())
+            .unwrap_or_default();
+        name.push(TEMP_SUFFIX);
+        self.path.with_file_name(name)
+    }
+
+    fn io_error(&self, path: &Path, error: std::io::Error) -> ContactStoreError {
+        ContactStoreError::IoError(format!("{}: {error}", path.display()))
+    }
+}
+
+impl ContactStore for JsonContactStore {
+    fn list(&self) -> Result<Vec<Contact>, ContactStoreError> {
+        Ok(self.document.contacts.clone())
+    }
+
+    fn get(&self, id: &str) -> Result<Option<Contact>, ContactStoreError> {
+        Ok(self.document.find(id).cloned())
+    }
+
+    fn create(&mut self, mut contact: Contact) -> Result<Contact, ContactStoreError> {
+        if contact.id.trim().is_empty() {
+            contact.id = self.document.next_id();
+        } else if self.document.find(&contact.id).is_some() {
+            return Err(ContactStoreError::DuplicateId(contact.id));
+        }
+
+        let mut document = self.document.clone();
+        document.contacts.push(contact.clone());
+        self.commit(document)?;
+        Ok(contact)
+    }
+
+    fn update(&mut self, contact: Contact) -> Result<Contact, ContactStoreError> {
+        let mut document = self.document.clone();
+        let existing = document
+            .contacts
+            .iter_mut()
+            .find(|existing| existing.id == contact.id)
+            .ok_or_else(|| ContactStoreError::NotFound(contact.id.clone()))?;
+        *existing = contact.clone();
+        self.commit(document)?;
+        Ok(contact)
+    }
+
+    fn delete(&mut self, id: &str) -> Result<Contact, ContactStoreError> {
+        let mut document = self.document.clone();
+        let index = document
+            .contacts
+            .iter()
+            .position(|contact| contact.id == id)
+            .ok_or_else(|| ContactStoreError::NotFound(id.to_string()))?;
+        let removed = document.contacts.remove(index);
+        self.commit(document)?;
+        Ok(removed)
+    }
+}
+
+const TEMP_SUFFIX: &str = ".tmp";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_store_path(name: &str) -> PathBuf {
+        std::env::temp_dir()
+            .join(format!("json_contact_store_{name}_{}", std::process::id()))
+            .join("contacts.json")
+    }
+
+    #[test]
+    fn test_open_missing_file_starts_empty() {
+        let path = temp_store_path("missing");
+        let store = JsonContactStore::open(&path).unwrap();
+        assert!(store.list().unwrap().is_empty());
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_crud_persists_every_write() {
+        let path = temp_store_path("crud");
+        let mut store = JsonContactStore::open(&path).unwrap();
+
+        let created = store.create(Contact::new("", "Eva", "Green")).unwrap();
+        assert_eq!(created.id, "c001");
+        assert!(matches!(
+            store.create(Contact::new("c001", "Eva", "Again")),
+            Err(ContactStoreError::DuplicateId(_))
+        ));
+
+        store
+            .update(created.clone().with_company("Green Studio"))
+            .unwrap();
+        let reopened = JsonContactStore::open(&path).unwrap();
+        assert_eq!(
+            reopened.get("c001").unwrap().and_then(|c| c.company),
+            Some("Green Studio".to_string())
+        );
+        assert_eq!(reopened.metadata().total_contacts, 1);
+
+        store.delete("c001").unwrap();
+        assert!(matches!(
+            store.delete("c001"),
+            Err(ContactStoreError::NotFound(_))
+        ));
+        assert!(matches!(
+            store.update(created),
+            Err(ContactStoreError::NotFound(_))
+        ));
+
+        let reopened = JsonContactStore::open(&path).unwrap();
+        assert!(reopened.list().unwrap().is_empty());
+        assert!(!store.temp_path().exists());
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn test_failed_write_keeps_previous_state() {
+        let path = temp_store_path("readonly");
+        let mut store = JsonContactStore::open(&path).unwrap();
+        store.create(Contact::new("c001", "Eva", "Green")).unwrap();
+
+        // A directory in place of the temp file makes the next write fail.
+        fs::create_dir_all(store.temp_path()).unwrap();
+        let result = store.create(Contact::new("c002", "Carlos", "Silva"));
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+
+        assert!(matches!(result, Err(ContactStoreError::IoError(_))));
+        assert_eq!(store.list().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_load_reports_parse_errors_with_path() {
+        let path = temp_store_path("invalid");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, "not json").unwrap();
+        let result = JsonContactStore::open(&path);
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+
+        match result {
+            Err(ContactStoreError::ParseError(msg)) => assert!(msg.contains("contacts.json")),
+            other => panic!("expected parse error, got {other:?}"),
+        }
+    }
+}
@@ -1 +1,45 @@
+pub mod contact;
+pub mod contact_address;
+pub mod contact_email;
+pub mod contact_label;
+pub mod contact_phone;
+pub mod contact_query;
+pub mod contact_store;
+pub mod contact_store_error;
+pub mod contacts_metadata;
+pub mod csv_address_part;
+pub mod csv_column_mapping;
+pub mod csv_field;
+pub mod csv_import_preview;
+pub mod csv_import_row;
+pub mod csv_importer;
+pub mod csv_row_status;
+pub mod json_contact_store;
+pub mod social_profile;
 pub mod user_contacts;
+pub mod vcard;
+pub mod vcard_property;
+pub mod vcard_version;
+
+pub use contact::Contact;
+pub use contact_address::ContactAddress;
+pub use contact_email::ContactEmail;
+pub use contact_label::ContactLabel;
+pub use contact_phone::ContactPhone;
+pub use contact_query::ContactQuery;
+pub use contact_store::ContactStore;
+pub use contact_store_error::ContactStoreError;
+pub use contacts_metadata::ContactsMetadata;
+pub use csv_address_part::CsvAddressPart;
+pub use csv_column_mapping::CsvColumnMapping;
+pub use csv_field::CsvField;
+pub use csv_import_preview::CsvImportPreview;
+pub use csv_import_row::CsvImportRow;
+pub use csv_importer::CsvImporter;
+pub use csv_row_status::CsvRowStatus;
+pub use json_contact_store::JsonContactStore;
+pub use social_profile::SocialProfile;
+pub use user_contacts::UserContacts;
+pub use vcard::VCard;
+pub use vcard_property::VCardProperty;
+pub use vcard_version::VCardVersion;
@@ -1 +1,88 @@
+use serde::{Deserialize, Serialize};
 
+use super::{
+    contact::Contact, contact_store_error::ContactStoreError, contacts_metadata::ContactsMetadata,
+};
+
+/// The contacts document: the root object of `spec/contacts.json`.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct UserContacts {
+    #[serde(default)]
+    pub contacts: Vec<Contact>,
+    #[serde(default)]
+    pub metadata: ContactsMetadata,
+}
+
+impl UserContacts {
+    pub fn new(contacts: Vec<Contact>) -> Self {
+        let mut document = Self {
+            contacts,
+            metadata: ContactsMetadata::default(),
+        };
+        document.refresh_metadata();
+        document
+    }
+
+    pub fn from_json_str(content: &str) -> Result<Self, ContactStoreError> {
+        serde_json::from_str(content).map_err(|e| ContactStoreError::ParseError(e.to_string()))
+    }
+
+    pub fn to_json_string(&self) -> Result<String, ContactStoreError> {
+        serde_json::to_string_pretty(self).map_err(|e| ContactStoreError::ParseError(e.to_string()))
+    }
+
+    pub fn find(&self, id: &str) -> Option<&Contact> {
+        self.contacts.iter().find(|contact| contact.id == id)
+    }
+
+    /// Next free id in the `c001` sequence used by the spec file.
+    pub fn next_id(&self) -> String {
+        let next = self
+            .contacts
+            .iter()
+            .filter_map(|contact| contact.id.strip_prefix(ID_PREFIX)?.parse::<u32>().ok())
+            .max()
+            .unwrap_or(0)
+            + 1;
+        format!("{ID_PREFIX}{next:03}")
+    }
+
+    /// Keeps `metadata.totalContacts` in line with the contact list.
+    pub fn refresh_metadata(&mut self) {
+        self.metadata.total_contacts = self.contacts.len();
+    }
+}
+
+const ID_PREFIX: &str = "c";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_id_continues_the_sequence() {
+        let document = UserContacts::new(vec![
+            Contact::new("c009", "Fox", "Clever"),
+            Contact::new("c017", "Turtle", "Patient"),
+            Contact::new("imported-1", "Eva", "Green"),
+        ]);
+        assert_eq!(document.next_id(), "c018");
+        assert_eq!(UserContacts::default().next_id(), "c001");
+    }
+
+    #[test]
+    fn test_new_counts_contacts() {
+        let document = UserContacts::new(vec![Contact::new("c001", "Eva", "Green")]);
+        assert_eq!(document.metadata.total_contacts, 1);
+        assert_eq!(
+            document.find("c001").map(Contact::name).as_deref(),
+            Some("Eva Green")
+        );
+    }
+
+    #[test]
+    fn test_from_json_str_rejects_invalid_documents() {
+        let result = UserContacts::from_json_str("{\"contacts\": 3}");
+        assert!(matches!(result, Err(ContactStoreError::ParseError(_))));
+    }
+}
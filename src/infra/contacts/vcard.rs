@@ -0,0 +1,613 @@
+use std::{fs, path::Path};
+
+use super::{
+    contact::Contact,
+    contact_address::ContactAddress,
+    contact_email::ContactEmail,
+    contact_label::ContactLabel,
+    contact_phone::ContactPhone,
+    contact_store_error::ContactStoreError,
+    social_profile::SocialProfile,
+    vcard_property::{VCardProperty, escape_text, split_components, unescape_text},
+    vcard_version::VCardVersion,
+};
+
+/// A single vCard (`BEGIN:VCARD` .. `END:VCARD`), version 3.0 or 4.0.
+///
+/// See the "vCard" section of ARCHITECTURE.md for how properties map to [`Contact`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct VCard {
+    pub version: VCardVersion,
+    /// Properties between `VERSION` and `END`, in file order.
+    pub properties: Vec<VCardProperty>,
+}
+
+impl VCard {
+    pub fn new(version: VCardVersion) -> Self {
+        Self {
+            version,
+            properties: Vec::new(),
+        }
+    }
+
+    /// Parses every card in `content`, unfolding continuation lines first.
+    pub fn parse_all(content: &str) -> Result<Vec<Self>, ContactStoreError> {
+        let mut cards = Vec::new();
+        let mut current: Option<Vec<VCardProperty>> = None;
+
+        for line in unfold(content) {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let property = VCardProperty::parse(&line)?;
+            match (property.name.as_str(), current.as_mut()) {
+                (BEGIN, None) => current = Some(Vec::new()),
+                (BEGIN, Some(_)) => return Err(parse_error("Nested BEGIN:VCARD")),
+                (END, Some(_)) => {
+                    let properties = current.take().unwrap_or_default();
+                    cards.push(Self::from_properties(properties)?);
+                }
+                (_, Some(properties)) => properties.push(property),
+                (_, None) => {
+                    return Err(parse_error(&format!(
+                        "Property outside BEGIN:VCARD: {}",
+                        property.name
+                    )));
+                }
+            }
+        }
+
+        if current.is_some() {
+            return Err(parse_error("Missing END:VCARD"));
+        }
+        Ok(cards)
+    }
+
+    fn from_properties(properties: Vec<VCardProperty>) -> Result<Self, ContactStoreError> {
+        let version = properties
+            .iter()
+            .find(|property| property.name == VERSION)
+            .ok_or_else(|| parse_error("Missing VERSION"))?
+            .value
+            .parse()?;
+        Ok(Self {
+            version,
+            properties: properties
+                .into_iter()
+                .filter(|property| property.name != VERSION)
+                .collect(),
+        })
+    }
+
+    /// Reads every card of a `.vcf` file into contacts.
+    pub fn import(path: &Path) -> Result<Vec<Contact>, ContactStoreError> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| ContactStoreError::IoError(format!("{}: {e}", path.display())))?;
+        Self::parse_all(&content)
+            .map_err(|e| ContactStoreError::ParseError(format!("{}: {e}", path.display())))?
+            .iter()
+            .map(Self::to_contact)
+            .collect()
+    }
+
+    /// Writes `contacts` as one `.vcf` file.
+    pub fn export(
+        path: &Path,
+        contacts: &[Contact],
+        version: VCardVersion,
+    ) -> Result<(), ContactStoreError> {
+        let content: String = contacts
+            .iter()
+            .map(|contact| Self::from_contact(contact, version).render())
+            .collect();
+        fs::write(path, content)
+            .map_err(|e| ContactStoreError::IoError(format!("{}: {e}", path.display())))
+    }
+
+    pub fn to_contact(&self) -> Result<Contact, ContactStoreError> {
+        let mut contact = Contact::new("", "", "");
+
+        for property in &self.properties {
+            let text = || unescape_text(&property.value);
+            match property.name.as_str() {
+                "UID" => contact.id = text(),
+                "FN" => contact.display_name = text(),
+                "N" => {
+                    let names = split_components(&property.value, ';');
+                    contact.last_name = names.first().cloned().unwrap_or_default();
+                    contact.first_name = names.get(1).cloned().unwrap_or_default();
+                }
+                "NICKNAME" => contact.nickname = non_empty(text()),
+                "ORG" => {
+                    let units = split_components(&property.value, ';');
+                    contact.company = units.first().cloned().and_then(non_empty);
+                    contact.department = units.get(1).cloned().and_then(non_empty);
+                }
+                "TITLE" => contact.job_title = non_empty(text()),
+                "TEL" => contact.phone_numbers.push(ContactPhone {
+                    label: phone_label(property),
+                    number: text().trim_start_matches(TEL_URI).to_string(),
+                    primary: is_preferred(property),
+                }),
+                "EMAIL" => contact.emails.push(ContactEmail {
+                    label: email_label(property),
+                    address: text(),
+                    primary: is_preferred(property),
+                }),
+                "ADR" => contact.addresses.push(address(property)),
+                "SOCIALPROFILE" | "X-SOCIALPROFILE" => {
+                    contact.social_profiles.push(social_profile(property))
+                }
+                "BDAY" => contact.birthday = non_empty(normalize_date(&text())),
+                "ANNIVERSARY" | X_ANNIVERSARY => {
+                    contact.anniversary = non_empty(normalize_date(&text()))
+                }
+                "NOTE" => contact.notes = Some(text()),
+                "PHOTO" => contact.photo = Some(photo(property)),
+                "CATEGORIES" => contact.tags.extend(
+                    split_components(&property.value, ',')
+                        .into_iter()
+                        .filter_map(non_empty),
+                ),
+                X_FAVORITE => contact.favorite = property.value.eq_ignore_ascii_case("true"),
+                X_CREATED => contact.created_at = non_empty(text()),
+                "REV" => contact.updated_at = non_empty(text()),
+                name if name.starts_with("X-") => {
+                    contact
+                        .extensions
+                        .insert(name.to_string(), property.value.clone());
+                }
+                _ => {}
+            }
+        }
+
+        if contact.display_name.is_empty() {
+            contact.display_name = contact.name();
+        }
+        Ok(contact)
+    }
+
+    pub fn from_contact(contact: &Contact, version: VCardVersion) -> Self {
+        let mut card = Self::new(version);
+        let text = |name: &str, value: &str| VCardProperty::new(name, &escape_text(value));
+
+        if !contact.id.is_empty() {
+            card.push(text("UID", &contact.id));
+        }
+        card.push(text("FN", &contact.name()));
+        card.push(VCardProperty::new(
+            "N",
+            &format!(
+                "{};{};;;",
+                escape_text(&contact.last_name),
+                escape_text(&contact.first_name)
+            ),
+        ));
+        if let Some(nickname) = &contact.nickname {
+            card.push(text("NICKNAME", nickname));
+        }
+        if contact.company.is_some() || contact.department.is_some() {
+            let company = escape_text(contact.company.as_deref().unwrap_or_default());
+            let value = match &contact.department {
+                Some(department) => format!("{company};{}", escape_text(department)),
+                None => company,
+            };
+            card.push(VCardProperty::new("ORG", &value));
+        }
+        if let Some(job_title) = &contact.job_title {
+            card.push(text("TITLE", job_title));
+        }
+        for phone in &contact.phone_numbers {
+            let property = match version {
+                VCardVersion::V3 => text("TEL", &phone.number),
+                VCardVersion::V4 => {
+                    VCardProperty::new("TEL", &format!("{TEL_URI}{}", phone.number))
+                        .with_param("VALUE", "uri")
+                }
+            };
+            card.push(typed(
+                property,
+                phone_type(phone.label),
+                phone.primary,
+                version,
+            ));
+        }
+        for email in &contact.emails {
+            let property = text("EMAIL", &email.address);
+            card.push(typed(
+                property,
+                email_type(email.label),
+                email.primary,
+                version,
+            ));
+        }
+        for address in &contact.addresses {
+            let components = [
+                "",
+                "",
+                address.street.as_deref().unwrap_or_default(),
+                address.city.as_deref().unwrap_or_default(),
+                address.state.as_deref().unwrap_or_default(),
+                address.postal_code.as_deref().unwrap_or_default(),
+                address.country.as_deref().unwrap_or_default(),
+            ];
+            let value: Vec<String> = components.iter().map(|c| escape_text(c)).collect();
+            let property = VCardProperty::new("ADR", &value.join(";"));
+            card.push(typed(
+                property,
+                address.label.to_str(),
+                address.primary,
+                version,
+            ));
+        }
+        for profile in &contact.social_profiles {
+            let mut property = text(X_SOCIALPROFILE, profile.url.as_deref().unwrap_or_default())
+                .with_param("TYPE", &profile.platform);
+            if let Some(username) = &profile.username {
+                property = property.with_param(X_USER, username);
+            }
+            card.push(property);
+        }
+        if let Some(birthday) = &contact.birthday {
+            card.push(VCardProperty::new("BDAY", &date_value(birthday, version)));
+        }
+        if let Some(anniversary) = &contact.anniversary {
+            let name = match version {
+                VCardVersion::V3 => X_ANNIVERSARY,
+                VCardVersion::V4 => "ANNIVERSARY",
+            };
+            card.push(VCardProperty::new(name, &date_value(anniversary, version)));
+        }
+        if let Some(notes) = &contact.notes {
+            card.push(text("NOTE", notes));
+        }
+        if let Some(photo) = &contact.photo {
+            card.push(photo_property(photo, version));
+        }
+        if !contact.tags.is_empty() {
+            let tags: Vec<String> = contact.tags.iter().map(|tag| escape_text(tag)).collect();
+            card.push(VCardProperty::new("CATEGORIES", &tags.join(",")));
+        }
+        if contact.favorite {
+            card.push(VCardProperty::new(X_FAVORITE, "true"));
+        }
+        if let Some(created_at) = &contact.created_at {
+            card.push(text(X_CREATED, created_at));
+        }
+        if let Some(updated_at) = &contact.updated_at {
+            card.push(text("REV", updated_at));
+        }
+        for (name, value) in &contact.extensions {
+            card.push(VCardProperty::new(name, value));
+        }
+        card
+    }
+
+    fn push(&mut self, property: VCardProperty) {
+        self.properties.push(property);
+    }
+
+    /// The card as CRLF-terminated lines folded at 75 octets.
+    pub fn render(&self) -> String {
+        let mut content = String::new();
+        let header = [
+            VCardProperty::new(BEGIN, "VCARD"),
+            VCardProperty::new(VERSION, self.version.to_str()),
+        ];
+        let footer = VCardProperty::new(END, "VCARD");
+        for property in header.iter().chain(&self.properties).chain([&footer]) {
+            content.push_str(&fold(&property.render()));
+        }
+        content
+    }
+}
+
+/// Joins continuation lines (starting with a space or tab) to the previous line.
+fn unfold(content: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for line in content.lines() {
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(continuation), Some(previous)) => previous.push_str(continuation),
+            _ => lines.push(line.to_string()),
+        }
+    }
+    lines
+}
+
+fn fold(line: &str) -> String {
+    let mut folded = String::new();
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > LINE_OCTETS {
+            folded.push_str("\r\n ");
+            width = 1;
+        }
+        folded.push(c);
+        width += c.len_utf8();
+    }
+    folded.push_str("\r\n");
+    folded
+}
+
+fn typed(
+    property: VCardProperty,
+    label: &str,
+    primary: bool,
+    version: VCardVersion,
+) -> VCardProperty {
+    match (version, primary) {
+        (VCardVersion::V3, true) => property.with_param("TYPE", &format!("{label},pref")),
+        (VCardVersion::V4, true) => property.with_param("TYPE", label).with_param("PREF", "1"),
+        (_, false) => property.with_param("TYPE", label),
+    }
+}
+
+fn is_preferred(property: &VCardProperty) -> bool {
+    property.param("PREF").is_some() || property.has_type("pref")
+}
+
+fn phone_label(property: &VCardProperty) -> ContactLabel {
+    let types = property.types();
+    if types.iter().any(|t| t == "cell" || t == "mobile") {
+        ContactLabel::Mobile
+    } else {
+        known_label(&types)
+    }
+}
+
+fn phone_type(label: ContactLabel) -> &'static str {
+    match label {
+        ContactLabel::Mobile => "cell",
+        ContactLabel::Home => "home",
+        ContactLabel::Work => "work",
+        ContactLabel::Personal => "personal",
+        ContactLabel::Other => "other",
+    }
+}
+
+/// Email `home` is the spec's `personal`.
+fn email_label(property: &VCardProperty) -> ContactLabel {
+    match known_label(&property.types()) {
+        ContactLabel::Home => ContactLabel::Personal,
+        label => label,
+    }
+}
+
+fn email_type(label: ContactLabel) -> &'static str {
+    match label {
+        ContactLabel::Personal | ContactLabel::Home => "home",
+        ContactLabel::Work => "work",
+        ContactLabel::Mobile => "mobile",
+        ContactLabel::Other => "other",
+    }
+}
+
+fn known_label(types: &[String]) -> ContactLabel {
+    types
+        .iter()
+        .find_map(|t| t.parse::<ContactLabel>().ok())
+        .unwrap_or(ContactLabel::Other)
+}
+
+fn address(property: &VCardProperty) -> ContactAddress {
+    let components = split_components(&property.value, ';');
+    let component = |index: usize| components.get(index).cloned().and_then(non_empty);
+    ContactAddress {
+        label: known_label(&property.types()),
+        street: component(2),
+        city: component(3),
+        state: component(4),
+        postal_code: component(5),
+        country: component(6),
+        primary: is_preferred(property),
+    }
+}
+
+fn social_profile(property: &VCardProperty) -> SocialProfile {
+    let platform = property
+        .types()
+        .into_iter()
+        .find(|t| t != "pref")
+        .unwrap_or_else(|| ContactLabel::Other.to_str().to_string());
+    SocialProfile {
+        platform,
+        url: non_empty(unescape_text(&property.value)),
+        username: property
+            .param(X_USER)
+            .or_else(|| property.param("USERNAME"))
+            .map(str::to_string),
+    }
+}
+
+/// Inline vCard 3.0 photos become `data:` URIs, as vCard 4.0 writes them.
+fn photo(property: &VCardProperty) -> String {
+    let encoding = property.param("ENCODING").unwrap_or_default();
+    if encoding.eq_ignore_ascii_case("b") || encoding.eq_ignore_ascii_case("base64") {
+        let media = property
+            .types()
+            .into_iter()
+            .next()
+            .unwrap_or_else(|| "jpeg".to_string());
+        format!("{DATA_URI}image/{media};base64,{}", property.value)
+    } else {
+        property.value.clone()
+    }
+}
+
+fn photo_property(photo: &str, version: VCardVersion) -> VCardProperty {
+    let inline = photo
+        .strip_prefix(DATA_URI)
+        .and_then(|rest| rest.split_once(";base64,"));
+    match (version, inline) {
+        (VCardVersion::V3, Some((media, data))) => VCardProperty::new("PHOTO", data)
+            .with_param("ENCODING", "b")
+            .with_param("TYPE", &media.trim_start_matches("image/").to_uppercase()),
+        (VCardVersion::V3, None) => VCardProperty::new("PHOTO", photo).with_param("VALUE", "uri"),
+        (VCardVersion::V4, _) => VCardProperty::new("PHOTO", photo),
+    }
+}
+
+/// vCard 4.0 writes dates without dashes: `19850615`, or `--0615` without the year.
+fn date_value(date: &str, version: VCardVersion) -> String {
+    match (version, date.strip_prefix("--")) {
+        (VCardVersion::V3, _) => date.to_string(),
+        (VCardVersion::V4, Some(month_day)) => format!("--{}", month_day.replace('-', "")),
+        (VCardVersion::V4, None) => date.replace('-', ""),
+    }
+}
+
+/// `19850615` and `--0615` (vCard 4.0) become `1985-06-15` and `--06-15`.
+fn normalize_date(value: &str) -> String {
+    let date = value.split('T').next().unwrap_or_default().trim();
+    let digits = |s: &str| s.len() == 8 && s.chars().all(|c| c.is_ascii_digit());
+    if digits(date) {
+        format!("{}-{}-{}", &date[..4], &date[4..6], &date[6..])
+    } else if let Some(month_day) = date.strip_prefix("--").filter(|md| md.len() == 4) {
+        format!("--{}-{}", &month_day[..2], &month_day[2..])
+    } else {
+        date.to_string()
+    }
+}
+
+fn non_empty(value: String) -> Option<String> {
+    (!value.trim().is_empty()).then_some(value)
+}
+
+fn parse_error(msg: &str) -> ContactStoreError {
+    ContactStoreError::ParseError(msg.to_string())
+}
+
+const BEGIN: &str = "BEGIN";
+const END: &str = "END";
+const VERSION: &str = "VERSION";
+const X_SOCIALPROFILE: &str = "X-SOCIALPROFILE";
+const X_USER: &str = "X-USER";
+const X_FAVORITE: &str = "X-FAVORITE";
+const X_CREATED: &str = "X-CREATED";
+const X_ANNIVERSARY: &str = "X-ANNIVERSARY";
+const TEL_URI: &str = "tel:";
+const DATA_URI: &str = "data:";
+const LINE_OCTETS: usize = 75;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const APPLE_V3: &str = "BEGIN:VCARD\r\n\
+VERSION:3.0\r\n\
+N:Gonçalves;João;;Dr.;\r\n\
+FN:Dr. João Gonçalves\r\n\
+ORG:Acme\\, Inc.;Research\r\n\
+TITLE:Lead\r\n\
+item1.TEL;type=CELL;type=VOICE;type=pref:+55 11 99999-0000\r\n\
+EMAIL;type=INTERNET;type=WORK:joao@acme.com\r\n\
+EMAIL;type=INTERNET;type=HOME;type=pref:joao@gmail.com\r\n\
+ADR;type=HOME:;;Rua A\\, 10;São Paulo;SP;01000-000;Brasil\r\n\
+BDAY:1980-02-29\r\n\
+NOTE:First line\\nsecond line that is long enough to be folded across tw\r\n\
+\x20o physical lines\r\n\
+PHOTO;ENCODING=b;TYPE=PNG:iVBORw0KGgo=\r\n\
+X-SOCIALPROFILE;type=twitter;x-user=joao:https://twitter.com/joao\r\n\
+X-PHONETIC-FIRST-NAME:zhoo-ow\r\n\
+END:VCARD\r\n";
+
+    #[test]
+    fn test_parse_vcard_3_from_a_mail_client() {
+        let cards = VCard::parse_all(APPLE_V3).unwrap();
+        assert_eq!(cards.len(), 1);
+        assert_eq!(cards[0].version, VCardVersion::V3);
+
+        let contact = cards[0].to_contact().unwrap();
+        assert_eq!(contact.first_name, "João");
+        assert_eq!(contact.last_name, "Gonçalves");
+        assert_eq!(contact.display_name, "Dr. João Gonçalves");
+        assert_eq!(contact.company.as_deref(), Some("Acme, Inc."));
+        assert_eq!(contact.department.as_deref(), Some("Research"));
+        assert_eq!(contact.phone_numbers[0].label, ContactLabel::Mobile);
+        assert!(contact.phone_numbers[0].primary);
+        assert_eq!(
+            contact.primary_email().map(|e| e.address.as_str()),
+            Some("joao@gmail.com")
+        );
+        assert_eq!(contact.emails[1].label, ContactLabel::Personal);
+        assert_eq!(contact.addresses[0].street.as_deref(), Some("Rua A, 10"));
+        assert_eq!(contact.birthday.as_deref(), Some("1980-02-29"));
+        assert_eq!(
+            contact.notes.as_deref(),
+            Some(
+                "First line\nsecond line that is long enough to be folded across two physical lines"
+            )
+        );
+        assert_eq!(
+            contact.photo.as_deref(),
+            Some("data:image/png;base64,iVBORw0KGgo=")
+        );
+        assert_eq!(contact.social_profiles[0].platform, "twitter");
+        assert_eq!(contact.social_profiles[0].username.as_deref(), Some("joao"));
+        assert_eq!(
+            contact
+                .extensions
+                .get("X-PHONETIC-FIRST-NAME")
+                .map(String::as_str),
+            Some("zhoo-ow")
+        );
+    }
+
+    #[test]
+    fn test_parse_vcard_4_dates_and_uris() {
+        let content = "BEGIN:VCARD\nVERSION:4.0\nFN:Eva\nTEL;VALUE=uri;TYPE=work;PREF=1:tel:+1-555\nBDAY:--0615\nEND:VCARD\nBEGIN:VCARD\nVERSION:4.0\nFN:Carlos\nBDAY:19850615\nEND:VCARD\n";
+        let contacts: Vec<Contact> = VCard::parse_all(content)
+            .unwrap()
+            .iter()
+            .map(|card| card.to_contact().unwrap())
+            .collect();
+
+        assert_eq!(contacts.len(), 2);
+        assert_eq!(contacts[0].phone_numbers[0].number, "+1-555");
+        assert!(contacts[0].phone_numbers[0].primary);
+        assert_eq!(contacts[0].birthday.as_deref(), Some("--06-15"));
+        assert_eq!(contacts[1].birthday.as_deref(), Some("1985-06-15"));
+    }
+
+    #[test]
+    fn test_render_folds_long_lines_at_75_octets() {
+        let contact = Contact::new("c1", "Eva", "Green").with_notes(&"é".repeat(60));
+        let rendered = VCard::from_contact(&contact, VCardVersion::V4).render();
+
+        assert!(rendered.starts_with("BEGIN:VCARD\r\nVERSION:4.0\r\n"));
+        assert!(rendered.ends_with("END:VCARD\r\n"));
+        assert!(rendered.split("\r\n").all(|line| line.len() <= 75));
+        let parsed = VCard::parse_all(&rendered).unwrap()[0]
+            .to_contact()
+            .unwrap();
+        assert_eq!(parsed.notes, contact.notes);
+    }
+
+    #[test]
+    fn test_export_writes_version_specific_forms() {
+        let contact = Contact::new("c1", "Eva", "Green")
+            .with_phone(ContactPhone::new(ContactLabel::Mobile, "+1-555").with_primary(true))
+            .with_birthday("1990-03-22")
+            .with_anniversary("--09-14");
+
+        let v3 = VCard::from_contact(&contact, VCardVersion::V3).render();
+        assert!(v3.contains("TEL;TYPE=cell,pref:+1-555\r\n"));
+        assert!(v3.contains("BDAY:1990-03-22\r\n"));
+        assert!(v3.contains("X-ANNIVERSARY:--09-14\r\n"));
+
+        let v4 = VCard::from_contact(&contact, VCardVersion::V4).render();
+        assert!(v4.contains("TEL;VALUE=uri;TYPE=cell;PREF=1:tel:+1-555\r\n"));
+        assert!(v4.contains("BDAY:19900322\r\n"));
+        assert!(v4.contains("ANNIVERSARY:--0914\r\n"));
+        let parsed = VCard::parse_all(&v4).unwrap()[0].to_contact().unwrap();
+        assert_eq!(parsed.anniversary.as_deref(), Some("--09-14"));
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed_files() {
+        assert!(VCard::parse_all("BEGIN:VCARD\nVERSION:4.0\nFN:Eva\n").is_err());
+        assert!(VCard::parse_all("FN:Eva\n").is_err());
+        assert!(VCard::parse_all("BEGIN:VCARD\nFN:Eva\nEND:VCARD\n").is_err());
+        assert!(VCard::parse_all("BEGIN:VCARD\nVERSION:2.1\nEND:VCARD\n").is_err());
+    }
+}
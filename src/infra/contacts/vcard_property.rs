@@ -0,0 +1,242 @@
+use super::contact_store_error::ContactStoreError;
+
+/// One content line of a vCard: `[group.]NAME;PARAM=value:value`.
+///
+/// `value` is kept in its encoded form; text values are escaped and
+/// unescaped by the caller with [`escape_text`] and [`unescape_text`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct VCardProperty {
+    pub group: Option<String>,
+    /// Upper-cased property name.
+    pub name: String,
+    /// Parameters in line order with upper-cased names.
+    pub params: Vec<(String, String)>,
+    pub value: String,
+}
+
+impl VCardProperty {
+    pub fn new(name: &str, value: &str) -> Self {
+        Self {
+            group: None,
+            name: name.to_uppercase(),
+            params: Vec::new(),
+            value: value.to_string(),
+        }
+    }
+
+    pub fn with_param(mut self, name: &str, value: &str) -> Self {
+        self.params.push((name.to_uppercase(), value.to_string()));
+        self
+    }
+
+    /// Parses an unfolded content line.
+    pub fn parse(line: &str) -> Result<Self, ContactStoreError> {
+        let colon = find_unquoted(line, ':')
+            .ok_or_else(|| parse_error(&format!("Missing ':' in vCard line: {line}")))?;
+        let (head, value) = (&line[..colon], &line[colon + 1..]);
+
+        let mut parts = split_unquoted(head, ';').into_iter();
+        let full_name = parts.next().unwrap_or_default();
+        let (group, name) = match full_name.rsplit_once('.') {
+            Some((group, name)) => (Some(group.to_string()), name),
+            None => (None, full_name),
+        };
+        if name.trim().is_empty() {
+            return Err(parse_error(&format!("Missing property name: {line}")));
+        }
+
+        let params = parts
+            .filter(|param| !param.is_empty())
+            .map(|param| match param.split_once('=') {
+                Some((key, value)) => (key.trim().to_uppercase(), value.replace('"', "")),
+                // vCard 2.1 style bare types such as `TEL;CELL:`
+                None => (TYPE.to_string(), param.to_string()),
+            })
+            .collect();
+
+        Ok(Self {
+            group,
+            name: name.trim().to_uppercase(),
+            params,
+            value: value.to_string(),
+        })
+    }
+
+    /// First value of the `name` parameter.
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// All `TYPE` values, lower-cased, across repeated and comma-separated parameters.
+    pub fn types(&self) -> Vec<String> {
+        self.params
+            .iter()
+            .filter(|(key, _)| key == TYPE)
+            .flat_map(|(_, value)| value.split(','))
+            .map(|value| value.trim().to_lowercase())
+            .filter(|value| !value.is_empty())
+            .collect()
+    }
+
+    pub fn has_type(&self, value: &str) -> bool {
+        self.types().iter().any(|t| t == value)
+    }
+
+    /// The content line, without folding.
+    pub fn render(&self) -> String {
+        let mut line = String::new();
+        if let Some(group) = &self.group {
+            line.push_str(group);
+            line.push('.');
+        }
+        line.push_str(&self.name);
+        for (key, value) in &self.params {
+            line.push(';');
+            line.push_str(key);
+            line.push('=');
+            if value.contains([':', ';']) {
+                line.push_str(&format!("\"{value}\""));
+            } else {
+                line.push_str(value);
+            }
+        }
+        line.push(':');
+        line.push_str(&self.value);
+        line
+    }
+}
+
+/// Escapes a text value (RFC 6350 section 3.4).
+pub fn escape_text(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            ',' => escaped.push_str("\\,"),
+            ';' => escaped.push_str("\\;"),
+            other => escaped.push(other),
+        }
+    }
+    escaped
+}
+
+pub fn unescape_text(value: &str) -> String {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') | Some('N') => unescaped.push('\n'),
+            Some(other) => unescaped.push(other),
+            None => unescaped.push('\\'),
+        }
+    }
+    unescaped
+}
+
+/// Splits an encoded value on `separator` where it is not escaped, unescaping each component.
+pub fn split_components(value: &str, separator: char) -> Vec<String> {
+    let mut components = Vec::new();
+    let mut current = String::new();
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            current.push(c);
+            if let Some(next) = chars.next() {
+                current.push(next);
+            }
+        } else if c == separator {
+            components.push(unescape_text(&current));
+            current.clear();
+        } else {
+            current.push(c);
+        }
+    }
+    components.push(unescape_text(&current));
+    components
+}
+
+fn find_unquoted(line: &str, target: char) -> Option<usize> {
+    let mut quoted = false;
+    line.char_indices().find_map(|(index, c)| {
+        if c == '"' {
+            quoted = !quoted;
+        }
+        (c == target && !quoted).then_some(index)
+    })
+}
+
+fn split_unquoted(line: &str, separator: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut rest = line;
+    while let Some(index) = find_unquoted(rest, separator) {
+        parts.push(&rest[..index]);
+        rest = &rest[index + separator.len_utf8()..];
+    }
+    parts.push(rest);
+    parts
+}
+
+fn parse_error(msg: &str) -> ContactStoreError {
+    ContactStoreError::ParseError(msg.to_string())
+}
+
+const TYPE: &str = "TYPE";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_group_params_and_quoted_colons() {
+        let property =
+            VCardProperty::parse("item1.TEL;type=WORK,voice;PREF=1;LABEL=\"a:b\":+1-555").unwrap();
+        assert_eq!(property.group.as_deref(), Some("item1"));
+        assert_eq!(property.name, "TEL");
+        assert_eq!(property.types(), vec!["work", "voice"]);
+        assert_eq!(property.param("pref"), Some("1"));
+        assert_eq!(property.param("LABEL"), Some("a:b"));
+        assert_eq!(property.value, "+1-555");
+    }
+
+    #[test]
+    fn test_bare_parameters_are_types() {
+        let property = VCardProperty::parse("TEL;CELL;TYPE=pref:123").unwrap();
+        assert!(property.has_type("cell"));
+        assert!(property.has_type("pref"));
+    }
+
+    #[test]
+    fn test_escape_round_trip_and_components() {
+        let text = "Met at conf; likes AI, ML\nand C:\\temp";
+        assert_eq!(unescape_text(&escape_text(text)), text);
+        assert_eq!(
+            split_components(r"Brilliant;Tiger;;Dr.\;Jr;", ';'),
+            vec!["Brilliant", "Tiger", "", "Dr.;Jr", ""]
+        );
+    }
+
+    #[test]
+    fn test_render_quotes_params_with_separators() {
+        let property = VCardProperty::new("adr", ";;Main St")
+            .with_param("type", "home")
+            .with_param("label", "Main St: 1");
+        assert_eq!(
+            property.render(),
+            "ADR;TYPE=home;LABEL=\"Main St: 1\":;;Main St"
+        );
+    }
+
+    #[test]
+    fn test_missing_colon_is_an_error() {
+        assert!(VCardProperty::parse("FN Tiger").is_err());
+    }
+}
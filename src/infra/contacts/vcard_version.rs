@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+use super::contact_store_error::ContactStoreError;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VCardVersion {
+    #[serde(rename = "3.0")]
+    V3,
+    #[default]
+    #[serde(rename = "4.0")]
+    V4,
+}
+
+impl VCardVersion {
+    pub fn to_str(&self) -> &str {
+        match self {
+            Self::V3 => V3,
+            Self::V4 => V4,
+        }
+    }
+}
+
+impl FromStr for VCardVersion {
+    type Err = ContactStoreError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input.trim() {
+            V3 => Ok(Self::V3),
+            V4 => Ok(Self::V4),
+            other => Err(ContactStoreError::ParseError(format!(
+                "Unsupported vCard version: {other}"
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for VCardVersion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_str())
+    }
+}
+
+const V3: &str = "3.0";
+const V4: &str = "4.0";
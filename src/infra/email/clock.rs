@@ -0,0 +1,6 @@
+use chrono::{DateTime, Utc};
+
+/// The current time, injected so scheduling can be tested without waiting.
+pub trait Clock: Send + Sync {
+    fn now(&self) -> DateTime<Utc>;
+}
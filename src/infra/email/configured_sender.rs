@@ -0,0 +1,64 @@
+use std::str::FromStr;
+
+use crate::config::EmailConfig;
+
+use super::{
+    email_error::EmailError, email_sender::EmailSender, email_transport_kind::EmailTransportKind,
+    local_sender::LocalSender, outgoing_email::OutgoingEmail, send_receipt::SendReceipt,
+    smtp_sender::SmtpSender,
+};
+
+/// The transport selected by `[email] transport`.
+pub enum ConfiguredSender {
+    Local(LocalSender),
+    Smtp(SmtpSender),
+}
+
+impl ConfiguredSender {
+    pub fn from_config(config: &EmailConfig) -> Result<Self, EmailError> {
+        match EmailTransportKind::from_str(&config.transport)? {
+            EmailTransportKind::Outbox => Ok(Self::Local(LocalSender::outbox(&config.path))),
+            EmailTransportKind::Maildir => Ok(Self::Local(LocalSender::maildir(&config.path))),
+            EmailTransportKind::Smtp => {
+                let smtp = config.smtp.as_ref().ok_or_else(|| {
+                    EmailError::ConfigError(
+                        "transport is smtp but [email.smtp] is missing".to_string(),
+                    )
+                })?;
+                Ok(Self::Smtp(SmtpSender::from_config(smtp)?))
+            }
+        }
+    }
+
+    /// The transport for a draft sent through `account` (`EmailDraft::smtp_account`):
+    /// with the `smtp` transport, the `[email.accounts.<account>]` server
+    /// instead of `[email.smtp]`. Local transports ignore the account.
+    pub fn for_account(config: &EmailConfig, account: Option<&str>) -> Result<Self, EmailError> {
+        let Some(account) = account else {
+            return Self::from_config(config);
+        };
+        if EmailTransportKind::from_str(&config.transport)? != EmailTransportKind::Smtp {
+            return Self::from_config(config);
+        }
+        let smtp = config.accounts.get(account).ok_or_else(|| {
+            EmailError::ConfigError(format!("[email.accounts.{account}] is missing"))
+        })?;
+        Ok(Self::Smtp(SmtpSender::from_config(smtp)?))
+    }
+
+    pub fn kind(&self) -> EmailTransportKind {
+        match self {
+            Self::Local(sender) => sender.kind(),
+            Self::Smtp(_) => EmailTransportKind::Smtp,
+        }
+    }
+}
+
+impl EmailSender for ConfiguredSender {
+    async fn send(&self, email: &OutgoingEmail) -> Result<SendReceipt, EmailError> {
+        match self {
+            Self::Local(sender) => sender.send(email).await,
+            Self::Smtp(sender) => sender.send(email).await,
+        }
+    }
+}
@@ -0,0 +1,109 @@
+use super::email_error::EmailError;
+
+/// Checks `address` (RFC 5321/5322 dot-atom local part, RFC 6531 UTF-8 allowed)
+/// and returns it with the domain in its ASCII form: internationalised domains
+/// (`joão@exemplo.com.br`, `ana@bücher.de`) become punycode, ready for SMTP.
+pub fn normalize_address(address: &str) -> Result<String, EmailError> {
+    let address = address.trim();
+    let invalid = |reason: &str| {
+        EmailError::InvalidMessage(format!(
+            "'{address}' is not a valid email address: {reason}"
+        ))
+    };
+    let (local, domain) = address.rsplit_once('@').ok_or_else(|| invalid("no @"))?;
+    if local.is_empty() || local.len() > MAX_LOCAL_PART {
+        return Err(invalid("the part before @ must have 1 to 64 characters"));
+    }
+    if local.starts_with('.') || local.ends_with('.') || local.contains("..") {
+        return Err(invalid("misplaced dot before @"));
+    }
+    if let Some(c) = local.chars().find(|&c| !is_local_part_char(c)) {
+        return Err(invalid(&format!("'{c}' is not allowed before @")));
+    }
+    let domain = idna::domain_to_ascii(domain).map_err(|_| invalid("invalid domain"))?;
+    let labels: Vec<&str> = domain.split('.').collect();
+    if domain.len() > MAX_DOMAIN || labels.len() < 2 {
+        return Err(invalid("the domain needs a name and a top-level domain"));
+    }
+    let valid_label = |label: &&str| {
+        !label.is_empty()
+            && label.len() <= MAX_LABEL
+            && !label.starts_with('-')
+            && !label.ends_with('-')
+            && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+    };
+    if !labels.iter().all(valid_label)
+        || labels
+            .last()
+            .is_some_and(|tld| tld.chars().all(|c| c.is_ascii_digit()))
+    {
+        return Err(invalid("invalid domain"));
+    }
+    Ok(format!("{local}@{domain}"))
+}
+
+/// Whether [`normalize_address`] accepts `address`.
+pub fn is_valid_address(address: &str) -> bool {
+    normalize_address(address).is_ok()
+}
+
+/// Letters, digits, `ATEXT` and dots, plus any non-ASCII character that is
+/// not a space or control character (RFC 6531).
+fn is_local_part_char(c: char) -> bool {
+    if c.is_ascii() {
+        c == '.' || c.is_ascii_alphanumeric() || ATEXT.contains(c)
+    } else {
+        !c.is_whitespace() && !c.is_control()
+    }
+}
+
+/// Characters besides letters and digits allowed in an unquoted local part.
+const ATEXT: &str = "!#$%&'*+-/=?^_`{|}~";
+const MAX_LOCAL_PART: usize = 64;
+const MAX_DOMAIN: usize = 253;
+const MAX_LABEL: usize = 63;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_addresses() {
+        assert_eq!(
+            normalize_address(" Eva.Green+news@Example.COM ").unwrap(),
+            "Eva.Green+news@example.com"
+        );
+        assert_eq!(
+            normalize_address("ana@bücher.de").unwrap(),
+            "ana@xn--bcher-kva.de"
+        );
+        assert_eq!(
+            normalize_address("joão@exemplo.com.br").unwrap(),
+            "joão@exemplo.com.br"
+        );
+    }
+
+    #[test]
+    fn test_invalid_addresses() {
+        for address in [
+            "Eva",
+            "eva@",
+            "@example.com",
+            "eva@example",
+            "eva..green@example.com",
+            ".eva@example.com",
+            "eva green@example.com",
+            "eva@exa_mple.com",
+            "eva@-example.com",
+            "eva@example.123",
+            "<eva@example.com>",
+            "eva@example..com",
+        ] {
+            assert!(!is_valid_address(address), "{address}");
+        }
+        assert!(!is_valid_address(&format!(
+            "{}@example.com",
+            "a".repeat(65)
+        )));
+    }
+}
@@ -0,0 +1,112 @@
+use std::{fs, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use super::email_error::EmailError;
+
+/// A file attached to a message. The content type is guessed from the file
+/// extension unless set with [`with_content_type`](Self::with_content_type).
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct EmailAttachment {
+    pub filename: String,
+    pub content_type: String,
+    pub data: Vec<u8>,
+}
+
+impl EmailAttachment {
+    pub fn new(filename: &str, data: &[u8]) -> Self {
+        Self {
+            filename: filename.to_string(),
+            content_type: content_type_for(filename).to_string(),
+            data: data.to_vec(),
+        }
+    }
+
+    pub fn from_path(path: &Path) -> Result<Self, EmailError> {
+        let data =
+            fs::read(path).map_err(|e| EmailError::IoError(format!("{}: {e}", path.display())))?;
+        let filename = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        Ok(Self::new(&filename, &data))
+    }
+
+    pub fn with_content_type(mut self, content_type: &str) -> Self {
+        self.content_type = content_type.to_string();
+        self
+    }
+}
+
+/// MIME type for a file name by extension; `application/octet-stream` when unknown.
+pub fn content_type_for(filename: &str) -> &'static str {
+    let extension = filename
+        .rsplit_once('.')
+        .map(|(_, extension)| extension.to_lowercase())
+        .unwrap_or_default();
+    CONTENT_TYPES
+        .iter()
+        .find(|(known, _)| *known == extension)
+        .map_or(DEFAULT_CONTENT_TYPE, |(_, content_type)| content_type)
+}
+
+const DEFAULT_CONTENT_TYPE: &str = "application/octet-stream";
+const CONTENT_TYPES: &[(&str, &str)] = &[
+    ("txt", "text/plain"),
+    ("html", "text/html"),
+    ("htm", "text/html"),
+    ("csv", "text/csv"),
+    ("md", "text/markdown"),
+    ("ics", "text/calendar"),
+    ("vcf", "text/vcard"),
+    ("json", "application/json"),
+    ("xml", "application/xml"),
+    ("pdf", "application/pdf"),
+    ("zip", "application/zip"),
+    ("doc", "application/msword"),
+    (
+        "docx",
+        "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+    ),
+    ("xls", "application/vnd.ms-excel"),
+    (
+        "xlsx",
+        "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+    ),
+    ("ppt", "application/vnd.ms-powerpoint"),
+    (
+        "pptx",
+        "application/vnd.openxmlformats-officedocument.presentationml.presentation",
+    ),
+    ("odt", "application/vnd.oasis.opendocument.text"),
+    ("png", "image/png"),
+    ("jpg", "image/jpeg"),
+    ("jpeg", "image/jpeg"),
+    ("gif", "image/gif"),
+    ("webp", "image/webp"),
+    ("svg", "image/svg+xml"),
+    ("mp3", "audio/mpeg"),
+    ("mp4", "video/mp4"),
+    ("eml", "message/rfc822"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_content_type_for() {
+        assert_eq!(content_type_for("Relatório.PDF"), "application/pdf");
+        assert_eq!(content_type_for("invite.ics"), "text/calendar");
+        assert_eq!(content_type_for("photo.jpeg"), "image/jpeg");
+        assert_eq!(content_type_for("archive.tar.bz2"), DEFAULT_CONTENT_TYPE);
+        assert_eq!(content_type_for("README"), DEFAULT_CONTENT_TYPE);
+    }
+
+    #[test]
+    fn test_with_content_type_overrides_the_guess() {
+        let attachment =
+            EmailAttachment::new("data.bin", b"{}").with_content_type("application/json");
+        assert_eq!(attachment.content_type, "application/json");
+    }
+}
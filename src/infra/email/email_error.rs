@@ -0,0 +1,39 @@
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum EmailError {
+    ConfigError(String),
+    IoError(String),
+    /// The server could not be reached or answered with a temporary (4xx)
+    /// failure; sending again later may work.
+    TransportError(String),
+    /// The server refused the message (5xx); sending it again will not help.
+    Rejected(String),
+    /// The message cannot be built or sent as is: an invalid address, no
+    /// recipients, a malformed header.
+    InvalidMessage(String),
+    /// The email queue could not be read or written.
+    StorageError(String),
+}
+
+impl EmailError {
+    pub fn is_transient(&self) -> bool {
+        matches!(self, Self::TransportError(_) | Self::IoError(_))
+    }
+}
+
+impl fmt::Display for EmailError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EmailError::ConfigError(msg) => write!(f, "Email configuration error: {msg}"),
+            EmailError::IoError(msg) => write!(f, "Email IO error: {msg}"),
+            EmailError::TransportError(msg) => write!(f, "Email transport error: {msg}"),
+            EmailError::Rejected(msg) => write!(f, "Email rejected: {msg}"),
+            EmailError::InvalidMessage(msg) => write!(f, "Invalid email: {msg}"),
+            EmailError::StorageError(msg) => write!(f, "Email storage error: {msg}"),
+        }
+    }
+}
+
+impl Error for EmailError {}
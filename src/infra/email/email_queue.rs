@@ -0,0 +1,620 @@
+use std::{
+    fmt,
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+use chrono::{DateTime, TimeDelta, Utc};
+
+use crate::{
+    agents::email::DraftStatus,
+    infra::persistence::{EmailQueueRepository, QueuedEmail},
+};
+
+use super::{
+    clock::Clock, email_error::EmailError, email_sender::EmailSender,
+    outgoing_email::OutgoingEmail, queue_run::QueueRun, queue_status::QueueStatus,
+    retry_policy::RetryPolicy, system_clock::SystemClock,
+};
+
+/// Persistent send queue: messages wait in the database until their send time,
+/// transient failures are retried with backoff ([`RetryPolicy`]) and messages
+/// that are rejected or run out of attempts become dead letters.
+///
+/// Clones share the repository, so an [`EmailQueueWorker`](super::EmailQueueWorker)
+/// can drain the queue while the application adds to it.
+///
+/// A message built from a draft claims that draft in the same repository: only
+/// an approved (or failed) draft can be queued, it stays `sending` while
+/// queued and becomes `sent` or `failed` when the message leaves the queue.
+pub struct EmailQueue<R: EmailQueueRepository> {
+    repository: Arc<Mutex<R>>,
+    clock: Arc<dyn Clock>,
+    policy: RetryPolicy,
+}
+
+impl<R: EmailQueueRepository> EmailQueue<R> {
+    pub fn new(repository: R) -> Self {
+        Self {
+            repository: Arc::new(Mutex::new(repository)),
+            clock: Arc::new(SystemClock),
+            policy: RetryPolicy::default(),
+        }
+    }
+
+    pub fn with_clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.clock = Arc::new(clock);
+        self
+    }
+
+    pub fn with_policy(mut self, policy: RetryPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    pub fn now(&self) -> DateTime<Utc> {
+        self.clock.now()
+    }
+
+    /// Sends as soon as the worker runs.
+    pub fn enqueue(&self, email: OutgoingEmail) -> Result<QueuedEmail, EmailError> {
+        self.schedule(email, self.now())
+    }
+
+    /// Sends at `send_at`, e.g. "tomorrow at 9".
+    pub fn schedule(
+        &self,
+        email: OutgoingEmail,
+        send_at: DateTime<Utc>,
+    ) -> Result<QueuedEmail, EmailError> {
+        let entry = QueuedEmail::new(email, send_at.timestamp(), self.now().timestamp());
+        self.add(entry)
+    }
+
+    /// Stores a prepared entry, such as one linked to a draft with
+    /// [`QueuedEmail::with_draft_id`]; that draft must be approved and is
+    /// marked `sending` with the entry.
+    pub fn add(&self, mut entry: QueuedEmail) -> Result<QueuedEmail, EmailError> {
+        if entry.email.recipients.is_empty() {
+            return Err(EmailError::InvalidMessage("no recipients".to_string()));
+        }
+        let mut repository = self.repository()?;
+        if let Some(draft_id) = &entry.draft_id {
+            claim_draft(&mut *repository, draft_id, self.now().timestamp())?;
+        }
+        entry.id = Some(repository.enqueue(&entry).map_err(storage_error)?);
+        Ok(entry)
+    }
+
+    pub fn get(&self, id: i64) -> Result<Option<QueuedEmail>, EmailError> {
+        self.repository()?.queued_email(id).map_err(storage_error)
+    }
+
+    /// Messages still to be sent, next first.
+    pub fn pending(&self) -> Result<Vec<QueuedEmail>, EmailError> {
+        self.with_status(QueueStatus::Queued)
+    }
+
+    pub fn dead_letters(&self) -> Result<Vec<QueuedEmail>, EmailError> {
+        self.with_status(QueueStatus::DeadLetter)
+    }
+
+    /// Cancels a message that was not sent yet; its draft becomes `failed`, so
+    /// it can be sent again or cancelled. Returns `false` when the message is
+    /// being sent or already left the queue (sent, dead-lettered or cancelled).
+    pub fn cancel(&self, id: i64) -> Result<bool, EmailError> {
+        self.change(id, |entry, _| {
+            if entry.status != QueueStatus::Queued {
+                return false;
+            }
+            entry.status = QueueStatus::Cancelled;
+            true
+        })
+    }
+
+    /// Puts a dead letter back in the queue with fresh attempts, e.g. after the
+    /// user fixed the SMTP settings. Its draft is claimed again, so this fails
+    /// when the draft was edited or cancelled in the meantime.
+    pub fn retry_dead_letter(&self, id: i64) -> Result<bool, EmailError> {
+        self.change(id, |entry, now| {
+            if entry.status != QueueStatus::DeadLetter {
+                return false;
+            }
+            entry.status = QueueStatus::Queued;
+            entry.attempts = 0;
+            entry.next_attempt_at = now;
+            true
+        })
+    }
+
+    /// Tries every due message once through `sender`. The due messages are
+    /// first claimed as `sending` under the repository lock, so concurrent
+    /// calls (e.g. a manual call next to the worker) never send one twice, and
+    /// a message being sent can no longer be cancelled.
+    pub async fn process_due(&self, sender: &impl EmailSender) -> Result<QueueRun, EmailError> {
+        let mut run = QueueRun::default();
+        for mut entry in self.claim_due()? {
+            let id = entry.id.unwrap_or_default();
+            let outcome = sender.send(&entry.email).await;
+            let now = self.now();
+            entry.updated_at = now.timestamp();
+            match outcome {
+                Ok(_) => {
+                    entry.status = QueueStatus::Sent;
+                    entry.last_error = None;
+                    run.sent.push(id);
+                }
+                Err(e) => {
+                    entry.attempts += 1;
+                    entry.last_error = Some(e.to_string());
+                    if e.is_transient() && !self.policy.gives_up_after(entry.attempts) {
+                        entry.status = QueueStatus::Queued;
+                        entry.next_attempt_at =
+                            (now + self.policy.backoff(entry.attempts)).timestamp();
+                        run.retrying.push(id);
+                    } else {
+                        entry.status = QueueStatus::DeadLetter;
+                        run.dead_lettered.push(id);
+                    }
+                }
+            }
+            let mut repository = self.repository()?;
+            repository
+                .update_queued_email(&entry)
+                .map_err(storage_error)?;
+            finish_draft(&mut *repository, &entry)?;
+        }
+        Ok(run)
+    }
+
+    /// Marks the due messages `sending` under one repository lock and returns
+    /// the ones this call now owns.
+    fn claim_due(&self) -> Result<Vec<QueuedEmail>, EmailError> {
+        let now = self.now().timestamp();
+        let mut repository = self.repository()?;
+        let mut claimed = Vec::new();
+        for mut entry in repository
+            .due_emails(now, BATCH_SIZE)
+            .map_err(storage_error)?
+        {
+            entry.status = QueueStatus::Sending;
+            entry.updated_at = now;
+            if repository
+                .update_queued_email(&entry)
+                .map_err(storage_error)?
+            {
+                claimed.push(entry);
+            }
+        }
+        Ok(claimed)
+    }
+
+    /// How long until the next queued message is due; `None` when nothing is queued.
+    pub fn next_due_in(&self) -> Result<Option<TimeDelta>, EmailError> {
+        let now = self.now().timestamp();
+        Ok(self
+            .pending()?
+            .first()
+            .map(|entry| TimeDelta::seconds((entry.next_attempt_at - now).max(0))))
+    }
+
+    fn with_status(&self, status: QueueStatus) -> Result<Vec<QueuedEmail>, EmailError> {
+        self.repository()?
+            .queued_emails(status)
+            .map_err(storage_error)
+    }
+
+    fn change(
+        &self,
+        id: i64,
+        change: impl FnOnce(&mut QueuedEmail, i64) -> bool,
+    ) -> Result<bool, EmailError> {
+        let now = self.now().timestamp();
+        let mut repository = self.repository()?;
+        let mut entry = repository
+            .queued_email(id)
+            .map_err(storage_error)?
+            .ok_or_else(|| EmailError::StorageError(format!("Queued email {id} not found")))?;
+        if !change(&mut entry, now) {
+            return Ok(false);
+        }
+        entry.updated_at = now;
+        match (&entry.draft_id, entry.status) {
+            (Some(draft_id), QueueStatus::Queued) => claim_draft(&mut *repository, draft_id, now)?,
+            _ => finish_draft(&mut *repository, &entry)?,
+        }
+        repository
+            .update_queued_email(&entry)
+            .map_err(storage_error)
+    }
+
+    fn repository(&self) -> Result<MutexGuard<'_, R>, EmailError> {
+        self.repository
+            .lock()
+            .map_err(|e| EmailError::StorageError(e.to_string()))
+    }
+}
+
+impl<R: EmailQueueRepository> Clone for EmailQueue<R> {
+    fn clone(&self) -> Self {
+        Self {
+            repository: Arc::clone(&self.repository),
+            clock: Arc::clone(&self.clock),
+            policy: self.policy,
+        }
+    }
+}
+
+impl<R: EmailQueueRepository> fmt::Debug for EmailQueue<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EmailQueue")
+            .field("policy", &self.policy)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Marks the draft `sending` for a queue entry; only an approved or failed
+/// draft can be claimed.
+fn claim_draft(
+    repository: &mut impl EmailQueueRepository,
+    draft_id: &str,
+    now: i64,
+) -> Result<(), EmailError> {
+    let mut draft = repository
+        .draft(draft_id)
+        .map_err(storage_error)?
+        .ok_or_else(|| EmailError::InvalidMessage(format!("Draft {draft_id} not found")))?;
+    if !draft.status.can_become(DraftStatus::Sending) {
+        return Err(EmailError::InvalidMessage(format!(
+            "Draft {draft_id} is {} and must be approved before sending",
+            draft.status
+        )));
+    }
+    draft.status = DraftStatus::Sending;
+    draft.updated_at = now;
+    repository.save_draft(&draft).map_err(storage_error)
+}
+
+/// Gives the entry's draft the outcome of a message that left the queue: `sent`,
+/// or `failed` with the reason. Entries still queued or sending leave it `sending`.
+fn finish_draft(
+    repository: &mut impl EmailQueueRepository,
+    entry: &QueuedEmail,
+) -> Result<(), EmailError> {
+    let (next, reason) = match entry.status {
+        QueueStatus::Sent => (DraftStatus::Sent, None),
+        QueueStatus::DeadLetter => (DraftStatus::Failed, entry.last_error.clone()),
+        QueueStatus::Cancelled => (DraftStatus::Failed, Some(CANCELLED_REASON.to_string())),
+        QueueStatus::Queued | QueueStatus::Sending => return Ok(()),
+    };
+    let Some(draft_id) = &entry.draft_id else {
+        return Ok(());
+    };
+    let Some(mut draft) = repository.draft(draft_id).map_err(storage_error)? else {
+        return Ok(());
+    };
+    if draft.status != DraftStatus::Sending {
+        return Ok(());
+    }
+    draft.status = next;
+    draft.reason = reason;
+    draft.updated_at = entry.updated_at;
+    repository.save_draft(&draft).map_err(storage_error)
+}
+
+fn storage_error(e: impl fmt::Display) -> EmailError {
+    EmailError::StorageError(e.to_string())
+}
+
+/// Why a draft failed when its queued message was cancelled.
+const CANCELLED_REASON: &str = "Cancelled in the send queue";
+
+/// Messages tried per pass, so one pass cannot run for too long.
+const BATCH_SIZE: usize = 50;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infra::{
+        email::{EmailTransportKind, ManualClock, SendReceipt},
+        persistence::{DraftRepository, InMemoryStore, StoredDraft},
+    };
+
+    /// Answers each send with the next scripted result, then succeeds.
+    struct ScriptedSender {
+        results: Mutex<Vec<Result<(), EmailError>>>,
+    }
+
+    impl ScriptedSender {
+        fn new(mut results: Vec<Result<(), EmailError>>) -> Self {
+            results.reverse();
+            Self {
+                results: Mutex::new(results),
+            }
+        }
+    }
+
+    impl EmailSender for ScriptedSender {
+        async fn send(&self, email: &OutgoingEmail) -> Result<SendReceipt, EmailError> {
+            let result = self.results.lock().unwrap().pop().unwrap_or(Ok(()));
+            result.map(|_| {
+                SendReceipt::new(EmailTransportKind::Outbox, email.recipients.clone(), "sent")
+            })
+        }
+    }
+
+    fn start() -> DateTime<Utc> {
+        DateTime::from_timestamp(1_750_000_000, 0).unwrap()
+    }
+
+    fn queue(clock: &ManualClock) -> EmailQueue<InMemoryStore> {
+        EmailQueue::new(InMemoryStore::new())
+            .with_clock(clock.clone())
+            .with_policy(RetryPolicy::new(
+                3,
+                TimeDelta::seconds(60),
+                TimeDelta::seconds(600),
+            ))
+    }
+
+    fn email() -> OutgoingEmail {
+        OutgoingEmail::new(
+            "ana@example.com",
+            vec!["eva@example.com".to_string()],
+            b"Subject: Hi\r\n\r\nHello\r\n",
+        )
+    }
+
+    fn transient() -> Result<(), EmailError> {
+        Err(EmailError::TransportError("connection refused".to_string()))
+    }
+
+    #[tokio::test]
+    async fn test_scheduled_email_waits_for_its_time() {
+        let clock = ManualClock::new(start());
+        let queue = queue(&clock);
+        let sender = ScriptedSender::new(vec![]);
+        let id = queue
+            .schedule(email(), start() + TimeDelta::hours(1))
+            .unwrap()
+            .id
+            .unwrap();
+
+        assert!(queue.process_due(&sender).await.unwrap().is_empty());
+        assert_eq!(queue.next_due_in().unwrap(), Some(TimeDelta::hours(1)));
+
+        clock.advance(TimeDelta::hours(1));
+        assert_eq!(queue.process_due(&sender).await.unwrap().sent, vec![id]);
+        assert_eq!(queue.get(id).unwrap().unwrap().status, QueueStatus::Sent);
+        assert!(queue.pending().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_transient_failures_back_off_then_dead_letter() {
+        let clock = ManualClock::new(start());
+        let queue = queue(&clock);
+        let sender = ScriptedSender::new(vec![transient(), transient(), transient()]);
+        let id = queue.enqueue(email()).unwrap().id.unwrap();
+
+        assert_eq!(queue.process_due(&sender).await.unwrap().retrying, vec![id]);
+        let entry = queue.get(id).unwrap().unwrap();
+        assert_eq!(entry.attempts, 1);
+        assert_eq!(entry.next_attempt_at, start().timestamp() + 60);
+        assert_eq!(
+            entry.last_error.as_deref(),
+            Some("Email transport error: connection refused")
+        );
+
+        clock.advance(TimeDelta::seconds(59));
+        assert!(queue.process_due(&sender).await.unwrap().is_empty());
+        clock.advance(TimeDelta::seconds(1));
+        assert_eq!(queue.process_due(&sender).await.unwrap().retrying, vec![id]);
+        assert_eq!(
+            queue.get(id).unwrap().unwrap().next_attempt_at,
+            clock.now().timestamp() + 120
+        );
+
+        clock.advance(TimeDelta::seconds(120));
+        let run = queue.process_due(&sender).await.unwrap();
+        assert_eq!(run.dead_lettered, vec![id]);
+        assert_eq!(queue.dead_letters().unwrap()[0].attempts, 3);
+
+        assert!(queue.retry_dead_letter(id).unwrap());
+        assert_eq!(queue.process_due(&sender).await.unwrap().sent, vec![id]);
+    }
+
+    #[tokio::test]
+    async fn test_rejected_email_is_dead_lettered_at_once() {
+        let clock = ManualClock::new(start());
+        let queue = queue(&clock);
+        let sender = ScriptedSender::new(vec![Err(EmailError::Rejected(
+            "550 5.1.1 No such user".to_string(),
+        ))]);
+        let id = queue.enqueue(email()).unwrap().id.unwrap();
+
+        assert_eq!(
+            queue.process_due(&sender).await.unwrap().dead_lettered,
+            vec![id]
+        );
+        assert_eq!(queue.get(id).unwrap().unwrap().attempts, 1);
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_email_is_not_sent() {
+        let clock = ManualClock::new(start());
+        let queue = queue(&clock);
+        let sender = ScriptedSender::new(vec![]);
+        let id = queue.enqueue(email()).unwrap().id.unwrap();
+
+        assert!(queue.cancel(id).unwrap());
+        assert!(!queue.cancel(id).unwrap());
+        assert!(queue.process_due(&sender).await.unwrap().is_empty());
+        assert_eq!(
+            queue.get(id).unwrap().unwrap().status,
+            QueueStatus::Cancelled
+        );
+        assert!(queue.cancel(404).is_err());
+        assert!(
+            queue
+                .enqueue(OutgoingEmail::new("ana@example.com", vec![], b""))
+                .is_err()
+        );
+    }
+
+    /// Tries to cancel the message while its send is in flight.
+    struct CancellingSender {
+        queue: EmailQueue<InMemoryStore>,
+        id: i64,
+        result: fn() -> Result<(), EmailError>,
+    }
+
+    impl EmailSender for CancellingSender {
+        async fn send(&self, email: &OutgoingEmail) -> Result<SendReceipt, EmailError> {
+            let entry = self.queue.get(self.id).unwrap().unwrap();
+            assert_eq!(entry.status, QueueStatus::Sending);
+            assert!(!self.queue.cancel(self.id).unwrap());
+            (self.result)().map(|_| {
+                SendReceipt::new(EmailTransportKind::Outbox, email.recipients.clone(), "sent")
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sending_email_cannot_be_cancelled() {
+        let clock = ManualClock::new(start());
+        let queue = queue(&clock);
+        let id = queue.enqueue(email()).unwrap().id.unwrap();
+        let sender = CancellingSender {
+            queue: queue.clone(),
+            id,
+            result: transient,
+        };
+
+        assert_eq!(queue.process_due(&sender).await.unwrap().retrying, vec![id]);
+        let entry = queue.get(id).unwrap().unwrap();
+        assert_eq!(entry.status, QueueStatus::Queued);
+        assert_eq!(entry.attempts, 1);
+        assert!(queue.cancel(id).unwrap());
+
+        let id = queue.enqueue(email()).unwrap().id.unwrap();
+        let sender = CancellingSender {
+            queue: queue.clone(),
+            id,
+            result: || Ok(()),
+        };
+        assert_eq!(queue.process_due(&sender).await.unwrap().sent, vec![id]);
+        assert_eq!(queue.get(id).unwrap().unwrap().status, QueueStatus::Sent);
+    }
+
+    fn with_draft(queue: &EmailQueue<InMemoryStore>, status: DraftStatus) -> QueuedEmail {
+        let draft = StoredDraft::new("d001", "Hi", "Hello").with_status(status);
+        queue.repository().unwrap().save_draft(&draft).unwrap();
+        QueuedEmail::new(email(), start().timestamp(), start().timestamp()).with_draft_id("d001")
+    }
+
+    fn draft_status(queue: &EmailQueue<InMemoryStore>) -> (DraftStatus, Option<String>) {
+        let draft = queue.repository().unwrap().draft("d001").unwrap().unwrap();
+        (draft.status, draft.reason)
+    }
+
+    #[tokio::test]
+    async fn test_queued_draft_follows_its_message() {
+        let clock = ManualClock::new(start());
+        let queue = queue(&clock);
+        let id = queue
+            .add(with_draft(&queue, DraftStatus::Approved))
+            .unwrap()
+            .id
+            .unwrap();
+        assert_eq!(draft_status(&queue), (DraftStatus::Sending, None));
+
+        let sender = ScriptedSender::new(vec![transient()]);
+        assert_eq!(queue.process_due(&sender).await.unwrap().retrying, vec![id]);
+        assert_eq!(draft_status(&queue).0, DraftStatus::Sending);
+
+        clock.advance(TimeDelta::seconds(60));
+        assert_eq!(queue.process_due(&sender).await.unwrap().sent, vec![id]);
+        assert_eq!(draft_status(&queue), (DraftStatus::Sent, None));
+    }
+
+    #[tokio::test]
+    async fn test_only_approved_drafts_can_be_queued() {
+        let clock = ManualClock::new(start());
+        let queue = queue(&clock);
+
+        let error = queue
+            .add(with_draft(&queue, DraftStatus::AwaitingApproval))
+            .unwrap_err();
+        assert!(error.to_string().contains("must be approved"), "{error}");
+        assert!(queue.add(with_draft(&queue, DraftStatus::Sent)).is_err());
+        assert!(
+            queue
+                .add(QueuedEmail::new(email(), 0, 0).with_draft_id("d404"))
+                .is_err()
+        );
+        assert!(queue.pending().unwrap().is_empty());
+        assert_eq!(draft_status(&queue).0, DraftStatus::Sent);
+    }
+
+    #[tokio::test]
+    async fn test_draft_fails_with_a_dead_letter_or_a_cancel() {
+        let clock = ManualClock::new(start());
+        let queue = queue(&clock);
+        let sender = ScriptedSender::new(vec![Err(EmailError::Rejected(
+            "550 5.1.1 No such user".to_string(),
+        ))]);
+        let id = queue
+            .add(with_draft(&queue, DraftStatus::Approved))
+            .unwrap()
+            .id
+            .unwrap();
+
+        assert_eq!(
+            queue.process_due(&sender).await.unwrap().dead_lettered,
+            vec![id]
+        );
+        let (status, reason) = draft_status(&queue);
+        assert_eq!(status, DraftStatus::Failed);
+        assert!(reason.unwrap().contains("No such user"));
+
+        assert!(queue.retry_dead_letter(id).unwrap());
+        assert_eq!(draft_status(&queue).0, DraftStatus::Sending);
+        assert!(queue.cancel(id).unwrap());
+        assert_eq!(
+            draft_status(&queue),
+            (DraftStatus::Failed, Some(CANCELLED_REASON.to_string()))
+        );
+    }
+
+    /// Counts deliveries and yields once, so a concurrent pass can interleave.
+    #[derive(Default)]
+    struct CountingSender(std::sync::atomic::AtomicUsize);
+
+    impl EmailSender for CountingSender {
+        async fn send(&self, email: &OutgoingEmail) -> Result<SendReceipt, EmailError> {
+            self.0.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            tokio::task::yield_now().await;
+            Ok(SendReceipt::new(
+                EmailTransportKind::Outbox,
+                email.recipients.clone(),
+                "sent",
+            ))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_passes_send_a_message_once() {
+        let clock = ManualClock::new(start());
+        let queue = queue(&clock);
+        let worker = queue.clone();
+        let id = queue.enqueue(email()).unwrap().id.unwrap();
+        let sender = CountingSender::default();
+
+        let (first, second) = tokio::join!(queue.process_due(&sender), worker.process_due(&sender));
+
+        assert_eq!(sender.0.load(std::sync::atomic::Ordering::SeqCst), 1);
+        let mut sent = first.unwrap().sent;
+        sent.extend(second.unwrap().sent);
+        assert_eq!(sent, vec![id]);
+        assert_eq!(queue.get(id).unwrap().unwrap().status, QueueStatus::Sent);
+    }
+}
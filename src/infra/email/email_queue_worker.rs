@@ -0,0 +1,76 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use tokio::{sync::Notify, task::JoinHandle};
+
+use crate::infra::persistence::EmailQueueRepository;
+
+use super::{email_error::EmailError, email_queue::EmailQueue, email_sender::EmailSender};
+
+/// Background task that drains an [`EmailQueue`]: it sends what is due every
+/// `interval`, or right away after [`wake`](Self::wake).
+#[derive(Debug)]
+pub struct EmailQueueWorker {
+    handle: JoinHandle<()>,
+    wake: Arc<Notify>,
+    shutdown: Arc<Notify>,
+    last_error: Arc<Mutex<Option<EmailError>>>,
+}
+
+impl EmailQueueWorker {
+    /// Starts the worker on the current Tokio runtime.
+    pub fn spawn<R, S>(queue: EmailQueue<R>, sender: S, interval: Duration) -> Self
+    where
+        R: EmailQueueRepository + Send + 'static,
+        S: EmailSender + Send + Sync + 'static,
+    {
+        let wake = Arc::new(Notify::new());
+        let shutdown = Arc::new(Notify::new());
+        let last_error = Arc::new(Mutex::new(None));
+        let handle = tokio::spawn({
+            let (wake, shutdown, last_error) = (
+                Arc::clone(&wake),
+                Arc::clone(&shutdown),
+                Arc::clone(&last_error),
+            );
+            async move {
+                loop {
+                    // A storage failure is kept for `last_error` and retried on the next pass.
+                    let error = queue.process_due(&sender).await.err();
+                    if let Ok(mut last) = last_error.lock() {
+                        *last = error;
+                    }
+                    tokio::select! {
+                        _ = shutdown.notified() => break,
+                        _ = wake.notified() => {}
+                        _ = tokio::time::sleep(interval) => {}
+                    }
+                }
+            }
+        });
+        Self {
+            handle,
+            wake,
+            shutdown,
+            last_error,
+        }
+    }
+
+    /// Runs a pass now, e.g. after enqueuing a message to send immediately.
+    pub fn wake(&self) {
+        self.wake.notify_one();
+    }
+
+    /// The error of the last pass, if it failed.
+    pub fn last_error(&self) -> Option<EmailError> {
+        self.last_error.lock().ok().and_then(|last| last.clone())
+    }
+
+    /// Stops after the pass in progress, if any.
+    pub async fn stop(self) {
+        self.shutdown.notify_one();
+        let _ = self.handle.await;
+    }
+}
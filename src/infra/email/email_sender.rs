@@ -1 +1,11 @@
+use super::{email_error::EmailError, outgoing_email::OutgoingEmail, send_receipt::SendReceipt};
 
+/// Delivers finished messages. Implemented by [`SmtpSender`](super::SmtpSender),
+/// [`LocalSender`](super::LocalSender) and [`ConfiguredSender`](super::ConfiguredSender),
+/// which picks one from `[email]` in `config.toml`.
+pub trait EmailSender {
+    fn send(
+        &self,
+        email: &OutgoingEmail,
+    ) -> impl std::future::Future<Output = Result<SendReceipt, EmailError>> + Send;
+}
@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+use super::email_error::EmailError;
+
+/// `[email] transport`: where sent messages go.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum EmailTransportKind {
+    /// `.eml` files in a directory; the default for development.
+    #[default]
+    Outbox,
+    /// A Maildir (`tmp/`, `new/`, `cur/`) that mail clients can open.
+    Maildir,
+    Smtp,
+}
+
+impl EmailTransportKind {
+    pub fn to_str(&self) -> &str {
+        match self {
+            Self::Outbox => OUTBOX,
+            Self::Maildir => MAILDIR,
+            Self::Smtp => SMTP,
+        }
+    }
+}
+
+impl FromStr for EmailTransportKind {
+    type Err = EmailError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input.trim().to_lowercase().as_str() {
+            OUTBOX => Ok(Self::Outbox),
+            MAILDIR => Ok(Self::Maildir),
+            SMTP => Ok(Self::Smtp),
+            other => Err(EmailError::ConfigError(format!(
+                "unknown transport '{other}' (expected outbox, maildir or smtp)"
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for EmailTransportKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_str())
+    }
+}
+
+const OUTBOX: &str = "outbox";
+const MAILDIR: &str = "maildir";
+const SMTP: &str = "smtp";
@@ -0,0 +1,161 @@
+use chrono::{DateTime, FixedOffset};
+use mail_parser::{Address, HeaderValue, MessageParser, MimeHeaders};
+use serde::{Deserialize, Serialize};
+
+use super::{email_error::EmailError, mailbox::Mailbox};
+
+/// A received message, read from a mailbox by a [`MailSource`](super::MailSource).
+///
+/// Message ids keep their angle brackets, as in [`OutgoingEmail`](super::OutgoingEmail).
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct IncomingEmail {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from: Option<Mailbox>,
+    #[serde(default)]
+    pub to: Vec<Mailbox>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cc: Vec<Mailbox>,
+    /// Where replies should go instead of `from`, when the sender set it.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub reply_to: Vec<Mailbox>,
+    pub subject: String,
+    /// The `Date` header, in the sender's offset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date: Option<DateTime<FixedOffset>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub in_reply_to: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub references: Vec<String>,
+    /// The text body; converted from the HTML body when the message has no text part.
+    pub text_body: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub html_body: Option<String>,
+    /// File names of the attachments.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attachments: Vec<String>,
+    /// `List-Id`, set on mailing list and newsletter traffic.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub list_id: Option<String>,
+    /// The receiving server marked the message as spam (`X-Spam-Flag: YES`).
+    #[serde(default)]
+    pub spam_flag: bool,
+}
+
+impl IncomingEmail {
+    /// Reads an RFC 5322 message.
+    pub fn parse(raw: &[u8]) -> Result<Self, EmailError> {
+        let message = MessageParser::default()
+            .parse(raw)
+            .filter(|message| !message.headers().is_empty())
+            .ok_or_else(|| EmailError::InvalidMessage("not an email message".to_string()))?;
+
+        Ok(Self {
+            message_id: message.message_id().map(angle_brackets),
+            from: message
+                .from()
+                .and_then(|from| mailboxes(Some(from)).into_iter().next()),
+            to: mailboxes(message.to()),
+            cc: mailboxes(message.cc()),
+            reply_to: mailboxes(message.reply_to()),
+            subject: message.subject().unwrap_or_default().trim().to_string(),
+            date: message
+                .date()
+                .and_then(|date| DateTime::parse_from_rfc3339(&date.to_rfc3339()).ok()),
+            in_reply_to: message_ids(message.in_reply_to()).into_iter().next(),
+            references: message_ids(message.references()),
+            text_body: message
+                .body_text(0)
+                .map(|body| body.trim_end().to_string())
+                .unwrap_or_default(),
+            html_body: message
+                .html_part(0)
+                .filter(|part| part.is_text_html())
+                .and_then(|part| part.text_contents())
+                .map(str::to_string),
+            attachments: message
+                .attachments()
+                .filter_map(|part| part.attachment_name())
+                .map(str::to_string)
+                .collect(),
+            list_id: message.header_raw(LIST_ID).map(|id| id.trim().to_string()),
+            spam_flag: message
+                .header_raw(SPAM_FLAG)
+                .is_some_and(|flag| flag.trim().eq_ignore_ascii_case("yes")),
+        })
+    }
+
+    /// The sender's address, or an empty string.
+    pub fn sender(&self) -> &str {
+        self.from.as_ref().map_or("", |from| from.address.as_str())
+    }
+}
+
+fn mailboxes(address: Option<&Address>) -> Vec<Mailbox> {
+    address
+        .map(|address| {
+            address
+                .iter()
+                .filter_map(|addr| {
+                    let mailbox = Mailbox::new(addr.address()?);
+                    Some(match addr.name() {
+                        Some(name) => mailbox.with_name(name),
+                        None => mailbox,
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn message_ids(value: &HeaderValue) -> Vec<String> {
+    match value {
+        HeaderValue::Text(id) => vec![angle_brackets(id)],
+        HeaderValue::TextList(ids) => ids.iter().map(|id| angle_brackets(id)).collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn angle_brackets(id: &str) -> String {
+    format!(
+        "<{}>",
+        id.trim().trim_start_matches('<').trim_end_matches('>')
+    )
+}
+
+const LIST_ID: &str = "List-Id";
+const SPAM_FLAG: &str = "X-Spam-Flag";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_reads_headers_and_body() {
+        let raw = b"From: \"Silva, Ana\" <ana@example.com>\r\nTo: eva@example.com, Carlos <carlos@example.com>\r\nSubject: Budget review\r\nDate: Tue, 2 Sep 2025 09:30:00 -0300\r\nMessage-ID: <42@example.com>\r\nIn-Reply-To: <41@example.com>\r\nReferences: <40@example.com> <41@example.com>\r\nX-Spam-Flag: NO\r\n\r\nCan you send the numbers by Friday?\r\n";
+
+        let email = IncomingEmail::parse(raw).unwrap();
+
+        assert_eq!(email.message_id.as_deref(), Some("<42@example.com>"));
+        assert_eq!(email.sender(), "ana@example.com");
+        assert_eq!(
+            email.from.as_ref().and_then(|from| from.name.as_deref()),
+            Some("Silva, Ana")
+        );
+        assert_eq!(email.to.len(), 2);
+        assert_eq!(email.subject, "Budget review");
+        assert_eq!(
+            email.date.unwrap().to_rfc3339(),
+            "2025-09-02T09:30:00-03:00"
+        );
+        assert_eq!(email.in_reply_to.as_deref(), Some("<41@example.com>"));
+        assert_eq!(
+            email.references,
+            vec!["<40@example.com>", "<41@example.com>"]
+        );
+        assert_eq!(email.text_body, "Can you send the numbers by Friday?");
+        assert!(!email.spam_flag);
+        assert!(IncomingEmail::parse(b"").is_err());
+    }
+}
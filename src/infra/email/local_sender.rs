@@ -0,0 +1,166 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicU64, Ordering},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use super::{
+    email_error::EmailError, email_sender::EmailSender, email_transport_kind::EmailTransportKind,
+    outgoing_email::OutgoingEmail, send_receipt::SendReceipt,
+};
+
+/// Writes messages to disk instead of sending them: one `.eml` file per
+/// message in an outbox directory, or a new message in a Maildir. Files are
+/// written under a temporary name and renamed, so readers never see half a
+/// message.
+#[derive(Debug, Clone)]
+pub struct LocalSender {
+    kind: EmailTransportKind,
+    path: PathBuf,
+}
+
+impl LocalSender {
+    pub fn outbox(path: impl Into<PathBuf>) -> Self {
+        Self {
+            kind: EmailTransportKind::Outbox,
+            path: path.into(),
+        }
+    }
+
+    pub fn maildir(path: impl Into<PathBuf>) -> Self {
+        Self {
+            kind: EmailTransportKind::Maildir,
+            path: path.into(),
+        }
+    }
+
+    pub fn kind(&self) -> EmailTransportKind {
+        self.kind
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Writes `email` and returns the path of the new file.
+    pub fn deliver(&self, email: &OutgoingEmail) -> Result<PathBuf, EmailError> {
+        let name = unique_name();
+        let (tmp, target) = match self.kind {
+            EmailTransportKind::Maildir => {
+                for dir in MAILDIR_DIRS {
+                    create_dir(&self.path.join(dir))?;
+                }
+                (
+                    self.path.join("tmp").join(&name),
+                    self.path.join("new").join(&name),
+                )
+            }
+            _ => {
+                create_dir(&self.path)?;
+                (
+                    self.path.join(format!(".{name}.tmp")),
+                    self.path.join(format!("{name}.eml")),
+                )
+            }
+        };
+        fs::write(&tmp, &email.raw).map_err(|e| io_error(&tmp, e))?;
+        fs::rename(&tmp, &target).map_err(|e| io_error(&target, e))?;
+        Ok(target)
+    }
+}
+
+impl EmailSender for LocalSender {
+    fn send(
+        &self,
+        email: &OutgoingEmail,
+    ) -> impl std::future::Future<Output = Result<SendReceipt, EmailError>> + Send {
+        let result = self.deliver(email).map(|path| {
+            SendReceipt::new(
+                self.kind,
+                email.recipients.clone(),
+                &path.display().to_string(),
+            )
+        });
+        async move { result }
+    }
+}
+
+/// `{seconds}.M{micros}P{pid}Q{counter}.{host}`, the Maildir naming scheme; it
+/// also sorts outbox files by time.
+fn unique_name() -> String {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    let host: String = std::env::var("HOSTNAME")
+        .unwrap_or_else(|_| "localhost".to_string())
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '.'))
+        .collect();
+    format!(
+        "{}.M{}P{}Q{}.{}",
+        now.as_secs(),
+        now.subsec_micros(),
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed),
+        if host.is_empty() { "localhost" } else { &host }
+    )
+}
+
+fn create_dir(path: &Path) -> Result<(), EmailError> {
+    fs::create_dir_all(path).map_err(|e| io_error(path, e))
+}
+
+fn io_error(path: &Path, error: std::io::Error) -> EmailError {
+    EmailError::IoError(format!("{}: {error}", path.display()))
+}
+
+static COUNTER: AtomicU64 = AtomicU64::new(0);
+const MAILDIR_DIRS: [&str; 3] = ["tmp", "new", "cur"];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("local_sender_{name}_{}", std::process::id()))
+    }
+
+    fn email() -> OutgoingEmail {
+        OutgoingEmail::new(
+            "ana@example.com",
+            vec!["eva@example.com".to_string()],
+            b"Subject: Hi\r\n\r\nHello\r\n",
+        )
+    }
+
+    #[tokio::test]
+    async fn test_outbox_writes_eml_files() {
+        let dir = temp_dir("outbox");
+        let sender = LocalSender::outbox(&dir);
+
+        let first = sender.send(&email()).await.unwrap();
+        let second = sender.send(&email()).await.unwrap();
+
+        assert_eq!(first.transport, EmailTransportKind::Outbox);
+        assert_eq!(first.recipients, vec!["eva@example.com"]);
+        assert_ne!(first.reference, second.reference);
+        assert!(first.reference.ends_with(".eml"));
+        assert_eq!(fs::read(&first.reference).unwrap(), email().raw);
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 2);
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[tokio::test]
+    async fn test_maildir_delivers_to_new() {
+        let dir = temp_dir("maildir");
+        let receipt = LocalSender::maildir(&dir).send(&email()).await.unwrap();
+
+        let delivered = PathBuf::from(&receipt.reference);
+        assert_eq!(delivered.parent().unwrap(), dir.join("new"));
+        assert!(dir.join("cur").is_dir());
+        assert_eq!(fs::read_dir(dir.join("tmp")).unwrap().count(), 0);
+        assert_eq!(fs::read(delivered).unwrap(), email().raw);
+        let _ = fs::remove_dir_all(dir);
+    }
+}
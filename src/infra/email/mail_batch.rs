@@ -0,0 +1,22 @@
+use super::{incoming_email::IncomingEmail, unreadable_mail::UnreadableMail};
+
+/// What one [`fetch`](super::MailSource::fetch) found, oldest first.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct MailBatch {
+    pub emails: Vec<IncomingEmail>,
+    /// Messages that could not be parsed; they are not fetched again.
+    pub unreadable: Vec<UnreadableMail>,
+}
+
+impl MailBatch {
+    pub fn new(emails: Vec<IncomingEmail>) -> Self {
+        Self {
+            emails,
+            unreadable: Vec::new(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.emails.is_empty() && self.unreadable.is_empty()
+    }
+}
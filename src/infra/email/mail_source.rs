@@ -0,0 +1,10 @@
+use super::{email_error::EmailError, mail_batch::MailBatch};
+
+/// Where incoming mail is read from: a local mbox file ([`MboxSource`](super::MboxSource)),
+/// a Maildir ([`MaildirSource`](super::MaildirSource)) or, in tests, a stand-in for a
+/// remote mailbox.
+pub trait MailSource {
+    /// Messages that arrived since the last call, oldest first, with the ones
+    /// that could not be parsed listed apart.
+    fn fetch(&mut self) -> Result<MailBatch, EmailError>;
+}
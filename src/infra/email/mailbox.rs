@@ -0,0 +1,116 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use super::{
+    email_address::normalize_address, email_error::EmailError, mime_encoding::encode_display_name,
+};
+
+/// An address with an optional display name: `Ana Silva <ana@example.com>`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct Mailbox {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub address: String,
+}
+
+impl Mailbox {
+    pub fn new(address: &str) -> Self {
+        Self {
+            name: None,
+            address: address.trim().to_string(),
+        }
+    }
+
+    pub fn with_name(mut self, name: &str) -> Self {
+        let name = name.trim();
+        self.name = (!name.is_empty()).then(|| name.to_string());
+        self
+    }
+
+    /// Reads `ana@example.com`, `Ana <ana@example.com>` or
+    /// `"Silva, Ana" <ana@example.com>`, validating the address and converting
+    /// an internationalised domain to ASCII ([`normalize_address`]).
+    pub fn parse(input: &str) -> Result<Self, EmailError> {
+        let input = input.trim();
+        let mut mailbox = match (input.rfind('<'), input.ends_with('>')) {
+            (Some(start), true) => {
+                let name = input[..start]
+                    .trim()
+                    .trim_matches('"')
+                    .replace("\\\"", "\"");
+                Self::new(&input[start + 1..input.len() - 1]).with_name(&name)
+            }
+            _ => Self::new(input),
+        };
+        mailbox.address = normalize_address(&mailbox.address)?;
+        Ok(mailbox)
+    }
+
+    /// The part after `@`.
+    pub fn domain(&self) -> &str {
+        self.address
+            .rsplit_once('@')
+            .map_or("", |(_, domain)| domain)
+    }
+
+    /// The form used in `From`/`To`/`Cc` headers, with non-ASCII names encoded.
+    pub fn to_header(&self) -> String {
+        match &self.name {
+            Some(name) => format!("{} <{}>", encode_display_name(name), self.address),
+            None => self.address.clone(),
+        }
+    }
+}
+
+impl fmt::Display for Mailbox {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.name {
+            Some(name) => write!(f, "{name} <{}>", self.address),
+            None => write!(f, "{}", self.address),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            Mailbox::parse("eva@example.com").unwrap(),
+            Mailbox::new("eva@example.com")
+        );
+        let named = Mailbox::parse("\"Silva, Ana\" <ana@example.com>").unwrap();
+        assert_eq!(named.name.as_deref(), Some("Silva, Ana"));
+        assert_eq!(named.address, "ana@example.com");
+        assert_eq!(named.domain(), "example.com");
+        assert!(Mailbox::parse("Eva").is_err());
+        assert!(Mailbox::parse("eva@").is_err());
+        assert!(Mailbox::parse("Eva <eva at example.com>").is_err());
+        assert_eq!(
+            Mailbox::parse("Ana <ana@bücher.de>").unwrap().address,
+            "ana@xn--bcher-kva.de"
+        );
+    }
+
+    #[test]
+    fn test_to_header() {
+        assert_eq!(
+            Mailbox::new("ana@example.com")
+                .with_name("Silva, Ana")
+                .to_header(),
+            "\"Silva, Ana\" <ana@example.com>"
+        );
+        assert!(
+            Mailbox::new("joao@example.com")
+                .with_name("João")
+                .to_header()
+                .starts_with("=?UTF-8?B?")
+        );
+        assert_eq!(
+            Mailbox::new("eva@example.com").with_name("Eva").to_string(),
+            "Eva <eva@example.com>"
+        );
+    }
+}
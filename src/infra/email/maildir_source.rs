@@ -0,0 +1,86 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use super::{
+    email_error::EmailError, incoming_email::IncomingEmail, mail_batch::MailBatch,
+    mail_source::MailSource, unreadable_mail::UnreadableMail,
+};
+
+/// Reads new messages from a Maildir: each [`fetch`](MailSource::fetch) returns
+/// the files in `new/` and moves them to `cur/`, as mail clients do, so they are
+/// returned once. Files are moved only after all of them were read, and a file
+/// that could not be moved stays in `new/` for the next fetch.
+#[derive(Debug, Clone)]
+pub struct MaildirSource {
+    path: PathBuf,
+}
+
+impl MaildirSource {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl MailSource for MaildirSource {
+    fn fetch(&mut self) -> Result<MailBatch, EmailError> {
+        let new = self.path.join("new");
+        let cur = self.path.join("cur");
+        let mut files: Vec<PathBuf> = match fs::read_dir(&new) {
+            Ok(entries) => entries
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|path| path.is_file() && !is_hidden(path))
+                .collect(),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(MailBatch::default()),
+            Err(e) => return Err(io_error(&new, e)),
+        };
+        // Maildir names start with the delivery time.
+        files.sort();
+
+        let mut read = Vec::with_capacity(files.len());
+        for file in files {
+            let raw = fs::read(&file).map_err(|e| io_error(&file, e))?;
+            read.push((file, IncomingEmail::parse(&raw)));
+        }
+
+        fs::create_dir_all(&cur).map_err(|e| io_error(&cur, e))?;
+        let mut batch = MailBatch::default();
+        for (file, parsed) in read {
+            let name = file
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let target = cur.join(format!("{name}{INFO}"));
+            if let Err(e) = fs::rename(&file, &target) {
+                if batch.is_empty() {
+                    return Err(io_error(&target, e));
+                }
+                break;
+            }
+            match parsed {
+                Ok(email) => batch.emails.push(email),
+                Err(error) => batch
+                    .unreadable
+                    .push(UnreadableMail::new(&target.display().to_string(), error)),
+            }
+        }
+        Ok(batch)
+    }
+}
+
+fn is_hidden(path: &Path) -> bool {
+    path.file_name()
+        .is_some_and(|name| name.to_string_lossy().starts_with('.'))
+}
+
+fn io_error(path: &Path, e: std::io::Error) -> EmailError {
+    EmailError::IoError(format!("{}: {e}", path.display()))
+}
+
+/// The info suffix of a message moved to `cur/` without flags.
+const INFO: &str = ":2,";
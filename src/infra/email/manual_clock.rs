@@ -0,0 +1,51 @@
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, TimeDelta, Utc};
+
+use super::clock::Clock;
+
+/// A clock that only moves when told to. Clones share the same time, so a test
+/// can keep one and hand another to the queue.
+#[derive(Debug, Clone)]
+pub struct ManualClock {
+    now: Arc<Mutex<DateTime<Utc>>>,
+}
+
+impl ManualClock {
+    pub fn new(now: DateTime<Utc>) -> Self {
+        Self {
+            now: Arc::new(Mutex::new(now)),
+        }
+    }
+
+    pub fn set(&self, now: DateTime<Utc>) {
+        *self.now.lock().unwrap_or_else(|e| e.into_inner()) = now;
+    }
+
+    pub fn advance(&self, delta: TimeDelta) {
+        let mut now = self.now.lock().unwrap_or_else(|e| e.into_inner());
+        *now += delta;
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.now.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clones_share_the_time() {
+        let start = DateTime::from_timestamp(1_750_000_000, 0).unwrap();
+        let clock = ManualClock::new(start);
+        let shared = clock.clone();
+
+        clock.advance(TimeDelta::minutes(5));
+
+        assert_eq!(shared.now(), start + TimeDelta::minutes(5));
+    }
+}
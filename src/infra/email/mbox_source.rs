@@ -0,0 +1,98 @@
+use std::{
+    fs::{self, File},
+    io::BufReader,
+    path::{Path, PathBuf},
+};
+
+use mail_parser::mailbox::mbox::MessageIterator;
+
+use super::{
+    email_error::EmailError, incoming_email::IncomingEmail, mail_batch::MailBatch,
+    mail_source::MailSource, unreadable_mail::UnreadableMail,
+};
+
+/// Reads an mbox file. The file is only read, never rewritten; messages
+/// appended after a [`fetch`](MailSource::fetch) are returned by the next one.
+///
+/// The number of messages already returned is kept in memory, so a new source
+/// starts from the first message; [`with_state`](Self::with_state) keeps it in
+/// a file so it survives restarts. The count assumes messages are only
+/// appended: after another client deletes messages from the mbox, start over
+/// with a new state file.
+#[derive(Debug, Clone)]
+pub struct MboxSource {
+    path: PathBuf,
+    seen: usize,
+    state: Option<PathBuf>,
+}
+
+impl MboxSource {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            seen: 0,
+            state: None,
+        }
+    }
+
+    /// Reads and writes the number of messages already returned at `path`.
+    pub fn with_state(mut self, path: impl Into<PathBuf>) -> Self {
+        self.state = Some(path.into());
+        self
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn seen(&self) -> Result<usize, EmailError> {
+        let Some(state) = &self.state else {
+            return Ok(self.seen);
+        };
+        match fs::read_to_string(state) {
+            Ok(text) => text
+                .trim()
+                .parse()
+                .map_err(|e| EmailError::IoError(format!("{}: {e}", state.display()))),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(0),
+            Err(e) => Err(EmailError::IoError(format!("{}: {e}", state.display()))),
+        }
+    }
+
+    fn set_seen(&mut self, count: usize) -> Result<(), EmailError> {
+        if let Some(state) = &self.state {
+            fs::write(state, count.to_string())
+                .map_err(|e| EmailError::IoError(format!("{}: {e}", state.display())))?;
+        }
+        self.seen = count;
+        Ok(())
+    }
+}
+
+impl MailSource for MboxSource {
+    fn fetch(&mut self) -> Result<MailBatch, EmailError> {
+        let io_error =
+            |e: std::io::Error| EmailError::IoError(format!("{}: {e}", self.path.display()));
+        let seen = self.seen()?;
+        let file = File::open(&self.path).map_err(io_error)?;
+        let mut batch = MailBatch::default();
+        let mut count = 0;
+        for message in MessageIterator::new(BufReader::new(file)) {
+            let message = message.map_err(io_error)?;
+            count += 1;
+            if count <= seen {
+                continue;
+            }
+            // Messages that cannot be read are reported rather than blocking the mailbox.
+            match IncomingEmail::parse(message.contents()) {
+                Ok(email) => batch.emails.push(email),
+                Err(error) => batch.unreadable.push(UnreadableMail::new(
+                    &format!("{} message {count}", self.path.display()),
+                    error,
+                )),
+            }
+        }
+        self.set_seen(count)?;
+        Ok(batch)
+    }
+}
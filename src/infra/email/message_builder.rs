@@ -0,0 +1,417 @@
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use chrono::{DateTime, FixedOffset, Local};
+
+use super::{
+    email_attachment::EmailAttachment,
+    email_error::EmailError,
+    mailbox::Mailbox,
+    mime_encoding::{
+        base64_lines, encode_words, header, is_7bit, normalize_lines, parameter, quoted_printable,
+    },
+    outgoing_email::OutgoingEmail,
+};
+
+/// Builds RFC 5322 messages with MIME bodies:
+///
+/// - plain text, HTML or both as `multipart/alternative`
+/// - attachments in `multipart/mixed`, base64 encoded, with the content type
+///   and (RFC 2231) file name
+/// - non-ASCII subjects and names as RFC 2047 encoded words, non-ASCII text
+///   as quoted-printable UTF-8
+/// - a generated `Message-ID` and `In-Reply-To`/`References` for replies
+///
+/// `Bcc` recipients only go to the envelope of the [`OutgoingEmail`].
+#[derive(Debug, Clone, Default)]
+pub struct MessageBuilder {
+    from: Option<Mailbox>,
+    to: Vec<Mailbox>,
+    cc: Vec<Mailbox>,
+    bcc: Vec<Mailbox>,
+    reply_to: Option<Mailbox>,
+    subject: String,
+    text_body: Option<String>,
+    html_body: Option<String>,
+    attachments: Vec<EmailAttachment>,
+    message_id: Option<String>,
+    in_reply_to: Option<String>,
+    references: Vec<String>,
+    date: Option<DateTime<FixedOffset>>,
+    headers: Vec<(String, String)>,
+}
+
+impl MessageBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_from(mut self, from: Mailbox) -> Self {
+        self.from = Some(from);
+        self
+    }
+
+    pub fn with_to(mut self, to: Mailbox) -> Self {
+        self.to.push(to);
+        self
+    }
+
+    pub fn with_cc(mut self, cc: Mailbox) -> Self {
+        self.cc.push(cc);
+        self
+    }
+
+    pub fn with_bcc(mut self, bcc: Mailbox) -> Self {
+        self.bcc.push(bcc);
+        self
+    }
+
+    pub fn with_reply_to(mut self, reply_to: Mailbox) -> Self {
+        self.reply_to = Some(reply_to);
+        self
+    }
+
+    pub fn with_subject(mut self, subject: &str) -> Self {
+        self.subject = subject.to_string();
+        self
+    }
+
+    pub fn with_text_body(mut self, text: &str) -> Self {
+        self.text_body = Some(text.to_string());
+        self
+    }
+
+    pub fn with_html_body(mut self, html: &str) -> Self {
+        self.html_body = Some(html.to_string());
+        self
+    }
+
+    pub fn with_attachment(mut self, attachment: EmailAttachment) -> Self {
+        self.attachments.push(attachment);
+        self
+    }
+
+    /// Generated from the sender's domain when not set.
+    pub fn with_message_id(mut self, message_id: &str) -> Self {
+        self.message_id = Some(angle_brackets(message_id));
+        self
+    }
+
+    pub fn with_in_reply_to(mut self, message_id: &str) -> Self {
+        self.in_reply_to = Some(angle_brackets(message_id));
+        self
+    }
+
+    pub fn with_references(mut self, references: Vec<String>) -> Self {
+        self.references = references.iter().map(|id| angle_brackets(id)).collect();
+        self
+    }
+
+    /// Threads the message under `message_id`: `In-Reply-To` is the parent and
+    /// `References` its references followed by the parent (RFC 5322 3.6.4).
+    pub fn replying_to(self, message_id: &str, references: &[String]) -> Self {
+        let mut thread = references.to_vec();
+        thread.push(message_id.to_string());
+        self.with_in_reply_to(message_id).with_references(thread)
+    }
+
+    /// The current local time when not set.
+    pub fn with_date(mut self, date: DateTime<FixedOffset>) -> Self {
+        self.date = Some(date);
+        self
+    }
+
+    /// An extra header such as `X-Mailer`. Names must be printable ASCII without `:`.
+    pub fn with_header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    pub fn build(&self) -> Result<OutgoingEmail, EmailError> {
+        let from = self
+            .from
+            .as_ref()
+            .ok_or_else(|| EmailError::InvalidMessage("no sender".to_string()))?;
+        let mut recipients: Vec<String> = Vec::new();
+        for mailbox in self.to.iter().chain(&self.cc).chain(&self.bcc) {
+            if !recipients
+                .iter()
+                .any(|known| known.eq_ignore_ascii_case(&mailbox.address))
+            {
+                recipients.push(mailbox.address.clone());
+            }
+        }
+        if recipients.is_empty() {
+            return Err(EmailError::InvalidMessage("no recipients".to_string()));
+        }
+        let message_id = self
+            .message_id
+            .clone()
+            .unwrap_or_else(|| generate_message_id(from.domain()));
+        let date = self.date.unwrap_or_else(|| Local::now().fixed_offset());
+
+        let mut raw = header("Date", &date.to_rfc2822());
+        raw.push_str(&header("From", &from.to_header()));
+        if self.to.is_empty() && self.cc.is_empty() {
+            raw.push_str(&header("To", UNDISCLOSED_RECIPIENTS));
+        }
+        if !self.to.is_empty() {
+            raw.push_str(&header("To", &address_list(&self.to)));
+        }
+        if !self.cc.is_empty() {
+            raw.push_str(&header("Cc", &address_list(&self.cc)));
+        }
+        if let Some(reply_to) = &self.reply_to {
+            raw.push_str(&header("Reply-To", &reply_to.to_header()));
+        }
+        raw.push_str(&header("Subject", &encode_words(&self.subject)));
+        raw.push_str(&header("Message-ID", &message_id));
+        if let Some(in_reply_to) = &self.in_reply_to {
+            raw.push_str(&header("In-Reply-To", in_reply_to));
+        }
+        if !self.references.is_empty() {
+            raw.push_str(&header("References", &self.references.join(" ")));
+        }
+        for (name, value) in &self.headers {
+            if name.is_empty() || !name.chars().all(|c| c.is_ascii_graphic() && c != ':') {
+                return Err(EmailError::InvalidMessage(format!(
+                    "invalid header name '{name}'"
+                )));
+            }
+            raw.push_str(&header(name, &encode_words(value)));
+        }
+        raw.push_str("MIME-Version: 1.0\r\n");
+        raw.push_str(&self.body());
+
+        Ok(
+            OutgoingEmail::new(&from.address, recipients, raw.as_bytes())
+                .with_message_id(&message_id),
+        )
+    }
+
+    /// The top-level MIME entity: its `Content-*` headers, a blank line and the content.
+    fn body(&self) -> String {
+        let text = self
+            .text_body
+            .as_deref()
+            .map(|text| text_part("plain", text));
+        let html = self
+            .html_body
+            .as_deref()
+            .map(|html| text_part("html", html));
+        let content = match (text, html) {
+            (Some(text), Some(html)) => multipart("alternative", &[text, html]),
+            (Some(part), None) | (None, Some(part)) => part,
+            (None, None) => text_part("plain", ""),
+        };
+        if self.attachments.is_empty() {
+            return content;
+        }
+        let mut parts = vec![content];
+        parts.extend(self.attachments.iter().map(attachment_part));
+        multipart("mixed", &parts)
+    }
+}
+
+fn text_part(subtype: &str, text: &str) -> String {
+    let text = normalize_lines(text);
+    let (encoding, content) = if is_7bit(&text) {
+        ("7bit", text)
+    } else {
+        ("quoted-printable", quoted_printable(&text))
+    };
+    format!(
+        "Content-Type: text/{subtype}; charset=utf-8\r\nContent-Transfer-Encoding: {encoding}\r\n\r\n{content}"
+    )
+}
+
+fn attachment_part(attachment: &EmailAttachment) -> String {
+    // An attached message must stay readable (RFC 2046 5.2.1); other content is base64.
+    let attached_message = (attachment.content_type == RFC822)
+        .then(|| std::str::from_utf8(&attachment.data).ok())
+        .flatten();
+    let (content_type, encoding, content) = match attached_message {
+        Some(message) => (RFC822, "8bit", normalize_lines(message)),
+        None if attachment.content_type == RFC822 => {
+            (OCTET_STREAM, "base64", base64_lines(&attachment.data))
+        }
+        None => (
+            attachment.content_type.as_str(),
+            "base64",
+            base64_lines(&attachment.data),
+        ),
+    };
+    let mut part = header(
+        "Content-Type",
+        &format!(
+            "{content_type}; {}",
+            parameter("name", &attachment.filename)
+        ),
+    );
+    part.push_str(&header(
+        "Content-Disposition",
+        &format!(
+            "attachment; {}",
+            parameter("filename", &attachment.filename)
+        ),
+    ));
+    part.push_str(&format!(
+        "Content-Transfer-Encoding: {encoding}\r\n\r\n{content}"
+    ));
+    part
+}
+
+/// Each part ends with CRLF, which doubles as the CRLF before the next delimiter.
+fn multipart(subtype: &str, parts: &[String]) -> String {
+    let boundary = generate_boundary();
+    let mut out = format!("Content-Type: multipart/{subtype};\r\n boundary=\"{boundary}\"\r\n\r\n");
+    for part in parts {
+        out.push_str(&format!("--{boundary}\r\n{part}"));
+    }
+    out.push_str(&format!("--{boundary}--\r\n"));
+    out
+}
+
+fn address_list(mailboxes: &[Mailbox]) -> String {
+    mailboxes
+        .iter()
+        .map(Mailbox::to_header)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn angle_brackets(message_id: &str) -> String {
+    let id = message_id
+        .trim()
+        .trim_start_matches('<')
+        .trim_end_matches('>');
+    format!("<{id}>")
+}
+
+/// `<{seconds}.{nanos}.{pid}.{counter}@{domain}>`, unique per process and time.
+pub fn generate_message_id(domain: &str) -> String {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    let domain = if domain.is_empty() {
+        "localhost"
+    } else {
+        domain
+    };
+    format!(
+        "<{:x}.{:x}.{:x}.{}@{domain}>",
+        now.as_secs(),
+        now.subsec_nanos(),
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    )
+}
+
+/// `=_` cannot occur in quoted-printable or base64 content.
+fn generate_boundary() -> String {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    format!(
+        "=_{:x}{:x}_{}",
+        now.as_secs(),
+        now.subsec_nanos(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    )
+}
+
+static COUNTER: AtomicU64 = AtomicU64::new(0);
+const UNDISCLOSED_RECIPIENTS: &str = "undisclosed-recipients:;";
+const RFC822: &str = "message/rfc822";
+const OCTET_STREAM: &str = "application/octet-stream";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn builder() -> MessageBuilder {
+        MessageBuilder::new()
+            .with_from(Mailbox::new("ana@example.com").with_name("Ana"))
+            .with_to(Mailbox::new("eva@example.com"))
+            .with_subject("Hello")
+            .with_date(DateTime::parse_from_rfc3339("2025-08-22T10:00:00-03:00").unwrap())
+    }
+
+    fn raw(builder: &MessageBuilder) -> String {
+        String::from_utf8(builder.build().unwrap().raw).unwrap()
+    }
+
+    #[test]
+    fn test_plain_text_message() {
+        let message = raw(&builder()
+            .with_text_body("Hi Eva\n\nAna")
+            .with_message_id("abc@example.com"));
+
+        assert!(message.starts_with("Date: Fri, 22 Aug 2025 10:00:00 -0300\r\n"));
+        assert!(message.contains("From: Ana <ana@example.com>\r\n"));
+        assert!(message.contains("Message-ID: <abc@example.com>\r\n"));
+        assert!(message.contains("Content-Type: text/plain; charset=utf-8\r\n"));
+        assert!(message.contains("Content-Transfer-Encoding: 7bit\r\n\r\nHi Eva\r\n\r\nAna\r\n"));
+        assert!(!message.contains("multipart"));
+    }
+
+    #[test]
+    fn test_generated_message_id_uses_sender_domain() {
+        let first = builder().build().unwrap();
+        let second = builder().build().unwrap();
+        let id = first.message_id.unwrap();
+
+        assert!(id.starts_with('<') && id.ends_with("@example.com>"));
+        assert_ne!(Some(id), second.message_id);
+    }
+
+    #[test]
+    fn test_bcc_only_in_envelope() {
+        let email = MessageBuilder::new()
+            .with_from(Mailbox::new("ana@example.com"))
+            .with_bcc(Mailbox::new("carlos@example.com"))
+            .with_text_body("FYI")
+            .build()
+            .unwrap();
+        let message = String::from_utf8(email.raw).unwrap();
+
+        assert_eq!(email.recipients, vec!["carlos@example.com"]);
+        assert!(message.contains("To: undisclosed-recipients:;\r\n"));
+        assert!(!message.contains("carlos"));
+    }
+
+    #[test]
+    fn test_invalid_messages() {
+        assert!(matches!(
+            MessageBuilder::new()
+                .with_to(Mailbox::new("eva@example.com"))
+                .build(),
+            Err(EmailError::InvalidMessage(_))
+        ));
+        assert!(matches!(
+            MessageBuilder::new()
+                .with_from(Mailbox::new("ana@example.com"))
+                .build(),
+            Err(EmailError::InvalidMessage(_))
+        ));
+        assert!(matches!(
+            builder().with_header("Bad: Name", "x").build(),
+            Err(EmailError::InvalidMessage(_))
+        ));
+    }
+
+    #[test]
+    fn test_replying_to_builds_the_thread() {
+        let message = raw(&builder().replying_to(
+            "<c@example.com>",
+            &["<a@example.com>".to_string(), "b@example.com".to_string()],
+        ));
+
+        assert!(message.contains("In-Reply-To: <c@example.com>\r\n"));
+        assert!(
+            message.contains("References: <a@example.com> <b@example.com> <c@example.com>\r\n")
+        );
+    }
+}
@@ -0,0 +1,213 @@
+//! Encodings used by [`MessageBuilder`](super::MessageBuilder): RFC 2047
+//! encoded words for non-ASCII headers, RFC 2231 parameters, quoted-printable
+//! text and base64 attachments, all with CRLF line endings.
+
+use base64::{Engine, engine::general_purpose::STANDARD};
+
+/// A header line folded at spaces to stay within 78 characters. CR and LF in
+/// `value` are replaced by spaces so a value can never start a new header.
+pub fn header(name: &str, value: &str) -> String {
+    let value = value.replace(['\r', '\n'], " ");
+    let mut line = format!("{name}:");
+    let mut width = line.len();
+    for token in value.split(' ').filter(|token| !token.is_empty()) {
+        if width + 1 + token.len() > MAX_LINE && width > name.len() + 1 {
+            line.push_str("\r\n");
+            width = 0;
+        }
+        line.push(' ');
+        line.push_str(token);
+        width += 1 + token.len();
+    }
+    line.push_str("\r\n");
+    line
+}
+
+/// `text` as is when it is printable ASCII, otherwise as space-separated
+/// `=?UTF-8?B?...?=` words of at most 75 characters.
+pub fn encode_words(text: &str) -> String {
+    if text
+        .chars()
+        .all(|c| c.is_ascii() && (!c.is_ascii_control() || c == '\t'))
+        && !text.contains("=?")
+    {
+        return text.to_string();
+    }
+    let mut words = Vec::new();
+    let mut chunk = String::new();
+    for c in text.chars() {
+        if chunk.len() + c.len_utf8() > WORD_BYTES {
+            words.push(encoded_word(&chunk));
+            chunk.clear();
+        }
+        chunk.push(c);
+    }
+    if !chunk.is_empty() {
+        words.push(encoded_word(&chunk));
+    }
+    words.join(" ")
+}
+
+/// A display name for an address header: atoms as is, other ASCII as a quoted
+/// string, non-ASCII as encoded words.
+pub fn encode_display_name(name: &str) -> String {
+    if !name.is_ascii() {
+        return encode_words(name);
+    }
+    if name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || " !#$%&'*+-/=?^_`{|}~".contains(c))
+    {
+        return name.to_string();
+    }
+    format!("\"{}\"", name.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// A `name="value"` parameter, or `name*=UTF-8''percent-encoded` (RFC 2231)
+/// when the value is not ASCII.
+pub fn parameter(name: &str, value: &str) -> String {
+    if value.is_ascii() {
+        return format!(
+            "{name}=\"{}\"",
+            value.replace('\\', "\\\\").replace('"', "\\\"")
+        );
+    }
+    let encoded: String = value
+        .bytes()
+        .map(|byte| {
+            if byte.is_ascii_alphanumeric() || b"!#$&+-.^_`|~".contains(&byte) {
+                (byte as char).to_string()
+            } else {
+                format!("%{byte:02X}")
+            }
+        })
+        .collect();
+    format!("{name}*=UTF-8''{encoded}")
+}
+
+/// `text` with CRLF line endings and a final line break.
+pub fn normalize_lines(text: &str) -> String {
+    let mut normalized = text
+        .replace("\r\n", "\n")
+        .replace('\r', "\n")
+        .replace('\n', "\r\n");
+    if !normalized.ends_with("\r\n") {
+        normalized.push_str("\r\n");
+    }
+    normalized
+}
+
+/// Whether `text` can be sent as `7bit`: ASCII with short lines.
+pub fn is_7bit(text: &str) -> bool {
+    text.is_ascii() && text.split("\r\n").all(|line| line.len() <= MAX_LINE)
+}
+
+/// Quoted-printable (RFC 2045) with soft breaks before 76 characters.
+pub fn quoted_printable(text: &str) -> String {
+    let mut out = String::new();
+    let normalized = normalize_lines(text);
+    let body = normalized.strip_suffix("\r\n").unwrap_or(&normalized);
+    for line in body.split("\r\n") {
+        let bytes = line.as_bytes();
+        let mut width = 0;
+        for (position, &byte) in bytes.iter().enumerate() {
+            let last = position == bytes.len() - 1;
+            let literal = (byte == b' ' || byte == b'\t') && !last
+                || (33..=126).contains(&byte) && byte != b'=';
+            let token = if literal {
+                (byte as char).to_string()
+            } else {
+                format!("={byte:02X}")
+            };
+            if width + token.len() > QP_LINE {
+                out.push_str("=\r\n");
+                width = 0;
+            }
+            out.push_str(&token);
+            width += token.len();
+        }
+        out.push_str("\r\n");
+    }
+    out
+}
+
+/// Base64 in lines of 76 characters.
+pub fn base64_lines(data: &[u8]) -> String {
+    let encoded = STANDARD.encode(data);
+    let mut out = String::with_capacity(encoded.len() + encoded.len() / 38 + 2);
+    for chunk in encoded.as_bytes().chunks(BASE64_LINE) {
+        out.push_str(std::str::from_utf8(chunk).unwrap_or_default());
+        out.push_str("\r\n");
+    }
+    out
+}
+
+fn encoded_word(text: &str) -> String {
+    format!("=?UTF-8?B?{}?=", STANDARD.encode(text))
+}
+
+const MAX_LINE: usize = 78;
+/// Leaves room for the soft line break `=` within 76 characters.
+const QP_LINE: usize = 75;
+const BASE64_LINE: usize = 76;
+/// 45 bytes encode to 60 base64 characters, 72 with the `=?UTF-8?B?` and `?=` markers.
+const WORD_BYTES: usize = 45;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_words() {
+        assert_eq!(encode_words("Meeting"), "Meeting");
+        assert_eq!(encode_words("Reunião"), "=?UTF-8?B?UmV1bmnDo28=?=");
+        let long = encode_words(&"ação ".repeat(20));
+        assert!(long.split(' ').all(|word| word.len() <= 75));
+        assert!(long.split(' ').count() > 1);
+    }
+
+    #[test]
+    fn test_header_folds_and_strips_line_breaks() {
+        let folded = header("Subject", &"word ".repeat(30));
+        assert!(folded.split("\r\n").all(|line| line.len() <= 78));
+        assert!(folded.ends_with("\r\n"));
+        assert_eq!(
+            header("Subject", "Hi\r\nBcc: everyone@example.com"),
+            "Subject: Hi Bcc: everyone@example.com\r\n"
+        );
+    }
+
+    #[test]
+    fn test_display_names_and_parameters() {
+        assert_eq!(encode_display_name("Ana Silva"), "Ana Silva");
+        assert_eq!(encode_display_name("Silva, Ana"), "\"Silva, Ana\"");
+        assert!(encode_display_name("João").starts_with("=?UTF-8?B?"));
+        assert_eq!(
+            parameter("filename", "report.pdf"),
+            "filename=\"report.pdf\""
+        );
+        assert_eq!(
+            parameter("filename", "relatório.pdf"),
+            "filename*=UTF-8''relat%C3%B3rio.pdf"
+        );
+    }
+
+    #[test]
+    fn test_quoted_printable() {
+        assert_eq!(quoted_printable("Olá\n"), "Ol=C3=A1\r\n");
+        assert_eq!(quoted_printable("a = b "), "a =3D b=20\r\n");
+        let long = quoted_printable(&"x".repeat(200));
+        assert!(long.split("\r\n").all(|line| line.len() <= 76));
+        assert_eq!(long.replace("=\r\n", "").trim_end(), "x".repeat(200));
+    }
+
+    #[test]
+    fn test_base64_lines() {
+        let encoded = base64_lines(&[0u8; 100]);
+        assert!(encoded.split("\r\n").all(|line| line.len() <= 76));
+        assert_eq!(
+            STANDARD.decode(encoded.replace("\r\n", "")).unwrap(),
+            vec![0u8; 100]
+        );
+    }
+}
@@ -1 +1,56 @@
+pub mod clock;
+pub mod configured_sender;
+pub mod email_address;
+pub mod email_attachment;
+pub mod email_error;
+pub mod email_queue;
+pub mod email_queue_worker;
 pub mod email_sender;
+pub mod email_transport_kind;
+pub mod incoming_email;
+pub mod local_sender;
+pub mod mail_batch;
+pub mod mail_source;
+pub mod mailbox;
+pub mod maildir_source;
+pub mod manual_clock;
+pub mod mbox_source;
+pub mod message_builder;
+pub mod mime_encoding;
+pub mod outgoing_email;
+pub mod queue_run;
+pub mod queue_status;
+pub mod retry_policy;
+pub mod send_receipt;
+pub mod smtp_security;
+pub mod smtp_sender;
+pub mod system_clock;
+pub mod unreadable_mail;
+
+pub use clock::Clock;
+pub use configured_sender::ConfiguredSender;
+pub use email_address::{is_valid_address, normalize_address};
+pub use email_attachment::{EmailAttachment, content_type_for};
+pub use email_error::EmailError;
+pub use email_queue::EmailQueue;
+pub use email_queue_worker::EmailQueueWorker;
+pub use email_sender::EmailSender;
+pub use email_transport_kind::EmailTransportKind;
+pub use incoming_email::IncomingEmail;
+pub use local_sender::LocalSender;
+pub use mail_batch::MailBatch;
+pub use mail_source::MailSource;
+pub use mailbox::Mailbox;
+pub use maildir_source::MaildirSource;
+pub use manual_clock::ManualClock;
+pub use mbox_source::MboxSource;
+pub use message_builder::{MessageBuilder, generate_message_id};
+pub use outgoing_email::OutgoingEmail;
+pub use queue_run::QueueRun;
+pub use queue_status::QueueStatus;
+pub use retry_policy::RetryPolicy;
+pub use send_receipt::SendReceipt;
+pub use smtp_security::SmtpSecurity;
+pub use smtp_sender::SmtpSender;
+pub use system_clock::SystemClock;
+pub use unreadable_mail::UnreadableMail;
@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+
+/// A message ready for a transport: the envelope plus the RFC 5322 text.
+///
+/// Envelope recipients include `Bcc` addresses, which never appear in `raw`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct OutgoingEmail {
+    pub from: String,
+    pub recipients: Vec<String>,
+    pub raw: Vec<u8>,
+    /// The `Message-ID` header, when the message was built by [`MessageBuilder`](super::MessageBuilder).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message_id: Option<String>,
+}
+
+impl OutgoingEmail {
+    pub fn new(from: &str, recipients: Vec<String>, raw: &[u8]) -> Self {
+        Self {
+            from: from.to_string(),
+            recipients,
+            raw: raw.to_vec(),
+            message_id: None,
+        }
+    }
+
+    pub fn with_message_id(mut self, message_id: &str) -> Self {
+        self.message_id = Some(message_id.to_string());
+        self
+    }
+}
@@ -0,0 +1,16 @@
+use serde::{Deserialize, Serialize};
+
+/// What one pass over the due messages of the [`EmailQueue`](super::EmailQueue) did, by id.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct QueueRun {
+    pub sent: Vec<i64>,
+    /// Failed transiently; tried again after the backoff.
+    pub retrying: Vec<i64>,
+    pub dead_lettered: Vec<i64>,
+}
+
+impl QueueRun {
+    pub fn is_empty(&self) -> bool {
+        self.sent.is_empty() && self.retrying.is_empty() && self.dead_lettered.is_empty()
+    }
+}
@@ -0,0 +1,61 @@
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+use super::email_error::EmailError;
+
+/// State of a message in the [`EmailQueue`](super::EmailQueue).
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum QueueStatus {
+    /// Waiting for its send time or for the next retry.
+    #[default]
+    Queued,
+    /// Claimed by a [`process_due`](super::EmailQueue::process_due) call that
+    /// is sending it; no other call picks it up.
+    Sending,
+    Sent,
+    /// Gave up: rejected by the server or out of attempts.
+    DeadLetter,
+    Cancelled,
+}
+
+impl QueueStatus {
+    pub fn to_str(&self) -> &str {
+        match self {
+            Self::Queued => QUEUED,
+            Self::Sending => SENDING,
+            Self::Sent => SENT,
+            Self::DeadLetter => DEAD_LETTER,
+            Self::Cancelled => CANCELLED,
+        }
+    }
+}
+
+impl FromStr for QueueStatus {
+    type Err = EmailError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input.trim().to_lowercase().as_str() {
+            QUEUED => Ok(Self::Queued),
+            SENDING => Ok(Self::Sending),
+            SENT => Ok(Self::Sent),
+            DEAD_LETTER => Ok(Self::DeadLetter),
+            CANCELLED => Ok(Self::Cancelled),
+            other => Err(EmailError::StorageError(format!(
+                "Unknown queue status: {other}"
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for QueueStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_str())
+    }
+}
+
+const QUEUED: &str = "queued";
+const SENDING: &str = "sending";
+const SENT: &str = "sent";
+const DEAD_LETTER: &str = "dead_letter";
+const CANCELLED: &str = "cancelled";
@@ -0,0 +1,69 @@
+use chrono::TimeDelta;
+
+use crate::config::EmailQueueConfig;
+
+/// How often a transient failure is retried: the wait doubles after each
+/// attempt, from `initial_backoff` up to `max_backoff`, and the message is
+/// dead-lettered after `max_attempts`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub initial_backoff: TimeDelta,
+    pub max_backoff: TimeDelta,
+}
+
+impl RetryPolicy {
+    pub fn new(max_attempts: u32, initial_backoff: TimeDelta, max_backoff: TimeDelta) -> Self {
+        Self {
+            max_attempts: max_attempts.max(1),
+            initial_backoff,
+            max_backoff,
+        }
+    }
+
+    /// `[email.queue]` from `config.toml`.
+    pub fn from_config(config: &EmailQueueConfig) -> Self {
+        Self::new(
+            config.max_attempts,
+            TimeDelta::seconds(config.retry_after_secs as i64),
+            TimeDelta::seconds(config.max_retry_after_secs as i64),
+        )
+    }
+
+    /// The wait before the next try, after `attempts` failed ones.
+    pub fn backoff(&self, attempts: u32) -> TimeDelta {
+        let factor = 2_i32.saturating_pow(attempts.saturating_sub(1));
+        self.initial_backoff
+            .checked_mul(factor)
+            .map_or(self.max_backoff, |backoff| backoff.min(self.max_backoff))
+    }
+
+    pub fn gives_up_after(&self, attempts: u32) -> bool {
+        attempts >= self.max_attempts
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::from_config(&EmailQueueConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_doubles_up_to_the_limit() {
+        let policy = RetryPolicy::new(5, TimeDelta::seconds(60), TimeDelta::seconds(300));
+
+        let waits: Vec<i64> = (1..=5)
+            .map(|attempts| policy.backoff(attempts).num_seconds())
+            .collect();
+
+        assert_eq!(waits, vec![60, 120, 240, 300, 300]);
+        assert_eq!(policy.backoff(200), TimeDelta::seconds(300));
+        assert!(!policy.gives_up_after(4));
+        assert!(policy.gives_up_after(5));
+    }
+}
@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+
+use super::email_transport_kind::EmailTransportKind;
+
+/// What a transport did with a message.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SendReceipt {
+    pub transport: EmailTransportKind,
+    pub recipients: Vec<String>,
+    /// The file written by a local transport or the SMTP server's reply.
+    pub reference: String,
+}
+
+impl SendReceipt {
+    pub fn new(transport: EmailTransportKind, recipients: Vec<String>, reference: &str) -> Self {
+        Self {
+            transport,
+            recipients,
+            reference: reference.to_string(),
+        }
+    }
+}
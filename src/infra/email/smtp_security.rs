@@ -0,0 +1,60 @@
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+use super::email_error::EmailError;
+
+/// `[email.smtp] security`: how the SMTP connection is encrypted.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SmtpSecurity {
+    /// Plain connection upgraded with `STARTTLS` (usually port 587).
+    #[default]
+    StartTls,
+    /// TLS from the first byte (usually port 465).
+    Tls,
+    /// No encryption. Only for local test servers.
+    None,
+}
+
+impl SmtpSecurity {
+    pub fn to_str(&self) -> &str {
+        match self {
+            Self::StartTls => STARTTLS,
+            Self::Tls => TLS,
+            Self::None => NONE,
+        }
+    }
+
+    pub fn default_port(&self) -> u16 {
+        match self {
+            Self::StartTls => 587,
+            Self::Tls => 465,
+            Self::None => 25,
+        }
+    }
+}
+
+impl FromStr for SmtpSecurity {
+    type Err = EmailError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input.trim().to_lowercase().as_str() {
+            STARTTLS => Ok(Self::StartTls),
+            TLS | "ssl" => Ok(Self::Tls),
+            NONE => Ok(Self::None),
+            other => Err(EmailError::ConfigError(format!(
+                "unknown SMTP security '{other}' (expected starttls, tls or none)"
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for SmtpSecurity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_str())
+    }
+}
+
+const STARTTLS: &str = "starttls";
+const TLS: &str = "tls";
+const NONE: &str = "none";
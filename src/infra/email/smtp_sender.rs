@@ -0,0 +1,172 @@
+use std::{str::FromStr, time::Duration};
+
+use lettre::{
+    Address, AsyncSmtpTransport, AsyncTransport, Tokio1Executor,
+    address::Envelope,
+    transport::smtp::{authentication::Credentials, response::Response},
+};
+
+use crate::config::SmtpConfig;
+
+use super::{
+    email_error::EmailError, email_sender::EmailSender, email_transport_kind::EmailTransportKind,
+    outgoing_email::OutgoingEmail, send_receipt::SendReceipt, smtp_security::SmtpSecurity,
+};
+
+/// Sends through an SMTP server configured in `[email.smtp]`.
+pub struct SmtpSender {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    server: String,
+}
+
+impl SmtpSender {
+    pub fn from_config(config: &SmtpConfig) -> Result<Self, EmailError> {
+        let security = SmtpSecurity::from_str(&config.security)?;
+        let port = config.port.unwrap_or(security.default_port());
+        let host = config.host.trim();
+        let builder = match security {
+            SmtpSecurity::StartTls => AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(host),
+            SmtpSecurity::Tls => AsyncSmtpTransport::<Tokio1Executor>::relay(host),
+            SmtpSecurity::None => Ok(AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(
+                host,
+            )),
+        }
+        .map_err(|e| EmailError::ConfigError(format!("{host}: {e}")))?;
+
+        let mut builder = builder.port(port);
+        if let Some(username) = &config.username {
+            builder = builder.credentials(Credentials::new(username.clone(), password(config)?));
+        }
+        if let Some(seconds) = config.timeout_secs {
+            builder = builder.timeout(Some(Duration::from_secs(seconds)));
+        }
+        Ok(Self {
+            transport: builder.build(),
+            server: format!("{host}:{port}"),
+        })
+    }
+
+    /// `host:port` of the server.
+    pub fn server(&self) -> &str {
+        &self.server
+    }
+}
+
+impl EmailSender for SmtpSender {
+    fn send(
+        &self,
+        email: &OutgoingEmail,
+    ) -> impl std::future::Future<Output = Result<SendReceipt, EmailError>> + Send {
+        let envelope = envelope(email);
+        let recipients = email.recipients.clone();
+        let raw = email.raw.clone();
+        async move {
+            let response = self
+                .transport
+                .send_raw(&envelope?, &raw)
+                .await
+                .map_err(|e| {
+                    let message = format!("{}: {e}", self.server);
+                    if e.is_permanent() {
+                        EmailError::Rejected(message)
+                    } else {
+                        EmailError::TransportError(message)
+                    }
+                })?;
+            Ok(SendReceipt::new(
+                EmailTransportKind::Smtp,
+                recipients,
+                &describe(&response),
+            ))
+        }
+    }
+}
+
+fn envelope(email: &OutgoingEmail) -> Result<Envelope, EmailError> {
+    let parse = |address: &str| {
+        Address::from_str(address.trim())
+            .map_err(|e| EmailError::InvalidMessage(format!("invalid address {address}: {e}")))
+    };
+    let recipients = email
+        .recipients
+        .iter()
+        .map(|recipient| parse(recipient))
+        .collect::<Result<Vec<_>, _>>()?;
+    Envelope::new(Some(parse(&email.from)?), recipients)
+        .map_err(|e| EmailError::InvalidMessage(e.to_string()))
+}
+
+fn password(config: &SmtpConfig) -> Result<String, EmailError> {
+    match &config.password_env {
+        Some(variable) => std::env::var(variable).map_err(|_| {
+            EmailError::ConfigError(format!("environment variable {variable} is not set"))
+        }),
+        None => Ok(config.password.clone().unwrap_or_default()),
+    }
+}
+
+fn describe(response: &Response) -> String {
+    let lines: Vec<&str> = response.message().collect();
+    format!("{} {}", response.code(), lines.join(" "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(security: &str) -> SmtpConfig {
+        SmtpConfig {
+            host: "smtp.example.com".to_string(),
+            security: security.to_string(),
+            ..SmtpConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_default_ports_follow_security() {
+        assert_eq!(
+            SmtpSender::from_config(&config("starttls"))
+                .unwrap()
+                .server(),
+            "smtp.example.com:587"
+        );
+        assert_eq!(
+            SmtpSender::from_config(&config("tls")).unwrap().server(),
+            "smtp.example.com:465"
+        );
+        let custom = SmtpConfig {
+            port: Some(2525),
+            ..config("none")
+        };
+        assert_eq!(
+            SmtpSender::from_config(&custom).unwrap().server(),
+            "smtp.example.com:2525"
+        );
+    }
+
+    #[test]
+    fn test_configuration_errors() {
+        assert!(matches!(
+            SmtpSender::from_config(&config("ssl3")),
+            Err(EmailError::ConfigError(_))
+        ));
+        let missing_password = SmtpConfig {
+            username: Some("ana".to_string()),
+            password_env: Some("SMTP_SENDER_TEST_UNSET_VARIABLE".to_string()),
+            ..config("starttls")
+        };
+        assert!(matches!(
+            SmtpSender::from_config(&missing_password),
+            Err(EmailError::ConfigError(_))
+        ));
+    }
+
+    #[test]
+    fn test_envelope_rejects_invalid_addresses() {
+        let email = OutgoingEmail::new("ana@example.com", vec!["Eva".to_string()], b"");
+        assert!(matches!(
+            envelope(&email),
+            Err(EmailError::InvalidMessage(_))
+        ));
+    }
+}
@@ -0,0 +1,13 @@
+use chrono::{DateTime, Utc};
+
+use super::clock::Clock;
+
+/// The system's wall clock.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
@@ -0,0 +1,19 @@
+use super::email_error::EmailError;
+
+/// A message a [`MailSource`](super::MailSource) found but could not parse. It
+/// is not returned again, so the caller should tell the user about it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnreadableMail {
+    /// Where the message is: its file in a Maildir, its position in an mbox.
+    pub location: String,
+    pub error: EmailError,
+}
+
+impl UnreadableMail {
+    pub fn new(location: &str, error: EmailError) -> Self {
+        Self {
+            location: location.to_string(),
+            error,
+        }
+    }
+}
@@ -1,3 +1,5 @@
 pub mod assistant_ollama_client;
+pub mod calendar;
 pub mod contacts;
+pub mod persistence;
 pub mod email;
@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+
+use super::database::unix_timestamp;
+
+/// Something the assistant did on the user's behalf, kept for later review.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AuditRecord {
+    /// Assigned by the repository when the record is stored.
+    pub id: Option<i64>,
+    /// Seconds since the Unix epoch.
+    pub recorded_at: i64,
+    pub actor: String,
+    pub action: String,
+    pub details: serde_json::Value,
+}
+
+impl AuditRecord {
+    pub fn new(actor: &str, action: &str) -> Self {
+        Self {
+            id: None,
+            recorded_at: unix_timestamp(),
+            actor: actor.to_string(),
+            action: action.to_string(),
+            details: serde_json::Value::Null,
+        }
+    }
+
+    pub fn with_details(mut self, details: serde_json::Value) -> Self {
+        self.details = details;
+        self
+    }
+}
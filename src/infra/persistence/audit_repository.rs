@@ -0,0 +1,10 @@
+use super::{audit_record::AuditRecord, database_error::DatabaseError};
+
+/// Append-only log of audit records.
+pub trait AuditRepository {
+    /// Stores the record and returns its id.
+    fn record(&mut self, record: &AuditRecord) -> Result<i64, DatabaseError>;
+
+    /// The latest `limit` records, newest first.
+    fn recent(&self, limit: usize) -> Result<Vec<AuditRecord>, DatabaseError>;
+}
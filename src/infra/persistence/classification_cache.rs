@@ -0,0 +1,20 @@
+use crate::agents::ClassificationResult;
+
+use super::database_error::DatabaseError;
+
+/// Classification results keyed by [`classification_cache_key`].
+pub trait ClassificationCache {
+    fn cached(&self, key: &str) -> Result<Option<ClassificationResult>, DatabaseError>;
+
+    fn cache(&mut self, key: &str, result: &ClassificationResult) -> Result<(), DatabaseError>;
+}
+
+/// Key for a classification: the same input classified by the same model and
+/// prompt version gives the same result.
+pub fn classification_cache_key(model: &str, prompt_version: Option<&str>, input: &str) -> String {
+    format!(
+        "{model}|{}|{}",
+        prompt_version.unwrap_or_default(),
+        input.trim()
+    )
+}
@@ -0,0 +1,22 @@
+use crate::agents::context::ConversationMessage;
+
+use super::database_error::DatabaseError;
+
+/// Conversation history, grouped by conversation id.
+pub trait ConversationRepository {
+    fn append(
+        &mut self,
+        conversation_id: &str,
+        message: &ConversationMessage,
+    ) -> Result<(), DatabaseError>;
+
+    /// The last `limit` messages of the conversation, oldest first.
+    fn history(
+        &self,
+        conversation_id: &str,
+        limit: usize,
+    ) -> Result<Vec<ConversationMessage>, DatabaseError>;
+
+    /// Removes the conversation and returns how many messages it had.
+    fn clear(&mut self, conversation_id: &str) -> Result<usize, DatabaseError>;
+}
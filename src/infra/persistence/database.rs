@@ -0,0 +1,183 @@
+use std::{
+    fs,
+    path::Path,
+    sync::{Arc, Mutex},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use rusqlite::{Connection, params};
+
+use crate::config::DatabaseConfig;
+
+use super::{
+    database_error::DatabaseError,
+    migration::{MIGRATIONS, Migration},
+};
+
+/// Embedded SQLite database, migrated to the latest schema when opened.
+///
+/// Cloning shares the same connection, so several stores can use one file.
+#[derive(Debug, Clone)]
+pub struct Database {
+    connection: Arc<Mutex<Connection>>,
+}
+
+impl Database {
+    pub fn open(path: &Path) -> Result<Self, DatabaseError> {
+        if let Some(parent) = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            fs::create_dir_all(parent).map_err(|e| {
+                DatabaseError::ConnectionError(format!("{}: {e}", parent.display()))
+            })?;
+        }
+        let connection = Connection::open(path)
+            .map_err(|e| DatabaseError::ConnectionError(format!("{}: {e}", path.display())))?;
+        Self::from_connection(connection)
+    }
+
+    /// Opens `[database] path` from `config.toml`.
+    pub fn from_config(config: &DatabaseConfig) -> Result<Self, DatabaseError> {
+        Self::open(Path::new(&config.path))
+    }
+
+    pub fn open_in_memory() -> Result<Self, DatabaseError> {
+        let connection = Connection::open_in_memory()
+            .map_err(|e| DatabaseError::ConnectionError(e.to_string()))?;
+        Self::from_connection(connection)
+    }
+
+    fn from_connection(connection: Connection) -> Result<Self, DatabaseError> {
+        connection
+            .execute_batch("PRAGMA foreign_keys = ON;")
+            .map_err(|e| DatabaseError::ConnectionError(e.to_string()))?;
+        let database = Self {
+            connection: Arc::new(Mutex::new(connection)),
+        };
+        database.migrate()?;
+        Ok(database)
+    }
+
+    /// Applies pending migrations, each in its own transaction. Returns how many ran.
+    pub fn migrate(&self) -> Result<usize, DatabaseError> {
+        self.with_connection(|connection| {
+            connection
+                .execute_batch(
+                    "CREATE TABLE IF NOT EXISTS schema_migrations (
+                        version INTEGER PRIMARY KEY,
+                        name TEXT NOT NULL,
+                        applied_at INTEGER NOT NULL
+                    );",
+                )
+                .map_err(|e| DatabaseError::MigrationError(e.to_string()))?;
+            let current = schema_version(connection)?;
+
+            let pending: Vec<&Migration> = MIGRATIONS
+                .iter()
+                .filter(|migration| migration.version > current)
+                .collect();
+            for migration in &pending {
+                apply(connection, migration).map_err(|e| {
+                    DatabaseError::MigrationError(format!(
+                        "{} ({}): {e}",
+                        migration.version, migration.name
+                    ))
+                })?;
+            }
+            Ok(pending.len())
+        })
+    }
+
+    /// Highest applied migration version; `0` on an empty database.
+    pub fn schema_version(&self) -> Result<u32, DatabaseError> {
+        self.with_connection(|connection| schema_version(connection))
+    }
+
+    pub(crate) fn with_connection<T>(
+        &self,
+        operation: impl FnOnce(&mut Connection) -> Result<T, DatabaseError>,
+    ) -> Result<T, DatabaseError> {
+        let mut connection = self
+            .connection
+            .lock()
+            .map_err(|e| DatabaseError::ConnectionError(e.to_string()))?;
+        operation(&mut connection)
+    }
+}
+
+fn schema_version(connection: &Connection) -> Result<u32, DatabaseError> {
+    connection
+        .query_row(
+            "SELECT COALESCE(MAX(version), 0) FROM schema_migrations",
+            [],
+            |row| row.get(0),
+        )
+        .map_err(|e| DatabaseError::MigrationError(e.to_string()))
+}
+
+fn apply(connection: &mut Connection, migration: &Migration) -> rusqlite::Result<()> {
+    let transaction = connection.transaction()?;
+    transaction.execute_batch(migration.sql)?;
+    transaction.execute(
+        "INSERT INTO schema_migrations (version, name, applied_at) VALUES (?1, ?2, ?3)",
+        params![migration.version, migration.name, unix_timestamp()],
+    )?;
+    transaction.commit()
+}
+
+/// Seconds since the Unix epoch, as stored in the `*_at` columns.
+pub(crate) fn unix_timestamp() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs() as i64)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_open_applies_all_migrations_once() {
+        let database = Database::open_in_memory().unwrap();
+        assert_eq!(
+            database.schema_version().unwrap(),
+            MIGRATIONS.last().unwrap().version
+        );
+        assert_eq!(database.migrate().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_reopening_a_file_keeps_the_schema() {
+        let path = std::env::temp_dir()
+            .join(format!("database_test_{}", std::process::id()))
+            .join("assistant.db");
+        {
+            let database = Database::open(&path).unwrap();
+            database
+                .with_connection(|connection| {
+                    connection
+                        .execute(
+                            "INSERT INTO drafts (id, subject, body, updated_at) VALUES ('d1', 's', 'b', 0)",
+                            [],
+                        )
+                        .map_err(DatabaseError::from)
+                })
+                .unwrap();
+        }
+
+        let reopened = Database::open(&path).unwrap();
+        let drafts: i64 = reopened
+            .with_connection(|connection| {
+                connection
+                    .query_row("SELECT COUNT(*) FROM drafts", [], |row| row.get(0))
+                    .map_err(DatabaseError::from)
+            })
+            .unwrap();
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+
+        assert_eq!(drafts, 1);
+        assert_eq!(reopened.migrate().unwrap(), 0);
+    }
+}
@@ -0,0 +1,46 @@
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug)]
+pub enum DatabaseError {
+    ConnectionError(String),
+    MigrationError(String),
+    QueryError(String),
+    SerializationError(String),
+}
+
+impl fmt::Display for DatabaseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DatabaseError::ConnectionError(msg) => write!(f, "Database connection error: {msg}"),
+            DatabaseError::MigrationError(msg) => write!(f, "Database migration error: {msg}"),
+            DatabaseError::QueryError(msg) => write!(f, "Database query error: {msg}"),
+            DatabaseError::SerializationError(msg) => {
+                write!(f, "Database serialization error: {msg}")
+            }
+        }
+    }
+}
+
+impl Error for DatabaseError {}
+
+impl From<rusqlite::Error> for DatabaseError {
+    fn from(error: rusqlite::Error) -> Self {
+        DatabaseError::QueryError(error.to_string())
+    }
+}
+
+impl From<serde_json::Error> for DatabaseError {
+    fn from(error: serde_json::Error) -> Self {
+        DatabaseError::SerializationError(error.to_string())
+    }
+}
+
+impl From<DatabaseError> for crate::infra::contacts::ContactStoreError {
+    fn from(error: DatabaseError) -> Self {
+        match error {
+            DatabaseError::SerializationError(msg) => Self::ParseError(msg),
+            other => Self::IoError(other.to_string()),
+        }
+    }
+}
@@ -0,0 +1,14 @@
+use super::{database_error::DatabaseError, stored_draft::StoredDraft};
+
+pub trait DraftRepository {
+    /// Inserts the draft or replaces the one with the same id.
+    fn save_draft(&mut self, draft: &StoredDraft) -> Result<(), DatabaseError>;
+
+    fn draft(&self, id: &str) -> Result<Option<StoredDraft>, DatabaseError>;
+
+    /// All drafts, most recently updated first.
+    fn drafts(&self) -> Result<Vec<StoredDraft>, DatabaseError>;
+
+    /// Returns whether a draft was removed.
+    fn delete_draft(&mut self, id: &str) -> Result<bool, DatabaseError>;
+}
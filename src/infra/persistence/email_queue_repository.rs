@@ -0,0 +1,23 @@
+use crate::infra::email::QueueStatus;
+
+use super::{
+    database_error::DatabaseError, draft_repository::DraftRepository, queued_email::QueuedEmail,
+};
+
+/// Queue entries built from a draft ([`QueuedEmail::draft_id`]) update that
+/// draft, so the repository keeps the drafts too.
+pub trait EmailQueueRepository: DraftRepository {
+    /// Stores a new message and returns its id.
+    fn enqueue(&mut self, email: &QueuedEmail) -> Result<i64, DatabaseError>;
+
+    fn queued_email(&self, id: i64) -> Result<Option<QueuedEmail>, DatabaseError>;
+
+    /// Queued messages whose next attempt is at or before `now`, oldest first.
+    fn due_emails(&self, now: i64, limit: usize) -> Result<Vec<QueuedEmail>, DatabaseError>;
+
+    /// Messages with `status`, by next attempt then id.
+    fn queued_emails(&self, status: QueueStatus) -> Result<Vec<QueuedEmail>, DatabaseError>;
+
+    /// Saves status, attempts and times of an enqueued message. Returns whether it exists.
+    fn update_queued_email(&mut self, email: &QueuedEmail) -> Result<bool, DatabaseError>;
+}
@@ -0,0 +1,226 @@
+use std::{cmp::Reverse, collections::BTreeMap};
+
+use crate::{
+    agents::{ClassificationResult, context::ConversationMessage},
+    infra::{
+        contacts::{Contact, ContactStore, ContactStoreError, UserContacts},
+        email::QueueStatus,
+    },
+};
+
+use super::{
+    audit_record::AuditRecord, audit_repository::AuditRepository,
+    classification_cache::ClassificationCache, conversation_repository::ConversationRepository,
+    database_error::DatabaseError, draft_repository::DraftRepository,
+    email_queue_repository::EmailQueueRepository, queued_email::QueuedEmail,
+    stored_draft::StoredDraft,
+};
+
+/// Repositories kept in memory, for tests and throwaway sessions.
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryStore {
+    contacts: UserContacts,
+    conversations: BTreeMap<String, Vec<ConversationMessage>>,
+    drafts: Vec<StoredDraft>,
+    audit: Vec<AuditRecord>,
+    email_queue: Vec<QueuedEmail>,
+    classifications: BTreeMap<String, ClassificationResult>,
+}
+
+impl InMemoryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_contacts(contacts: Vec<Contact>) -> Self {
+        Self {
+            contacts: UserContacts::new(contacts),
+            ..Self::default()
+        }
+    }
+}
+
+impl ContactStore for InMemoryStore {
+    fn list(&self) -> Result<Vec<Contact>, ContactStoreError> {
+        Ok(self.contacts.contacts.clone())
+    }
+
+    fn get(&self, id: &str) -> Result<Option<Contact>, ContactStoreError> {
+        Ok(self.contacts.find(id).cloned())
+    }
+
+    fn create(&mut self, mut contact: Contact) -> Result<Contact, ContactStoreError> {
+        if contact.id.trim().is_empty() {
+            contact.id = self.contacts.next_id();
+        } else if self.contacts.find(&contact.id).is_some() {
+            return Err(ContactStoreError::DuplicateId(contact.id));
+        }
+        self.contacts.contacts.push(contact.clone());
+        self.contacts.refresh_metadata();
+        Ok(contact)
+    }
+
+    fn update(&mut self, contact: Contact) -> Result<Contact, ContactStoreError> {
+        let existing = self
+            .contacts
+            .contacts
+            .iter_mut()
+            .find(|existing| existing.id == contact.id)
+            .ok_or_else(|| ContactStoreError::NotFound(contact.id.clone()))?;
+        *existing = contact.clone();
+        Ok(contact)
+    }
+
+    fn delete(&mut self, id: &str) -> Result<Contact, ContactStoreError> {
+        let index = self
+            .contacts
+            .contacts
+            .iter()
+            .position(|contact| contact.id == id)
+            .ok_or_else(|| ContactStoreError::NotFound(id.to_string()))?;
+        let removed = self.contacts.contacts.remove(index);
+        self.contacts.refresh_metadata();
+        Ok(removed)
+    }
+}
+
+impl ConversationRepository for InMemoryStore {
+    fn append(
+        &mut self,
+        conversation_id: &str,
+        message: &ConversationMessage,
+    ) -> Result<(), DatabaseError> {
+        self.conversations
+            .entry(conversation_id.to_string())
+            .or_default()
+            .push(message.clone());
+        Ok(())
+    }
+
+    fn history(
+        &self,
+        conversation_id: &str,
+        limit: usize,
+    ) -> Result<Vec<ConversationMessage>, DatabaseError> {
+        let messages = self
+            .conversations
+            .get(conversation_id)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        Ok(messages[messages.len().saturating_sub(limit)..].to_vec())
+    }
+
+    fn clear(&mut self, conversation_id: &str) -> Result<usize, DatabaseError> {
+        Ok(self
+            .conversations
+            .remove(conversation_id)
+            .map(|messages| messages.len())
+            .unwrap_or_default())
+    }
+}
+
+impl DraftRepository for InMemoryStore {
+    fn save_draft(&mut self, draft: &StoredDraft) -> Result<(), DatabaseError> {
+        self.drafts.retain(|existing| existing.id != draft.id);
+        self.drafts.push(draft.clone());
+        Ok(())
+    }
+
+    fn draft(&self, id: &str) -> Result<Option<StoredDraft>, DatabaseError> {
+        Ok(self.drafts.iter().find(|draft| draft.id == id).cloned())
+    }
+
+    fn drafts(&self) -> Result<Vec<StoredDraft>, DatabaseError> {
+        let mut drafts: Vec<StoredDraft> = self.drafts.iter().rev().cloned().collect();
+        drafts.sort_by_key(|draft| Reverse(draft.updated_at));
+        Ok(drafts)
+    }
+
+    fn delete_draft(&mut self, id: &str) -> Result<bool, DatabaseError> {
+        let before = self.drafts.len();
+        self.drafts.retain(|draft| draft.id != id);
+        Ok(self.drafts.len() < before)
+    }
+}
+
+impl EmailQueueRepository for InMemoryStore {
+    fn enqueue(&mut self, email: &QueuedEmail) -> Result<i64, DatabaseError> {
+        let id = self.email_queue.len() as i64 + 1;
+        self.email_queue.push(QueuedEmail {
+            id: Some(id),
+            ..email.clone()
+        });
+        Ok(id)
+    }
+
+    fn queued_email(&self, id: i64) -> Result<Option<QueuedEmail>, DatabaseError> {
+        Ok(self
+            .email_queue
+            .iter()
+            .find(|email| email.id == Some(id))
+            .cloned())
+    }
+
+    fn due_emails(&self, now: i64, limit: usize) -> Result<Vec<QueuedEmail>, DatabaseError> {
+        let mut due: Vec<QueuedEmail> = self
+            .email_queue
+            .iter()
+            .filter(|email| email.status == QueueStatus::Queued && email.next_attempt_at <= now)
+            .cloned()
+            .collect();
+        due.sort_by_key(|email| (email.next_attempt_at, email.id));
+        due.truncate(limit);
+        Ok(due)
+    }
+
+    fn queued_emails(&self, status: QueueStatus) -> Result<Vec<QueuedEmail>, DatabaseError> {
+        let mut emails: Vec<QueuedEmail> = self
+            .email_queue
+            .iter()
+            .filter(|email| email.status == status)
+            .cloned()
+            .collect();
+        emails.sort_by_key(|email| (email.next_attempt_at, email.id));
+        Ok(emails)
+    }
+
+    fn update_queued_email(&mut self, email: &QueuedEmail) -> Result<bool, DatabaseError> {
+        match self
+            .email_queue
+            .iter_mut()
+            .find(|existing| existing.id.is_some() && existing.id == email.id)
+        {
+            Some(existing) => {
+                *existing = email.clone();
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+}
+
+impl AuditRepository for InMemoryStore {
+    fn record(&mut self, record: &AuditRecord) -> Result<i64, DatabaseError> {
+        let id = self.audit.len() as i64 + 1;
+        self.audit.push(AuditRecord {
+            id: Some(id),
+            ..record.clone()
+        });
+        Ok(id)
+    }
+
+    fn recent(&self, limit: usize) -> Result<Vec<AuditRecord>, DatabaseError> {
+        Ok(self.audit.iter().rev().take(limit).cloned().collect())
+    }
+}
+
+impl ClassificationCache for InMemoryStore {
+    fn cached(&self, key: &str) -> Result<Option<ClassificationResult>, DatabaseError> {
+        Ok(self.classifications.get(key).cloned())
+    }
+
+    fn cache(&mut self, key: &str, result: &ClassificationResult) -> Result<(), DatabaseError> {
+        self.classifications.insert(key.to_string(), result.clone());
+        Ok(())
+    }
+}
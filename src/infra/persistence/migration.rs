@@ -0,0 +1,106 @@
+/// A schema change applied once, in `version` order, and recorded in `schema_migrations`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Migration {
+    pub version: u32,
+    pub name: &'static str,
+    pub sql: &'static str,
+}
+
+/// Every migration of the assistant database. Append new ones; never edit applied ones.
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        name: "create_contacts",
+        sql: "CREATE TABLE contacts (
+                id TEXT PRIMARY KEY,
+                display_name TEXT NOT NULL,
+                company TEXT,
+                data TEXT NOT NULL
+            );
+            CREATE INDEX contacts_display_name ON contacts (display_name);",
+    },
+    Migration {
+        version: 2,
+        name: "create_conversation_messages",
+        sql: "CREATE TABLE conversation_messages (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                conversation_id TEXT NOT NULL,
+                role TEXT NOT NULL,
+                content TEXT NOT NULL,
+                created_at INTEGER NOT NULL
+            );
+            CREATE INDEX conversation_messages_conversation
+                ON conversation_messages (conversation_id, id);",
+    },
+    Migration {
+        version: 3,
+        name: "create_drafts",
+        sql: "CREATE TABLE drafts (
+                id TEXT PRIMARY KEY,
+                recipient TEXT,
+                subject TEXT NOT NULL,
+                body TEXT NOT NULL,
+                updated_at INTEGER NOT NULL
+            );",
+    },
+    Migration {
+        version: 4,
+        name: "create_audit_records",
+        sql: "CREATE TABLE audit_records (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                recorded_at INTEGER NOT NULL,
+                actor TEXT NOT NULL,
+                action TEXT NOT NULL,
+                details TEXT NOT NULL
+            );",
+    },
+    Migration {
+        version: 5,
+        name: "create_classification_cache",
+        sql: "CREATE TABLE classification_cache (
+                key TEXT PRIMARY KEY,
+                result TEXT NOT NULL,
+                created_at INTEGER NOT NULL
+            );",
+    },
+    Migration {
+        version: 6,
+        name: "add_draft_status",
+        sql: "ALTER TABLE drafts ADD COLUMN status TEXT NOT NULL DEFAULT 'draft';
+            ALTER TABLE drafts ADD COLUMN email TEXT;
+            ALTER TABLE drafts ADD COLUMN reason TEXT;
+            CREATE INDEX drafts_status ON drafts (status);",
+    },
+    Migration {
+        version: 7,
+        name: "create_email_queue",
+        sql: "CREATE TABLE email_queue (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                sender TEXT NOT NULL,
+                recipients TEXT NOT NULL,
+                raw BLOB NOT NULL,
+                message_id TEXT,
+                draft_id TEXT,
+                status TEXT NOT NULL,
+                attempts INTEGER NOT NULL DEFAULT 0,
+                send_at INTEGER NOT NULL,
+                next_attempt_at INTEGER NOT NULL,
+                last_error TEXT,
+                created_at INTEGER NOT NULL,
+                updated_at INTEGER NOT NULL
+            );
+            CREATE INDEX email_queue_due ON email_queue (status, next_attempt_at);",
+    },
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_versions_are_sequential() {
+        for (index, migration) in MIGRATIONS.iter().enumerate() {
+            assert_eq!(migration.version as usize, index + 1, "{}", migration.name);
+        }
+    }
+}
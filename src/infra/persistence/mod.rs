@@ -0,0 +1,27 @@
+pub mod audit_record;
+pub mod audit_repository;
+pub mod classification_cache;
+pub mod conversation_repository;
+pub mod database;
+pub mod database_error;
+pub mod draft_repository;
+pub mod email_queue_repository;
+pub mod in_memory_store;
+pub mod migration;
+pub mod queued_email;
+pub mod sqlite_store;
+pub mod stored_draft;
+
+pub use audit_record::AuditRecord;
+pub use audit_repository::AuditRepository;
+pub use classification_cache::{ClassificationCache, classification_cache_key};
+pub use conversation_repository::ConversationRepository;
+pub use database::Database;
+pub use database_error::DatabaseError;
+pub use draft_repository::DraftRepository;
+pub use email_queue_repository::EmailQueueRepository;
+pub use in_memory_store::InMemoryStore;
+pub use migration::{MIGRATIONS, Migration};
+pub use queued_email::QueuedEmail;
+pub use sqlite_store::SqliteStore;
+pub use stored_draft::StoredDraft;
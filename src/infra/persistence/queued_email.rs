@@ -0,0 +1,46 @@
+use serde::{Deserialize, Serialize};
+
+use crate::infra::email::{OutgoingEmail, QueueStatus};
+
+/// A message in the persistent send queue. Times are seconds since the Unix epoch.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct QueuedEmail {
+    /// Assigned when the message is enqueued.
+    pub id: Option<i64>,
+    pub email: OutgoingEmail,
+    /// The approved draft the message was built from, if any. The queue keeps
+    /// it `sending` until the message is sent (`sent`) or given up (`failed`).
+    pub draft_id: Option<String>,
+    pub status: QueueStatus,
+    /// Failed tries so far.
+    pub attempts: u32,
+    /// When the user asked for it to go out.
+    pub send_at: i64,
+    /// `send_at` at first, then the time of the next retry.
+    pub next_attempt_at: i64,
+    pub last_error: Option<String>,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+impl QueuedEmail {
+    pub fn new(email: OutgoingEmail, send_at: i64, now: i64) -> Self {
+        Self {
+            id: None,
+            email,
+            draft_id: None,
+            status: QueueStatus::Queued,
+            attempts: 0,
+            send_at,
+            next_attempt_at: send_at,
+            last_error: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    pub fn with_draft_id(mut self, draft_id: &str) -> Self {
+        self.draft_id = Some(draft_id.to_string());
+        self
+    }
+}
@@ -0,0 +1,447 @@
+use std::str::FromStr;
+
+use rusqlite::{OptionalExtension, params};
+
+use crate::{
+    agents::{
+        ClassificationResult,
+        context::{ConversationMessage, ConversationRole},
+        email::DraftStatus,
+    },
+    infra::{
+        contacts::{Contact, ContactStore, ContactStoreError, UserContacts},
+        email::{OutgoingEmail, QueueStatus},
+    },
+};
+
+use super::{
+    audit_record::AuditRecord,
+    audit_repository::AuditRepository,
+    classification_cache::ClassificationCache,
+    conversation_repository::ConversationRepository,
+    database::{Database, unix_timestamp},
+    database_error::DatabaseError,
+    draft_repository::DraftRepository,
+    email_queue_repository::EmailQueueRepository,
+    queued_email::QueuedEmail,
+    stored_draft::StoredDraft,
+};
+
+/// Repositories backed by the SQLite [`Database`].
+#[derive(Debug, Clone)]
+pub struct SqliteStore {
+    database: Database,
+}
+
+impl SqliteStore {
+    pub fn new(database: Database) -> Self {
+        Self { database }
+    }
+
+    pub fn database(&self) -> &Database {
+        &self.database
+    }
+}
+
+impl ContactStore for SqliteStore {
+    fn list(&self) -> Result<Vec<Contact>, ContactStoreError> {
+        let contacts = self.database.with_connection(|connection| {
+            let mut statement = connection.prepare("SELECT data FROM contacts ORDER BY rowid")?;
+            let rows = statement.query_map([], |row| row.get::<_, String>(0))?;
+            let mut contacts = Vec::new();
+            for data in rows {
+                contacts.push(serde_json::from_str(&data?)?);
+            }
+            Ok(contacts)
+        })?;
+        Ok(contacts)
+    }
+
+    fn get(&self, id: &str) -> Result<Option<Contact>, ContactStoreError> {
+        let contact = self.database.with_connection(|connection| {
+            let data: Option<String> = connection
+                .query_row("SELECT data FROM contacts WHERE id = ?1", [id], |row| {
+                    row.get(0)
+                })
+                .optional()?;
+            data.map(|data| serde_json::from_str(&data))
+                .transpose()
+                .map_err(DatabaseError::from)
+        })?;
+        Ok(contact)
+    }
+
+    fn create(&mut self, mut contact: Contact) -> Result<Contact, ContactStoreError> {
+        if contact.id.trim().is_empty() {
+            contact.id = UserContacts::new(self.list()?).next_id();
+        } else if self.get(&contact.id)?.is_some() {
+            return Err(ContactStoreError::DuplicateId(contact.id));
+        }
+
+        let data = serde_json::to_string(&contact).map_err(DatabaseError::from)?;
+        self.database.with_connection(|connection| {
+            connection.execute(
+                "INSERT INTO contacts (id, display_name, company, data) VALUES (?1, ?2, ?3, ?4)",
+                params![contact.id, contact.name(), contact.company, data],
+            )?;
+            Ok(())
+        })?;
+        Ok(contact)
+    }
+
+    fn update(&mut self, contact: Contact) -> Result<Contact, ContactStoreError> {
+        let data = serde_json::to_string(&contact).map_err(DatabaseError::from)?;
+        let changed = self.database.with_connection(|connection| {
+            Ok(connection.execute(
+                "UPDATE contacts SET display_name = ?2, company = ?3, data = ?4 WHERE id = ?1",
+                params![contact.id, contact.name(), contact.company, data],
+            )?)
+        })?;
+        if changed == 0 {
+            return Err(ContactStoreError::NotFound(contact.id));
+        }
+        Ok(contact)
+    }
+
+    fn delete(&mut self, id: &str) -> Result<Contact, ContactStoreError> {
+        let contact = self
+            .get(id)?
+            .ok_or_else(|| ContactStoreError::NotFound(id.to_string()))?;
+        self.database.with_connection(|connection| {
+            connection.execute("DELETE FROM contacts WHERE id = ?1", [id])?;
+            Ok(())
+        })?;
+        Ok(contact)
+    }
+}
+
+impl ConversationRepository for SqliteStore {
+    fn append(
+        &mut self,
+        conversation_id: &str,
+        message: &ConversationMessage,
+    ) -> Result<(), DatabaseError> {
+        let role = serde_json::to_value(message.role)?;
+        self.database.with_connection(|connection| {
+            connection.execute(
+                "INSERT INTO conversation_messages (conversation_id, role, content, created_at)
+                 VALUES (?1, ?2, ?3, ?4)",
+                params![
+                    conversation_id,
+                    role.as_str().unwrap_or_default(),
+                    message.content,
+                    unix_timestamp()
+                ],
+            )?;
+            Ok(())
+        })
+    }
+
+    fn history(
+        &self,
+        conversation_id: &str,
+        limit: usize,
+    ) -> Result<Vec<ConversationMessage>, DatabaseError> {
+        self.database.with_connection(|connection| {
+            let mut statement = connection.prepare(
+                "SELECT role, content FROM (
+                    SELECT id, role, content FROM conversation_messages
+                    WHERE conversation_id = ?1 ORDER BY id DESC LIMIT ?2
+                 ) ORDER BY id",
+            )?;
+            let rows = statement.query_map(params![conversation_id, limit as i64], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+            })?;
+            let mut messages = Vec::new();
+            for row in rows {
+                let (role, content) = row?;
+                let role: ConversationRole = serde_json::from_value(role.into())?;
+                messages.push(ConversationMessage { role, content });
+            }
+            Ok(messages)
+        })
+    }
+
+    fn clear(&mut self, conversation_id: &str) -> Result<usize, DatabaseError> {
+        self.database.with_connection(|connection| {
+            Ok(connection.execute(
+                "DELETE FROM conversation_messages WHERE conversation_id = ?1",
+                [conversation_id],
+            )?)
+        })
+    }
+}
+
+impl DraftRepository for SqliteStore {
+    fn save_draft(&mut self, draft: &StoredDraft) -> Result<(), DatabaseError> {
+        let email = draft
+            .email
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()?;
+        self.database.with_connection(|connection| {
+            connection.execute(
+                "INSERT INTO drafts (id, recipient, subject, body, updated_at, status, email, reason)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+                 ON CONFLICT (id) DO UPDATE SET recipient = ?2, subject = ?3, body = ?4,
+                 updated_at = ?5, status = ?6, email = ?7, reason = ?8",
+                params![
+                    draft.id,
+                    draft.recipient,
+                    draft.subject,
+                    draft.body,
+                    draft.updated_at,
+                    draft.status.to_str(),
+                    email,
+                    draft.reason
+                ],
+            )?;
+            Ok(())
+        })
+    }
+
+    fn draft(&self, id: &str) -> Result<Option<StoredDraft>, DatabaseError> {
+        self.database.with_connection(|connection| {
+            Ok(connection
+                .query_row(
+                    &format!("SELECT {DRAFT_COLUMNS} FROM drafts WHERE id = ?1"),
+                    [id],
+                    draft_from_row,
+                )
+                .optional()?)
+        })
+    }
+
+    fn drafts(&self) -> Result<Vec<StoredDraft>, DatabaseError> {
+        self.database.with_connection(|connection| {
+            let mut statement = connection.prepare(&format!(
+                "SELECT {DRAFT_COLUMNS} FROM drafts ORDER BY updated_at DESC, rowid DESC"
+            ))?;
+            let drafts = statement
+                .query_map([], draft_from_row)?
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(drafts)
+        })
+    }
+
+    fn delete_draft(&mut self, id: &str) -> Result<bool, DatabaseError> {
+        self.database.with_connection(|connection| {
+            Ok(connection.execute("DELETE FROM drafts WHERE id = ?1", [id])? > 0)
+        })
+    }
+}
+
+impl EmailQueueRepository for SqliteStore {
+    fn enqueue(&mut self, email: &QueuedEmail) -> Result<i64, DatabaseError> {
+        let recipients = serde_json::to_string(&email.email.recipients)?;
+        self.database.with_connection(|connection| {
+            connection.execute(
+                "INSERT INTO email_queue (sender, recipients, raw, message_id, draft_id, status,
+                 attempts, send_at, next_attempt_at, last_error, created_at, updated_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+                params![
+                    email.email.from,
+                    recipients,
+                    email.email.raw,
+                    email.email.message_id,
+                    email.draft_id,
+                    email.status.to_str(),
+                    email.attempts,
+                    email.send_at,
+                    email.next_attempt_at,
+                    email.last_error,
+                    email.created_at,
+                    email.updated_at
+                ],
+            )?;
+            Ok(connection.last_insert_rowid())
+        })
+    }
+
+    fn queued_email(&self, id: i64) -> Result<Option<QueuedEmail>, DatabaseError> {
+        self.database.with_connection(|connection| {
+            Ok(connection
+                .query_row(
+                    &format!("SELECT {QUEUE_COLUMNS} FROM email_queue WHERE id = ?1"),
+                    [id],
+                    queued_email_from_row,
+                )
+                .optional()?)
+        })
+    }
+
+    fn due_emails(&self, now: i64, limit: usize) -> Result<Vec<QueuedEmail>, DatabaseError> {
+        self.database.with_connection(|connection| {
+            let mut statement = connection.prepare(&format!(
+                "SELECT {QUEUE_COLUMNS} FROM email_queue
+                 WHERE status = ?1 AND next_attempt_at <= ?2
+                 ORDER BY next_attempt_at, id LIMIT ?3"
+            ))?;
+            let emails = statement
+                .query_map(
+                    params![QueueStatus::Queued.to_str(), now, limit as i64],
+                    queued_email_from_row,
+                )?
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(emails)
+        })
+    }
+
+    fn queued_emails(&self, status: QueueStatus) -> Result<Vec<QueuedEmail>, DatabaseError> {
+        self.database.with_connection(|connection| {
+            let mut statement = connection.prepare(&format!(
+                "SELECT {QUEUE_COLUMNS} FROM email_queue WHERE status = ?1
+                 ORDER BY next_attempt_at, id"
+            ))?;
+            let emails = statement
+                .query_map([status.to_str()], queued_email_from_row)?
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(emails)
+        })
+    }
+
+    fn update_queued_email(&mut self, email: &QueuedEmail) -> Result<bool, DatabaseError> {
+        let Some(id) = email.id else {
+            return Ok(false);
+        };
+        self.database.with_connection(|connection| {
+            Ok(connection.execute(
+                "UPDATE email_queue SET status = ?2, attempts = ?3, next_attempt_at = ?4,
+                 last_error = ?5, updated_at = ?6 WHERE id = ?1",
+                params![
+                    id,
+                    email.status.to_str(),
+                    email.attempts,
+                    email.next_attempt_at,
+                    email.last_error,
+                    email.updated_at
+                ],
+            )? > 0)
+        })
+    }
+}
+
+impl AuditRepository for SqliteStore {
+    fn record(&mut self, record: &AuditRecord) -> Result<i64, DatabaseError> {
+        let details = serde_json::to_string(&record.details)?;
+        self.database.with_connection(|connection| {
+            connection.execute(
+                "INSERT INTO audit_records (recorded_at, actor, action, details)
+                 VALUES (?1, ?2, ?3, ?4)",
+                params![record.recorded_at, record.actor, record.action, details],
+            )?;
+            Ok(connection.last_insert_rowid())
+        })
+    }
+
+    fn recent(&self, limit: usize) -> Result<Vec<AuditRecord>, DatabaseError> {
+        self.database.with_connection(|connection| {
+            let mut statement = connection.prepare(
+                "SELECT id, recorded_at, actor, action, details FROM audit_records
+                 ORDER BY id DESC LIMIT ?1",
+            )?;
+            let rows = statement.query_map([limit as i64], |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, i64>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, String>(3)?,
+                    row.get::<_, String>(4)?,
+                ))
+            })?;
+            let mut records = Vec::new();
+            for row in rows {
+                let (id, recorded_at, actor, action, details) = row?;
+                records.push(AuditRecord {
+                    id: Some(id),
+                    recorded_at,
+                    actor,
+                    action,
+                    details: serde_json::from_str(&details)?,
+                });
+            }
+            Ok(records)
+        })
+    }
+}
+
+impl ClassificationCache for SqliteStore {
+    fn cached(&self, key: &str) -> Result<Option<ClassificationResult>, DatabaseError> {
+        self.database.with_connection(|connection| {
+            let result: Option<String> = connection
+                .query_row(
+                    "SELECT result FROM classification_cache WHERE key = ?1",
+                    [key],
+                    |row| row.get(0),
+                )
+                .optional()?;
+            Ok(result
+                .map(|result| serde_json::from_str(&result))
+                .transpose()?)
+        })
+    }
+
+    fn cache(&mut self, key: &str, result: &ClassificationResult) -> Result<(), DatabaseError> {
+        let result = serde_json::to_string(result)?;
+        self.database.with_connection(|connection| {
+            connection.execute(
+                "INSERT OR REPLACE INTO classification_cache (key, result, created_at)
+                 VALUES (?1, ?2, ?3)",
+                params![key, result, unix_timestamp()],
+            )?;
+            Ok(())
+        })
+    }
+}
+
+const QUEUE_COLUMNS: &str = "id, sender, recipients, raw, message_id, draft_id, status, attempts,
+     send_at, next_attempt_at, last_error, created_at, updated_at";
+
+fn queued_email_from_row(row: &rusqlite::Row) -> rusqlite::Result<QueuedEmail> {
+    let conversion_error = |column, e: Box<dyn std::error::Error + Send + Sync>| {
+        rusqlite::Error::FromSqlConversionFailure(column, rusqlite::types::Type::Text, e)
+    };
+    let recipients: String = row.get(2)?;
+    let status: String = row.get(6)?;
+    Ok(QueuedEmail {
+        id: Some(row.get(0)?),
+        email: OutgoingEmail {
+            from: row.get(1)?,
+            recipients: serde_json::from_str(&recipients)
+                .map_err(|e| conversion_error(2, e.into()))?,
+            raw: row.get(3)?,
+            message_id: row.get(4)?,
+        },
+        draft_id: row.get(5)?,
+        status: QueueStatus::from_str(&status).map_err(|e| conversion_error(6, e.into()))?,
+        attempts: row.get(7)?,
+        send_at: row.get(8)?,
+        next_attempt_at: row.get(9)?,
+        last_error: row.get(10)?,
+        created_at: row.get(11)?,
+        updated_at: row.get(12)?,
+    })
+}
+
+const DRAFT_COLUMNS: &str = "id, recipient, subject, body, updated_at, status, email, reason";
+
+fn draft_from_row(row: &rusqlite::Row) -> rusqlite::Result<StoredDraft> {
+    let conversion_error = |column, e: Box<dyn std::error::Error + Send + Sync>| {
+        rusqlite::Error::FromSqlConversionFailure(column, rusqlite::types::Type::Text, e)
+    };
+    let status: String = row.get(5)?;
+    let email: Option<String> = row.get(6)?;
+    Ok(StoredDraft {
+        id: row.get(0)?,
+        recipient: row.get(1)?,
+        subject: row.get(2)?,
+        body: row.get(3)?,
+        updated_at: row.get(4)?,
+        status: DraftStatus::from_str(&status).map_err(|e| conversion_error(5, e.into()))?,
+        email: email
+            .map(|email| serde_json::from_str(&email))
+            .transpose()
+            .map_err(|e| conversion_error(6, e.into()))?,
+        reason: row.get(7)?,
+    })
+}
@@ -0,0 +1,67 @@
+use serde::{Deserialize, Serialize};
+
+use crate::agents::email::{DraftStatus, EmailDraft};
+
+use super::database::unix_timestamp;
+
+/// A message draft kept between turns until it is sent or discarded.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct StoredDraft {
+    pub id: String,
+    pub recipient: Option<String>,
+    pub subject: String,
+    pub body: String,
+    /// Seconds since the Unix epoch.
+    pub updated_at: i64,
+    #[serde(default)]
+    pub status: DraftStatus,
+    /// The full email, for drafts written by the `EmailAgent`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<EmailDraft>,
+    /// Why the draft was cancelled or failed to send.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+impl StoredDraft {
+    pub fn new(id: &str, subject: &str, body: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            recipient: None,
+            subject: subject.to_string(),
+            body: body.to_string(),
+            updated_at: unix_timestamp(),
+            status: DraftStatus::Draft,
+            email: None,
+            reason: None,
+        }
+    }
+
+    /// Keeps `recipient`, `subject` and `body` in line with `email` for listings.
+    pub fn from_email(id: &str, email: &EmailDraft) -> Self {
+        Self::new(id, "", "").with_email(email.clone())
+    }
+
+    pub fn with_recipient(mut self, recipient: &str) -> Self {
+        self.recipient = Some(recipient.to_string());
+        self
+    }
+
+    pub fn with_status(mut self, status: DraftStatus) -> Self {
+        self.status = status;
+        self
+    }
+
+    pub fn with_email(mut self, email: EmailDraft) -> Self {
+        self.recipient = (!email.to.is_empty()).then(|| email.to.join(", "));
+        self.subject = email.subject.clone();
+        self.body = email.body.clone();
+        self.email = Some(email);
+        self
+    }
+
+    pub fn with_reason(mut self, reason: &str) -> Self {
+        self.reason = Some(reason.to_string());
+        self
+    }
+}
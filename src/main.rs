@@ -2,12 +2,14 @@ mod config;
 
 use ollama_ai_agents_playground::{
     agents::{
-        Agent,
+        Agent, Intent,
         assistant::{
-            CheckAssistantAgent, CheckParam, CreateAssistantAgent, CreateParam,
+            CheckAssistantAgent, CheckParam, CreateAssistantAgent, CreateParam, PersonaProfile,
             build_assistant_name,
         },
         classifier::{IntentClassifierAgent, IntentParam},
+        email::{EmailAgent, EmailParam},
+        prompts::PromptLibrary,
     },
     config::Config,
 };
@@ -23,6 +25,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("🚀 Loading user settings...");
     // Access User Settings
     let user_settings = Config::get().user.settings.clone();
+    for error in PromptLibrary::get().errors() {
+        println!(" Prompt file skipped: {error}");
+    }
 
     println!("🚀 Checking if assistant already created...");
     // Check if user assistant exists
@@ -43,7 +48,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 let create_param = CreateParam::new(
                     user_settings.name.to_owned(),
                     user_settings.assistant.to_owned(),
-                );
+                )
+                .with_persona(PersonaProfile::from_config(
+                    &Config::get().assistant.persona,
+                )?);
                 let create_assistant_agent = CreateAssistantAgent::new();
                 let create = create_assistant_agent.process(create_param).await;
                 match create {
@@ -68,7 +76,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let input = "Envie um e-mail para Eva informando que não vou poder comparecer à reunião e que peço desculpas por avisar tão em cima da hora.";
     let intent_classifier_agent = IntentClassifierAgent::default();
     let result = intent_classifier_agent
-        .process(IntentParam::new(input.to_string(), user_settings.assistant))
+        .process(IntentParam::new(
+            input.to_string(),
+            user_settings.assistant.clone(),
+        ))
         .await;
     match result {
         Ok(classification_result) => {
@@ -80,6 +91,26 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 classification_result.params.recipient().unwrap()
             );
             println!();
+
+            if classification_result.intent == Intent::SendEmail {
+                println!("🚀 Drafting email...");
+                // Drafts wait for approval in the configured database
+                let email_agent = EmailAgent::from_config()?;
+                let param = EmailParam::from_params(input, &classification_result.params)
+                    .with_assistant(&user_settings.assistant);
+                match email_agent.process(param).await {
+                    Ok(email_result) => {
+                        println!();
+                        println!("🚀 Draft saved!");
+                        println!("Draft: {}", email_result.draft_id.unwrap_or_default());
+                        println!("Status: {}", email_result.status);
+                        println!();
+                    }
+                    Err(e) => {
+                        println!("Drafting Failed: {e}");
+                    }
+                }
+            }
         }
         Err(e) => {
             println!("Classification Failed: {e}");
@@ -1,5 +1,11 @@
-use ollama_ai_agents_playground::agents::assistant::{
-    CheckParam, CheckResult, CreateParam, CreateResult, build_assistant_name,
+use ollama_ai_agents_playground::agents::{
+    assistant::{
+        Capability, CheckParam, CheckResult, CreateAssistantAgent, CreateParam, CreateResult,
+        Formality, Modelfile, ModelfileRole, PersonaPreset, PersonaProfile, PersonaTone,
+        WorkingHours, build_assistant_name,
+    },
+    context::ConversationMessage,
+    prompts::PromptSet,
 };
 
 // --- CheckParam ---
@@ -77,6 +83,156 @@ fn test_create_param_with_unicode() {
     assert_eq!(param.name(), "Tereza-助理");
 }
 
+#[test]
+fn test_create_param_defaults_to_personal_assistant_persona() {
+    let param = CreateParam::new("Ana".to_string(), "Tereza".to_string());
+    assert_eq!(param.persona().preset, PersonaPreset::PersonalAssistant);
+}
+
+#[test]
+fn test_create_param_with_persona() {
+    let persona = PersonaPreset::CodeReviewer
+        .profile()
+        .with_signature("Ana")
+        .with_working_hours(WorkingHours::new("08:00", "16:00"));
+    let param = CreateParam::new("Ana".to_string(), "Tereza".to_string()).with_persona(persona);
+
+    assert_eq!(param.persona().preset, PersonaPreset::CodeReviewer);
+    assert_eq!(param.persona().signature.as_deref(), Some("Ana"));
+    assert!(param.persona().has_capability(Capability::CodeReview));
+}
+
+// --- Persona presets ---
+
+#[test]
+fn test_persona_presets_parse_from_their_names() {
+    for preset in PersonaPreset::ALL {
+        assert_eq!(preset.to_str().parse::<PersonaPreset>().unwrap(), preset);
+    }
+    assert!("astronaut".parse::<PersonaPreset>().is_err());
+}
+
+#[test]
+fn test_persona_presets_render_distinct_prompts() {
+    let prompts: Vec<String> = PersonaPreset::ALL
+        .iter()
+        .map(|preset| {
+            preset
+                .profile()
+                .build_prompt(&PromptSet::builtin("personal_assistant"))
+        })
+        .collect();
+
+    for (i, prompt) in prompts.iter().enumerate() {
+        assert!(prompt.contains("PROFESSIONAL IDENTITY:"));
+        assert!(prompt.contains("COMMUNICATION STYLE:"));
+        assert!(!prompts[i + 1..].contains(prompt));
+    }
+}
+
+#[test]
+fn test_persona_preset_profiles_keep_their_preset() {
+    for preset in PersonaPreset::ALL {
+        let profile = preset.profile();
+        assert_eq!(profile.preset, preset);
+        assert!(!profile.capabilities.is_empty());
+        assert!(!profile.guidelines.is_empty());
+    }
+}
+
+#[test]
+fn test_persona_profile_serialization_roundtrip() {
+    let original = PersonaPreset::FinancialAnalyst
+        .profile()
+        .with_tone(PersonaTone::Friendly)
+        .with_formality(Formality::Casual);
+
+    let json = serde_json::to_string(&original).unwrap();
+    let restored: PersonaProfile = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(restored, original);
+    assert!(json.contains("\"financial_analyst\""));
+}
+
+#[test]
+fn test_persona_enums_parse_case_insensitively() {
+    assert_eq!(
+        " Friendly ".parse::<PersonaTone>().unwrap(),
+        PersonaTone::Friendly
+    );
+    assert_eq!("FORMAL".parse::<Formality>().unwrap(), Formality::Formal);
+    assert_eq!(
+        "code_review".parse::<Capability>().unwrap(),
+        Capability::CodeReview
+    );
+    assert!("sarcastic".parse::<PersonaTone>().is_err());
+}
+
+#[test]
+fn test_working_hours_parse_range() {
+    let hours = WorkingHours::parse_range("09:00-18:00").unwrap();
+    assert_eq!(hours.describe(), "Mon-Fri 09:00-18:00");
+    assert!(WorkingHours::parse_range("9-18").is_none());
+    assert!(WorkingHours::parse_range("25:00-18:00").is_none());
+}
+
+// --- Modelfile export / import (requires config.toml) ---
+
+#[test]
+fn test_generated_modelfile_round_trips() {
+    let param = CreateParam::new("Ana".to_string(), "Tereza".to_string())
+        .with_persona(
+            PersonaPreset::TechnicalWriter
+                .profile()
+                .with_signature("Ana"),
+        )
+        .with_template("{{ .System }}\n{{ .Prompt }}")
+        .with_parameter("temperature", "0.2")
+        .with_examples(vec![
+            ConversationMessage::user("Document the create endpoint"),
+            ConversationMessage::assistant("## POST /api/create\n\nCreates a model."),
+        ]);
+
+    let modelfile = CreateAssistantAgent::new().build_modelfile(&param);
+    let restored = Modelfile::parse(&modelfile.render()).unwrap();
+
+    assert_eq!(restored, modelfile);
+    assert!(
+        restored
+            .system
+            .as_deref()
+            .unwrap()
+            .contains("Your name is Tereza")
+    );
+    assert_eq!(restored.parameter("temperature"), Some("0.2"));
+    assert_eq!(restored.messages.len(), 2);
+    assert_eq!(restored.messages[0].role, ModelfileRole::User);
+}
+
+#[test]
+fn test_default_modelfile_has_only_from_and_system() {
+    let param = CreateParam::new("Ana".to_string(), "Tereza".to_string());
+
+    let modelfile = CreateAssistantAgent::new().build_modelfile(&param);
+
+    assert!(modelfile.system.is_some());
+    assert!(modelfile.parameters.is_empty());
+    assert!(modelfile.template.is_none());
+    assert!(modelfile.messages.is_empty());
+}
+
+#[test]
+fn test_imported_modelfile_is_used_as_is() {
+    let imported = Modelfile::parse("FROM llama3.2\nSYSTEM You are terse.\n").unwrap();
+    let param =
+        CreateParam::new("Ana".to_string(), "Tereza".to_string()).with_modelfile(imported.clone());
+
+    assert_eq!(
+        CreateAssistantAgent::new().build_modelfile(&param),
+        imported
+    );
+}
+
 // --- CreateResult ---
 
 #[test]
@@ -0,0 +1,533 @@
+use std::{fs, path::PathBuf};
+
+use ollama_ai_agents_playground::{
+    agents::{
+        Agent,
+        contact::{
+            AddressSelector, ContactActionStatus, ContactAgent, ContactChannel,
+            ContactCommandParam, ContactDeduplicator, ContactOperation, ContactParam,
+            DuplicateSignal, MERGED_FROM, MatchField, MessageContext, ResolutionStatus,
+            SelectionReason,
+        },
+    },
+    infra::calendar::UserCalendar,
+    infra::contacts::{
+        Contact, ContactEmail, ContactLabel, ContactQuery, ContactStore, CsvImporter, CsvRowStatus,
+        JsonContactStore, UserContacts, VCard, VCardVersion,
+    },
+};
+
+const SPEC_CONTACTS: &str = "spec/contacts.json";
+
+fn temp_copy(name: &str) -> PathBuf {
+    let path = std::env::temp_dir()
+        .join(format!("contacts_tests_{name}_{}", std::process::id()))
+        .join("contacts.json");
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::copy(SPEC_CONTACTS, &path).unwrap();
+    path
+}
+
+#[test]
+fn test_spec_contacts_load_into_typed_model() {
+    let store = JsonContactStore::open(SPEC_CONTACTS.as_ref()).unwrap();
+    let contacts = store.list().unwrap();
+
+    assert_eq!(contacts.len(), 17);
+    assert_eq!(store.metadata().total_contacts, 17);
+    assert_eq!(
+        store.metadata().source.as_deref(),
+        Some("multi-platform-sync")
+    );
+
+    let tiger = store.get("c001").unwrap().unwrap();
+    assert_eq!(tiger.nickname.as_deref(), Some("Tiggy"));
+    assert_eq!(tiger.department.as_deref(), Some("Engineering"));
+    assert_eq!(tiger.emails.len(), 2);
+    assert_eq!(tiger.emails[1].label, ContactLabel::Work);
+    assert_eq!(tiger.addresses[0].postal_code.as_deref(), Some("10001"));
+    assert_eq!(tiger.social_profiles[0].platform, "linkedin");
+    assert_eq!(tiger.birthday.as_deref(), Some("1985-06-15"));
+    assert!(tiger.favorite);
+}
+
+#[test]
+fn test_spec_contacts_serialize_back_to_the_same_document() {
+    let original: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(SPEC_CONTACTS).unwrap()).unwrap();
+    let document = UserContacts::from_json_str(&original.to_string()).unwrap();
+    let serialized: serde_json::Value =
+        serde_json::from_str(&document.to_json_string().unwrap()).unwrap();
+
+    assert_eq!(serialized, original);
+}
+
+#[test]
+fn test_crud_on_a_copy_of_the_spec_file() {
+    let path = temp_copy("crud");
+    let mut store = JsonContactStore::open(&path).unwrap();
+
+    let eva = store
+        .create(Contact::new("", "Eva", "Green").with_email(
+            ContactEmail::new(ContactLabel::Work, "eva@example.com").with_primary(true),
+        ))
+        .unwrap();
+    assert_eq!(eva.id, "c018");
+
+    let mut tiger = store.get("c001").unwrap().unwrap();
+    tiger.tags.push("mentor".to_string());
+    store.update(tiger).unwrap();
+    store.delete("c002").unwrap();
+
+    let reopened = JsonContactStore::open(&path).unwrap();
+    let _ = fs::remove_dir_all(path.parent().unwrap());
+
+    assert_eq!(reopened.metadata().total_contacts, 17);
+    assert!(reopened.get("c002").unwrap().is_none());
+    assert!(reopened.get("c001").unwrap().unwrap().has_tag("mentor"));
+    assert_eq!(
+        reopened
+            .get("c018")
+            .unwrap()
+            .and_then(|c| c.primary_email().map(|e| e.address.clone())),
+        Some("eva@example.com".to_string())
+    );
+    assert_eq!(
+        reopened.metadata().last_sync.as_deref(),
+        Some("2024-08-21T18:30:00Z")
+    );
+}
+
+async fn resolve(reference: &str) -> ollama_ai_agents_playground::agents::contact::ContactResult {
+    let agent = ContactAgent::new(JsonContactStore::open(SPEC_CONTACTS.as_ref()).unwrap());
+    agent
+        .process(ContactParam::new(&reference.to_string()))
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_contact_agent_resolves_spec_recipients() {
+    let tiger = resolve("tiger").await;
+    assert_eq!(tiger.status, ResolutionStatus::Unique);
+    assert_eq!(
+        tiger.resolved().and_then(|c| c.email.as_deref()),
+        Some("tiger.brilliant@gmail.com")
+    );
+
+    let big_bear = resolve("Big Bear").await;
+    assert_eq!(
+        big_bear.resolved().map(|c| c.contact_id.as_str()),
+        Some("c010")
+    );
+    assert_eq!(
+        big_bear.resolved().unwrap().matched_on,
+        MatchField::Nickname
+    );
+
+    let typo = resolve("Pengiun").await;
+    assert_eq!(
+        typo.resolved().map(|c| c.name.as_str()),
+        Some("Penguin Swift")
+    );
+}
+
+#[tokio::test]
+async fn test_contact_agent_asks_when_ambiguous_or_unknown() {
+    let wise = resolve("Dr. Wise").await;
+    assert_eq!(wise.status, ResolutionStatus::Ambiguous);
+    let ids: Vec<&str> = wise
+        .candidates
+        .iter()
+        .map(|c| c.contact_id.as_str())
+        .collect();
+    assert_eq!(ids, vec!["c003", "c014"]);
+    assert!(
+        wise.follow_up_question()
+            .unwrap()
+            .contains("Dr. Owl Wise <o.wise@wisdomuni.edu>")
+    );
+
+    let eva = resolve("Eva").await;
+    assert_eq!(eva.status, ResolutionStatus::NotFound);
+    assert!(eva.follow_up_question().is_some());
+}
+
+#[test]
+fn test_spec_contacts_round_trip_through_vcard_files() {
+    let contacts = JsonContactStore::open(SPEC_CONTACTS.as_ref())
+        .unwrap()
+        .list()
+        .unwrap();
+
+    for version in [VCardVersion::V3, VCardVersion::V4] {
+        let path = std::env::temp_dir()
+            .join(format!(
+                "contacts_tests_vcard_{version}_{}",
+                std::process::id()
+            ))
+            .with_extension("vcf");
+        VCard::export(&path, &contacts, version).unwrap();
+        let imported = VCard::import(&path).unwrap();
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(imported.len(), contacts.len(), "vCard {version}");
+        for (imported, original) in imported.iter().zip(&contacts) {
+            assert_eq!(imported, original, "vCard {version}");
+        }
+    }
+}
+
+#[test]
+fn test_csv_import_previews_then_commits_into_the_spec_store() {
+    let path = temp_copy("csv_import");
+    let mut store = JsonContactStore::open(&path).unwrap();
+    let csv = "First Name,Last Name,Organization Name,E-mail 1 - Label,E-mail 1 - Value,Phone 1 - Label,Phone 1 - Value\n\
+               Tiger,Brilliant,Tech Solutions Inc.,Work,tiger.brilliant@gmail.com,,\n\
+               Dolphin,Wise,,Mobile,,Mobile,(555) 444-7890\n\
+               Owl,Night,Observatory,* Work,owl@observatory.org,Mobile,+1 555 010 2020\n\
+               Lynx,,,,lynx@,,\n";
+
+    let preview = CsvImporter::new()
+        .preview(csv, &store.list().unwrap())
+        .unwrap();
+    assert_eq!(preview.mapping, "google");
+    assert_eq!(preview.count(CsvRowStatus::Valid), 1);
+    assert_eq!(preview.count(CsvRowStatus::Invalid), 1);
+    let duplicates: Vec<_> = preview
+        .rows_with_status(CsvRowStatus::Duplicate)
+        .map(|row| row.duplicate_of.as_deref().unwrap())
+        .collect();
+    assert_eq!(duplicates, vec!["c001", "c003"]);
+    assert_eq!(store.list().unwrap().len(), 17);
+
+    let created = preview.commit(&mut store).unwrap();
+    assert_eq!(created.len(), 1);
+    assert_eq!(created[0].display_name, "Owl Night");
+    assert_eq!(
+        created[0]
+            .primary_email()
+            .map(|email| email.address.as_str()),
+        Some("owl@observatory.org")
+    );
+
+    let reopened = JsonContactStore::open(&path).unwrap();
+    assert_eq!(reopened.list().unwrap().len(), 18);
+    assert!(reopened.get(&created[0].id).unwrap().is_some());
+    let _ = fs::remove_dir_all(path.parent().unwrap());
+}
+
+#[test]
+fn test_dedupe_merges_a_reimported_contact_and_undoes_the_merge() {
+    let path = temp_copy("dedupe");
+    let original = fs::read_to_string(&path).unwrap();
+    let mut store = JsonContactStore::open(&path).unwrap();
+    let deduplicator = ContactDeduplicator::new();
+
+    // c001 and c014 share an office line but are different people.
+    assert!(
+        deduplicator
+            .find_duplicates(&store.list().unwrap())
+            .is_empty()
+    );
+
+    let reimported = Contact::new("", "Tiger", "Brilliant")
+        .with_email(ContactEmail::new(
+            ContactLabel::Work,
+            "T.Brilliant@TechSolutions.com",
+        ))
+        .with_email(ContactEmail::new(
+            ContactLabel::Personal,
+            "tiger@jungle.net",
+        ))
+        .with_tag("imported");
+    let reimported = store.create(reimported).unwrap();
+
+    let candidates = deduplicator.find_duplicates(&store.list().unwrap());
+    assert_eq!(candidates.len(), 1);
+    assert_eq!(candidates[0].primary_id, "c001");
+    assert_eq!(candidates[0].duplicate_id, reimported.id);
+    assert!(
+        candidates[0]
+            .signals
+            .contains(&DuplicateSignal::SharedEmail)
+    );
+
+    let merge = deduplicator
+        .merge(&mut store, "c001", &reimported.id)
+        .unwrap();
+    let tiger = store.get("c001").unwrap().unwrap();
+    assert_eq!(tiger.emails.len(), 3);
+    assert_eq!(tiger.emails.iter().filter(|e| e.primary).count(), 1);
+    assert!(tiger.has_tag("imported"));
+    assert_eq!(
+        tiger.extensions.get(MERGED_FROM),
+        Some(&format!("c001,{}", reimported.id))
+    );
+    assert_eq!(store.list().unwrap().len(), 17);
+
+    merge.undo(&mut store).unwrap();
+    store.delete(&reimported.id).unwrap();
+    let restored = UserContacts::from_json_str(&fs::read_to_string(&path).unwrap()).unwrap();
+    let expected = UserContacts::from_json_str(&original).unwrap();
+    assert_eq!(restored.contacts, expected.contacts);
+    let _ = fs::remove_dir_all(path.parent().unwrap());
+}
+
+async fn plan(
+    agent: &ContactAgent<JsonContactStore>,
+    request: &str,
+) -> ollama_ai_agents_playground::agents::contact::ContactActionResult {
+    agent
+        .process(ContactCommandParam::new(request))
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_contact_agent_answers_queries_without_writing() {
+    let agent = ContactAgent::new(JsonContactStore::open(SPEC_CONTACTS.as_ref()).unwrap());
+
+    let action = plan(&agent, "What's Tiger's email?").await;
+    assert_eq!(action.status, ContactActionStatus::Answered);
+    assert_eq!(
+        action.message,
+        "Tiger Brilliant's email: tiger.brilliant@gmail.com (personal, primary), \
+         t.brilliant@techsolutions.com (work)"
+    );
+
+    let action = plan(&agent, "qual o telefone do Dr. Wise?").await;
+    assert_eq!(action.status, ContactActionStatus::NeedsClarification);
+    assert!(action.message.starts_with("Which Dr. Wise did you mean"));
+
+    let action = plan(&agent, "Send an email to Carlos").await;
+    assert_eq!(action.status, ContactActionStatus::NeedsClarification);
+    assert_eq!(action.command, None);
+}
+
+#[tokio::test]
+async fn test_contact_agent_writes_only_after_confirmation() {
+    let path = temp_copy("manage");
+    let mut agent = ContactAgent::new(JsonContactStore::open(&path).unwrap());
+
+    let action = plan(&agent, "tag Dolphin as cardiologist").await;
+    assert!(action.needs_confirmation());
+    assert_eq!(
+        action.message,
+        "Update Dr. Dolphin Wise: add tag cardiologist? Reply yes to confirm."
+    );
+    let tagged = |agent: &ContactAgent<JsonContactStore>| {
+        let dolphin = agent.store().get("c003").unwrap().unwrap();
+        dolphin.has_tag("cardiologist")
+    };
+    assert!(!tagged(&agent));
+    agent.confirm(&action).unwrap();
+    assert!(tagged(&agent));
+
+    let action = plan(&agent, "add Penguin's new work phone +55 11 91234-5678").await;
+    assert_eq!(
+        action.command.as_ref().unwrap().operation,
+        ContactOperation::Update
+    );
+    let penguin = agent.confirm(&action).unwrap();
+    assert_eq!(penguin.phone_numbers.len(), 2);
+    assert_eq!(penguin.phone_numbers[1].label, ContactLabel::Work);
+
+    let action = plan(
+        &agent,
+        "Add John Smith from XYZ Company to my professional contacts",
+    )
+    .await;
+    assert!(action.needs_confirmation());
+    let john = agent.confirm(&action).unwrap();
+    assert_eq!(john.id, "c018");
+    assert_eq!(john.company.as_deref(), Some("XYZ Company"));
+    assert!(john.has_tag("professional"));
+
+    let reopened = JsonContactStore::open(&path).unwrap();
+    assert_eq!(reopened.list().unwrap().len(), 18);
+    let _ = fs::remove_dir_all(path.parent().unwrap());
+}
+
+#[tokio::test]
+async fn test_contact_agent_confirms_each_plan_once_against_the_current_contact() {
+    let path = temp_copy("manage_once");
+    let mut agent = ContactAgent::new(JsonContactStore::open(&path).unwrap());
+
+    let tag = plan(&agent, "tag Dolphin as cardiologist").await;
+    let email = plan(&agent, "add Dolphin's email dolphin@clinic.com").await;
+    assert_ne!(tag.token, email.token);
+    agent.confirm(&email).unwrap();
+    let dolphin = agent.confirm(&tag).unwrap();
+    assert!(dolphin.has_tag("cardiologist"));
+    assert!(
+        dolphin
+            .emails
+            .iter()
+            .any(|e| e.address == "dolphin@clinic.com")
+    );
+    assert!(agent.confirm(&tag).is_err());
+
+    let create = plan(
+        &agent,
+        "Add John Smith from XYZ Company to my professional contacts",
+    )
+    .await;
+    agent.confirm(&create).unwrap();
+    assert!(agent.confirm(&create).is_err());
+    let again = plan(
+        &agent,
+        "Add John Smith from XYZ Company to my professional contacts",
+    )
+    .await;
+    let error = agent.confirm(&again).unwrap_err();
+    assert!(
+        error.to_string().contains("already in your contacts"),
+        "{error}"
+    );
+    assert_eq!(agent.store().list().unwrap().len(), 18);
+    let _ = fs::remove_dir_all(path.parent().unwrap());
+}
+
+#[tokio::test]
+async fn test_contact_agent_reports_unchanged_and_unconfirmable_actions() {
+    let path = temp_copy("manage_noop");
+    let mut agent = ContactAgent::new(JsonContactStore::open(&path).unwrap());
+
+    let action = plan(&agent, "tag Dolphin as doctor").await;
+    assert_eq!(action.status, ContactActionStatus::Answered);
+    assert_eq!(
+        action.message,
+        "Dr. Dolphin Wise already has that information."
+    );
+
+    let action = plan(&agent, "add Tiger's email tiger.brilliant@gmail.com").await;
+    assert_eq!(action.status, ContactActionStatus::Answered);
+    assert!(agent.confirm(&action).is_err());
+
+    let action = plan(&agent, "tag Carlos as friend").await;
+    assert_eq!(action.status, ContactActionStatus::NeedsClarification);
+    assert_eq!(action.message, "I could not find Carlos in your contacts.");
+    let _ = fs::remove_dir_all(path.parent().unwrap());
+}
+
+#[tokio::test]
+async fn test_group_queries_expand_to_recipient_lists() {
+    let store = JsonContactStore::open(SPEC_CONTACTS.as_ref()).unwrap();
+    let engineering = store
+        .query(
+            &ContactQuery::new()
+                .with_company("Tech Solutions")
+                .with_department("Engineering"),
+        )
+        .unwrap();
+    assert_eq!(engineering.len(), 1);
+    assert_eq!(engineering[0].id, "c001");
+
+    let team = resolve("everyone in Engineering at Tech Solutions").await;
+    assert_eq!(team.status, ResolutionStatus::Group);
+    assert!(!team.needs_follow_up());
+    let emails: Vec<&str> = team
+        .recipients()
+        .iter()
+        .filter_map(|c| c.email.as_deref())
+        .collect();
+    assert_eq!(emails, vec!["tiger.brilliant@gmail.com"]);
+
+    // No city is called "Philosophy", so the agent retries it as a department.
+    let philosophy = resolve("everyone in Philosophy").await;
+    assert_eq!(philosophy.recipients()[0].contact_id, "c014");
+
+    let agent = ContactAgent::new(store);
+    let family = agent
+        .process(
+            ContactParam::new(&"my family".to_string())
+                .with_query(ContactQuery::new().with_tag("family").with_favorite(true)),
+        )
+        .await
+        .unwrap();
+    let ids: Vec<&str> = family
+        .recipients()
+        .iter()
+        .map(|c| c.contact_id.as_str())
+        .collect();
+    assert_eq!(ids, vec!["c012", "c015", "c017"]);
+
+    let action = plan(&agent, "Show my favourite contacts in New York").await;
+    assert_eq!(action.status, ContactActionStatus::Answered);
+    assert_eq!(
+        action.message,
+        "1 contact matches favourites, city New York: Tiger Brilliant."
+    );
+    assert_eq!(action.matches[0].id, "c001");
+
+    let nobody = resolve("everyone at Acme Corp").await;
+    assert_eq!(nobody.status, ResolutionStatus::NotFound);
+    assert!(nobody.recipients().is_empty());
+}
+
+#[test]
+fn test_addresses_follow_message_context_and_corrections() {
+    let path = temp_copy("addresses");
+    let mut agent = ContactAgent::new(JsonContactStore::open(&path).unwrap());
+    let selector = AddressSelector::new();
+
+    let work = selector.infer_from_text("Send Tiger the project status report");
+    let selection = agent
+        .select_address("c001", ContactChannel::Email, work.as_ref())
+        .unwrap()
+        .unwrap();
+    assert_eq!(selection.address, "t.brilliant@techsolutions.com");
+    assert_eq!(selection.reason, SelectionReason::ContextLabel);
+
+    let personal = selector.infer_from_text("Invite Penguin to the family dinner");
+    let penguin = agent
+        .select_address("c004", ContactChannel::Email, personal.as_ref())
+        .unwrap()
+        .unwrap();
+    assert_eq!(penguin.address, "penguin.swift@yahoo.com");
+
+    // Dr. Wise only has a work email; the explanation says so.
+    let wise = agent
+        .select_address("c003", ContactChannel::Email, personal.as_ref())
+        .unwrap()
+        .unwrap();
+    assert_eq!(wise.address, "d.wise@citymedical.com");
+    assert!(wise.explanation.contains("has no personal email"));
+
+    let calendar = UserCalendar::read_from("spec/calendar.json").unwrap();
+    let meeting = calendar.calendar.find("evt_002").unwrap();
+    let from_event = selector.infer_from_event(meeting).unwrap();
+    assert_eq!(from_event.context, MessageContext::Work);
+    let phone = agent
+        .select_address("c001", ContactChannel::Phone, Some(&from_event))
+        .unwrap()
+        .unwrap();
+    assert_eq!(phone.address, "+1-555-987-6543");
+
+    // The user corrects the choice; the correction is stored with the contact.
+    agent
+        .prefer_address(&selection, "tiger.brilliant@gmail.com")
+        .unwrap();
+    assert!(
+        agent
+            .prefer_address(&selection, "nobody@example.com")
+            .is_err()
+    );
+    let reopened = ContactAgent::new(JsonContactStore::open(&path).unwrap());
+    let _ = fs::remove_dir_all(path.parent().unwrap());
+    let corrected = reopened
+        .select_address("c001", ContactChannel::Email, work.as_ref())
+        .unwrap()
+        .unwrap();
+    assert_eq!(corrected.address, "tiger.brilliant@gmail.com");
+    assert_eq!(corrected.reason, SelectionReason::Preference);
+
+    // Personal messages are unaffected by a work correction.
+    let birthday = selector.infer_from_text("Happy birthday, Tiger!");
+    let personal_choice = reopened
+        .select_address("c001", ContactChannel::Email, birthday.as_ref())
+        .unwrap()
+        .unwrap();
+    assert_eq!(personal_choice.reason, SelectionReason::ContextLabel);
+}
@@ -0,0 +1,872 @@
+use std::{
+    io::{BufRead, BufReader, Write},
+    net::TcpListener,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    thread,
+    time::Duration,
+};
+
+use mail_parser::{MessageParser, MimeHeaders};
+use ollama_ai_agents_playground::{
+    agents::{
+        ClassificationResult, Intent,
+        assistant::PersonaTone,
+        contact::{ContactAgent, SelectionReason},
+        email::{
+            DraftEdit, DraftStatus, EmailAgent, EmailDraft, EmailParam, IdentityReason,
+            IdentitySelector, RecipientProblem, RecipientSource, ResponseKind, TemplateKind,
+            TemplateLibrary, TemplateValues, build_draft_prompts, detect_language, parse_draft,
+        },
+        prompts::PromptLibrary,
+    },
+    config::{EmailConfig, IdentityConfig, PromptsConfig, SmtpConfig, UserSettings},
+    infra::{
+        contacts::{Contact, JsonContactStore},
+        email::{
+            ConfiguredSender, EmailAttachment, EmailError, EmailQueue, EmailQueueWorker,
+            EmailSender, EmailTransportKind, IncomingEmail, LocalSender, Mailbox, MessageBuilder,
+            OutgoingEmail, QueueStatus, SmtpSender,
+        },
+        persistence::{Database, SqliteStore},
+    },
+};
+
+fn shipped_prompts(language: &str) -> PromptLibrary {
+    let config = PromptsConfig {
+        path: "prompts".to_string(),
+        language: language.to_string(),
+    };
+    PromptLibrary::load(&config, "nobody").expect("shipped prompts should be valid")
+}
+
+const INPUT: &str = "Envie um e-mail para Eva informando que não vou poder comparecer à reunião e que peço desculpas por avisar tão em cima da hora.";
+
+fn classified() -> ClassificationResult {
+    ClassificationResult::from_json_str(
+        r#"{"intent":"send_email","params":{"recipient":"Eva","message":"Não vou poder comparecer à reunião; desculpas pelo aviso em cima da hora"}}"#,
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_classifier_output_becomes_a_draft_request() {
+    let param = EmailParam::from_params(INPUT, &classified().params)
+        .with_signer("Ana")
+        .with_language("pt-BR")
+        .with_tone(PersonaTone::Empathetic);
+    let prompts = shipped_prompts("pt-BR").prompt_set("email_draft");
+
+    let (system, user) = build_draft_prompts(&prompts, &param);
+
+    assert_eq!(prompts.version(), "1.3.0-pt-BR");
+    assert!(system.contains("em nome de Ana"));
+    assert!(system.contains("Output-Format"));
+    assert!(system.contains(PersonaTone::Empathetic.instruction()));
+    assert!(user.contains("Destinatário: \"Eva\""));
+    assert!(user.contains(INPUT));
+    assert!(user.ends_with("Output: "));
+}
+
+#[test]
+fn test_model_response_becomes_a_signed_draft() {
+    let param = EmailParam::from_params(INPUT, &classified().params)
+        .with_to(vec!["eva.green@example.com".to_string()])
+        .with_signer("Ana")
+        .with_language("pt-BR")
+        .with_tone(PersonaTone::Empathetic);
+    let response = "```json\n{\"subject\": \"Ausência na reunião\", \"body\": \"Olá Eva,\\n\\nInfelizmente não vou poder comparecer à reunião. Peço desculpas por avisar tão em cima da hora.\\n\\nAtenciosamente,\\nAna\", \"html\": \"\"}\n```";
+
+    let draft = parse_draft(response, &param).unwrap();
+
+    assert_eq!(draft.to, vec!["eva.green@example.com"]);
+    assert!(draft.cc.is_empty());
+    assert_eq!(draft.subject, "Ausência na reunião");
+    assert!(draft.body.ends_with("Atenciosamente,\nAna"));
+    assert_eq!(draft.html_body, None);
+    assert_eq!(draft.language, "pt-BR");
+    assert_eq!(draft.tone, PersonaTone::Empathetic);
+}
+
+// --- Transports ---
+
+/// What the SMTP stand-in received for one message.
+#[derive(Debug, Clone, Default)]
+struct Captured {
+    auth: Option<String>,
+    from: String,
+    recipients: Vec<String>,
+    data: String,
+}
+
+/// A minimal in-process SMTP server that records every message. Recipients in
+/// `reject` get a permanent 550.
+fn smtp_stand_in(reject: &'static [&'static str]) -> (u16, Arc<Mutex<Vec<Captured>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let captured = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&captured);
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut message = Captured::default();
+            let mut reply = |text: &str| stream.write_all(format!("{text}\r\n").as_bytes());
+            reply("220 stand-in ESMTP").unwrap();
+            let mut line = String::new();
+            while reader.read_line(&mut line).unwrap_or(0) > 0 {
+                let command = line.trim_end().to_string();
+                line.clear();
+                let upper = command.to_uppercase();
+                let address = || {
+                    let start = command.find('<').map_or(0, |i| i + 1);
+                    let end = command.find('>').unwrap_or(command.len());
+                    command[start..end].to_string()
+                };
+                let response = if upper.starts_with("EHLO") {
+                    "250-stand-in\r\n250-AUTH PLAIN LOGIN\r\n250 8BITMIME".to_string()
+                } else if upper.starts_with("AUTH") {
+                    message.auth = Some(command.clone());
+                    "235 2.7.0 Authentication successful".to_string()
+                } else if upper.starts_with("MAIL FROM") {
+                    message.from = address();
+                    "250 2.1.0 Ok".to_string()
+                } else if upper.starts_with("RCPT TO") {
+                    let recipient = address();
+                    if reject.contains(&recipient.as_str()) {
+                        "550 5.1.1 No such user".to_string()
+                    } else {
+                        message.recipients.push(recipient);
+                        "250 2.1.5 Ok".to_string()
+                    }
+                } else if upper == "DATA" {
+                    reply("354 End data with <CR><LF>.<CR><LF>").unwrap();
+                    let mut data = String::new();
+                    loop {
+                        let mut data_line = String::new();
+                        reader.read_line(&mut data_line).unwrap();
+                        if data_line == ".\r\n" {
+                            break;
+                        }
+                        data.push_str(data_line.strip_prefix('.').unwrap_or(&data_line));
+                    }
+                    message.data = data;
+                    sink.lock().unwrap().push(std::mem::take(&mut message));
+                    "250 2.0.0 Ok: queued as 1".to_string()
+                } else if upper == "QUIT" {
+                    reply("221 2.0.0 Bye").unwrap();
+                    break;
+                } else {
+                    "250 2.0.0 Ok".to_string()
+                };
+                reply(&response).unwrap();
+            }
+        }
+    });
+    (port, captured)
+}
+
+fn smtp_config(port: u16) -> SmtpConfig {
+    SmtpConfig {
+        host: "127.0.0.1".to_string(),
+        port: Some(port),
+        security: "none".to_string(),
+        ..SmtpConfig::default()
+    }
+}
+
+fn outgoing() -> OutgoingEmail {
+    OutgoingEmail::new(
+        "ana@example.com",
+        vec![
+            "eva.green@example.com".to_string(),
+            "carlos@example.com".to_string(),
+        ],
+        b"From: ana@example.com\r\nTo: eva.green@example.com\r\nSubject: Meeting\r\n\r\nHi Eva,\r\n.hidden dot\r\n\r\nAna\r\n",
+    )
+}
+
+#[tokio::test]
+async fn test_smtp_sender_delivers_to_every_envelope_recipient() {
+    let (port, captured) = smtp_stand_in(&[]);
+    let sender = SmtpSender::from_config(&SmtpConfig {
+        username: Some("ana@example.com".to_string()),
+        password: Some("secret".to_string()),
+        ..smtp_config(port)
+    })
+    .unwrap();
+
+    let receipt = sender.send(&outgoing()).await.unwrap();
+
+    assert_eq!(receipt.transport, EmailTransportKind::Smtp);
+    assert!(receipt.reference.starts_with("250"));
+    let captured = captured.lock().unwrap();
+    assert_eq!(captured.len(), 1);
+    assert!(captured[0].auth.is_some());
+    assert_eq!(captured[0].from, "ana@example.com");
+    // Carlos is only in the envelope (a Bcc), not in the headers.
+    assert_eq!(
+        captured[0].recipients,
+        vec!["eva.green@example.com", "carlos@example.com"]
+    );
+    // Lettre ends DATA with an extra CRLF before the final dot.
+    assert_eq!(
+        captured[0].data.trim_end(),
+        String::from_utf8(outgoing().raw).unwrap().trim_end(),
+        "message must arrive unchanged, dot-stuffing included"
+    );
+}
+
+#[tokio::test]
+async fn test_smtp_sender_reports_rejections_as_permanent() {
+    let (port, _) = smtp_stand_in(&["carlos@example.com"]);
+    let sender = SmtpSender::from_config(&smtp_config(port)).unwrap();
+
+    let error = sender.send(&outgoing()).await.unwrap_err();
+
+    assert!(matches!(error, EmailError::Rejected(_)), "{error}");
+    assert!(!error.is_transient());
+}
+
+#[tokio::test]
+async fn test_smtp_sender_reports_unreachable_servers_as_transient() {
+    let port = TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port();
+    let sender = SmtpSender::from_config(&SmtpConfig {
+        timeout_secs: Some(2),
+        ..smtp_config(port)
+    })
+    .unwrap();
+
+    let error = sender.send(&outgoing()).await.unwrap_err();
+
+    assert!(error.is_transient(), "{error}");
+}
+
+#[tokio::test]
+async fn test_configured_sender_defaults_to_the_outbox() {
+    let dir: PathBuf =
+        std::env::temp_dir().join(format!("email_tests_outbox_{}", std::process::id()));
+    let config = EmailConfig {
+        path: dir.display().to_string(),
+        ..EmailConfig::default()
+    };
+
+    let sender = ConfiguredSender::from_config(&config).unwrap();
+    let receipt = sender.send(&outgoing()).await.unwrap();
+
+    assert_eq!(sender.kind(), EmailTransportKind::Outbox);
+    assert_eq!(std::fs::read(&receipt.reference).unwrap(), outgoing().raw);
+    let _ = std::fs::remove_dir_all(dir);
+
+    let (port, captured) = smtp_stand_in(&[]);
+    let smtp = ConfiguredSender::from_config(&EmailConfig {
+        transport: "smtp".to_string(),
+        smtp: Some(smtp_config(port)),
+        ..EmailConfig::default()
+    })
+    .unwrap();
+    smtp.send(&outgoing()).await.unwrap();
+    assert_eq!(captured.lock().unwrap().len(), 1);
+
+    assert!(matches!(
+        ConfiguredSender::from_config(&EmailConfig {
+            transport: "smtp".to_string(),
+            ..EmailConfig::default()
+        }),
+        Err(EmailError::ConfigError(_))
+    ));
+}
+
+#[test]
+fn test_built_message_round_trips_through_a_mime_parser() {
+    let pdf: Vec<u8> = (0..=255u8).cycle().take(3000).collect();
+    let draft = EmailDraft::new(
+        vec!["Eva Souza <eva@example.com>".to_string()],
+        "Reunião de amanhã — ausência",
+        "Olá Eva,\n\nNão vou poder comparecer à reunião.\n\nAna",
+    )
+    .with_cc(vec!["carlos@example.com".to_string()])
+    .with_bcc(vec!["joao@example.com".to_string()])
+    .with_html_body("<p>Olá Eva,</p><p>Não vou poder comparecer à reunião.</p>");
+
+    let email = draft
+        .to_message(Mailbox::new("ana@example.com").with_name("Ana Conceição"))
+        .unwrap()
+        .with_attachment(EmailAttachment::new("relatório.pdf", &pdf))
+        .with_attachment(EmailAttachment::new("notes.txt", b"line one\n"))
+        .build()
+        .unwrap();
+    let message = MessageParser::default().parse(&email.raw).unwrap();
+
+    assert_eq!(message.subject(), Some("Reunião de amanhã — ausência"));
+    let from = message.from().unwrap().first().unwrap();
+    assert_eq!(from.name(), Some("Ana Conceição"));
+    assert_eq!(from.address(), Some("ana@example.com"));
+    let to = message.to().unwrap().first().unwrap();
+    assert_eq!(to.name(), Some("Eva Souza"));
+    assert_eq!(to.address(), Some("eva@example.com"));
+    assert!(message.bcc().is_none());
+    assert_eq!(
+        email.recipients,
+        vec!["eva@example.com", "carlos@example.com", "joao@example.com"]
+    );
+
+    assert_eq!(
+        message.body_text(0).unwrap().trim_end(),
+        "Olá Eva,\r\n\r\nNão vou poder comparecer à reunião.\r\n\r\nAna"
+    );
+    assert!(
+        message
+            .body_html(0)
+            .unwrap()
+            .contains("<p>Não vou poder comparecer à reunião.</p>")
+    );
+
+    let attachments: Vec<_> = message.attachments().collect();
+    assert_eq!(attachments.len(), 2);
+    assert_eq!(attachments[0].attachment_name(), Some("relatório.pdf"));
+    let content_type = attachments[0].content_type().unwrap();
+    assert_eq!(content_type.ctype(), "application");
+    assert_eq!(content_type.subtype(), Some("pdf"));
+    assert_eq!(attachments[0].contents(), pdf.as_slice());
+    assert_eq!(attachments[1].attachment_name(), Some("notes.txt"));
+    assert_eq!(attachments[1].contents(), b"line one\n");
+
+    let message_id = email.message_id.unwrap();
+    assert_eq!(
+        message.message_id(),
+        Some(message_id.trim_matches(['<', '>']))
+    );
+}
+
+#[test]
+fn test_reply_headers_round_trip() {
+    let email = MessageBuilder::new()
+        .with_from(Mailbox::new("ana@example.com"))
+        .with_to(Mailbox::new("eva@example.com"))
+        .with_subject("Re: Reunião")
+        .with_text_body("Combinado.")
+        .with_message_id("reply-1@example.com")
+        .replying_to("<parent@example.com>", &["<root@example.com>".to_string()])
+        .build()
+        .unwrap();
+    let message = MessageParser::default().parse(&email.raw).unwrap();
+
+    assert_eq!(message.message_id(), Some("reply-1@example.com"));
+    assert_eq!(message.in_reply_to().as_text(), Some("parent@example.com"));
+    assert_eq!(
+        message
+            .references()
+            .as_text_list()
+            .unwrap()
+            .iter()
+            .map(|id| id.as_ref())
+            .collect::<Vec<_>>(),
+        vec!["root@example.com", "parent@example.com"]
+    );
+    assert_eq!(message.body_text(0).unwrap().trim_end(), "Combinado.");
+}
+
+#[test]
+fn test_demo_request_renders_the_meeting_apology_template() {
+    let prompts = shipped_prompts("en");
+    let library = TemplateLibrary::new(&prompts);
+    let classified = classified();
+    let mut eva = Contact::new("c1", "Evelyn", "Green");
+    eva.nickname = Some("Eva".to_string());
+
+    let template = library
+        .for_request(&classified.intent, INPUT, None)
+        .unwrap();
+    let values = TemplateValues::from_params(&classified.params)
+        .with_contact(&eva)
+        .with_sender("Ana");
+    let draft = template
+        .to_draft(vec!["eva.green@example.com".to_string()], &values)
+        .unwrap();
+
+    assert_eq!(template.kind, TemplateKind::MeetingApology);
+    assert_eq!(template.language, "pt-BR");
+    assert_eq!(template.version, "1.0.0-pt-BR");
+    assert_eq!(draft.subject, "Ausência na reunião");
+    assert!(draft.body.starts_with(
+        "Olá Eva,\n\nNão vou poder comparecer à reunião; desculpas pelo aviso em cima da hora."
+    ));
+    assert!(draft.body.ends_with("Atenciosamente,\nAna"));
+    assert_eq!(draft.language, "pt-BR");
+}
+
+#[test]
+fn test_templates_follow_the_language_of_the_request() {
+    let prompts = shipped_prompts("en");
+    let library = TemplateLibrary::new(&prompts);
+
+    let cases = [
+        (
+            "Escribe a Carlos para agradecer la ayuda con el informe de la semana",
+            TemplateKind::ThankYou,
+            "es",
+        ),
+        (
+            "Écris à Claire que je dois reporter la réunion à une nouvelle date",
+            TemplateKind::Reschedule,
+            "fr",
+        ),
+        (
+            "Send Bruno a follow-up about the contract, I haven't heard back",
+            TemplateKind::FollowUp,
+            "en",
+        ),
+    ];
+    for (request, kind, language) in cases {
+        let template = library
+            .for_request(&Intent::SendEmail, request, None)
+            .unwrap();
+        assert_eq!(
+            (template.kind, template.language.as_str()),
+            (kind, language)
+        );
+    }
+
+    let fallback = library.template(TemplateKind::ThankYou, "de");
+    assert_eq!(fallback.language, "en");
+    assert_eq!(
+        library.for_request(&Intent::NoAction, "Thanks!", None),
+        None
+    );
+}
+
+#[test]
+fn test_template_guides_the_model_draft() {
+    let prompts = shipped_prompts("pt-BR");
+    let template = TemplateLibrary::new(&prompts).template(TemplateKind::MeetingApology, "pt-BR");
+    let param = EmailParam::from_params(INPUT, &classified().params)
+        .with_signer("Ana")
+        .with_language("pt-BR")
+        .with_template(template);
+
+    let (system, _) = build_draft_prompts(&prompts.prompt_set("email_draft"), &param);
+
+    assert!(system.contains("guia de estilo"));
+    assert!(system.contains("Subject: Ausência na reunião\nOlá {recipient},"));
+}
+
+const INCOMING: &str = "From: Carlos Souza <carlos@example.com>\r\n\
+To: Ana <ana@example.com>, Eva <eva@example.com>\r\n\
+Cc: Bruno <bruno@example.com>\r\n\
+Subject: RE: Orçamento do terceiro trimestre\r\n\
+Date: Tue, 2 Sep 2025 09:30:00 -0300\r\n\
+Message-ID: <msg-2@example.com>\r\n\
+In-Reply-To: <msg-1@example.com>\r\n\
+References: <msg-1@example.com>\r\n\
+Content-Type: text/plain; charset=utf-8\r\n\
+\r\n\
+Olá Ana, você pode me enviar os números do orçamento até sexta? Preciso deles para a reunião.\r\n\
+\r\n\
+Em 1 de set. de 2025, Ana escreveu:\r\n\
+> Segue a primeira versão da planilha.\r\n";
+
+fn incoming() -> IncomingEmail {
+    IncomingEmail::parse(INCOMING.as_bytes()).unwrap()
+}
+
+#[test]
+fn test_reply_all_quotes_the_original_in_its_language() {
+    let original = incoming();
+    let language = detect_language(&original.text_body).unwrap();
+    let param = EmailParam::new(&"Responda a todos dizendo que envio até quinta".to_string())
+        .with_original(original, ResponseKind::ReplyAll)
+        .with_user_address("ANA@example.com")
+        .with_signer("Ana")
+        .with_language(language);
+    let prompts = shipped_prompts("pt-BR").prompt_set("email_draft");
+
+    let (system, user) = build_draft_prompts(&prompts, &param);
+    assert_eq!(language, "pt-BR");
+    assert!(system.contains("respondendo ao e-mail original"));
+    assert!(!system.contains("summary"));
+    assert!(user.contains("Remetente original: \"Carlos Souza <carlos@example.com>\""));
+    assert!(user.contains("Mensagem original: \"Olá Ana, você pode me enviar"));
+
+    let response = r#"{"subject": "Números", "body": "Olá Carlos,\n\nEnvio os números até quinta.\n\nAna", "html": ""}"#;
+    let draft = parse_draft(response, &param).unwrap();
+
+    assert_eq!(draft.subject, "Re: Orçamento do terceiro trimestre");
+    assert_eq!(draft.to, vec!["Carlos Souza <carlos@example.com>"]);
+    assert_eq!(
+        draft.cc,
+        vec!["Eva <eva@example.com>", "Bruno <bruno@example.com>"]
+    );
+    assert_eq!(draft.in_reply_to.as_deref(), Some("<msg-2@example.com>"));
+    assert!(draft.body.starts_with("Olá Carlos,"));
+    assert!(
+        draft
+            .body
+            .contains("Carlos Souza <carlos@example.com> escreveu:\n> Olá Ana")
+    );
+
+    let email = draft
+        .to_message(Mailbox::new("ana@example.com"))
+        .unwrap()
+        .build()
+        .unwrap();
+    let message = MessageParser::default().parse(&email.raw).unwrap();
+    assert_eq!(
+        message.subject(),
+        Some("Re: Orçamento do terceiro trimestre")
+    );
+    assert_eq!(message.in_reply_to().as_text(), Some("msg-2@example.com"));
+    assert_eq!(
+        message
+            .references()
+            .as_text_list()
+            .unwrap()
+            .iter()
+            .map(|id| id.as_ref())
+            .collect::<Vec<_>>(),
+        vec!["msg-1@example.com", "msg-2@example.com"]
+    );
+    assert!(
+        message
+            .body_html(0)
+            .unwrap()
+            .contains("<blockquote type=\"cite\">")
+    );
+}
+
+#[test]
+fn test_forward_keeps_the_thread_but_needs_recipients() {
+    let param = EmailParam::new(&"Forward this to Bruno".to_string())
+        .with_original(incoming(), ResponseKind::Forward)
+        .with_to(vec!["bruno@example.com".to_string()])
+        .with_language("en");
+    let response = r#"{"subject": "", "body": "Bruno, see Carlos' request below.", "html": ""}"#;
+
+    let draft = parse_draft(response, &param).unwrap();
+
+    assert_eq!(draft.subject, "Fwd: RE: Orçamento do terceiro trimestre");
+    assert_eq!(draft.to, vec!["bruno@example.com"]);
+    assert!(draft.cc.is_empty());
+    assert_eq!(draft.in_reply_to, None);
+    assert_eq!(
+        draft.references,
+        vec!["<msg-1@example.com>", "<msg-2@example.com>"]
+    );
+    assert!(draft.body.contains("Forwarded message"));
+    assert!(
+        draft
+            .body
+            .contains("Subject: RE: Orçamento do terceiro trimestre")
+    );
+}
+
+#[test]
+fn test_long_threads_are_summarised_instead_of_quoted() {
+    let mut original = incoming();
+    original.text_body = format!(
+        "Combinado, obrigado.\n\nEm 1 de set. de 2025, Ana escreveu:\n{}",
+        "> Linha antiga da conversa sobre o orçamento.\n".repeat(100)
+    );
+    let param = EmailParam::new(&"Responda agradecendo".to_string())
+        .with_original(original, ResponseKind::Reply)
+        .with_language("pt-BR");
+    let prompts = shipped_prompts("pt-BR").prompt_set("email_draft");
+
+    let (system, user) = build_draft_prompts(&prompts, &param);
+    assert!(system.contains("\"summary\""));
+    assert!(user.contains("Mensagens anteriores: \"Em 1 de set."));
+
+    let response = r#"{"subject": "", "body": "Obrigada, Carlos.", "html": "", "summary": "Ana enviou a planilha e Carlos pediu os números."}"#;
+    let draft = parse_draft(response, &param).unwrap();
+
+    assert!(draft.body.contains("> Combinado, obrigado."));
+    assert!(
+        draft
+            .body
+            .contains("Ana enviou a planilha e Carlos pediu os números.")
+    );
+    assert!(!draft.body.contains("Linha antiga"));
+}
+
+#[tokio::test]
+async fn test_drafts_are_sent_only_after_approval() {
+    let dir = std::env::temp_dir().join(format!("email_tests_approval_{}", std::process::id()));
+    let outbox = LocalSender::outbox(dir.clone());
+    let agent = EmailAgent::with_repository(SqliteStore::new(Database::open_in_memory().unwrap()));
+    let ana = || Mailbox::new("ana@example.com").with_name("Ana");
+
+    let draft = agent
+        .save_draft(EmailDraft::new(
+            vec!["Eva".to_string()],
+            "Reunião",
+            "Não vou poder ir.\n\nAna",
+        ))
+        .unwrap();
+    agent.submit_for_approval(&draft.id).unwrap();
+    assert!(
+        agent
+            .send_approved(&draft.id, ana(), &outbox)
+            .await
+            .is_err()
+    );
+    assert_eq!(agent.pending_drafts().unwrap().len(), 1);
+
+    // A name that was never resolved cannot be approved.
+    assert!(agent.approve(&draft.id).is_err());
+    agent
+        .edit_draft(
+            &draft.id,
+            &DraftEdit::new().with_to(vec!["eva@example.com".to_string()]),
+        )
+        .unwrap();
+    agent.approve(&draft.id).unwrap();
+
+    // A rejected delivery marks the draft failed; editing asks for approval again.
+    let (port, _) = smtp_stand_in(&["eva@example.com"]);
+    let smtp = SmtpSender::from_config(&smtp_config(port)).unwrap();
+    let failed = agent.send_approved(&draft.id, ana(), &smtp).await.unwrap();
+    assert_eq!(failed.status, DraftStatus::Failed);
+    assert!(failed.reason.unwrap().contains("rejected"));
+    assert_eq!(agent.pending_drafts().unwrap().len(), 1);
+
+    let edited = agent
+        .edit_draft(
+            &draft.id,
+            &DraftEdit::new().with_subject("Reunião de amanhã"),
+        )
+        .unwrap();
+    assert_eq!(edited.status, DraftStatus::AwaitingApproval);
+    agent.approve(&draft.id).unwrap();
+    let sent = agent
+        .send_approved(&draft.id, ana(), &outbox)
+        .await
+        .unwrap();
+
+    assert_eq!(sent.status, DraftStatus::Sent);
+    assert_eq!(sent.reason, None);
+    assert!(agent.pending_drafts().unwrap().is_empty());
+    assert!(agent.reject(&draft.id, None).is_err());
+    let files: Vec<_> = std::fs::read_dir(&dir).unwrap().collect();
+    assert_eq!(files.len(), 1);
+    let _ = std::fs::remove_dir_all(dir);
+}
+
+fn identities() -> UserSettings {
+    UserSettings {
+        name: "Ana".to_string(),
+        assistant: "Tereza".to_string(),
+        identities: vec![
+            IdentityConfig {
+                name: "work".to_string(),
+                display_name: "Ana Souza".to_string(),
+                address: "ana.souza@techsolutions.com".to_string(),
+                smtp_account: Some("work".to_string()),
+                signature: Some("Ana Souza\nProduct Manager, Tech Solutions".to_string()),
+                signature_html: Some(
+                    "<p>Ana Souza<br>Product Manager, Tech Solutions</p>".to_string(),
+                ),
+                language: Some("en".to_string()),
+                context: Some("work".to_string()),
+                recipient_domains: vec!["techsolutions.com".to_string()],
+                ..IdentityConfig::default()
+            },
+            IdentityConfig {
+                name: "personal".to_string(),
+                display_name: "Ana".to_string(),
+                address: "ana@example.com".to_string(),
+                language: Some("pt-BR".to_string()),
+                context: Some("personal".to_string()),
+                default: true,
+                ..IdentityConfig::default()
+            },
+        ],
+    }
+}
+
+#[tokio::test]
+async fn test_sender_identity_signs_and_sends_the_draft() {
+    let selector = IdentitySelector::from_config(&identities()).unwrap();
+    let param = EmailParam::new(&"Tell Eva the report is ready".to_string())
+        .with_to(vec!["Eva Green <eva.green@techsolutions.com>".to_string()])
+        .with_html(true);
+    let selection = selector.select(&param).unwrap();
+    assert_eq!(selection.identity.name, "work");
+    assert_eq!(selection.reason, IdentityReason::RecipientDomain);
+    assert_eq!(
+        selector
+            .select(&EmailParam::new(
+                &"Invite Eva to the birthday party".to_string()
+            ))
+            .map(|selection| (selection.identity.name, selection.reason)),
+        Some(("personal".to_string(), IdentityReason::Context))
+    );
+
+    let param = param.with_identity(selection.identity);
+    let prompts = shipped_prompts("en").prompt_set("email_draft");
+    let (system, _) = build_draft_prompts(&prompts, &param);
+    assert!(system.contains("the signature is added automatically"));
+    assert!(!system.contains("followed by the name"));
+
+    let response = r#"{"subject": "Report", "body": "Hi Eva,\n\nThe report is ready.\n\nBest regards,", "html": "<p>Hi Eva,</p><p>The report is ready.</p><p>Best regards,</p>"}"#;
+    let draft = parse_draft(response, &param).unwrap();
+    assert!(
+        draft
+            .body
+            .ends_with("Best regards,\n\nAna Souza\nProduct Manager, Tech Solutions")
+    );
+    assert!(
+        draft
+            .html_body
+            .as_deref()
+            .unwrap()
+            .ends_with("<p>Ana Souza<br>Product Manager, Tech Solutions</p>")
+    );
+    assert_eq!(
+        draft.from.as_deref(),
+        Some("Ana Souza <ana.souza@techsolutions.com>")
+    );
+
+    // The draft goes out from the identity, through its SMTP account.
+    let (default_port, default_server) = smtp_stand_in(&[]);
+    let (work_port, work_server) = smtp_stand_in(&[]);
+    let config = EmailConfig {
+        transport: "smtp".to_string(),
+        smtp: Some(smtp_config(default_port)),
+        accounts: [("work".to_string(), smtp_config(work_port))].into(),
+        ..EmailConfig::default()
+    };
+    let sender = ConfiguredSender::for_account(&config, draft.smtp_account.as_deref()).unwrap();
+    let agent = EmailAgent::new();
+    let stored = agent.save_draft(draft).unwrap();
+    agent.submit_for_approval(&stored.id).unwrap();
+    agent.approve(&stored.id).unwrap();
+    let sent = agent
+        .send_approved(&stored.id, Mailbox::new("ana@example.com"), &sender)
+        .await
+        .unwrap();
+
+    assert_eq!(sent.status, DraftStatus::Sent);
+    assert!(default_server.lock().unwrap().is_empty());
+    let captured = work_server.lock().unwrap();
+    assert_eq!(captured[0].from, "ana.souza@techsolutions.com");
+    assert!(
+        captured[0]
+            .data
+            .contains("From: Ana Souza <ana.souza@techsolutions.com>")
+    );
+    assert!(matches!(
+        ConfiguredSender::for_account(&config, Some("missing")),
+        Err(EmailError::ConfigError(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_recipients_are_validated_resolved_and_expanded() {
+    let contacts =
+        ContactAgent::new(JsonContactStore::open("spec/contacts.json".as_ref()).unwrap());
+    let agent = EmailAgent::new();
+    let param = EmailParam::new(&"Email Tiger, Pengiun, ana@bücher.de, Wise, Zebra and eva@@example.com about the project deadline".to_string())
+        .with_recipient("Tiger, Pengiun, ana@bücher.de, Wise, Zebra and eva@@example.com")
+        .with_message("The project deadline moved to Friday");
+
+    let resolution = agent.resolve_recipients(&param, &contacts).await.unwrap();
+
+    assert_eq!(
+        resolution.addresses(),
+        vec![
+            "t.brilliant@techsolutions.com",
+            "penguin@swiftrealestate.com",
+            "ana@xn--bcher-kva.de"
+        ]
+    );
+    let tiger = &resolution.resolved[0];
+    assert_eq!(tiger.source, RecipientSource::Contact);
+    assert_eq!(tiger.contact_id.as_deref(), Some("c001"));
+    assert_eq!(tiger.selection, Some(SelectionReason::ContextLabel));
+    assert!(
+        tiger.explanation.contains("work-related"),
+        "{}",
+        tiger.explanation
+    );
+    assert!(!resolution.resolved[2].is_inferred());
+    assert_eq!(resolution.inferred().count(), 2);
+
+    let problems: Vec<(&str, RecipientProblem)> = resolution
+        .unresolved
+        .iter()
+        .map(|recipient| (recipient.input.as_str(), recipient.problem))
+        .collect();
+    assert_eq!(
+        problems,
+        vec![
+            ("Wise", RecipientProblem::Ambiguous),
+            ("Zebra", RecipientProblem::NotFound),
+            ("eva@@example.com", RecipientProblem::InvalidAddress),
+        ]
+    );
+    assert_eq!(resolution.unresolved[0].candidates.len(), 2);
+    assert!(!resolution.is_complete());
+    assert_eq!(resolution.follow_up_questions().len(), 3);
+
+    let team = agent
+        .resolve_recipients(
+            &EmailParam::new(&"Email the team".to_string())
+                .with_recipient("everyone in Engineering at Tech Solutions"),
+            &contacts,
+        )
+        .await
+        .unwrap();
+    assert!(team.is_complete());
+    assert_eq!(team.resolved[0].source, RecipientSource::Group);
+    assert_eq!(team.resolved[0].name.as_deref(), Some("Tiger Brilliant"));
+
+    let param = param.with_recipients(resolution);
+    assert_eq!(param.to().len(), 3);
+    assert!(param.recipients().is_some());
+}
+
+#[tokio::test]
+async fn test_worker_drains_the_persistent_queue() {
+    let path = std::env::temp_dir()
+        .join(format!("email_tests_queue_{}", std::process::id()))
+        .join("assistant.db");
+    let (port, captured) = smtp_stand_in(&[]);
+    let queue = EmailQueue::new(SqliteStore::new(Database::open(&path).unwrap()));
+    let id = queue.enqueue(outgoing()).unwrap().id.unwrap();
+    let cancelled = queue.enqueue(outgoing()).unwrap().id.unwrap();
+    queue.cancel(cancelled).unwrap();
+
+    let worker = EmailQueueWorker::spawn(
+        queue.clone(),
+        SmtpSender::from_config(&smtp_config(port)).unwrap(),
+        Duration::from_secs(3600),
+    );
+    worker.wake();
+    let sent = tokio::time::timeout(Duration::from_secs(10), async {
+        loop {
+            let entry = queue.get(id).unwrap().unwrap();
+            if !matches!(entry.status, QueueStatus::Queued | QueueStatus::Sending) {
+                return entry;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+    })
+    .await
+    .unwrap();
+    worker.stop().await;
+
+    assert_eq!(sent.status, QueueStatus::Sent);
+    assert_eq!(captured.lock().unwrap().len(), 1);
+    let reopened = EmailQueue::new(SqliteStore::new(Database::open(&path).unwrap()));
+    assert_eq!(reopened.get(id).unwrap().unwrap().status, QueueStatus::Sent);
+    assert_eq!(
+        reopened.get(cancelled).unwrap().unwrap().status,
+        QueueStatus::Cancelled
+    );
+    assert!(reopened.pending().unwrap().is_empty());
+    let _ = std::fs::remove_dir_all(path.parent().unwrap());
+}
@@ -0,0 +1,104 @@
+use ollama_ai_agents_playground::agents::{
+    Intent,
+    experiments::{
+        ClassifierCall, ExperimentReport, ExperimentRunner, LabelledDataset, LabelledExample,
+        PromptVariant, RecordingBackend, ReplayBackend,
+    },
+    prompts::{PromptFile, PromptSet},
+};
+
+fn dataset() -> LabelledDataset {
+    LabelledDataset::new(vec![
+        LabelledExample::new("Send an email to Eva", Intent::SendEmail),
+        LabelledExample::new("Email Carlos about the delay", Intent::SendEmail),
+        LabelledExample::new("Reply to Ana with the invoice", Intent::SendEmail),
+        LabelledExample::new("Write to Sofia", Intent::SendEmail),
+        LabelledExample::new("Schedule a meeting with Carlos", Intent::ScheduleMeeting),
+        LabelledExample::new("Book a room for Monday's review", Intent::ScheduleMeeting),
+        LabelledExample::new("Move the standup to 10am", Intent::ScheduleMeeting),
+        LabelledExample::new("Set up a call with Bruno", Intent::ScheduleMeeting),
+    ])
+}
+
+fn variants() -> Vec<PromptVariant> {
+    let baseline = PromptSet::builtin("classifier");
+    let candidate = PromptVariant::with_override(
+        "candidate",
+        &baseline,
+        PromptFile {
+            version: "exp-1".to_string(),
+            sections: [(
+                "task".to_string(),
+                "Task: Return JSON with: intent (send_email, schedule_meeting, no_action)"
+                    .to_string(),
+            )]
+            .into(),
+        },
+    );
+    vec![PromptVariant::new("baseline", baseline), candidate]
+}
+
+/// Records what a model would have answered: the baseline never detects meetings.
+fn recorded_responses(seeds: &[u64]) -> ReplayBackend {
+    let mut backend = ReplayBackend::new();
+    for variant in variants() {
+        for example in dataset().examples() {
+            let (system, user) = variant.build_prompts(&example.input);
+            let intent = match (variant.name(), &example.intent) {
+                ("baseline", Intent::ScheduleMeeting) => "no_action",
+                _ => example.intent.to_str(),
+            };
+            for seed in seeds {
+                backend.record(
+                    &ClassifierCall::new(variant.name(), &system, &user, *seed),
+                    &format!("```json\n{{\"intent\":\"{intent}\",\"params\":{{}}}}\n```"),
+                );
+            }
+        }
+    }
+    backend
+}
+
+// --- Replayable experiments ---
+
+#[tokio::test]
+async fn test_experiment_replays_recorded_responses() {
+    let seeds = [1, 2, 3, 4, 5];
+    let runner = ExperimentRunner::new(recorded_responses(&seeds), dataset()).with_seeds(&seeds);
+
+    let report = runner.run(&variants()).await.unwrap();
+
+    assert_eq!(report.variant("baseline").unwrap().accuracy.mean, 0.5);
+    assert_eq!(report.variant("candidate").unwrap().accuracy.mean, 1.0);
+    assert_eq!(report.variant("candidate").unwrap().prompt_version, "exp-1");
+    assert_eq!(report.winner().unwrap().name, "candidate");
+}
+
+#[tokio::test]
+async fn test_recording_backend_produces_an_equivalent_replay() {
+    let seeds = [7, 8];
+    let recording = RecordingBackend::new(recorded_responses(&seeds));
+    let runner = ExperimentRunner::new(recording, dataset()).with_seeds(&seeds);
+    let live = runner.run(&variants()).await.unwrap();
+
+    let replay = runner.into_backend().into_replay();
+    assert_eq!(replay.len(), 2 * dataset().len() * seeds.len());
+
+    let replayed = ExperimentRunner::new(replay, dataset())
+        .with_seeds(&seeds)
+        .run(&variants())
+        .await
+        .unwrap();
+    assert_eq!(replayed, live);
+}
+
+#[tokio::test]
+async fn test_experiment_report_serialization_roundtrip() {
+    let runner = ExperimentRunner::new(recorded_responses(&[1]), dataset()).with_seeds(&[1]);
+    let report = runner.run(&variants()).await.unwrap();
+
+    let json = serde_json::to_string(&report).unwrap();
+    let restored: ExperimentReport = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(restored, report);
+}
@@ -0,0 +1,221 @@
+use std::{fs, path::PathBuf};
+
+use ollama_ai_agents_playground::{
+    agents::{
+        ClassificationResult, Intent,
+        classifier::Params,
+        context::{ConversationMessage, ConversationRole},
+        email::{DraftStatus, EmailDraft},
+    },
+    infra::{
+        contacts::{Contact, ContactStore, ContactStoreError, JsonContactStore},
+        email::{OutgoingEmail, QueueStatus},
+        persistence::{
+            AuditRecord, AuditRepository, ClassificationCache, ConversationRepository, Database,
+            EmailQueueRepository, InMemoryStore, QueuedEmail, SqliteStore, StoredDraft,
+            classification_cache_key,
+        },
+    },
+};
+
+fn temp_path(name: &str, file: &str) -> PathBuf {
+    std::env::temp_dir()
+        .join(format!("persistence_tests_{name}_{}", std::process::id()))
+        .join(file)
+}
+
+fn sqlite_store() -> SqliteStore {
+    SqliteStore::new(Database::open_in_memory().unwrap())
+}
+
+/// The behaviour every `ContactStore` must share.
+fn check_contact_store(store: &mut impl ContactStore) {
+    let eva = store.create(Contact::new("", "Eva", "Green")).unwrap();
+    let carlos = store.create(Contact::new("", "Carlos", "Silva")).unwrap();
+    assert_eq!((eva.id.as_str(), carlos.id.as_str()), ("c001", "c002"));
+    assert!(matches!(
+        store.create(Contact::new("c001", "Eva", "Again")),
+        Err(ContactStoreError::DuplicateId(_))
+    ));
+
+    store
+        .update(eva.clone().with_company("Green Studio"))
+        .unwrap();
+    assert_eq!(
+        store.get("c001").unwrap().and_then(|c| c.company),
+        Some("Green Studio".to_string())
+    );
+    assert!(matches!(
+        store.update(Contact::new("c404", "No", "One")),
+        Err(ContactStoreError::NotFound(_))
+    ));
+
+    assert_eq!(store.delete("c001").unwrap().first_name, "Eva");
+    assert!(store.get("c001").unwrap().is_none());
+    let names: Vec<String> = store.list().unwrap().iter().map(Contact::name).collect();
+    assert_eq!(names, vec!["Carlos Silva"]);
+}
+
+fn check_repositories(
+    store: &mut (
+             impl ConversationRepository + AuditRepository + ClassificationCache + EmailQueueRepository
+         ),
+) {
+    for content in ["one", "two", "three"] {
+        store
+            .append("chat-1", &ConversationMessage::user(content))
+            .unwrap();
+    }
+    store
+        .append("chat-2", &ConversationMessage::assistant("other"))
+        .unwrap();
+    let history = store.history("chat-1", 2).unwrap();
+    let contents: Vec<&str> = history.iter().map(|m| m.content.as_str()).collect();
+    assert_eq!(contents, vec!["two", "three"]);
+    assert_eq!(history[0].role, ConversationRole::User);
+    assert_eq!(store.clear("chat-1").unwrap(), 3);
+    assert!(store.history("chat-1", 10).unwrap().is_empty());
+    assert_eq!(store.history("chat-2", 10).unwrap().len(), 1);
+
+    let mut draft =
+        StoredDraft::new("d1", "Meeting", "I can't make it").with_recipient("eva@example.com");
+    store.save_draft(&draft).unwrap();
+    draft.body = "Sorry, I can't make it".to_string();
+    store.save_draft(&draft).unwrap();
+    assert_eq!(store.drafts().unwrap(), vec![draft.clone()]);
+    assert_eq!(store.draft("d1").unwrap(), Some(draft));
+    assert!(store.delete_draft("d1").unwrap());
+    assert!(!store.delete_draft("d1").unwrap());
+
+    let email = EmailDraft::new(vec!["eva@example.com".to_string()], "Meeting", "See you")
+        .with_cc(vec!["carlos@example.com".to_string()]);
+    let failed = StoredDraft::from_email("d2", &email)
+        .with_status(DraftStatus::Failed)
+        .with_reason("Email transport error: connection refused");
+    store.save_draft(&failed).unwrap();
+    let stored = store.draft("d2").unwrap().unwrap();
+    assert_eq!(stored, failed);
+    assert_eq!(stored.recipient.as_deref(), Some("eva@example.com"));
+    assert_eq!(stored.email, Some(email));
+
+    let first = store
+        .record(
+            &AuditRecord::new("assistant", "email_sent")
+                .with_details(serde_json::json!({"to": "eva@example.com"})),
+        )
+        .unwrap();
+    let second = store
+        .record(&AuditRecord::new("user", "contact_updated"))
+        .unwrap();
+    assert!(second > first);
+    let recent = store.recent(1).unwrap();
+    assert_eq!(recent.len(), 1);
+    assert_eq!(recent[0].action, "contact_updated");
+    assert_eq!(
+        store.recent(10).unwrap()[1].details["to"],
+        "eva@example.com"
+    );
+
+    let key = classification_cache_key("qwen3:0.6b", Some("1.0.0"), "Send an email to Eva ");
+    assert!(store.cached(&key).unwrap().is_none());
+    let result = ClassificationResult::new(
+        Intent::SendEmail,
+        Params::with_values("Eva".to_string(), "hello".to_string()),
+    );
+    store.cache(&key, &result).unwrap();
+    let cached = store.cached(&key).unwrap().unwrap();
+    assert_eq!(cached.intent, Intent::SendEmail);
+    assert_eq!(cached.params.recipient(), Some("Eva"));
+    assert_eq!(
+        key,
+        classification_cache_key("qwen3:0.6b", Some("1.0.0"), "Send an email to Eva")
+    );
+
+    let email = OutgoingEmail::new(
+        "ana@example.com",
+        vec!["eva@example.com".to_string()],
+        b"Subject: Hi\r\n\r\nHello\r\n",
+    )
+    .with_message_id("<1@example.com>");
+    let later = store
+        .enqueue(&QueuedEmail::new(email.clone(), 2_000, 1_000).with_draft_id("d2"))
+        .unwrap();
+    let sooner = store
+        .enqueue(&QueuedEmail::new(email.clone(), 1_500, 1_000))
+        .unwrap();
+    let due: Vec<Option<i64>> = store
+        .due_emails(2_000, 10)
+        .unwrap()
+        .iter()
+        .map(|queued| queued.id)
+        .collect();
+    assert_eq!(due, vec![Some(sooner), Some(later)]);
+    assert_eq!(store.due_emails(1_999, 10).unwrap().len(), 1);
+    assert_eq!(store.due_emails(2_000, 1).unwrap().len(), 1);
+
+    let mut retried = store.queued_email(later).unwrap().unwrap();
+    assert_eq!(retried.email, email);
+    assert_eq!(retried.draft_id.as_deref(), Some("d2"));
+    retried.attempts = 1;
+    retried.next_attempt_at = 2_060;
+    retried.last_error = Some("Email transport error: timed out".to_string());
+    assert!(store.update_queued_email(&retried).unwrap());
+    assert_eq!(store.queued_email(later).unwrap(), Some(retried.clone()));
+    assert_eq!(store.due_emails(2_000, 10).unwrap().len(), 1);
+
+    retried.status = QueueStatus::DeadLetter;
+    store.update_queued_email(&retried).unwrap();
+    assert_eq!(
+        store.queued_emails(QueueStatus::DeadLetter).unwrap(),
+        vec![retried]
+    );
+    assert_eq!(store.queued_emails(QueueStatus::Queued).unwrap().len(), 1);
+    assert!(store.queued_email(404).unwrap().is_none());
+}
+
+#[test]
+fn test_contact_stores_are_interchangeable() {
+    check_contact_store(&mut InMemoryStore::new());
+    check_contact_store(&mut sqlite_store());
+
+    let path = temp_path("json", "contacts.json");
+    check_contact_store(&mut JsonContactStore::open(&path).unwrap());
+    let _ = fs::remove_dir_all(path.parent().unwrap());
+}
+
+#[test]
+fn test_repositories_are_interchangeable() {
+    check_repositories(&mut InMemoryStore::new());
+    check_repositories(&mut sqlite_store());
+}
+
+#[test]
+fn test_sqlite_store_persists_across_reopen() {
+    let path = temp_path("reopen", "assistant.db");
+    {
+        let mut store = SqliteStore::new(Database::open(&path).unwrap());
+        let spec = JsonContactStore::open("spec/contacts.json".as_ref()).unwrap();
+        for contact in spec.list().unwrap() {
+            store.create(contact).unwrap();
+        }
+        store
+            .append("chat-1", &ConversationMessage::user("hello"))
+            .unwrap();
+    }
+
+    let store = SqliteStore::new(Database::open(&path).unwrap());
+    let contacts = store.list().unwrap();
+    let history = store.history("chat-1", 10).unwrap();
+    let _ = fs::remove_dir_all(path.parent().unwrap());
+
+    assert_eq!(contacts.len(), 17);
+    assert_eq!(contacts[0].id, "c001");
+    assert_eq!(
+        JsonContactStore::open("spec/contacts.json".as_ref())
+            .unwrap()
+            .get("c001")
+            .unwrap(),
+        Some(contacts[0].clone())
+    );
+    assert_eq!(history, vec![ConversationMessage::user("hello")]);
+}
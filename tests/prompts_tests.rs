@@ -0,0 +1,174 @@
+use ollama_ai_agents_playground::{
+    agents::{
+        assistant::{Capability, CreateResult, PersonaPreset},
+        email::{TemplateKind, TemplateLibrary, template_library::TEMPLATE_LANGUAGES},
+        prompts::{PromptLibrary, PromptSet},
+    },
+    config::PromptsConfig,
+};
+
+fn shipped_prompts(language: &str) -> PromptLibrary {
+    let config = PromptsConfig {
+        path: "prompts".to_string(),
+        language: language.to_string(),
+    };
+    PromptLibrary::load(&config, "nobody").expect("shipped prompts should be valid")
+}
+
+// --- Shipped prompt files ---
+
+#[test]
+fn test_shipped_prompts_load_and_validate() {
+    let library = shipped_prompts("en");
+    assert!(!library.is_empty());
+}
+
+#[test]
+fn test_shipped_classifier_prompts_are_versioned() {
+    let set = shipped_prompts("en").prompt_set("classifier");
+
+    assert!(!set.is_builtin());
+    assert_eq!(set.version(), "1.2.0");
+    assert!(set.section("task", "").contains("send_email"));
+    assert!(set.section("task", "").contains("contact_management"));
+    assert_eq!(set.section("input", ""), "Input: \"{}\"");
+}
+
+#[test]
+fn test_shipped_personal_assistant_prompts_keep_placeholders() {
+    let set = shipped_prompts("en").prompt_set("personal_assistant");
+
+    assert!(set.section("your_name", "").contains("{}"));
+    assert!(set.section("assistant_to", "").contains("{}"));
+    assert!(set.section("methodology", "").contains("SUPPORTED INTENTS"));
+}
+
+#[test]
+fn test_shipped_pt_br_variant_falls_back_to_english_sections() {
+    let set = shipped_prompts("pt-BR").prompt_set("personal_assistant");
+
+    assert_eq!(set.version(), "1.1.0-pt-BR");
+    assert!(set.section("your_name", "").contains("Seu nome"));
+    assert!(
+        set.section("methodology", "")
+            .contains("ASSISTANT METHODOLOGY")
+    );
+}
+
+#[test]
+fn test_shipped_english_persona_wording_matches_the_builtin_text() {
+    let set = shipped_prompts("en").prompt_set("personal_assistant");
+    assert_eq!(set.version(), "1.2.0");
+
+    for preset in PersonaPreset::ALL {
+        let profile = preset
+            .profile()
+            .with_capabilities(&Capability::ALL)
+            .with_signature("Ana");
+        assert_eq!(
+            profile.build_prompt(&set),
+            profile.build_prompt(&PromptSet::builtin("personal_assistant")),
+            "{preset}"
+        );
+    }
+}
+
+#[test]
+fn test_shipped_pt_br_persona_is_in_portuguese() {
+    let set = shipped_prompts("pt-BR").prompt_set("personal_assistant");
+
+    let prompt = PersonaPreset::PersonalAssistant
+        .profile()
+        .with_signature("Ana")
+        .build_prompt(&set);
+
+    assert!(prompt.contains("IDENTIDADE PROFISSIONAL:"));
+    assert!(prompt.contains("Você é uma Assistente Pessoal Inteligente"));
+    assert!(prompt.contains("- Gestão de Agenda e Otimização de Compromissos"));
+    assert!(prompt.contains("- Tom profissional, mas cordial"));
+    assert!(prompt.contains("- Respeito às preferências pessoais"));
+    assert!(prompt.contains("- Principal: Inglês"));
+    assert!(prompt.contains("- Secundários: Português (BR), Espanhol, Francês"));
+    assert!(prompt.contains("ASSINATURA:"));
+    assert!(!prompt.contains("PROFESSIONAL IDENTITY:"));
+}
+
+#[test]
+fn test_unknown_prompt_set_is_builtin() {
+    let set = shipped_prompts("en").prompt_set("does_not_exist");
+    assert!(set.is_builtin());
+    assert_eq!(set.section("task", "fallback"), "fallback");
+}
+
+#[test]
+fn test_builtin_prompt_set_reports_builtin_version() {
+    assert_eq!(PromptSet::builtin("classifier").version(), "builtin");
+}
+
+// --- Prompt version on agent results ---
+
+#[test]
+fn test_create_result_carries_prompt_version() {
+    let result = CreateResult::new(true).with_prompt_version("1.0.0");
+
+    let json = serde_json::to_string(&result).unwrap();
+    let restored: CreateResult = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(restored.prompt_version.as_deref(), Some("1.0.0"));
+}
+
+#[test]
+fn test_shipped_email_draft_prompts_keep_placeholders() {
+    let set = shipped_prompts("en").prompt_set("email_draft");
+
+    assert_eq!(set.version(), "1.3.0");
+    for key in [
+        "you_are",
+        "tone",
+        "language",
+        "signature",
+        "recipient",
+        "original_from",
+        "original_subject",
+        "original_body",
+        "earlier_messages",
+        "input",
+    ] {
+        assert!(set.section(key, "").contains("{}"), "{key}");
+    }
+    assert!(set.section("output_format", "").contains("\"subject\""));
+}
+
+#[test]
+fn test_shipped_email_templates_exist_in_every_language() {
+    let prompts = shipped_prompts("en");
+    let library = TemplateLibrary::new(&prompts);
+
+    for language in TEMPLATE_LANGUAGES {
+        for kind in TemplateKind::ALL {
+            let template = library.template(kind, language);
+            assert_eq!(template.language, language, "{kind} in {language}");
+            for variable in template.variables() {
+                assert!(
+                    ["recipient", "recipient_full_name", "sender", "message"].contains(&variable),
+                    "{kind} in {language} uses {{{variable}}}"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_shipped_email_triage_prompts_keep_placeholders() {
+    let set = shipped_prompts("pt-BR").prompt_set("email_triage");
+
+    assert_eq!(set.version(), "1.0.0-pt-BR");
+    for key in ["you_are", "language", "task", "from", "subject", "body"] {
+        assert!(set.section(key, "").contains("{}"), "{key}");
+    }
+    assert!(set.section("categories", "").contains("meeting_request"));
+    assert!(
+        set.section("output_format", "")
+            .contains("\"action_items\"")
+    );
+}
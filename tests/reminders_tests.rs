@@ -0,0 +1,74 @@
+use chrono::{FixedOffset, NaiveDate};
+
+use ollama_ai_agents_playground::{
+    agents::reminders::{OccasionKind, OccasionPlanner},
+    infra::{
+        calendar::UserCalendar,
+        contacts::{ContactStore, JsonContactStore},
+    },
+};
+
+const SPEC_CONTACTS: &str = "spec/contacts.json";
+const SPEC_CALENDAR: &str = "spec/calendar.json";
+
+fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+#[test]
+fn test_upcoming_birthdays_from_the_spec_contacts() {
+    let contacts = JsonContactStore::open(SPEC_CONTACTS.as_ref())
+        .unwrap()
+        .list()
+        .unwrap();
+    let upcoming = OccasionPlanner::new().upcoming(&contacts, date(2025, 11, 1));
+
+    let summary: Vec<(&str, i64, Option<u32>)> = upcoming
+        .iter()
+        .map(|o| (o.name.as_str(), o.days_until, o.turning))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("Dr. Dolphin Wise", 7, Some(47)),
+            ("Swan Elegant", 21, Some(39)),
+        ]
+    );
+    assert!(upcoming.iter().all(|o| o.kind == OccasionKind::Birthday));
+}
+
+#[test]
+fn test_materialized_birthdays_match_the_spec_calendar() {
+    let contacts = JsonContactStore::open(SPEC_CONTACTS.as_ref())
+        .unwrap()
+        .list()
+        .unwrap();
+    let mut document = UserCalendar::read_from(SPEC_CALENDAR).unwrap();
+    let planner = OccasionPlanner::new().with_utc_offset(FixedOffset::west_opt(5 * 3600).unwrap());
+    let today = date(2025, 11, 1);
+
+    // c004 and c007 have no birthday; c003 is already evt_008.
+    assert_eq!(
+        planner.materialize(&mut document.calendar, &contacts, today),
+        14
+    );
+    assert_eq!(document.calendar.metadata.total_events, 26);
+    assert!(document.calendar.find("birthday_c003").is_none());
+
+    let dolphin = contacts.iter().find(|c| c.id == "c003").unwrap();
+    let occasion = &planner.occasions_of(dolphin, today)[0];
+    let generated = planner.calendar_event(occasion);
+    let spec = document.calendar.find("evt_008").unwrap();
+    assert_eq!(generated.title, spec.title);
+    assert_eq!(generated.description, spec.description);
+    assert_eq!(generated.start_date_time, spec.start_date_time);
+    assert_eq!(generated.end_date_time, spec.end_date_time);
+    assert_eq!(generated.attendees, spec.attendees);
+    assert_eq!(generated.recurrence, spec.recurrence);
+    assert_eq!(generated.reminders, spec.reminders);
+    assert_eq!(generated.color, spec.color);
+
+    let draft = planner.greeting(occasion, dolphin, "Alice");
+    assert_eq!(draft.to.as_deref(), Some("d.wise@citymedical.com"));
+    assert_eq!(draft.subject, "Happy birthday, Dolphin!");
+}
@@ -0,0 +1,257 @@
+use std::{fs, path::PathBuf};
+
+use chrono::NaiveDate;
+
+use ollama_ai_agents_playground::{
+    agents::{
+        prompts::PromptLibrary,
+        triage::{
+            TriageCategory, TriageParam, build_triage_prompts, heuristic_category, parse_triage,
+        },
+    },
+    config::PromptsConfig,
+    infra::{
+        calendar::UserCalendar,
+        email::{
+            EmailError, IncomingEmail, LocalSender, MailBatch, MailSource, MaildirSource,
+            MboxSource, OutgoingEmail,
+        },
+    },
+};
+
+fn temp_dir(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("triage_tests_{name}_{}", std::process::id()))
+}
+
+fn shipped_prompts(language: &str) -> PromptLibrary {
+    let config = PromptsConfig {
+        path: "prompts".to_string(),
+        language: language.to_string(),
+    };
+    PromptLibrary::load(&config, "nobody").expect("shipped prompts should be valid")
+}
+
+const INVITATION: &str = "From: Carlos Silva <carlos@techsolutions.com>\r\nTo: ana@example.com\r\nSubject: Budget review\r\nDate: Tue, 2 Sep 2025 09:30:00 -0400\r\nMessage-ID: <budget-1@techsolutions.com>\r\n\r\nHi Ana,\r\n\r\nCan we review the Q3 budget on Thursday at 3pm in Meeting Room A?\r\nPlease send me the updated numbers by Friday.\r\n\r\nCarlos\r\n";
+
+const NEWSLETTER: &str = "From: News <news@example.org>\r\nTo: ana@example.com\r\nSubject: Weekly digest\r\nDate: Wed, 3 Sep 2025 07:00:00 +0000\r\nMessage-ID: <digest-36@example.org>\r\nList-Id: Weekly digest <digest.example.org>\r\n\r\nThis week in AI agents...\r\nFrom the archive: an old post.\r\n";
+
+const SPAM: &str = "From: Prize <winner@spam.example>\r\nTo: ana@example.com\r\nSubject: You won!\r\nMessage-ID: <prize@spam.example>\r\nX-Spam-Flag: YES\r\n\r\nClaim your prize now.\r\n";
+
+/// Messages as an mbox file writes them: a `From ` separator line and body
+/// lines starting with "From " quoted as ">From ".
+fn mbox(messages: &[&str]) -> String {
+    messages
+        .iter()
+        .map(|message| {
+            let quoted = message.replace("\r\nFrom ", "\r\n>From ");
+            format!("From sender@example.com Tue Sep  2 09:30:00 2025\r\n{quoted}\r\n")
+        })
+        .collect()
+}
+
+/// Stands in for an IMAP inbox: the server keeps every message and the client
+/// remembers the last UID it fetched.
+struct ImapStandIn {
+    server: Vec<(u32, &'static str)>,
+    last_uid: u32,
+}
+
+impl MailSource for ImapStandIn {
+    fn fetch(&mut self) -> Result<MailBatch, EmailError> {
+        let new: Vec<IncomingEmail> = self
+            .server
+            .iter()
+            .filter(|(uid, _)| *uid > self.last_uid)
+            .map(|(_, raw)| IncomingEmail::parse(raw.as_bytes()))
+            .collect::<Result<_, _>>()?;
+        self.last_uid = self.server.iter().map(|(uid, _)| *uid).max().unwrap_or(0);
+        Ok(MailBatch::new(new))
+    }
+}
+
+#[test]
+fn test_mbox_messages_are_read_once() {
+    let dir = temp_dir("mbox");
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("inbox.mbox");
+    fs::write(&path, mbox(&[INVITATION, NEWSLETTER])).unwrap();
+    let mut source = MboxSource::new(&path);
+
+    let emails = source.fetch().unwrap().emails;
+    assert_eq!(emails.len(), 2);
+    assert_eq!(emails[0].subject, "Budget review");
+    assert_eq!(emails[0].sender(), "carlos@techsolutions.com");
+    assert!(
+        emails[1]
+            .text_body
+            .ends_with("\nFrom the archive: an old post.")
+    );
+    assert!(source.fetch().unwrap().is_empty());
+
+    let mut file = fs::read_to_string(&path).unwrap();
+    file.push_str(&mbox(&[SPAM]));
+    fs::write(&path, file).unwrap();
+    let emails = source.fetch().unwrap().emails;
+    let _ = fs::remove_dir_all(dir);
+
+    assert_eq!(emails.len(), 1);
+    assert!(emails[0].spam_flag);
+}
+
+#[test]
+fn test_mbox_position_survives_a_new_source() {
+    let dir = temp_dir("mbox_state");
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("inbox.mbox");
+    let state = dir.join("inbox.seen");
+    fs::write(&path, mbox(&[INVITATION, "\r\nno headers here"])).unwrap();
+
+    let first = MboxSource::new(&path).with_state(&state).fetch().unwrap();
+    let mut file = fs::read_to_string(&path).unwrap();
+    file.push_str(&mbox(&[NEWSLETTER]));
+    fs::write(&path, file).unwrap();
+    let restarted = MboxSource::new(&path).with_state(&state).fetch().unwrap();
+    let _ = fs::remove_dir_all(dir);
+
+    assert_eq!(first.emails.len(), 1);
+    assert_eq!(first.unreadable.len(), 1);
+    assert!(
+        first.unreadable[0]
+            .location
+            .ends_with("inbox.mbox message 2")
+    );
+    assert_eq!(restarted.emails.len(), 1);
+    assert_eq!(restarted.emails[0].subject, "Weekly digest");
+    assert!(restarted.unreadable.is_empty());
+}
+
+#[tokio::test]
+async fn test_maildir_messages_move_to_cur() {
+    let dir = temp_dir("maildir");
+    let delivery = LocalSender::maildir(&dir);
+    for raw in [INVITATION, NEWSLETTER] {
+        delivery
+            .deliver(&OutgoingEmail::new(
+                "carlos@techsolutions.com",
+                vec!["ana@example.com".to_string()],
+                raw.as_bytes(),
+            ))
+            .unwrap();
+    }
+    fs::write(
+        dir.join("new").join("9999999999.broken.host"),
+        "\r\nno headers",
+    )
+    .unwrap();
+    let mut source = MaildirSource::new(&dir);
+
+    let batch = source.fetch().unwrap();
+    let emails = batch.emails;
+    let again = source.fetch().unwrap();
+    let new = fs::read_dir(dir.join("new")).unwrap().count();
+    let cur: Vec<String> = fs::read_dir(dir.join("cur"))
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+        .collect();
+    let _ = fs::remove_dir_all(dir);
+
+    let subjects: Vec<&str> = emails.iter().map(|e| e.subject.as_str()).collect();
+    assert_eq!(subjects, vec!["Budget review", "Weekly digest"]);
+    assert_eq!(
+        emails[1].list_id.as_deref(),
+        Some("Weekly digest <digest.example.org>")
+    );
+    assert_eq!(batch.unreadable.len(), 1);
+    assert!(
+        batch.unreadable[0]
+            .location
+            .ends_with("9999999999.broken.host:2,")
+    );
+    assert!(again.is_empty());
+    assert_eq!(new, 0);
+    assert_eq!(cur.len(), 3);
+    assert!(cur.iter().all(|name| name.ends_with(":2,")));
+    assert!(
+        MaildirSource::new(temp_dir("missing"))
+            .fetch()
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[test]
+fn test_remote_inbox_stand_in_and_heuristics() {
+    let mut inbox = ImapStandIn {
+        server: vec![(1, INVITATION), (2, NEWSLETTER), (3, SPAM)],
+        last_uid: 0,
+    };
+
+    let categories: Vec<TriageCategory> = inbox
+        .fetch()
+        .unwrap()
+        .emails
+        .iter()
+        .map(heuristic_category)
+        .collect();
+
+    assert_eq!(
+        categories,
+        vec![
+            TriageCategory::NeedsReply,
+            TriageCategory::Fyi,
+            TriageCategory::Spam
+        ]
+    );
+    assert!(inbox.fetch().unwrap().is_empty());
+}
+
+#[test]
+fn test_triage_surfaces_candidate_tasks_and_events() {
+    let email = IncomingEmail::parse(INVITATION.as_bytes()).unwrap();
+    let prompts = shipped_prompts("pt-BR").prompt_set("email_triage");
+    let (system, user) = build_triage_prompts(
+        &prompts,
+        &TriageParam::new(email.clone())
+            .with_user("Ana")
+            .with_language("pt-BR"),
+    );
+    assert!(system.contains("e-mails recebidos por Ana"));
+    assert!(system.contains("Output-Format"));
+    assert!(user.contains("Enviado em: 2025-09-02 09:30 (Tuesday) -04:00"));
+    assert!(user.contains("Can we review the Q3 budget"));
+
+    let response = "```json\n{\"category\": \"meeting_request\", \"summary\": \"Carlos propõe revisar o orçamento do 3º trimestre na quinta às 15h e pede os números até sexta.\", \"action_items\": [{\"description\": \"Enviar os números atualizados para Carlos\", \"due\": \"2025-09-05\"}, {\"description\": \"Confirmar a reunião\", \"due\": \"\"}], \"deadlines\": [{\"description\": \"Números do orçamento\", \"date\": \"2025-09-05\"}], \"meeting\": {\"title\": \"Revisão do orçamento Q3\", \"start\": \"2025-09-04T15:00\", \"end\": \"\", \"location\": \"Meeting Room A\"}}\n```";
+    let result = parse_triage(response, email).unwrap();
+
+    assert_eq!(result.category, TriageCategory::MeetingRequest);
+    let tasks = result.candidate_tasks();
+    assert_eq!(tasks.len(), 2);
+    assert_eq!(tasks[0].due, NaiveDate::from_ymd_opt(2025, 9, 5));
+    assert_eq!(tasks[0].from, "carlos@techsolutions.com");
+    assert_eq!(
+        tasks[0].message_id.as_deref(),
+        Some("<budget-1@techsolutions.com>")
+    );
+
+    let events = result.candidate_events();
+    assert_eq!(events.len(), 2);
+    let meeting = &events[0];
+    assert_eq!(meeting.id, "email_budget_1_techsolutions_com_meeting");
+    assert_eq!(meeting.title, "Revisão do orçamento Q3");
+    assert_eq!(meeting.start_date_time, "2025-09-04T15:00:00-04:00");
+    assert_eq!(meeting.end_date_time, "2025-09-04T16:00:00-04:00");
+    assert_eq!(meeting.status, "tentative");
+    assert_eq!(meeting.extra["location"]["name"], "Meeting Room A");
+    assert_eq!(meeting.attendees[0].name, "Carlos Silva");
+    let deadline = &events[1];
+    assert!(deadline.all_day);
+    assert_eq!(deadline.start_date_time, "2025-09-05T00:00:00-04:00");
+
+    // Candidates fit the calendar model and can be added next to the spec events.
+    let mut document = UserCalendar::read_from("spec/calendar.json").unwrap();
+    let before = document.calendar.events.len();
+    for event in events {
+        assert!(document.calendar.upsert_event(event));
+    }
+    assert_eq!(document.calendar.events.len(), before + 2);
+}